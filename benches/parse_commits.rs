@@ -0,0 +1,41 @@
+//! Micro-benchmark of commit parsing with the header regex compiled once, as the
+//! conventional parser does, against compiling it for every message.
+//!
+//! Run with `cargo bench --bench parse_commits`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+use vnext::models::commit::CommitParser;
+use vnext::parsers::{ConventionalCommitParser, CONVENTIONAL_COMMIT_REGEX_STR};
+
+const MESSAGES: usize = 20_000;
+
+fn time(label: &str, messages: &[String], parse: impl Fn(&str) -> bool) -> Duration {
+    let started = Instant::now();
+    let matched = messages.iter().filter(|message| parse(black_box(message))).count();
+    let elapsed = started.elapsed();
+    assert_eq!(matched, messages.len());
+    println!("{:<28} {:>10.2?} ({:.2?} per message)", label, elapsed, elapsed / messages.len() as u32);
+    elapsed
+}
+
+fn main() {
+    let messages: Vec<String> = (0..MESSAGES)
+        .map(|i| format!("feat(pkg-{}): Change number {}\n\nDetails of change {}.", i % 7, i, i))
+        .collect();
+    let parser = ConventionalCommitParser::new();
+
+    let compiled_once = time("compiled once (parser)", &messages, |message| {
+        !parser.parse_commit(String::new(), message.to_string()).commit_type.is_empty()
+    });
+    let recompiled = time("compiled per message", &messages, |message| {
+        let header = message.lines().next().unwrap_or("");
+        Regex::new(CONVENTIONAL_COMMIT_REGEX_STR).unwrap().is_match(header)
+    });
+    println!(
+        "Compiling per message is {:.1}x slower than the whole parse with the cached regex",
+        recompiled.as_secs_f64() / compiled_once.as_secs_f64()
+    );
+}
@@ -0,0 +1,31 @@
+//! Print the next release of the repository in the current directory as JSON.
+//!
+//! Run with `cargo run --example release_json`.
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use vnext::{calculate_release, ReleaseOptions};
+
+fn main() -> ExitCode {
+    let options = ReleaseOptions { changelog: true, ..ReleaseOptions::default() };
+    match calculate_release(Path::new("."), &options) {
+        Ok(plan) => {
+            let json = serde_json::json!({
+                "current_version": plan.current_version.to_string(),
+                "version": plan.next_version.to_string(),
+                "bump": plan.bump.level(),
+                "previous_tag": plan.previous_tag,
+                "repository": plan.repo_info,
+                "commits": plan.summary.commits,
+                "changelog": plan.changelog,
+            });
+            println!("{}", serde_json::to_string_pretty(&json).expect("the release serializes to JSON"));
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Failed to calculate the release: {}", e);
+            ExitCode::from(e.exit_code() as u8)
+        }
+    }
+}
@@ -1,18 +1,41 @@
 //! CLI interface definition
 
-use clap::{Parser, Subcommand};
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use std::collections::HashMap;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::time::Duration;
 use crate::commands;
+use crate::commands::changelog::ChangelogRange;
+use crate::core::footers::parse_footer_rule;
+use crate::core::version::{parse_bump_map, parse_version, render_build_metadata};
+use crate::core::auth::{GitHubAppCredentials, GitHubAuth};
+use crate::core::git;
+use crate::core::github;
 use crate::models::error::VNextError;
+use crate::models::footer::FooterRule;
+use crate::models::review::IgnoreRevs;
+use crate::models::version::{BumpLevel, BumpMap, VersionOverride, VersionScheme};
+use crate::models::repo::{validate_github_name, RepoSlug};
+use crate::models::options::{split_list, ChangelogOptions, ColorChoice, CheckOptions, DeployKeyOptions, ChangelogOrder, EmptyCommitPolicy, FetchMode, LineEnding, OutputFormat, PrereleaseBase, PrintSection, VNextOptions, WalkOptions};
+use crate::utils::{date, logging, output};
+use clap_complete::Shell;
+use crate::utils::logging::LogFormat;
+use log::LevelFilter;
+use crate::parsers::{parse_type_aliases, ParserFactory, ParserHandle, ParserKind, ParserStrategy};
 use crate::parsers::custom::{COMMIT_TYPE_REGEX_STR, TITLE_REGEX_STR, BODY_REGEX_STR, BREAKING_REGEX_STR, SCOPE_REGEX_STR};
 
 /// CLI for calculating the next version based on conventional commits
 #[derive(Parser, Debug)]
 #[clap(author, version, about = "Calculate the next version based on conventional commits")]
 pub struct Cli {
-    /// Parser strategy to use (conventional or custom)
-    #[clap(long, default_value = "conventional")]
-    pub parser: String,
+    /// Parser strategy to use
+    #[clap(long, value_enum, default_value_t = ParserKind::Conventional)]
+    pub parser: ParserKind,
 
+    /// Fall back to the default patterns instead of failing when a custom parser pattern is invalid
+    #[clap(long)]
+    pub allow_regex_fallback: bool,
 
     /// Regex pattern for commits indicating a breaking change (used with custom parser)
     #[clap(long, default_value = BREAKING_REGEX_STR)]
@@ -35,7 +58,7 @@ pub struct Cli {
     pub scope_pattern: String,
 
     /// Comma-separated list of commit types that trigger a major version bump
-    #[clap(long, default_value = "major")]
+    #[clap(long, default_value = "major,breaking")]
     pub major_commit_types: String,
 
     /// Comma-separated list of commit types that trigger a minor version bump
@@ -46,18 +69,370 @@ pub struct Cli {
     #[clap(long, default_value = "chore,noop")]
     pub noop_commit_types: String,
 
+    /// Comma-separated type=level pairs setting the bump of each commit type, where level is major, minor, patch or none (e.g. perf=patch,docs=none); overrides the type lists above
+    #[clap(long, value_parser = parse_bump_map)]
+    pub bump_map: Option<HashMap<String, BumpLevel>>,
+
+    /// Comma-separated alias=type pairs mapping commit types onto configured ones (e.g. feature=feat,bugfix=fix)
+    #[clap(long, value_parser = parse_type_aliases)]
+    pub type_aliases: Option<HashMap<String, String>>,
+
+    /// Footer rule TOKEN[:VALUE]=ACTION, where ACTION is classify-as:TYPE, force-include or link-as-issue (repeatable)
+    #[clap(long = "footer-rule", value_parser = parse_footer_rule)]
+    pub footer_rules: Vec<FooterRule>,
+
+    /// How to classify commits that change no files: by their message (classify) or as no-ops (noop)
+    #[clap(long, value_enum, default_value_t = EmptyCommitPolicy::Classify)]
+    pub empty_commits: EmptyCommitPolicy,
+
+    /// Calculate from a shallow clone on a best-effort basis instead of failing
+    #[clap(long)]
+    pub allow_shallow: bool,
+
+    /// Fetch tags from origin before calculating; a failed fetch only warns unless --fetch=required
+    #[clap(long, value_enum, num_args = 0..=1, require_equals = true, default_missing_value = "optional", overrides_with = "no_fetch")]
+    pub fetch: Option<FetchMode>,
+
+    /// Use the local tags only (the default), overriding an earlier --fetch
+    #[clap(long, overrides_with = "fetch")]
+    pub no_fetch: bool,
+
+    /// SSH private key used by --fetch (default: $GIT_SSH_KEY, then the SSH agent)
+    #[clap(long)]
+    pub ssh_key: Option<PathBuf>,
+
+    /// Stop after walking this many commits; older ones are summarized as "...and N more commits"
+    #[clap(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_commits: Option<u64>,
+
+    /// Trunk branch name (detected from origin/HEAD, then main, master, trunk or develop when unset)
+    #[clap(long)]
+    pub trunk: Option<String>,
+
+    /// Remote the repository owner and name are read from, for compare links and forge lookups; falls back to the first remote when absent
+    #[clap(long, default_value = "origin")]
+    pub remote: String,
+
+    /// Only consider release tags starting with this prefix, e.g. api-v for api-v1.2.3
+    #[clap(long)]
+    pub tag_prefix: Option<String>,
+
+    /// Fail when several tags name the latest version (e.g. v1.2.3 and 1.2.3) on different commits, instead of using the one reachable from HEAD
+    #[clap(long)]
+    pub strict_tags: bool,
+
+    /// Whether a prerelease tag (e.g. v1.3.0-rc.1) can be the current version; stable releases are calculated from the latest stable tag by default
+    #[clap(long, value_enum, default_value_t = PrereleaseBase::Exclude)]
+    pub prerelease_base: PrereleaseBase,
+
+    /// Only count commits touching files under this path (for per-package versioning)
+    #[clap(long)]
+    pub path: Option<String>,
+
+    /// Only count commits whose scope matches this value or glob, e.g. api or api-* (repeatable)
+    #[clap(long = "scope")]
+    pub scopes: Vec<String>,
+
+    /// Count commits without a scope when --scope is given (the default)
+    #[clap(long, overrides_with = "exclude_unscoped")]
+    pub include_unscoped: bool,
+
+    /// Treat commits without a scope as no-ops when --scope is given
+    #[clap(long, overrides_with = "include_unscoped")]
+    pub exclude_unscoped: bool,
+
+    /// Omit commits outside the --scope filter from the changelog (they are listed as no-ops otherwise)
+    #[clap(long)]
+    pub changelog_scope_filter: bool,
+
+    /// Regex marking a commit as not releasable when found in its subject or body (repeatable;
+    /// replaces the default `[skip release]` and `[no release]` markers)
+    #[clap(long = "skip-markers", value_parser = parse_skip_marker)]
+    pub skip_markers: Vec<regex::Regex>,
+
+    /// Omit commits with a skip-release marker from the changelog (they are annotated otherwise)
+    #[clap(long)]
+    pub hide_skipped: bool,
+
+    /// Only consider release tags under this namespace (e.g. releases/ for releases/v1.2.3)
+    #[clap(long, value_parser = parse_tag_namespace)]
+    pub tag_namespace: Option<String>,
+
     /// Output the changelog with the next version
     #[clap(long)]
     pub changelog: bool,
 
-    /// Disable header scaling in changelog (by default, h1->h4, h2->h5, h3->h6)
+    /// Comma-separated sections of the text output, in order: version, changelog (e.g. version,changelog prints the version, a --- line and the changelog)
+    #[clap(long, value_enum, value_delimiter = ',', conflicts_with_all = ["changelog", "current"])]
+    pub print: Vec<PrintSection>,
+
+    /// Omit commits whose type is in the no-op list from the changelog (they still count as no-ops)
+    #[clap(long)]
+    pub changelog_exclude_noop: bool,
+
+    /// Comma-separated list of commit types to show in the changelog (all types when empty)
+    #[clap(long, default_value = "")]
+    pub changelog_include_types: String,
+
+    /// Comma-separated list of pull request labels whose commits are omitted from the changelog
+    /// (e.g. dependencies,internal); labels are only known with --github-graphql and GITHUB_TOKEN
+    #[clap(long, default_value = "")]
+    pub exclude_labels: String,
+
+    /// Also count the commits excluded by --exclude-labels as no-ops toward the bump
+    #[clap(long)]
+    pub labels_affect_bump: bool,
+
+    /// Disable header scaling in changelog (by default, h1->h4, h2->h5, h3 and deeper->h6)
     #[clap(long)]
     pub no_header_scaling: bool,
 
+    /// Number of levels to scale headers down by, capped at h6 (3 by default: h1->h4)
+    #[clap(long, visible_alias = "header-scale", value_parser = clap::value_parser!(u8).range(1..=5))]
+    pub header_scaling_offset: Option<u8>,
+
+    /// Changelog heading template; supports {version}, {previous_version} and {date} placeholders
+    #[clap(long)]
+    pub changelog_heading: Option<String>,
+
+    /// Order of the changelog entries by commit time, or grouped by type (breaking, features, fixes, others)
+    #[clap(long, value_enum)]
+    pub changelog_order: Option<ChangelogOrder>,
+
+    /// Version scheme: semver, or calver with an optional format (calver:YYYY.0M.MICRO by default; years YYYY, YY or 0Y, months MM or 0M)
+    #[clap(long, value_parser = VersionScheme::parse, default_value = "semver")]
+    pub scheme: VersionScheme,
+
+    /// Release date (YYYY-MM-DD) used for the {date} placeholder and the calver period instead of today's UTC date
+    #[clap(long, value_parser = parse_release_date)]
+    pub release_date: Option<String>,
+
+    /// Keep git trailers (Signed-off-by, Co-authored-by, ...) in changelog commit bodies
+    #[clap(long)]
+    pub keep_trailers: bool,
+
+    /// Credit Co-authored-by trailers as additional authors in the changelog
+    #[clap(long)]
+    pub credit_coauthors: bool,
+
+    /// Show Refs, Closes, Fixes and Resolves footers as issue references in the changelog
+    #[clap(long)]
+    pub changelog_issue_refs: bool,
+
+    /// Append a contributors section, including first-time contributors, to the changelog
+    #[clap(long)]
+    pub contributors: bool,
+
+    /// Append a summary line such as "12 commits, 3 features, 5 fixes, by 4 contributors" to the changelog
+    #[clap(long)]
+    pub changelog_summary: bool,
+
+    /// List changelog entries under breaking changes, features, fixes and other changes headings
+    #[clap(long)]
+    pub group_sections: bool,
+
+    /// Show the number of entries in each --group-sections heading and a totals line such as "37 commits from 9 contributors"
+    #[clap(long, requires = "group_sections")]
+    pub section_counts: bool,
+
+    /// List breaking changes with their descriptions above the changelog commits (the default)
+    #[clap(long, overrides_with = "no_highlight_breaking")]
+    pub highlight_breaking: bool,
+
+    /// Leave out the breaking changes section of the changelog
+    #[clap(long, overrides_with = "highlight_breaking")]
+    pub no_highlight_breaking: bool,
+
+    /// Truncate changelog commit bodies after this many lines, noting how many were omitted
+    #[clap(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_body_lines: Option<u64>,
+
+    /// List only the first line of each commit in the changelog, without bodies
+    #[clap(long)]
+    pub first_line_only: bool,
+
+    /// Render commit titles and bodies in the changelog verbatim, without escaping markdown
+    #[clap(long)]
+    pub no_escape: bool,
+
+    /// Append the commit date (YYYY-MM-DD, UTC) to each changelog entry
+    #[clap(long)]
+    pub show_dates: bool,
+
+    /// List commits merged through a pull request under the pull request title and number (with --github-graphql)
+    #[clap(long)]
+    pub prefer_pr_titles: bool,
+
     /// Output the current version that vnext is bumping from
     #[clap(long)]
     pub current: bool,
 
+    /// Apply this bump (major, minor or patch) instead of the one the commits call for
+    #[clap(long, value_parser = parse_force_bump, conflicts_with = "force_version")]
+    pub force_bump: Option<BumpLevel>,
+
+    /// Release this version instead of calculating one; the changelog still covers the commits since the last release
+    #[clap(long, value_parser = parse_semver_arg)]
+    pub force_version: Option<semver::Version>,
+
+    /// Allow --force-version to be lower than or equal to the current version
+    #[clap(long, requires = "force_version")]
+    pub allow_downgrade: bool,
+
+    /// Report the next version even when its release tag already exists on another commit
+    #[clap(long)]
+    pub allow_existing_tag: bool,
+
+    /// Version to start from when no release tag exists; the commits since the first commit bump it
+    #[clap(long, value_parser = parse_semver_arg)]
+    pub initial_version: Option<semver::Version>,
+
+    /// Release --initial-version as is, whatever the commit types (fails once a release tag exists)
+    #[clap(long, requires = "initial_version", conflicts_with_all = ["force_bump", "force_version"])]
+    pub first_release: bool,
+
+    /// Build metadata appended to the next version, with {sha}, {short-sha}, {commits} and {date} placeholders (e.g. build.{commits}.sha.{short-sha})
+    #[clap(long, value_parser = parse_build_metadata)]
+    pub build_metadata: Option<String>,
+
+    /// Format of the result written to stdout (json: a single object with version, current_version and changelog)
+    #[clap(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// Color the text result: auto (on a terminal unless NO_COLOR is set), always or never; files are never colored
+    #[clap(long, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
+    /// Read `<old> <new> <ref>` lines from stdin, as a pre-receive hook does, and print one JSON object per ref
+    #[clap(long, conflicts_with_all = ["current", "version_file_out", "changelog_out", "provenance_out", "github_output"])]
+    pub stdin_range: bool,
+
+    /// Print why each analyzed commit maps to its bump level (type, scope, breaking flag, matched rule) and the resulting version
+    #[clap(long, conflicts_with_all = ["current", "stdin_range", "version_file_out", "changelog_out", "provenance_out", "github_output"])]
+    pub explain: bool,
+
+    /// Append the analyzed commit range (base_sha, head_sha, base_ref, commit_count) to the text output; JSON always has it
+    #[clap(long)]
+    pub show_range: bool,
+
+    /// Show the tag scan, commit walk and author lookups on stderr when it is a terminal
+    #[clap(long)]
+    pub progress: bool,
+
+    /// Log more: -v for debug, -vv for trace
+    #[clap(short, long, action = clap::ArgAction::Count, conflicts_with = "log_level")]
+    pub verbose: u8,
+
+    /// Log level: off, error, warn, info, debug or trace (default: $LOG_LEVEL, then info)
+    #[clap(long, value_parser = parse_log_level)]
+    pub log_level: Option<LevelFilter>,
+
+    /// Format of log lines on stderr (json: one object per line with timestamp, level, target and message)
+    #[clap(long, value_enum, default_value_t = LogFormat::Text)]
+    pub log_format: LogFormat,
+
+    /// Write nothing to stderr except errors that end the run; stdout still has the result
+    #[clap(short, long, conflicts_with_all = ["verbose", "log_level"])]
+    pub quiet: bool,
+
+    /// Also write the bare next version to this file
+    #[clap(long)]
+    pub version_file_out: Option<PathBuf>,
+
+    /// Also write the rendered changelog to this file
+    #[clap(long)]
+    pub changelog_out: Option<PathBuf>,
+
+    /// Also write release provenance metadata (JSON) to this file
+    #[clap(long)]
+    pub provenance_out: Option<PathBuf>,
+
+    /// Also append version, current_version, bump and changelog step outputs to the file named by GITHUB_OUTPUT (warns when it is not set)
+    #[clap(long, conflicts_with = "current")]
+    pub github_output: bool,
+
+    /// Line endings of the --version-file-out, --changelog-out and --provenance-out files: lf, crlf or native
+    #[clap(long, value_enum, default_value_t = LineEnding::Lf)]
+    pub line_ending: LineEnding,
+
+    /// Path to the repository's git directory, overriding GIT_DIR and the search from the current directory
+    #[clap(long)]
+    pub git_dir: Option<PathBuf>,
+
+    /// Fail when the working tree has uncommitted changes (default: warn)
+    #[clap(long, alias = "fail-on-dirty")]
+    pub require_clean: bool,
+
+    /// Do not treat untracked files as uncommitted changes
+    #[clap(long, alias = "ignore-untracked")]
+    pub allow_untracked: bool,
+
+    /// Fail when a commit message raises a warning (subject over 72 characters, no blank line before the body, or a type in no type list)
+    #[clap(long)]
+    pub deny_warnings: bool,
+
+    /// JSON file mapping author emails (or globs like *@example.com) to names and handles
+    #[clap(long)]
+    pub authors_file: Option<PathBuf>,
+
+    /// Let --authors-file entries override author information from the GitHub API
+    #[clap(long)]
+    pub authors_file_wins: bool,
+
+    /// Number of commit author lookups sent to the GitHub API in parallel
+    #[clap(long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..=16))]
+    pub github_concurrency: u8,
+
+    /// Directory commit authors from the GitHub API are cached in (default: $XDG_CACHE_HOME/vnext)
+    #[clap(long)]
+    pub github_cache: Option<PathBuf>,
+
+    /// Neither read nor write the GitHub author cache
+    #[clap(long, conflicts_with = "github_cache")]
+    pub no_cache: bool,
+
+    /// Fetch authors and pull requests (number, title and labels) with one GitHub GraphQL query per 100 commits; needs a GitHub token and falls back to REST without it
+    #[clap(long)]
+    pub github_graphql: bool,
+
+    /// Fail (exit code 6) instead of warning when GitHub author information cannot be fetched for the changelog
+    #[clap(long)]
+    pub require_github: bool,
+
+    /// GitHub token; by default GITHUB_TOKEN, GH_TOKEN or the token of the GitHub CLI (`gh auth token`) is used
+    #[clap(long)]
+    pub github_token: Option<String>,
+
+    /// ID (or client ID) of a GitHub App to authenticate as when no other token is found
+    /// (needs vnext built with the github-app feature)
+    #[clap(long, requires_all = ["github_app_key", "github_app_installation"])]
+    pub github_app_id: Option<String>,
+
+    /// PEM file with the private key of the --github-app-id app
+    #[clap(long, requires = "github_app_id")]
+    pub github_app_key: Option<PathBuf>,
+
+    /// Installation of the --github-app-id app to create a token for
+    #[clap(long, requires = "github_app_id")]
+    pub github_app_installation: Option<u64>,
+
+    /// Output the initial version and exit 0 when no repository is found or the calculation fails
+    #[clap(long)]
+    pub lenient: bool,
+
+    /// File with commits to hide or treat as no-ops, as written by `vnext review`
+    /// (defaults to .vnext-ignore-revs; relative paths are resolved against the repository root)
+    #[clap(long)]
+    pub ignore_revs_file: Option<PathBuf>,
+
+    /// Pipe the rendered changelog through this command (stdin to stdout) before output
+    #[clap(long)]
+    pub changelog_post_process: Option<String>,
+
+    /// Seconds the changelog post-process command may run before it is killed
+    #[clap(long, default_value = "30")]
+    pub changelog_post_process_timeout: u64,
+
     /// Subcommands
     #[clap(subcommand)]
     pub command: Option<Commands>,
@@ -66,24 +441,285 @@ pub struct Cli {
 /// CLI subcommands
 #[derive(Subcommand, Debug)]
 pub enum Commands {
-    /// Generate a deploy key for GitHub repository
-    GenerateDeployKey {
-        /// GitHub repository owner (e.g., unbounded-tech)
+    /// Print the release notes of the next release, or of the unreleased commits
+    Changelog {
+        /// Notes of the commits since the last release, without calculating a version
         #[clap(long)]
-        owner: Option<String>,
-        
-        /// GitHub repository name
+        unreleased: bool,
+
+        /// Regenerate the notes of an existing release tag (e.g. v1.4.0), since the release before it
+        #[clap(long, conflicts_with = "unreleased")]
+        for_tag: Option<String>,
+
+        /// Heading of the notes (default: ### Unreleased with --unreleased, else --changelog-heading)
         #[clap(long)]
-        name: Option<String>,
-        
-        /// Name of the deploy key
-        #[clap(long, default_value = "DEPLOY_KEY")]
-        key_name: Option<String>,
-        
-        /// Overwrite existing deploy key and secret if they exist
+        heading: Option<String>,
+
+        /// Output format
+        #[clap(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Check that a pull request title is a valid commit subject for the configured parser and types
+    Check {
+        /// Title to check
+        #[clap(long, required_unless_present = "from_github_event", conflicts_with = "from_github_event")]
+        title: Option<String>,
+
+        /// Check the pull request title of the GitHub Actions event payload in $GITHUB_EVENT_PATH
+        #[clap(long)]
+        from_github_event: bool,
+
+        /// Comma-separated list of allowed commit types (default: the configured types plus fix, perf, refactor, docs, style, test, build, ci and revert)
         #[clap(long)]
-        overwrite: bool,
+        allowed_types: Option<String>,
+
+        /// Comma-separated list of allowed scopes (default: any scope)
+        #[clap(long)]
+        allowed_scopes: Option<String>,
+
+        /// Maximum length of the title in characters
+        #[clap(long)]
+        max_length: Option<usize>,
+
+        /// Output format
+        #[clap(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Write the shell completion script for bash, zsh, fish, powershell or elvish to stdout
+    #[clap(hide = true)]
+    Completions {
+        /// Shell to complete in
+        #[clap(value_enum)]
+        shell: Shell,
+    },
+    /// Manage the deploy keys of GitHub repositories
+    DeployKey {
+        #[clap(subcommand)]
+        action: DeployKeyAction,
+    },
+    /// Generate a deploy key for GitHub repository (same as `deploy-key generate`)
+    #[clap(hide = true)]
+    GenerateDeployKey(GenerateDeployKeyArgs),
+    /// Write the man page (roff) to stdout
+    #[clap(hide = true)]
+    Man {
+        /// Write a page for every subcommand (vnext.1, vnext-deploy-key.1, ...) to this directory instead
+        #[clap(long)]
+        dir: Option<PathBuf>,
+    },
+    /// Review the pending commits, hide or no-op individual commits, and tag the release
+    Review,
+    /// Preview the next version if the given commit messages were merged
+    Simulate {
+        /// Synthetic commit message to classify (repeatable)
+        #[clap(long = "message", required = true)]
+        messages: Vec<String>,
+
+        /// Output format
+        #[clap(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+}
+
+/// Actions of the `deploy-key` subcommand
+#[derive(Subcommand, Debug)]
+pub enum DeployKeyAction {
+    /// Generate a deploy key and store its private key as an Actions secret
+    Generate(GenerateDeployKeyArgs),
+    /// List the deploy keys of a repository
+    List {
+        #[clap(flatten)]
+        repo: DeployKeyRepoArgs,
     },
+    /// Replace a deploy key and its secret with a fresh key pair
+    Rotate {
+        #[clap(flatten)]
+        repo: DeployKeyRepoArgs,
+
+        /// Name of the deploy key and secret to rotate
+        #[clap(long, default_value = "DEPLOY_KEY")]
+        key_name: String,
+
+        /// The secret is an Actions secret of this organization
+        #[clap(long, value_parser = parse_github_name)]
+        org_secret: Option<String>,
+    },
+    /// Delete a deploy key and its secret
+    Delete {
+        #[clap(flatten)]
+        repo: DeployKeyRepoArgs,
+
+        /// Name of the deploy key and secret to delete
+        #[clap(long, default_value = "DEPLOY_KEY")]
+        key_name: String,
+
+        /// The secret is an Actions secret of this organization
+        #[clap(long, value_parser = parse_github_name)]
+        org_secret: Option<String>,
+
+        /// Delete without asking (required when stdin is not a terminal)
+        #[clap(long, short = 'y')]
+        yes: bool,
+    },
+}
+
+/// Repository a `deploy-key` action works on, detected from the current repository when unset
+#[derive(Args, Debug)]
+pub struct DeployKeyRepoArgs {
+    /// GitHub repository owner (e.g., unbounded-tech)
+    #[clap(long, value_parser = parse_github_name)]
+    pub owner: Option<String>,
+
+    /// GitHub repository name
+    #[clap(long, value_parser = parse_github_name)]
+    pub name: Option<String>,
+}
+
+/// Arguments of `deploy-key generate`
+#[derive(Args, Debug)]
+pub struct GenerateDeployKeyArgs {
+    /// GitHub repository owner (e.g., unbounded-tech)
+    #[clap(long, value_parser = parse_github_name)]
+    pub owner: Option<String>,
+    
+    /// GitHub repository name
+    #[clap(long, value_parser = parse_github_name)]
+    pub name: Option<String>,
+    
+    /// Name of the deploy key
+    #[clap(long, default_value = "DEPLOY_KEY")]
+    pub key_name: String,
+    
+    /// Overwrite existing deploy key and secret if they exist
+    #[clap(long)]
+    pub overwrite: bool,
+
+    /// Answer yes to all prompts (required when stdin is not a terminal); existing keys are only replaced with --overwrite
+    #[clap(long, short = 'y')]
+    pub yes: bool,
+
+    /// Also write the generated key pair to deploy_key and deploy_key.pub in the current directory
+    /// (in an owner/name directory per repository with --repos)
+    #[clap(long)]
+    pub keep_files: bool,
+
+    /// Comma-separated list of owner/name repositories to set up, each with its own key
+    /// (with --org-secret: the repositories allowed to read the secret)
+    #[clap(long, value_delimiter = ',', value_parser = parse_repo_slug)]
+    pub repos: Vec<RepoSlug>,
+
+    /// File listing owner/name repositories to set up, one per line (# starts a comment)
+    /// (with --org-secret: the repositories allowed to read the secret)
+    #[clap(long, conflicts_with = "repos")]
+    pub repos_file: Option<PathBuf>,
+
+    /// Store the private key as an Actions secret of this organization instead of a
+    /// repository secret, visible to the --repos repositories (the repository only without them)
+    #[clap(long, value_parser = parse_github_name)]
+    pub org_secret: Option<String>,
+
+    /// Give the deploy key write access, e.g. so workflows can push release tags with it
+    #[clap(long)]
+    pub write_access: bool,
+}
+
+impl GenerateDeployKeyArgs {
+    /// The deploy key options of these arguments, reading `--repos-file` if given
+    pub fn to_options(&self) -> Result<DeployKeyOptions, VNextError> {
+        let repos = match &self.repos_file {
+            Some(path) => commands::deploy_key::read_repos_file(path)?,
+            None => self.repos.clone(),
+        };
+        Ok(DeployKeyOptions {
+            owner: self.owner.clone(),
+            name: self.name.clone(),
+            key_name: self.key_name.clone(),
+            overwrite: self.overwrite,
+            yes: self.yes,
+            keep_files: self.keep_files,
+            repos,
+            org_secret: self.org_secret.clone(),
+            write_access: self.write_access,
+            ..DeployKeyOptions::default()
+        })
+    }
+}
+
+impl DeployKeyRepoArgs {
+    /// The deploy key options of a single-repository action
+    fn to_options(&self, key_name: &str, org_secret: &Option<String>, yes: bool) -> DeployKeyOptions {
+        DeployKeyOptions {
+            owner: self.owner.clone(),
+            name: self.name.clone(),
+            key_name: key_name.to_string(),
+            yes,
+            org_secret: org_secret.clone(),
+            ..DeployKeyOptions::default()
+        }
+    }
+}
+
+/// Compile a `--skip-markers` pattern
+fn parse_skip_marker(value: &str) -> Result<regex::Regex, String> {
+    if value.is_empty() {
+        return Err("the skip marker must not be empty".to_string());
+    }
+    regex::Regex::new(value).map_err(|e| format!("'{}' is not a valid regex: {}", value, e))
+}
+
+/// Validate the `--release-date` value
+fn parse_release_date(value: &str) -> Result<String, String> {
+    if date::is_iso_date(value) {
+        Ok(value.to_string())
+    } else {
+        Err(format!("'{}' is not a date in YYYY-MM-DD format", value))
+    }
+}
+
+/// Parse the `--force-bump` level, which must be an actual bump
+fn parse_force_bump(value: &str) -> Result<BumpLevel, String> {
+    match BumpLevel::parse(value) {
+        Some(BumpLevel::None) | None => Err(format!("'{}' is not a bump, expected major, minor or patch", value)),
+        Some(level) => Ok(level),
+    }
+}
+
+/// Parse a `--force-version` or `--initial-version` value, allowing a leading `v`
+fn parse_semver_arg(value: &str) -> Result<semver::Version, String> {
+    parse_version(value).map_err(|e| e.to_string())
+}
+
+/// Check that the `--build-metadata` template renders to valid build metadata
+fn parse_build_metadata(value: &str) -> Result<String, String> {
+    render_build_metadata(value, "0000000000000000000000000000000000000000", 0, "1970-01-01")?;
+    Ok(value.to_string())
+}
+
+/// Parse a `--log-level` value, ignoring case
+fn parse_log_level(value: &str) -> Result<LevelFilter, String> {
+    value.parse::<LevelFilter>()
+        .map_err(|_| format!("Invalid log level '{}'; expected off, error, warn, info, debug or trace", value))
+}
+
+/// Normalize the `--tag-namespace` value so it ends with a `/`
+fn parse_tag_namespace(value: &str) -> Result<String, String> {
+    let namespace = git::normalize_tag_namespace(value);
+    if namespace.is_empty() {
+        Err("the tag namespace must not be empty".to_string())
+    } else {
+        Ok(namespace)
+    }
+}
+
+/// Validate an `--owner`, `--name` or `--org-secret` value
+fn parse_github_name(value: &str) -> Result<String, String> {
+    validate_github_name(value)?;
+    Ok(value.to_string())
+}
+
+/// Parse an `owner/name` entry of `--repos`
+fn parse_repo_slug(value: &str) -> Result<RepoSlug, String> {
+    RepoSlug::parse(value)
 }
 
 /// Parse command line arguments
@@ -93,28 +729,248 @@ pub fn parse_cli() -> Cli {
 
 /// Run the CLI
 pub fn run(cli: Cli) -> Result<(), VNextError> {
+    if let Some(git_dir) = &cli.git_dir {
+        if !git_dir.is_dir() {
+            return Err(VNextError::ConfigError(format!("--git-dir {} is not a directory", git_dir.display())));
+        }
+    }
+
     // Check if a subcommand was provided
     if let Some(command) = &cli.command {
         match command {
-            Commands::GenerateDeployKey { owner, name, key_name, overwrite } => {
-                return commands::deploy_key::generate_deploy_key(owner.clone(), name.clone(), key_name.clone(), *overwrite);
+            Commands::GenerateDeployKey(args) | Commands::DeployKey { action: DeployKeyAction::Generate(args) } => {
+                let options = DeployKeyOptions { github_auth: cli.github_auth(), remote: Some(cli.remote.clone()), git_dir: cli.git_dir(), ..args.to_options()? };
+                return commands::deploy_key::generate_deploy_key(&options);
+            }
+            Commands::DeployKey { action: DeployKeyAction::List { repo } } => {
+                let options = DeployKeyOptions { github_auth: cli.github_auth(), remote: Some(cli.remote.clone()), git_dir: cli.git_dir(), ..repo.to_options("", &None, false) };
+                return commands::deploy_key::run_list_command(&options, cli.format);
+            }
+            Commands::DeployKey { action: DeployKeyAction::Rotate { repo, key_name, org_secret } } => {
+                let options = DeployKeyOptions { github_auth: cli.github_auth(), remote: Some(cli.remote.clone()), git_dir: cli.git_dir(), ..repo.to_options(key_name, org_secret, false) };
+                return commands::deploy_key::run_rotate_command(&options);
+            }
+            Commands::DeployKey { action: DeployKeyAction::Delete { repo, key_name, org_secret, yes } } => {
+                let options = DeployKeyOptions { github_auth: cli.github_auth(), remote: Some(cli.remote.clone()), git_dir: cli.git_dir(), ..repo.to_options(key_name, org_secret, *yes) };
+                return commands::deploy_key::run_delete_command(&options);
+            }
+            Commands::Changelog { unreleased, for_tag, heading, format } => {
+                let range = match (for_tag, unreleased) {
+                    (Some(tag), _) => ChangelogRange::Tag(tag.clone()),
+                    (None, true) => ChangelogRange::Unreleased,
+                    (None, false) => ChangelogRange::Next,
+                };
+                return commands::changelog::run_changelog_command(cli.to_options(), &range, heading.as_deref(), *format);
+            }
+            Commands::Check { title, from_github_event, allowed_types, allowed_scopes, max_length, format } => {
+                let title = match title {
+                    Some(title) if !*from_github_event => title.clone(),
+                    _ => commands::check::github_event_title()?,
+                };
+                let check = CheckOptions {
+                    allowed_types: allowed_types.as_deref().map(split_list).unwrap_or_default(),
+                    allowed_scopes: allowed_scopes.as_deref().map(split_list).unwrap_or_default(),
+                    max_length: *max_length,
+                };
+                return commands::check::run_check_command(&cli.to_options(), &check, &title, *format);
+            }
+            Commands::Completions { shell } => {
+                let mut command = Cli::command();
+                let name = command.get_name().to_string();
+                // clap_complete panics on write errors, so a closed pipe is reported from here instead
+                let mut script = Vec::new();
+                clap_complete::generate(*shell, &mut command, name, &mut script);
+                io::stdout().write_all(&script)?;
+                return Ok(());
+            }
+            Commands::Man { dir } => {
+                match dir {
+                    Some(dir) => clap_mangen::generate_to(Cli::command(), dir)?,
+                    None => clap_mangen::Man::new(Cli::command()).render(&mut io::stdout())?,
+                }
+                return Ok(());
+            }
+            Commands::Simulate { messages, format } => {
+                return commands::simulate::run_simulate_command(&cli.to_options(), messages, *format);
+            }
+            Commands::Review => {
+                return commands::review::run_review_command(&cli.to_options());
             }
         }
     }
     
+    if cli.explain {
+        return commands::explain::run_explain_command(&cli.to_options());
+    }
+
+    if cli.stdin_range {
+        return commands::stdin_range::run_stdin_range_command(&cli.to_options(), &mut std::io::stdin().lock());
+    }
+
     // If no subcommand was provided, run the default vnext calculation logic
-    commands::vnext::run_vnext_command(
-        &cli.parser,
-        &cli.breaking_pattern,
-        &cli.type_pattern,
-        &cli.title_pattern,
-        &cli.body_pattern,
-        &cli.scope_pattern,
-        &cli.major_commit_types,
-        &cli.minor_commit_types,
-        &cli.noop_commit_types,
-        cli.changelog,
-        cli.no_header_scaling,
-        cli.current,
-    )
-}
\ No newline at end of file
+    commands::vnext::run_vnext_command(&cli.to_options())
+}
+
+impl Cli {
+    /// Resolve the parser strategy from the `--parser` name and pattern flags
+    pub fn parser_strategy(&self) -> ParserStrategy {
+        log::debug!("Using parser strategy: {:?}", self.parser);
+        match self.parser {
+            ParserKind::Conventional => {
+                log::debug!("Selected conventional commit parser strategy");
+                ParserStrategy::Conventional
+            },
+            ParserKind::Custom => {
+                log::debug!("Selected custom regex parser strategy");
+                let strategy = ParserStrategy::CustomRegex {
+                    commit_type_pattern: self.type_pattern.clone(),
+                    title_pattern: self.title_pattern.clone(),
+                    body_pattern: self.body_pattern.clone(),
+                    breaking_pattern: self.breaking_pattern.clone(),
+                    scope_pattern: self.scope_pattern.clone(),
+                };
+                if self.allow_regex_fallback {
+                    ParserFactory::with_regex_fallback(strategy)
+                } else {
+                    strategy
+                }
+            },
+        }
+    }
+
+    /// Git directory of the repository: `--git-dir`, or else `GIT_DIR` like git itself
+    pub fn git_dir(&self) -> Option<PathBuf> {
+        self.git_dir.clone().or_else(git::env_git_dir)
+    }
+
+    /// How the GitHub token is found: `--github-token`, the environment, the GitHub CLI,
+    /// then the `--github-app-*` app
+    pub fn github_auth(&self) -> GitHubAuth {
+        let app = match (&self.github_app_id, &self.github_app_key, self.github_app_installation) {
+            (Some(app_id), Some(private_key), Some(installation_id)) => Some(GitHubAppCredentials {
+                app_id: app_id.clone(),
+                private_key: private_key.clone(),
+                installation_id,
+            }),
+            _ => None,
+        };
+        GitHubAuth { token: self.github_token.clone(), app }
+    }
+
+    /// Resolve the bump of each commit type: the type lists, overridden by `--bump-map`
+    pub fn bump_map(&self) -> BumpMap {
+        let mut bump_map = BumpMap::from_types(
+            &split_list(&self.major_commit_types),
+            &split_list(&self.minor_commit_types),
+            &split_list(&self.noop_commit_types),
+        );
+        bump_map.extend(self.bump_map.clone().unwrap_or_default());
+        bump_map
+    }
+
+    /// Log level from --quiet, -v, --log-level or the `LOG_LEVEL` environment variable
+    pub fn log_level(&self) -> LevelFilter {
+        match (self.quiet, self.verbose, self.log_level) {
+            (true, _, _) => LevelFilter::Off,
+            (_, _, Some(level)) => level,
+            (_, 0, None) => logging::env_log_level(),
+            (_, 1, None) => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        }
+    }
+
+    /// Build the run options from the parsed command line arguments
+    pub fn to_options(&self) -> VNextOptions {
+        VNextOptions {
+            parser: ParserHandle::with_type_aliases(self.parser_strategy(), self.type_aliases.clone().unwrap_or_default()),
+            bump_map: self.bump_map(),
+            walk: WalkOptions {
+                empty_commits: self.empty_commits,
+                allow_shallow: self.allow_shallow,
+                tag_namespace: self.tag_namespace.clone(),
+                tag_prefix: self.tag_prefix.clone(),
+                path: self.path.clone(),
+                trunk: self.trunk.clone(),
+                remote: Some(self.remote.clone()),
+                footer_rules: self.footer_rules.clone(),
+                scopes: self.scopes.clone(),
+                exclude_unscoped: self.exclude_unscoped,
+                initial_version: self.initial_version.clone(),
+                max_commits: self.max_commits.map(|max| max as usize),
+                ignore_revs: IgnoreRevs::default(),
+                skip_markers: if self.skip_markers.is_empty() { None } else { Some(self.skip_markers.clone()) },
+                strict_tags: self.strict_tags,
+                prerelease_base: self.prerelease_base,
+                scheme: self.scheme,
+                release_date: self.release_date.clone(),
+            },
+            show_changelog: self.changelog || self.print.contains(&PrintSection::Changelog),
+            print: self.print.clone(),
+            changelog_exclude_noop: self.changelog_exclude_noop,
+            changelog_scope_filter: self.changelog_scope_filter,
+            changelog_hide_skipped: self.hide_skipped,
+            changelog_include_types: split_list(&self.changelog_include_types),
+            changelog_exclude_labels: split_list(&self.exclude_labels),
+            labels_affect_bump: self.labels_affect_bump,
+            changelog: ChangelogOptions {
+                no_header_scaling: self.no_header_scaling,
+                header_scaling_offset: self.header_scaling_offset,
+                heading: self.changelog_heading.clone(),
+                order: self.changelog_order,
+                release_date: self.release_date.clone(),
+                keep_trailers: self.keep_trailers,
+                credit_coauthors: self.credit_coauthors,
+                contributors: self.contributors,
+                summary_line: self.changelog_summary,
+                group_sections: self.group_sections,
+                section_counts: self.section_counts,
+                tag_namespace: self.tag_namespace.clone(),
+                tag_prefix: self.tag_prefix.clone(),
+                scheme: self.scheme,
+                issue_references: self.changelog_issue_refs,
+                no_highlight_breaking: self.no_highlight_breaking,
+                max_body_lines: self.max_body_lines.map(|max| max as usize),
+                first_line_only: self.first_line_only,
+                no_escape: self.no_escape,
+                show_dates: self.show_dates,
+                prefer_pr_titles: self.prefer_pr_titles,
+            },
+            current: self.current,
+            version_override: self.force_bump.map(VersionOverride::Bump)
+                .or_else(|| self.force_version.clone().map(VersionOverride::Version)),
+            allow_downgrade: self.allow_downgrade,
+            allow_existing_tag: self.allow_existing_tag,
+            first_release: self.first_release,
+            build_metadata: self.build_metadata.clone(),
+            format: self.format,
+            color: self.color,
+            show_range: self.show_range,
+            version_file_out: self.version_file_out.clone(),
+            changelog_out: self.changelog_out.clone(),
+            provenance_out: self.provenance_out.clone(),
+            github_output: self.github_output,
+            line_ending: self.line_ending,
+            require_clean: self.require_clean,
+            allow_untracked: self.allow_untracked,
+            deny_warnings: self.deny_warnings,
+            authors_file: self.authors_file.clone(),
+            authors_file_wins: self.authors_file_wins,
+            github_concurrency: self.github_concurrency as usize,
+            github_graphql: self.github_graphql,
+            github_auth: self.github_auth(),
+            fetch: if self.no_fetch { None } else { self.fetch },
+            ssh_key: self.ssh_key.clone().or_else(|| std::env::var_os("GIT_SSH_KEY").filter(|key| !key.is_empty()).map(PathBuf::from)),
+            require_github: self.require_github,
+            lenient: self.lenient,
+            github_cache: if self.no_cache { None } else { self.github_cache.clone().or_else(github::default_cache_dir) },
+            ignore_revs_file: self.ignore_revs_file.clone(),
+            git_dir: self.git_dir(),
+            changelog_post_process: self.changelog_post_process.clone(),
+            changelog_post_process_timeout: Duration::from_secs(self.changelog_post_process_timeout),
+            post_process: None,
+            // Progress is drawn for people watching; logs and stdout stay clean otherwise
+            progress: (self.progress && !self.quiet && std::io::stderr().is_terminal()).then(output::stderr_progress),
+            cancellation: None,
+        }
+    }
+}
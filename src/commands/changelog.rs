@@ -0,0 +1,164 @@
+//! Changelog command implementation
+
+use crate::commands::simulate::commit_json;
+use crate::core::calculator::{ReleasePlan, VersionCalculator};
+use crate::core::git;
+use crate::core::ignore_revs;
+use crate::core::progress::ProgressReporter;
+use crate::core::release;
+use crate::core::version;
+use crate::models::error::VNextError;
+use crate::models::options::{OutputFormat, PrereleaseBase, VNextOptions, WalkOptions};
+use crate::utils::output::OutputWriter;
+
+/// Heading of the notes of commits since the last release
+pub const UNRELEASED_HEADING: &str = "### Unreleased";
+
+/// Commits the changelog command lists
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChangelogRange {
+    /// The pending commits, as the notes of the next release
+    Next,
+    /// The pending commits, without calculating a version
+    Unreleased,
+    /// The commits of an existing release tag since the release before it
+    Tag(String),
+}
+
+/// Run the changelog command: print the notes of the commits in `range` under `heading`,
+/// which defaults to `### Unreleased` for unreleased commits and to the release heading
+/// otherwise
+pub fn run_changelog_command(
+    mut options: VNextOptions,
+    range: &ChangelogRange,
+    heading: Option<&str>,
+    format: OutputFormat,
+) -> Result<(), VNextError> {
+    let progress = ProgressReporter::from_options(&options);
+    let mut plan = match range {
+        ChangelogRange::Next => VersionCalculator::from_options(".", &options)
+            .with_ignore_revs(ignore_revs::load_for_run(&options)?)
+            .run()?,
+        ChangelogRange::Unreleased => unreleased_changes(&options, &progress)?,
+        ChangelogRange::Tag(tag) => tag_changes(&options, tag, &progress)?,
+    };
+    let unreleased = *range == ChangelogRange::Unreleased;
+
+    let repo = git::open_repository_with(".", options.git_dir.as_deref())?;
+    release::attribute_commits(&repo, &mut plan, &options, &progress)?;
+    // The release heading template names the next version, which unreleased notes do not have
+    if let Some(heading) = heading {
+        options.changelog.heading = Some(heading.to_string());
+    } else if unreleased {
+        options.changelog.heading = Some(UNRELEASED_HEADING.to_string());
+    }
+    let changelog = release::render_changelog(&mut plan, &options)?;
+
+    let commits = plan.summary.commits.iter().map(commit_json).collect::<Vec<_>>();
+    let next_version = (!unreleased).then(|| options.walk.scheme.render(&plan.next_version));
+    let (text, changelog) = if unreleased && commits.is_empty() {
+        ("No unreleased changes".to_string(), None)
+    } else {
+        (changelog.clone(), Some(changelog))
+    };
+    let json = serde_json::json!({
+        "current_version": options.walk.scheme.render(&plan.current_version),
+        "next_version": next_version,
+        "previous_tag": plan.previous_tag,
+        "changelog": changelog,
+        "commits": commits,
+    });
+    OutputWriter::stdout(format).result(&text, json)
+}
+
+/// Collect the commits since the last release without calculating the next version;
+/// the plan's next version is the current one
+pub fn unreleased_changes(options: &VNextOptions, progress: &ProgressReporter) -> Result<ReleasePlan, VNextError> {
+    let parser = options.parser.parser()?;
+    let walk = WalkOptions {
+        ignore_revs: ignore_revs::load_for_run(options)?,
+        ..options.walk.clone()
+    };
+
+    let repo = git::open_repository_with(".", options.git_dir.as_deref())?;
+    git::check_shallow(&repo, walk.allow_shallow)?;
+    let head = git::resolve_head(&repo)?;
+    let base = version::find_version_base(&repo, &head, &walk, progress)?;
+    let (bump, summary) = version::calculate_version_bump(
+        &repo, base.tag_commit(), &head, &*parser, &options.bump_map, &walk, progress,
+    )?;
+    log::debug!("{} commits since {}", summary.commits.len(), base.version);
+
+    Ok(ReleasePlan {
+        next_version: base.version.clone(),
+        current_version: base.version,
+        bump,
+        version_override: None,
+        summary,
+        repo_info: git::get_repo_info_from_remote(&repo, options.walk.remote_name()),
+        base_commit: base.base_commit.id(),
+        head_commit: head.id(),
+        previous_tag: base.latest_tag.map(|(name, _)| name),
+        working_tree: git::working_tree_status(&repo)?,
+        changelog: None,
+    })
+}
+
+/// Collect the commits of the release tag `tag` since the release before it in version
+/// order, as the plan of a release from that version to the tagged one. Without an
+/// earlier release the range starts at the root commit.
+pub fn tag_changes(options: &VNextOptions, tag: &str, progress: &ProgressReporter) -> Result<ReleasePlan, VNextError> {
+    let parser = options.parser.parser()?;
+    let walk = WalkOptions {
+        ignore_revs: ignore_revs::load_for_run(options)?,
+        ..options.walk.clone()
+    };
+
+    let repo = git::open_repository_with(".", options.git_dir.as_deref())?;
+    git::check_shallow(&repo, walk.allow_shallow)?;
+    let tags = git::list_semver_tags(&repo, &version::VersionParser::from_walk_options(&walk), progress)?;
+    let Some(position) = tags.iter().position(|(_, name)| name == tag) else {
+        return Err(VNextError::ConfigError(if repo.find_reference(&format!("refs/tags/{}", tag)).is_ok() {
+            format!("Tag {} is not a release tag (a semantic version under the configured namespace and prefix)", tag)
+        } else {
+            format!("Tag {} not found", tag)
+        }));
+    };
+    let tag_commit = git::peel_tag(&repo, tag)
+        .ok_or_else(|| VNextError::ConfigError(format!("Tag {} does not point at a commit", tag)))?;
+    let next_version = tags[position].0.clone();
+
+    // Tags of the same version (e.g. v1.4.0 and v1.4.0+build.2) are not earlier releases,
+    // and the notes of a stable release cover its prereleases unless they count as releases
+    let include_prereleases = walk.prerelease_base == PrereleaseBase::Include || !next_version.pre.is_empty();
+    let previous = tags[position + 1..]
+        .iter()
+        .filter(|(version, _)| git::tag_precedence(version) < git::tag_precedence(&next_version))
+        .filter(|(version, _)| include_prereleases || version.pre.is_empty())
+        .find_map(|(version, name)| git::peel_tag(&repo, name).map(|commit| (version.clone(), name.clone(), commit)));
+    let (bump, summary) = version::calculate_version_bump(
+        &repo, previous.as_ref().map(|(_, _, commit)| commit), &tag_commit, &*parser, &options.bump_map, &walk, progress,
+    )?;
+    let (current_version, previous_tag, base_commit) = match previous {
+        Some((version, name, commit)) => {
+            let base = repo.merge_base(tag_commit.id(), commit.id())?;
+            (version, Some(name), base)
+        }
+        None => (walk.initial_base_version(), None, git::root_commit(&tag_commit).id()),
+    };
+    log::debug!("{} commits in {} since {}", summary.commits.len(), tag, current_version);
+
+    Ok(ReleasePlan {
+        current_version,
+        next_version,
+        bump,
+        version_override: None,
+        summary,
+        repo_info: git::get_repo_info_from_remote(&repo, options.walk.remote_name()),
+        base_commit,
+        head_commit: tag_commit.id(),
+        previous_tag,
+        working_tree: Default::default(),
+        changelog: None,
+    })
+}
@@ -0,0 +1,135 @@
+//! Check command implementation
+
+use crate::core::version;
+use crate::models::commit::{Commit, CommitParser};
+use crate::models::error::VNextError;
+use crate::models::github::GitHubEvent;
+use crate::models::options::{CheckOptions, OutputFormat, VNextOptions};
+use crate::models::version::BumpMap;
+use crate::utils::output::OutputWriter;
+use std::path::Path;
+
+/// Common conventional commit types, allowed besides the types of the bump map unless
+/// `--allowed-types` is set
+pub const CONVENTIONAL_TYPES: [&str; 9] = ["fix", "perf", "refactor", "docs", "style", "test", "build", "ci", "revert"];
+
+/// Result of checking a title
+pub struct TitleCheck {
+    /// The title as parsed by the configured parser
+    pub commit: Commit,
+    /// What is wrong with the title; empty when it is valid
+    pub problems: Vec<String>,
+}
+
+/// Run the check command: validate `title` as a commit subject and fail with an
+/// explanation of every problem found
+pub fn run_check_command(
+    options: &VNextOptions,
+    check: &CheckOptions,
+    title: &str,
+    format: OutputFormat,
+) -> Result<(), VNextError> {
+    let parser = options.parser.parser()?;
+    let result = check_title(&*parser, title, &options.bump_map, check);
+    let commit = &result.commit;
+    let bump = version::classify_commit(commit, &options.bump_map).level();
+
+    let text = if result.problems.is_empty() {
+        format!("'{}' is a valid {} commit ({} bump)", title, commit.commit_type, bump.as_str())
+    } else {
+        format!(
+            "'{}' is not a valid commit subject:\n{}",
+            title,
+            result.problems.iter().map(|problem| format!("  - {}", problem)).collect::<Vec<_>>().join("\n")
+        )
+    };
+    let json = serde_json::json!({
+        "valid": result.problems.is_empty(),
+        "title": title,
+        "type": (!commit.commit_type.is_empty()).then_some(&commit.commit_type),
+        "scopes": commit.scopes,
+        "breaking": commit.has_breaking_change,
+        "bump": bump.as_str(),
+        "problems": result.problems,
+    });
+    if result.problems.is_empty() {
+        return OutputWriter::stdout(format).result(&text, json);
+    }
+    // The JSON result goes to stdout for tooling; the explanation ends the run either way
+    if format == OutputFormat::Json {
+        OutputWriter::stdout(format).result(&text, json)?;
+    }
+    Err(VNextError::Other(text))
+}
+
+/// Check `title` against the parser, the allowed types and scopes and the maximum length
+pub fn check_title(parser: &dyn CommitParser, title: &str, bump_map: &BumpMap, check: &CheckOptions) -> TitleCheck {
+    let commit = parser.parse_commit(String::new(), title.to_string());
+    let mut problems = Vec::new();
+
+    if title.trim().contains('\n') {
+        problems.push("the title must be a single line".to_string());
+    }
+    if commit.commit_type.is_empty() {
+        problems.push(format!(
+            "it does not match the {} format; expected 'type(scope): description', e.g. 'feat(api): add export'",
+            parser.name()
+        ));
+    } else {
+        let allowed_types = allowed_types(bump_map, check);
+        if !allowed_types.iter().any(|allowed| allowed.eq_ignore_ascii_case(&commit.commit_type)) {
+            problems.push(format!("'{}' is not an allowed type; use one of: {}", commit.commit_type, allowed_types.join(", ")));
+        }
+        if commit.title.trim().is_empty() {
+            problems.push("the description after the colon is empty".to_string());
+        }
+    }
+    if !check.allowed_scopes.is_empty() {
+        for scope in &commit.scopes {
+            if !check.allowed_scopes.iter().any(|allowed| allowed.eq_ignore_ascii_case(scope)) {
+                problems.push(format!("'{}' is not an allowed scope; use one of: {}", scope, check.allowed_scopes.join(", ")));
+            }
+        }
+    }
+    if let Some(max_length) = check.max_length {
+        let length = title.trim().chars().count();
+        if length > max_length {
+            problems.push(format!("it is {} characters long; the limit is {}", length, max_length));
+        }
+    }
+    TitleCheck { commit, problems }
+}
+
+/// The allowed types: `--allowed-types`, or the types of the bump map and the common
+/// conventional commit types
+fn allowed_types(bump_map: &BumpMap, check: &CheckOptions) -> Vec<String> {
+    if !check.allowed_types.is_empty() {
+        return check.allowed_types.clone();
+    }
+    let mut types: Vec<String> = bump_map.entries().into_iter().map(|(commit_type, _)| commit_type.to_string()).collect();
+    for commit_type in CONVENTIONAL_TYPES {
+        if !types.iter().any(|known| known == commit_type) {
+            types.push(commit_type.to_string());
+        }
+    }
+    types
+}
+
+/// Read the pull request title from the GitHub Actions event payload at `path`
+pub fn read_event_title(path: &Path) -> Result<String, VNextError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| VNextError::ConfigError(format!("Failed to read the event payload {}: {}", path.display(), e)))?;
+    let event: GitHubEvent = serde_json::from_str(&content)
+        .map_err(|e| VNextError::ConfigError(format!("Failed to parse the event payload {}: {}", path.display(), e)))?;
+    event.pull_request.map(|pull_request| pull_request.title).ok_or_else(|| {
+        VNextError::ConfigError(format!("The event payload {} has no pull request; run the check on pull_request events", path.display()))
+    })
+}
+
+/// Read the pull request title from the event payload `GITHUB_EVENT_PATH` names
+pub fn github_event_title() -> Result<String, VNextError> {
+    let path = std::env::var_os("GITHUB_EVENT_PATH")
+        .filter(|path| !path.is_empty())
+        .ok_or_else(|| VNextError::ConfigError("--from-github-event needs GITHUB_EVENT_PATH, which GitHub Actions sets".to_string()))?;
+    read_event_title(Path::new(&path))
+}
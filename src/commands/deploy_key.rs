@@ -1,239 +1,252 @@
 //! Deploy key command implementation
 
-use crate::models::error::VNextError;
-use crate::models::deploy_key::{DeployKeyResponse, DeployKeyList, SecretList};
+use crate::core::deploy_key::{DeployKeyApi, DeployKeyClient};
 use crate::core::git;
+use crate::models::deploy_key::DeployKeyResponse;
+use crate::models::error::VNextError;
+use crate::models::options::{DeployKeyOptions, OutputFormat};
+use crate::models::repo::RepoSlug;
+use crate::utils::date;
+use crate::utils::output::OutputWriter;
 use log::info;
-use reqwest::blocking::Client;
-use serde_json;
-use std::fs::{self, create_dir_all};
-use std::io::{self, Write};
-use std::path::Path;
-use std::process::Command;
-
-/// Prompt user for input with a default value
-fn prompt_with_default(prompt: &str, default: &str) -> Result<String, VNextError> {
-    print!("{} [{}]: ", prompt, default);
-    io::stdout().flush().map_err(|e| VNextError::Other(format!("Failed to flush stdout: {}", e)))?;
-    
+use ssh_key::rand_core::OsRng;
+use ssh_key::{Algorithm, HashAlg, LineEnding, PrivateKey};
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+/// Files the key pair is written to with `--keep-files`
+const PRIVATE_KEY_FILE: &str = "deploy_key";
+const PUBLIC_KEY_FILE: &str = "deploy_key.pub";
+
+/// An Ed25519 key pair in the OpenSSH formats, held in memory
+pub struct DeployKeyPair {
+    /// The private key, unencrypted since CI reads it straight from the secret
+    pub private_key: PrivateKey,
+}
+
+impl DeployKeyPair {
+    /// Generate a new Ed25519 key pair with `comment` (e.g. the key name)
+    pub fn generate(comment: &str) -> Result<Self, VNextError> {
+        let mut private_key = PrivateKey::random(&mut OsRng, Algorithm::Ed25519)
+            .map_err(|e| VNextError::Other(format!("Failed to generate SSH key: {}", e)))?;
+        private_key.set_comment(comment);
+        Ok(DeployKeyPair { private_key })
+    }
+
+    /// The private key in the OpenSSH format, as stored in the repository secret
+    pub fn private_openssh(&self) -> Result<String, VNextError> {
+        let encoded = self.private_key.to_openssh(LineEnding::LF)
+            .map_err(|e| VNextError::Other(format!("Failed to encode the private key: {}", e)))?;
+        Ok(encoded.to_string())
+    }
+
+    /// The SHA-256 fingerprint of the public key (`SHA256:...`), as GitHub shows it
+    pub fn fingerprint(&self) -> String {
+        self.private_key.public_key().fingerprint(HashAlg::Sha256).to_string()
+    }
+
+    /// The public key as an `authorized_keys` line (`ssh-ed25519 AAAA... comment`)
+    pub fn public_openssh(&self) -> Result<String, VNextError> {
+        self.private_key.public_key().to_openssh()
+            .map_err(|e| VNextError::Other(format!("Failed to encode the public key: {}", e)))
+    }
+
+    /// Write the key pair to `deploy_key` and `deploy_key.pub` in `dir`; the private
+    /// key file is only readable by its owner
+    pub fn write_files(&self, dir: &Path) -> Result<(), VNextError> {
+        let private_path = dir.join(PRIVATE_KEY_FILE);
+        self.private_key.write_openssh_file(&private_path, LineEnding::LF)
+            .map_err(|e| VNextError::Other(format!("Failed to write {}: {}", private_path.display(), e)))?;
+        let public_path = dir.join(PUBLIC_KEY_FILE);
+        std::fs::write(&public_path, format!("{}\n", self.public_openssh()?))?;
+        info!("Wrote the key pair to {} and {}", private_path.display(), public_path.display());
+        Ok(())
+    }
+}
+
+/// Read one line from stdin, treating a closed stdin as a configuration error
+fn read_input_line(flag: &str) -> Result<String, VNextError> {
     let mut input = String::new();
-    io::stdin().read_line(&mut input).map_err(|e| VNextError::Other(format!("Failed to read input: {}", e)))?;
+    let read = io::stdin().read_line(&mut input)
+        .map_err(|e| VNextError::ConfigError(format!("Failed to read input ({}); pass {} instead", e, flag)))?;
+    if read == 0 {
+        return Err(VNextError::ConfigError(format!("stdin was closed before an answer was given; pass {} instead", flag)));
+    }
+    Ok(input.trim().to_string())
+}
+
+/// Flush the prompt to stderr, keeping stdout free of interactive text
+fn flush_prompt() -> Result<(), VNextError> {
+    io::stderr().flush().map_err(|e| VNextError::Other(format!("Failed to flush stderr: {}", e)))
+}
+
+/// Prompt user for a required value; `flag` names the option that provides it non-interactively
+fn prompt_required(prompt: &str, flag: &str) -> Result<String, VNextError> {
+    eprint!("{}: ", prompt);
+    flush_prompt()?;
     
-    let input = input.trim();
+    let input = read_input_line(flag)?;
     if input.is_empty() {
-        Ok(default.to_string())
+        Err(VNextError::ConfigError(format!("A value is required; pass {}", flag)))
     } else {
-        Ok(input.to_string())
+        Ok(input)
     }
 }
 
-/// Prompt user for confirmation (y/n)
-fn prompt_for_confirmation(prompt: &str) -> Result<bool, VNextError> {
-    print!("{} (y/n): ", prompt);
-    io::stdout().flush().map_err(|e| VNextError::Other(format!("Failed to flush stdout: {}", e)))?;
-    
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).map_err(|e| VNextError::Other(format!("Failed to read input: {}", e)))?;
+/// Prompt user for confirmation (y/n); `flag` names the option that answers it non-interactively
+fn prompt_for_confirmation(prompt: &str, flag: &str) -> Result<bool, VNextError> {
+    eprint!("{} (y/n): ", prompt);
+    flush_prompt()?;
     
-    let input = input.trim().to_lowercase();
+    let input = read_input_line(flag)?.to_lowercase();
     Ok(input == "y" || input == "yes")
 }
 
-/// Get the ID of a deploy key with the given name if it exists
-fn get_deploy_key_id(
-    owner: &str,
-    repo_name: &str,
-    key_name: &str,
-) -> Result<Option<u64>, VNextError> {
-    // First try using GitHub CLI
-    let list_keys_cmd = format!(
-        "gh api repos/{}/{}/keys",
-        owner,
-        repo_name
-    );
-    
-    let output = Command::new("sh")
-        .arg("-c")
-        .arg(&list_keys_cmd)
-        .output();
-        
-    match output {
-        Ok(output) => {
-            if output.status.success() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                match serde_json::from_str::<DeployKeyList>(&stdout) {
-                    Ok(keys) => {
-                        // Check if any key has the given title
-                        for key in keys.0 {
-                            if key.title == key_name {
-                                return Ok(Some(key.id));
-                            }
-                        }
-                        Ok(None)
-                    },
-                    Err(e) => {
-                        log::warn!("Failed to parse deploy keys response: {}", e);
-                        Ok(None)
-                    }
-                }
-            } else {
-                log::warn!("Failed to list deploy keys: {}", String::from_utf8_lossy(&output.stderr));
-                Ok(None)
-            }
-        },
-        Err(e) => {
-            log::warn!("Failed to execute gh api command: {}", e);
-            Ok(None)
-        }
+/// Check that a non-interactive run has everything it needs before any API or process call
+fn check_non_interactive(owner: &Option<String>, name: &Option<String>, yes: Option<&str>) -> Result<(), VNextError> {
+    if let Some(command) = yes {
+        return Err(VNextError::ConfigError(format!(
+            "stdin is not a terminal; pass --yes to run {} non-interactively",
+            command
+        )));
+    }
+    if owner.is_none() {
+        return Err(VNextError::ConfigError(
+            "stdin is not a terminal; pass --owner to set the repository owner".to_string(),
+        ));
     }
+    if name.is_none() {
+        return Err(VNextError::ConfigError(
+            "stdin is not a terminal; pass --name to set the repository name".to_string(),
+        ));
+    }
+    Ok(())
 }
 
-/// Delete a deploy key by ID
-fn delete_deploy_key(
-    owner: &str,
-    repo_name: &str,
-    key_id: u64,
-) -> Result<(), VNextError> {
-    info!("Deleting existing deploy key with ID: {}...", key_id);
-    
-    // Try using GitHub CLI first
-    let delete_key_cmd = format!(
-        "gh api -X DELETE repos/{}/{}/keys/{}",
-        owner,
-        repo_name,
-        key_id
-    );
-    
-    let output = Command::new("sh")
-        .arg("-c")
-        .arg(&delete_key_cmd)
-        .output();
-        
-    match output {
-        Ok(output) => {
-            if output.status.success() {
-                info!("Successfully deleted deploy key with ID: {}", key_id);
-                Ok(())
-            } else {
-                let error = String::from_utf8_lossy(&output.stderr);
-                // If we get a 404, the key might have been deleted already
-                if error.contains("404") {
-                    info!("Deploy key with ID {} not found (may have been deleted already)", key_id);
-                    Ok(())
-                } else {
-                    Err(VNextError::Other(format!("Failed to delete deploy key: {}", error)))
-                }
-            }
-        },
-        Err(e) => {
-            Err(VNextError::Other(format!("Failed to execute delete command: {}", e)))
-        }
-    }
+/// What `setup_repository` did for one repository
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeployKeyOutcome {
+    /// Neither the deploy key nor the secret existed; both were created
+    Created,
+    /// An existing deploy key or secret was replaced
+    Replaced,
+    /// The deploy key or secret existed and overwriting was declined
+    Skipped,
 }
 
-/// Check if a deploy key with the given name already exists in the repository
-fn check_deploy_key_exists(
-    owner: &str,
-    repo_name: &str,
-    key_name: &str,
-) -> Result<bool, VNextError> {
-    match get_deploy_key_id(owner, repo_name, key_name)? {
-        Some(_) => Ok(true),
-        None => Ok(false)
+impl fmt::Display for DeployKeyOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DeployKeyOutcome::Created => "created",
+            DeployKeyOutcome::Replaced => "replaced",
+            DeployKeyOutcome::Skipped => "skipped",
+        })
     }
 }
 
-/// Clean up temporary key files
-fn cleanup_temp_keys() {
-    let tmp_dir_path = Path::new(".tmp");
-    let private_key_path = tmp_dir_path.join("deploy_key");
-    let public_key_path = tmp_dir_path.join("deploy_key.pub");
+/// Read the repositories of `--repos-file`: one `owner/name` per line, skipping blank
+/// lines and lines starting with `#`
+pub fn read_repos_file(path: &Path) -> Result<Vec<RepoSlug>, VNextError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| VNextError::ConfigError(format!("Failed to read {}: {}", path.display(), e)))?;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(i, line)| {
+            RepoSlug::parse(line).map_err(|e| VNextError::ConfigError(format!("{}:{}: {}", path.display(), i + 1, e)))
+        })
+        .collect()
+}
 
-    if private_key_path.exists() {
-        if let Err(e) = fs::remove_file(&private_key_path) {
-            log::warn!("Failed to clean up private key file: {}", e);
-        }
-    }
-    if public_key_path.exists() {
-        if let Err(e) = fs::remove_file(&public_key_path) {
-            log::warn!("Failed to clean up public key file: {}", e);
-        }
+/// Render the per-repository results of a batch run as a two-column table
+pub fn format_summary(results: &[(RepoSlug, Result<DeployKeyOutcome, VNextError>)]) -> String {
+    let width = results.iter().map(|(repo, _)| repo.to_string().len()).max().unwrap_or(0).max("Repository".len());
+    let mut table = format!("{:<width$}  Result\n", "Repository", width = width);
+    for (repo, result) in results {
+        let outcome = match result {
+            Ok(outcome) => outcome.to_string(),
+            Err(e) => format!("failed: {}", e.to_string().trim()),
+        };
+        table.push_str(&format!("{:<width$}  {}\n", repo.to_string(), outcome, width = width));
     }
+    table
 }
 
-/// Check if a secret with the given name already exists in the repository
-fn check_secret_exists(
-    owner: &str,
-    repo_name: &str,
-    secret_name: &str,
-) -> Result<bool, VNextError> {
-    // Try using GitHub CLI to list secrets
-    let list_secrets_cmd = format!(
-        "gh api repos/{}/{}/actions/secrets",
-        owner,
-        repo_name
-    );
-    
-    let output = Command::new("sh")
-        .arg("-c")
-        .arg(&list_secrets_cmd)
-        .output();
-        
-    match output {
-        Ok(output) => {
-            if output.status.success() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                match serde_json::from_str::<SecretList>(&stdout) {
-                    Ok(secrets) => {
-                        // Check if any secret has the given name
-                        for secret in secrets.secrets {
-                            if secret.name == secret_name {
-                                return Ok(true);
-                            }
-                        }
-                        Ok(false)
-                    },
-                    Err(e) => {
-                        log::warn!("Failed to parse secrets response: {}", e);
-                        Ok(false)
-                    }
-                }
-            } else {
-                log::warn!("Failed to list secrets: {}", String::from_utf8_lossy(&output.stderr));
-                Ok(false)
-            }
-        },
-        Err(e) => {
-            log::warn!("Failed to execute gh api command: {}", e);
-            Ok(false)
+/// Generate a deploy key for a GitHub repository, or for each of `options.repos`
+///
+/// `yes` answers every confirmation prompt with yes. When stdin is not a terminal,
+/// `yes` is required, and so are `owner` and `name` unless `repos` is set, so the
+/// command never waits for input.
+///
+/// Every repository gets its own key pair: a failure on one does not stop the others,
+/// and a summary table is printed to stderr at the end. The command fails if any
+/// repository failed.
+///
+/// With `org_secret`, a single repository gets the deploy key and `repos` lists the
+/// repositories allowed to read the organization secret holding its private key.
+pub fn generate_deploy_key(options: &DeployKeyOptions) -> Result<(), VNextError> {
+    let api = DeployKeyClient::from_auth(&options.github_auth);
+    if let Some(org) = &options.org_secret {
+        if let Some(repo) = options.repos.iter().find(|repo| !repo.owner.eq_ignore_ascii_case(org)) {
+            return Err(VNextError::ConfigError(format!(
+                "{} cannot read a secret of the {} organization; --repos lists the repositories allowed to read the --org-secret secret",
+                repo, org
+            )));
         }
     }
-}
+    if options.repos.is_empty() || options.org_secret.is_some() {
+        let repo = resolve_repository(options, Some("deploy-key generate"))?;
+        return setup_repository(&api, &repo, options).map(|_| ());
+    }
+    if options.owner.is_some() || options.name.is_some() {
+        return Err(VNextError::ConfigError(
+            "--owner and --name cannot be combined with --repos or --repos-file, which list the repositories to set up".to_string(),
+        ));
+    }
+
+    if !io::stdin().is_terminal() && !options.yes {
+        return Err(VNextError::ConfigError(
+            "stdin is not a terminal; pass --yes to run deploy-key generate non-interactively".to_string(),
+        ));
+    }
+
+    let results: Vec<(RepoSlug, Result<DeployKeyOutcome, VNextError>)> = options
+        .repos
+        .iter()
+        .map(|repo| {
+            info!("Setting up deploy key for {}...", repo);
+            let result = setup_repository(&api, repo, options);
+            if let Err(e) = &result {
+                log::warn!("Failed to set up deploy key for {}: {}", repo, e);
+            }
+            (repo.clone(), result)
+        })
+        .collect();
 
-/// Generate a deploy key for a GitHub repository
-pub fn generate_deploy_key(
-    owner: Option<String>,
-    name: Option<String>,
-    key_name: Option<String>,
-    overwrite: bool,
-) -> Result<(), VNextError> {
-    // Always clean up temp keys on exit, regardless of success or failure
-    let result = generate_deploy_key_inner(owner, name, key_name, overwrite);
-    cleanup_temp_keys();
-    result
+    eprint!("{}", format_summary(&results));
+    let failed = results.iter().filter(|(_, result)| result.is_err()).count();
+    if failed > 0 {
+        return Err(VNextError::Other(format!("{} of {} repositories failed", failed, results.len())));
+    }
+    Ok(())
 }
 
-/// Inner implementation of deploy key generation
-fn generate_deploy_key_inner(
-    owner: Option<String>,
-    name: Option<String>,
-    key_name: Option<String>,
-    overwrite: bool,
-) -> Result<(), VNextError> {
+/// Resolve the repository of a single-repository run from `--owner`/`--name`, the
+/// current repository's GitHub remote or a prompt.
+///
+/// When stdin is not a terminal, `--owner` and `--name` are required, and so is
+/// `--yes` for the command named by `requires_yes`.
+fn resolve_repository(options: &DeployKeyOptions, requires_yes: Option<&str>) -> Result<RepoSlug, VNextError> {
+    let yes = options.yes;
+    if !io::stdin().is_terminal() {
+        check_non_interactive(&options.owner, &options.name, requires_yes.filter(|_| !yes))?;
+    }
     // Try to detect current repository information
-    let (detected_owner, detected_name) = match git::open_repository() {
+    let (detected_owner, detected_name) = match git::open_repository_with(".", options.git_dir.as_deref()) {
         Ok(repo) => {
-            let repo_info = git::get_repo_info(&repo);
+            let repo_info = git::get_repo_info_from_remote(&repo, options.remote.as_deref().unwrap_or(git::DEFAULT_REMOTE));
             if repo_info.is_github_repo && !repo_info.owner.is_empty() && !repo_info.name.is_empty() {
                 info!("Detected GitHub repository: {}/{}", repo_info.owner, repo_info.name);
                 (Some(repo_info.owner), Some(repo_info.name))
@@ -245,223 +258,288 @@ fn generate_deploy_key_inner(
     };
 
     // Get repository owner
-    let owner = match (owner, detected_owner) {
+    let owner = match (options.owner.clone(), detected_owner) {
         (Some(o), _) => o,  // Use provided owner if specified
         (None, Some(detected)) => {
             // Ask if user wants to use detected owner
-            if prompt_for_confirmation(&format!("Use detected repository owner '{}'?", detected))? {
+            if yes || prompt_for_confirmation(&format!("Use detected repository owner '{}'?", detected), "--owner")? {
                 detected
             } else {
-                // Prompt for owner
-                prompt_with_default("Enter repository owner (e.g., unbounded-tech)", "")?
+                prompt_required("Enter repository owner (e.g., unbounded-tech)", "--owner")?
             }
         },
-        (None, None) => {
-            // Prompt for owner with no default
-            print!("Enter repository owner (e.g., unbounded-tech): ");
-            io::stdout().flush().map_err(|e| VNextError::Other(format!("Failed to flush stdout: {}", e)))?;
-            let mut input = String::new();
-            io::stdin().read_line(&mut input).map_err(|e| VNextError::Other(format!("Failed to read input: {}", e)))?;
-            input.trim().to_string()
-        }
+        (None, None) => prompt_required("Enter repository owner (e.g., unbounded-tech)", "--owner")?,
     };
 
     // Get repository name
-    let name = match (name, detected_name) {
+    let name = match (options.name.clone(), detected_name) {
         (Some(n), _) => n,  // Use provided name if specified
         (None, Some(detected)) => {
             // Ask if user wants to use detected name
-            if prompt_for_confirmation(&format!("Use detected repository name '{}'?", detected))? {
+            if yes || prompt_for_confirmation(&format!("Use detected repository name '{}'?", detected), "--name")? {
                 detected
             } else {
-                // Prompt for name
-                prompt_with_default("Enter repository name", "")?
+                prompt_required("Enter repository name", "--name")?
             }
         },
-        (None, None) => {
-            // Prompt for name with no default
-            print!("Enter repository name: ");
-            io::stdout().flush().map_err(|e| VNextError::Other(format!("Failed to flush stdout: {}", e)))?;
-            let mut input = String::new();
-            io::stdin().read_line(&mut input).map_err(|e| VNextError::Other(format!("Failed to read input: {}", e)))?;
-            input.trim().to_string()
-        }
+        (None, None) => prompt_required("Enter repository name", "--name")?,
     };
 
-    let key_name = key_name.unwrap_or_else(|| "DEPLOY_KEY".to_string());
+    RepoSlug::new(&owner, &name).map_err(VNextError::ConfigError)
+}
+
+/// Title of a new deploy key: the key name followed by the key's fingerprint and the
+/// host and date it was created on, e.g. `DEPLOY_KEY (SHA256:..., ci-runner, 2025-01-10)`
+pub fn key_title(key_name: &str, fingerprint: &str, host: Option<&str>, date: &str) -> String {
+    match host {
+        Some(host) => format!("{} ({}, {}, {})", key_name, fingerprint, host, date),
+        None => format!("{} ({}, {})", key_name, fingerprint, date),
+    }
+}
+
+/// Whether the deploy key titled `title` is the key `key_name`, created with or
+/// without the details suffix of `key_title`
+pub fn key_title_matches(title: &str, key_name: &str) -> bool {
+    match title.strip_prefix(key_name) {
+        Some("") => true,
+        Some(suffix) => suffix.starts_with(" (") && suffix.ends_with(')'),
+        None => false,
+    }
+}
+
+/// Name of the host the key is created on, if it can be found
+fn host_name() -> Option<String> {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+}
+
+/// Title for a deploy key named `key_name` holding `key_pair`, created on this host today
+fn new_key_title(key_name: &str, key_pair: &DeployKeyPair) -> String {
+    key_title(key_name, &key_pair.fingerprint(), host_name().as_deref(), &date::today_utc())
+}
+
+/// Deploy keys of `repo` named `key_name`, whatever the details in their titles
+fn find_keys(api: &dyn DeployKeyApi, repo: &RepoSlug, key_name: &str) -> Result<Vec<DeployKeyResponse>, VNextError> {
+    Ok(api.list_keys(repo)?.into_iter().filter(|key| key_title_matches(&key.title, key_name)).collect())
+}
+
+/// Create the deploy key and secret of one repository, replacing existing ones when
+/// `options.overwrite` is set or the user confirms it. `options.yes` skips prompts but
+/// never confirms a replacement, so existing keys make a `--yes` run without
+/// `--overwrite` fail.
+///
+/// With `keep_files` the key pair is written to the current directory, or to an
+/// `owner/name` directory when several repositories are set up. An organization secret
+/// is visible to `options.repos`, or to `repo` without them.
+pub fn setup_repository(api: &dyn DeployKeyApi, repo: &RepoSlug, options: &DeployKeyOptions) -> Result<DeployKeyOutcome, VNextError> {
+    let key_name = &options.key_name;
+    let org_secret = options.org_secret.as_deref();
     
     // Check if both deploy key and secret already exist
-    let deploy_key_exists = check_deploy_key_exists(&owner, &name, &key_name)?;
-    let secret_exists = check_secret_exists(&owner, &name, &key_name)?;
+    let existing_keys = find_keys(api, repo, key_name)?;
+    let deploy_key_exists = !existing_keys.is_empty();
+    let secret_exists = api.secret_exists(repo, key_name, org_secret)?;
     
     // Determine if we should overwrite existing keys/secrets
-    let mut should_overwrite = overwrite;
+    let mut should_overwrite = options.overwrite;
     
     if (deploy_key_exists || secret_exists) && !should_overwrite {
+        if options.yes {
+            return Err(VNextError::ConfigError(format!(
+                "Deploy key or secret '{}' already exists for repository {}; pass --overwrite to replace it",
+                key_name, repo
+            )));
+        }
         // If either exists and overwrite wasn't specified, ask the user
         let prompt = format!(
-            "Deploy key or secret '{}' already exists for repository {}/{}. Overwrite?", 
-            key_name, owner, name
+            "Deploy key or secret '{}' already exists for repository {}. Overwrite?", 
+            key_name, repo
         );
-        should_overwrite = prompt_for_confirmation(&prompt)?;
+        should_overwrite = prompt_for_confirmation(&prompt, "--overwrite")?;
         
         if !should_overwrite {
             info!("Skipping creation as overwrite was not confirmed.");
-            return Ok(());
+            return Ok(DeployKeyOutcome::Skipped);
         }
     }
+    let outcome = if deploy_key_exists || secret_exists { DeployKeyOutcome::Replaced } else { DeployKeyOutcome::Created };
 
-    // Create .tmp directory if it doesn't exist
-    let tmp_dir_path = Path::new(".tmp");
-    if !tmp_dir_path.exists() {
-        create_dir_all(tmp_dir_path).map_err(|e| VNextError::Other(format!("Failed to create .tmp directory: {}", e)))?;
-    }
-
-    let private_key_path = tmp_dir_path.join("deploy_key");
-    let public_key_path = tmp_dir_path.join("deploy_key.pub");
-
-    // Generate SSH key pair if it doesn't exist or we're overwriting
-    if !private_key_path.exists() || should_overwrite {
-        // Remove existing key files if they exist (ssh-keygen won't overwrite)
-        cleanup_temp_keys();
-
-        // Generate SSH key pair using ssh-keygen
-        info!("Generating SSH key pair...");
-        let keygen_output = Command::new("ssh-keygen")
-            .arg("-t")
-            .arg("ed25519")
-            .arg("-f")
-            .arg(&private_key_path)
-            .arg("-N")
-            .arg("")
-            .arg("-q")
-            .output()
-            .map_err(|e| VNextError::Other(format!("Failed to execute ssh-keygen: {}", e)))?;
-
-        if !keygen_output.status.success() {
-            let stderr = String::from_utf8_lossy(&keygen_output.stderr);
-            let stdout = String::from_utf8_lossy(&keygen_output.stdout);
-            
-            // Combine stdout and stderr for a more complete error message
-            let error_msg = if stderr.trim().is_empty() {
-                if stdout.trim().is_empty() {
-                    "Unknown error (no output from ssh-keygen)".to_string()
-                } else {
-                    format!("Output: {}", stdout.trim())
-                }
-            } else {
-                format!("Error: {}", stderr.trim())
-            };
-            
-            return Err(VNextError::Other(format!("Failed to generate SSH key: {}", error_msg)));
-        }
-        
-        // Verify the key files were created
-        if !private_key_path.exists() || !public_key_path.exists() {
-            return Err(VNextError::Other(
-                "SSH key files were not created. Please check if ssh-keygen is installed and working properly.".to_string()
-            ));
-        }
-    } else {
-        info!("Using existing SSH key pair...");
+    // Generate the SSH key pair in memory
+    info!("Generating SSH key pair...");
+    let key_pair = DeployKeyPair::generate(key_name)?;
+    if options.keep_files {
+        let dir = if options.repos.is_empty() || org_secret.is_some() { PathBuf::from(".") } else { Path::new(&repo.owner).join(&repo.name) };
+        std::fs::create_dir_all(&dir)?;
+        key_pair.write_files(&dir)?;
     }
 
     // Set GitHub secret with private key if it doesn't exist or we're overwriting
     if !secret_exists || should_overwrite {
-        info!("Creating repository secret {}...", key_name);
-        let secret_cmd = format!(
-            "gh secret set \"{}\" --body \"$(cat {})\" --repo \"{}/{}\" --app actions",
-            key_name,
-            private_key_path.display(),
-            owner,
-            name
-        );
-        
-        let secret_output = Command::new("sh")
-            .arg("-c")
-            .arg(&secret_cmd)
-            .output()
-            .map_err(|e| VNextError::Other(format!("Failed to execute gh secret set command: {}", e)))?;
-        
-        if !secret_output.status.success() {
-            let error = String::from_utf8_lossy(&secret_output.stderr);
-            return Err(VNextError::Other(format!("Failed to set GitHub secret: {}", error)));
+        match org_secret {
+            Some(org) => info!("Creating organization secret {} of {}...", key_name, org),
+            None => info!("Creating repository secret {}...", key_name),
         }
-        info!("Repository secret created successfully.");
+        let readers = if options.repos.is_empty() { std::slice::from_ref(repo) } else { &options.repos[..] };
+        api.set_secret(repo, key_name, &key_pair.private_openssh()?, org_secret, readers)?;
+        info!("Secret created successfully.");
     } else {
-        info!("Repository secret '{}' already exists. Skipping creation.", key_name);
+        info!("Secret '{}' already exists. Skipping creation.", key_name);
     }
     
     // Add public key as deploy key if it doesn't exist or we're overwriting
     if !deploy_key_exists || should_overwrite {
         // If we're overwriting and the key exists, delete it first
-        if should_overwrite && deploy_key_exists {
-            if let Some(key_id) = get_deploy_key_id(&owner, &name, &key_name)? {
-                delete_deploy_key(&owner, &name, key_id)?;
-            }
-        }
-        
-        info!("Adding deploy key to the repository...");
-        let public_key_content = fs::read_to_string(&public_key_path)
-            .map_err(|e| VNextError::Other(format!("Failed to read public key: {}", e)))?;
-        
-        // Check for GITHUB_TOKEN environment variable
-        let token = match std::env::var("GITHUB_TOKEN") {
-            Ok(t) => t,
-            Err(_) => {
-                // Use gh api command if GITHUB_TOKEN is not available
-                let deploy_key_cmd = format!(
-                    "gh api repos/{}/{}/keys --field title=\"{}\" --field key=\"$(cat {})\"",
-                    owner,
-                    name,
-                    key_name,
-                    public_key_path.display()
-                );
-                
-                let deploy_key_output = Command::new("sh")
-                    .arg("-c")
-                    .arg(&deploy_key_cmd)
-                    .output()
-                    .map_err(|e| VNextError::Other(format!("Failed to execute gh api command: {}", e)))?;
-                
-                if !deploy_key_output.status.success() {
-                    let error = String::from_utf8_lossy(&deploy_key_output.stderr);
-                    return Err(VNextError::Other(format!("Failed to add deploy key: {}", error)));
-                }
-                
-                info!("Deploy key setup completed.");
-                return Ok(());
-            }
-        };
-        
-        // Use GitHub API directly if GITHUB_TOKEN is available
-        let client = Client::new();
-        let url = format!("https://api.github.com/repos/{}/{}/keys", owner, name);
-        
-        let response = client
-            .post(&url)
-            .header("Authorization", format!("token {}", token))
-            .header("User-Agent", "vnext-cli")
-            .json(&serde_json::json!({
-                "title": key_name,
-                "key": public_key_content.trim()
-            }))
-            .send()
-            .map_err(|e| VNextError::Other(format!("Failed to send request to GitHub API: {}", e)))?;
-        
-        if !response.status().is_success() {
-            let error = response.text().unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(VNextError::Other(format!("Failed to add deploy key: {}", error)));
+        for key in &existing_keys {
+            info!("Deleting existing deploy key with ID: {}...", key.id);
+            api.delete_key(repo, key.id)?;
         }
         
-        let deploy_key: DeployKeyResponse = response.json()
-            .map_err(|e| VNextError::Other(format!("Failed to parse response: {}", e)))?;
-        
+        let access = if options.write_access { "read-write" } else { "read-only" };
+        info!("Adding {} deploy key to the repository...", access);
+        let title = new_key_title(key_name, &key_pair);
+        let deploy_key = api.add_key(repo, &title, &key_pair.public_openssh()?, !options.write_access)?;
         info!("Deploy key setup completed.");
         info!("Deploy key ID: {}", deploy_key.id);
     } else {
         info!("Deploy key '{}' already exists. Skipping creation.", key_name);
     }
 
+    Ok(outcome)
+}
+
+/// Render deploy keys as a table with their ID, title, creation date, access and last use
+pub fn format_key_table(keys: &[DeployKeyResponse]) -> String {
+    let id_width = keys.iter().map(|key| key.id.to_string().len()).max().unwrap_or(0).max("ID".len());
+    let title_width = keys.iter().map(|key| key.title.len()).max().unwrap_or(0).max("Title".len());
+    let mut table = format!("{:<id_width$}  {:<title_width$}  {:<20}  {:<10}  Last used\n", "ID", "Title", "Created", "Access");
+    for key in keys {
+        let access = if key.read_only { "read-only" } else { "read-write" };
+        let last_used = key.last_used.as_deref().unwrap_or("never");
+        table.push_str(&format!(
+            "{:<id_width$}  {:<title_width$}  {:<20}  {:<10}  {}\n",
+            key.id, key.title, key.created_at, access, last_used
+        ));
+    }
+    table
+}
+
+/// Run `deploy-key list`: print the deploy keys of the repository
+pub fn run_list_command(options: &DeployKeyOptions, format: OutputFormat) -> Result<(), VNextError> {
+    let repo = resolve_repository(options, None)?;
+    let keys = DeployKeyClient::from_auth(&options.github_auth).list_keys(&repo)?;
+    let text = if keys.is_empty() {
+        format!("No deploy keys in {}", repo)
+    } else {
+        format_key_table(&keys).trim_end().to_string()
+    };
+    let json = serde_json::to_value(&keys)
+        .map_err(|e| VNextError::Other(format!("Failed to serialize deploy keys: {}", e)))?;
+    OutputWriter::stdout(format).result(&text, json)
+}
+
+/// Replace the deploy key `key_name` of `repo` and its secret with a fresh key pair,
+/// returning the new deploy key.
+///
+/// The new key is read-only unless an old key had write access. It is added before the
+/// secret is updated, and the old keys are only deleted once the new one is listed on
+/// the repository, so the secret always matches a key the repository accepts. If the
+/// secret cannot be updated, the new key is removed again and the old key and secret
+/// stay in place. An organization secret stays visible to the same repositories.
+pub fn rotate_deploy_key(
+    api: &dyn DeployKeyApi,
+    repo: &RepoSlug,
+    key_name: &str,
+    org_secret: Option<&str>,
+) -> Result<DeployKeyResponse, VNextError> {
+    let old_keys = find_keys(api, repo, key_name)?;
+    if old_keys.is_empty() {
+        return Err(VNextError::ConfigError(format!(
+            "{} has no deploy key titled '{}'; create one with `vnext deploy-key generate`",
+            repo, key_name
+        )));
+    }
+
+    info!("Generating SSH key pair...");
+    let key_pair = DeployKeyPair::generate(key_name)?;
+    // The new key gets the access of the old one
+    let read_only = old_keys.iter().all(|key| key.read_only);
+    let new_key = api.add_key(repo, &new_key_title(key_name, &key_pair), &key_pair.public_openssh()?, read_only)?;
+    info!("Added deploy key with ID: {}", new_key.id);
+
+    if !api.list_keys(repo)?.iter().any(|key| key.id == new_key.id) {
+        return Err(VNextError::GithubError(format!(
+            "The new deploy key {} is not listed on {}; the old key and secret were kept",
+            new_key.id, repo
+        )));
+    }
+
+    if let Err(e) = api.set_secret(repo, key_name, &key_pair.private_openssh()?, org_secret, &[]) {
+        // Keep the old key and secret working together
+        if let Err(cleanup) = api.delete_key(repo, new_key.id) {
+            log::warn!("Failed to remove the new deploy key {}: {}", new_key.id, cleanup);
+        }
+        return Err(e);
+    }
+    info!("Updated secret {}", key_name);
+
+    for key in &old_keys {
+        info!("Deleting old deploy key with ID: {}...", key.id);
+        api.delete_key(repo, key.id)?;
+    }
+    Ok(new_key)
+}
+
+/// Run `deploy-key rotate` on the repository of `options`
+pub fn run_rotate_command(options: &DeployKeyOptions) -> Result<(), VNextError> {
+    let repo = resolve_repository(options, None)?;
+    let api = DeployKeyClient::from_auth(&options.github_auth);
+    let new_key = rotate_deploy_key(&api, &repo, &options.key_name, options.org_secret.as_deref())?;
+    info!("Rotated deploy key '{}' of {}; the new key has ID {}", options.key_name, repo, new_key.id);
+    Ok(())
+}
+
+/// Delete the deploy keys titled `key_name` from `repo` along with the secret holding
+/// the private key, returning the number of deleted keys
+pub fn delete_deploy_key(
+    api: &dyn DeployKeyApi,
+    repo: &RepoSlug,
+    key_name: &str,
+    org_secret: Option<&str>,
+) -> Result<usize, VNextError> {
+    let keys = find_keys(api, repo, key_name)?;
+    let secret_exists = api.secret_exists(repo, key_name, org_secret)?;
+    if keys.is_empty() && !secret_exists {
+        return Err(VNextError::ConfigError(format!(
+            "{} has no deploy key or secret named '{}'",
+            repo, key_name
+        )));
+    }
+    for key in &keys {
+        info!("Deleting deploy key with ID: {}...", key.id);
+        api.delete_key(repo, key.id)?;
+    }
+    if secret_exists {
+        info!("Deleting secret {}...", key_name);
+        api.delete_secret(repo, key_name, org_secret)?;
+    }
+    Ok(keys.len())
+}
+
+/// Run `deploy-key delete` on the repository of `options`, asking for confirmation
+/// unless `yes` is set
+pub fn run_delete_command(options: &DeployKeyOptions) -> Result<(), VNextError> {
+    let repo = resolve_repository(options, Some("deploy-key delete"))?;
+    let prompt = format!("Delete deploy key and secret '{}' of {}?", options.key_name, repo);
+    if !options.yes && !prompt_for_confirmation(&prompt, "--yes")? {
+        info!("Nothing was deleted.");
+        return Ok(());
+    }
+    let api = DeployKeyClient::from_auth(&options.github_auth);
+    let deleted = delete_deploy_key(&api, &repo, &options.key_name, options.org_secret.as_deref())?;
+    info!("Deleted '{}' from {} ({} deploy keys)", options.key_name, repo, deleted);
     Ok(())
-}
\ No newline at end of file
+}
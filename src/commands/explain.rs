@@ -0,0 +1,117 @@
+//! Explain command implementation: why each pending commit bumps the version as it does
+
+use crate::core::calculator::{ReleasePlan, VersionCalculator};
+use crate::core::ignore_revs;
+use crate::models::error::VNextError;
+use crate::models::options::VNextOptions;
+use crate::models::version::VersionOverride;
+use crate::utils::output::OutputWriter;
+
+/// Run the explain command: print the type, scope, breaking flag, bump level and deciding
+/// rule of every analyzed commit, followed by the resulting version
+pub fn run_explain_command(options: &VNextOptions) -> Result<(), VNextError> {
+    let plan = VersionCalculator::from_options(".", options)
+        .with_ignore_revs(ignore_revs::load_for_run(options)?)
+        .run()?;
+    let rows = explain_rows(&plan);
+
+    let mut text = table(&rows);
+    text.push('\n');
+    text.push_str(&decision(&plan, options));
+    let json = serde_json::json!({
+        "current_version": options.walk.scheme.render(&plan.current_version),
+        "version": options.walk.scheme.render(&plan.next_version),
+        "bump": plan.bump.level(),
+        "forced": plan.version_override.as_ref().map(VersionOverride::kind),
+        "omitted_commits": plan.summary.omitted_commits,
+        "commits": rows.iter().map(|row| serde_json::json!({
+            "id": row.id,
+            "type": row.commit_type,
+            "scope": row.scope,
+            "breaking": row.breaking,
+            "level": row.level,
+            "reason": row.reason,
+        })).collect::<Vec<_>>(),
+    });
+    OutputWriter::stdout(options.format).result(&text, json)
+}
+
+/// One analyzed commit, newest first
+struct ExplainRow {
+    id: String,
+    commit_type: String,
+    scope: Option<String>,
+    breaking: bool,
+    level: &'static str,
+    reason: &'static str,
+}
+
+fn explain_rows(plan: &ReleasePlan) -> Vec<ExplainRow> {
+    plan.summary
+        .commits
+        .iter()
+        .filter_map(|commit| {
+            let bump = plan.summary.bump_of(&commit.commit_id)?;
+            Some(ExplainRow {
+                id: commit.commit_id.clone(),
+                commit_type: commit.commit_type.clone(),
+                scope: commit.scope.clone(),
+                breaking: commit.has_breaking_change,
+                level: bump.level.as_str(),
+                reason: bump.reason.as_str(),
+            })
+        })
+        .collect()
+}
+
+/// Commits as aligned columns under a header line; `-` marks a missing type or scope
+fn table(rows: &[ExplainRow]) -> String {
+    let header = ["SHA", "TYPE", "SCOPE", "BREAKING", "LEVEL", "REASON"].map(str::to_string);
+    let mut lines = vec![header];
+    for row in rows {
+        lines.push([
+            row.id[..7.min(row.id.len())].to_string(),
+            if row.commit_type.is_empty() { "-".to_string() } else { row.commit_type.clone() },
+            row.scope.clone().unwrap_or_else(|| "-".to_string()),
+            if row.breaking { "yes" } else { "no" }.to_string(),
+            row.level.to_string(),
+            row.reason.to_string(),
+        ]);
+    }
+    let widths: Vec<usize> = (0..6).map(|column| lines.iter().map(|line| line[column].chars().count()).max().unwrap_or(0)).collect();
+    let mut table = String::new();
+    for line in lines {
+        let cells: Vec<String> = line.iter().zip(&widths).map(|(cell, width)| format!("{:<width$}", cell, width = width)).collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+/// Final decision: the bump and the version it leads to, noting forced versions and
+/// commits past the commit limit
+fn decision(plan: &ReleasePlan, options: &VNextOptions) -> String {
+    let analyzed = plan.summary.commits.len();
+    let scheme = &options.walk.scheme;
+    let mut decision = format!(
+        "Decision: {} bump, {} -> {}",
+        plan.bump.level(), scheme.render(&plan.current_version), scheme.render(&plan.next_version)
+    );
+    match &plan.version_override {
+        _ if options.first_release => decision.push_str(" (first release of --initial-version)"),
+        Some(VersionOverride::Bump(_)) => decision.push_str(" (forced with --force-bump)"),
+        Some(VersionOverride::Version(_)) => decision.push_str(" (forced with --force-version)"),
+        None => decision.push_str(&format!(
+            " (highest level of {} commit{})",
+            analyzed,
+            if analyzed == 1 { "" } else { "s" }
+        )),
+    }
+    if plan.summary.omitted_commits > 0 {
+        decision.push_str(&format!(
+            "\n{} older commits past --max-commits were not analyzed",
+            plan.summary.omitted_commits
+        ));
+    }
+    decision
+}
@@ -3,9 +3,21 @@
 //! This module contains the implementation of each CLI command defined in
 //! the `Commands` enum in cli.rs.
 
+pub mod changelog;
+pub mod check;
 pub mod deploy_key;
+pub mod explain;
+pub mod review;
+pub mod simulate;
+pub mod stdin_range;
 pub mod vnext;
 
 // Re-export command functions
+pub use changelog::run_changelog_command;
+pub use check::run_check_command;
 pub use deploy_key::generate_deploy_key;
+pub use explain::run_explain_command;
+pub use review::run_review_command;
+pub use simulate::run_simulate_command;
+pub use stdin_range::run_stdin_range_command;
 pub use vnext::run_vnext_command;
\ No newline at end of file
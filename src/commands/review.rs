@@ -0,0 +1,117 @@
+//! Review command implementation
+
+use crate::core::calculator::{ReleasePlan, VersionCalculator};
+use crate::core::git;
+use crate::core::ignore_revs;
+use crate::core::version::{self, VersionBumpType};
+use crate::models::commit::Commit;
+use crate::models::error::VNextError;
+use crate::models::options::VNextOptions;
+use crate::models::review::{IgnoreRevs, RevDecision};
+use crate::utils::output::OutputWriter;
+use std::io::{self, IsTerminal, Write};
+
+/// Run the review command: list the pending commits with their classification, let the
+/// user hide commits or mark them as no-ops, and create the release tag on confirmation.
+///
+/// Decisions are written to the ignore-revs file as soon as they are made, so later
+/// runs of vnext honor them whether or not the release is tagged.
+///
+/// The review itself is shown on stderr; only the name of the created tag is written
+/// to stdout.
+pub fn run_review_command(options: &VNextOptions) -> Result<(), VNextError> {
+    let path = ignore_revs::ignore_revs_path(options);
+    if !io::stdin().is_terminal() {
+        return Err(VNextError::ConfigError(format!(
+            "stdin is not a terminal; vnext review is interactive. To hide commits or mark them as no-ops without it, add `<sha> hide` or `<sha> noop` lines to {}",
+            path.display()
+        )));
+    }
+
+    let mut decisions = ignore_revs::load_for_run(options)?;
+    // Every pending commit is listed, including the hidden ones, so they can be restored
+    let all_commits = VersionCalculator::from_options(".", options)
+        .with_ignore_revs(IgnoreRevs::new())
+        .run()?
+        .summary
+        .commits;
+
+    loop {
+        let plan = VersionCalculator::from_options(".", options)
+            .with_ignore_revs(decisions.clone())
+            .run()?;
+        print_review(&plan, &all_commits, &decisions, options);
+
+        eprint!("[h N] hide, [n N] no-op, [y] tag {}, [q] quit: ", release_tag(options, &plan));
+        io::stderr().flush().map_err(|e| VNextError::Other(format!("Failed to flush stderr: {}", e)))?;
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            eprintln!();
+            return Ok(());
+        }
+
+        let mut words = input.split_whitespace();
+        match (words.next(), words.next().and_then(|n| n.parse::<usize>().ok())) {
+            (Some(command @ ("h" | "n")), Some(n)) if (1..=all_commits.len()).contains(&n) => {
+                // Choosing the current decision again clears it
+                let wanted = if command == "h" { RevDecision::Hide } else { RevDecision::Noop };
+                let commit_id = &all_commits[n - 1].commit_id;
+                let decision = decisions.decision(commit_id);
+                decisions.set(commit_id, if decision == Some(wanted) { None } else { Some(wanted) });
+                ignore_revs::save_ignore_revs(&path, &decisions)?;
+            }
+            (Some("y"), None) => return create_release_tag(options, &plan),
+            (Some("q"), None) => {
+                eprintln!("Aborted; no tag was created");
+                return Ok(());
+            }
+            _ => eprintln!("Unknown command: {}", input.trim()),
+        }
+    }
+}
+
+/// Print the calculated version and every pending commit with its classification
+fn print_review(plan: &ReleasePlan, commits: &[Commit], decisions: &IgnoreRevs, options: &VNextOptions) {
+    eprintln!();
+    eprintln!("{} -> {} ({} bump)", plan.current_version, plan.next_version, plan.bump.level());
+    eprintln!();
+    // Commits are listed oldest first, like in the changelog
+    for (i, commit) in commits.iter().enumerate().rev() {
+        let decision = decisions.decision(&commit.commit_id);
+        let label = match decision {
+            Some(RevDecision::Hide) => "hidden",
+            Some(RevDecision::Noop) => "no-op",
+            None if commit.is_empty || commit.out_of_scope || commit.skip_release => "no-op",
+            None => match version::classify_commit(commit, &options.bump_map) {
+                VersionBumpType::Major => "major",
+                VersionBumpType::Minor => "minor",
+                VersionBumpType::Patch => "patch",
+                VersionBumpType::NoOp => "no-op",
+            },
+        };
+        let header = commit.raw_message.lines().next().unwrap_or("");
+        // Commits with a review decision are marked with a star
+        let marker = if decision.is_some() { '*' } else { ' ' };
+        let short_id = &commit.commit_id[..7.min(commit.commit_id.len())];
+        eprintln!("{:>4}.{}[{:<6}] {} {}", i + 1, marker, label, short_id, header);
+    }
+    eprintln!();
+}
+
+/// Name of the tag the release will be published under
+fn release_tag(options: &VNextOptions, plan: &ReleasePlan) -> String {
+    options.walk.release_tag_name(&plan.next_version)
+}
+
+/// Create the release tag at HEAD, refusing to move an existing tag
+fn create_release_tag(options: &VNextOptions, plan: &ReleasePlan) -> Result<(), VNextError> {
+    let name = release_tag(options, plan);
+    let repo = git::open_repository_with(".", options.git_dir.as_deref())?;
+    if repo.find_reference(&format!("refs/tags/{}", name)).is_ok() {
+        return Err(VNextError::ConfigError(format!("Tag {} already exists", name)));
+    }
+    let head = repo.find_object(plan.head_commit, None)?;
+    repo.tag_lightweight(&name, &head, false)?;
+    eprintln!("Created tag {}; push it with `git push origin {}`", name, name);
+    OutputWriter::stdout(options.format).result(&name, serde_json::json!({ "tag": name }))
+}
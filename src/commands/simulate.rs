@@ -0,0 +1,145 @@
+//! Simulate command implementation
+
+use crate::core::changelog;
+use crate::core::footers;
+use crate::core::git;
+use crate::core::ignore_revs;
+use crate::core::progress::ProgressReporter;
+use crate::core::version;
+use crate::models::changeset::ChangesetSummary;
+use crate::models::commit::Commit;
+use crate::models::error::VNextError;
+use crate::models::options::{ChangelogOptions, OutputFormat, VNextOptions, WalkOptions, DEFAULT_CHANGELOG_HEADING};
+use crate::models::repo::{RepoInfo, WorkingTreeStatus};
+use crate::models::version::{BumpLevel, BumpReason, CommitBump, VersionBump};
+use crate::utils::output::OutputWriter;
+use semver::Version;
+
+/// Result of a what-if simulation
+pub struct Simulation {
+    pub current_version: Version,
+    pub next_version: Version,
+    pub bump: VersionBump,
+    pub summary: ChangesetSummary,
+    pub working_tree: WorkingTreeStatus,
+}
+
+/// Run the simulate command: classify the repository's pending commits plus the
+/// given synthetic commit messages and print the resulting version
+pub fn run_simulate_command(options: &VNextOptions, messages: &[String], format: OutputFormat) -> Result<(), VNextError> {
+    let simulation = simulate(options, messages)?;
+
+    // Mark the preview so it cannot be mistaken for real release notes
+    let heading = options.changelog.heading.as_deref().unwrap_or(DEFAULT_CHANGELOG_HEADING);
+    let changelog_options = ChangelogOptions {
+        heading: Some(format!("{} (simulated)", heading)),
+        ..options.changelog.clone()
+    };
+    let preview = changelog::format_changelog_with_options(
+        &simulation.summary,
+        &simulation.next_version,
+        &simulation.current_version,
+        &RepoInfo::new(),
+        &changelog_options,
+    );
+
+    let text = format!(
+        "{} ({} bump from {})\n\n{}",
+        options.walk.scheme.render(&simulation.next_version),
+        simulation.bump.level(),
+        options.walk.scheme.render(&simulation.current_version),
+        preview
+    );
+    let json = serde_json::json!({
+        "simulated": true,
+        "current_version": options.walk.scheme.render(&simulation.current_version),
+        "version": options.walk.scheme.render(&simulation.next_version),
+        "bump": simulation.bump.level(),
+        "dirty": simulation.working_tree.is_dirty(options.allow_untracked),
+        "changelog": preview,
+        "commits": simulation.summary.commits.iter().map(commit_json).collect::<Vec<_>>(),
+    });
+    OutputWriter::stdout(format).result(&text, json)
+}
+
+/// JSON representation of a commit, including its footers
+pub fn commit_json(commit: &Commit) -> serde_json::Value {
+    serde_json::json!({
+        "id": commit.commit_id,
+        "type": commit.commit_type,
+        "scope": commit.scope,
+        "scopes": commit.scopes,
+        "title": commit.display_title(),
+        "breaking": commit.has_breaking_change,
+        "footers": commit.footers.iter()
+            .map(|(token, value)| serde_json::json!({ "token": token, "value": value }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Classify the pending commits of the current repository together with `messages`,
+/// treating the messages as if they were committed on top of HEAD.
+///
+/// The repository is only read; outside a repository the baseline is the initial version
+/// (0.0.0 by default) with no commits.
+pub fn simulate(options: &VNextOptions, messages: &[String]) -> Result<Simulation, VNextError> {
+    let progress = ProgressReporter::from_options(options);
+    let parser = options.parser.parser()?;
+    let walk = WalkOptions {
+        ignore_revs: ignore_revs::load_for_run(options)?,
+        ..options.walk.clone()
+    };
+
+    let repo = git::open_repository_with(".", options.git_dir.as_deref()).ok();
+    let head = repo.as_ref().and_then(|repo| git::resolve_head(repo).ok());
+    let (current_version, bump, mut summary, working_tree) = match (&repo, &head) {
+        (Some(repo), Some(head)) => {
+            git::check_shallow(repo, walk.allow_shallow)?;
+            let base = version::find_version_base(repo, head, &walk, &progress)?;
+            let (bump, summary) = version::calculate_version_bump(
+                repo, base.tag_commit(), head, &*parser, &options.bump_map, &walk, &progress,
+            )?;
+            (base.version, bump, summary, git::working_tree_status(repo)?)
+        }
+        _ => {
+            let initial_version = walk.initial_base_version();
+            log::debug!("No repository baseline found, simulating from {}", initial_version);
+            (
+                initial_version,
+                VersionBump::from(BumpLevel::None),
+                ChangesetSummary::new(),
+                WorkingTreeStatus::default(),
+            )
+        }
+    };
+
+    // Synthetic commits are newer than HEAD, and the last message is the newest
+    let mut level = bump.bump_level();
+    let mut simulated = Vec::with_capacity(messages.len());
+    let mut simulated_bumps = Vec::with_capacity(messages.len());
+    let skip_markers = walk.skip_release_markers();
+    for (i, message) in messages.iter().enumerate().rev() {
+        let mut commit = parser.parse_commit(format!("simulated-{}", i + 1), message.clone());
+        footers::apply_footer_rules(&mut commit, &walk.footer_rules);
+        commit.out_of_scope = !version::commit_in_scope(&commit, &walk);
+        commit.skip_release = version::has_skip_marker(&commit, &skip_markers);
+        let (bump_type, reason) = if commit.skip_release {
+            (version::VersionBumpType::NoOp, BumpReason::SkipRelease)
+        } else if commit.out_of_scope {
+            (version::VersionBumpType::NoOp, BumpReason::OutOfScope)
+        } else {
+            version::explain_commit(&commit, &options.bump_map)
+        };
+        simulated_bumps.push(CommitBump { commit_id: commit.commit_id.clone(), level: bump_type.level(), reason });
+        version::record_bump(&mut level, &mut summary, bump_type, &commit.commit_id);
+        simulated.push(commit);
+    }
+    summary.commits.splice(0..0, simulated);
+    summary.bumps.splice(0..0, simulated_bumps);
+    let bump = VersionBump::from(level);
+
+    let next_version = walk.next_version(&current_version, &bump);
+    log::debug!("Simulated next version: {}", next_version);
+
+    Ok(Simulation { current_version, next_version, bump, summary, working_tree })
+}
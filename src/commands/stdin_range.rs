@@ -0,0 +1,109 @@
+//! Stdin range command implementation, for server-side pre-receive hooks
+
+use crate::commands::simulate::commit_json;
+use crate::core::calculator::ReleasePlan;
+use crate::core::git;
+use crate::core::ignore_revs;
+use crate::core::progress::ProgressReporter;
+use crate::core::push::{self, RefUpdate};
+use crate::core::release;
+use crate::core::version;
+use crate::models::commit::CommitParser;
+use crate::models::error::VNextError;
+use crate::models::options::{OutputFormat, VNextOptions, WalkOptions};
+use crate::utils::output::OutputWriter;
+use std::io::Read;
+
+/// Run vnext on the ref updates of a push: read `<old> <new> <ref>` lines from `input`
+/// and print one JSON object per ref with the bump and commits of the pushed range.
+///
+/// Works in bare repositories, including the quarantined objects of a pre-receive hook.
+pub fn run_stdin_range_command(options: &VNextOptions, input: &mut dyn Read) -> Result<(), VNextError> {
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+    let updates = push::parse_ref_updates(&text)?;
+
+    let progress = ProgressReporter::from_options(options);
+    let parser = options.parser.parser()?;
+    let walk = WalkOptions {
+        ignore_revs: ignore_revs::load_for_run(options)?,
+        ..options.walk.clone()
+    };
+    let repo = git::open_repository_with(".", options.git_dir.as_deref())?;
+    let trunk = walk.trunk.clone().or_else(|| git::find_trunk_branch(&repo));
+    let mut writer = OutputWriter::stdout(OutputFormat::Json);
+    for update in &updates {
+        let json = ref_update_json(&repo, update, trunk.as_deref(), options, &*parser, &walk, &progress)?;
+        writer.result("", json)?;
+    }
+    Ok(())
+}
+
+/// JSON result of one ref update: the bump, commits and changelog of the pushed range,
+/// and the version the ref would be released as
+fn ref_update_json(
+    repo: &git2::Repository,
+    update: &RefUpdate,
+    trunk: Option<&str>,
+    options: &VNextOptions,
+    parser: &dyn CommitParser,
+    walk: &WalkOptions,
+    progress: &ProgressReporter,
+) -> Result<serde_json::Value, VNextError> {
+    let sha = |oid: Option<git2::Oid>| oid.map(|oid| oid.to_string());
+    let Some(new) = update.new else {
+        return Ok(serde_json::json!({
+            "ref": update.refname,
+            "old_sha": sha(update.old),
+            "new_sha": null,
+            "deleted": true,
+        }));
+    };
+    let head = repo.find_commit(new)?;
+    let base = push::range_base(repo, update, trunk)?;
+    let base_commit = base.map(|oid| repo.find_commit(oid)).transpose()?;
+    let (bump, summary) = version::calculate_version_bump(
+        repo, base_commit.as_ref(), &head, parser, &options.bump_map, walk, progress,
+    )?;
+
+    // The release of the ref counts every commit since its latest tag, not just the pushed ones
+    let release_base = version::find_version_base(repo, &head, walk, progress)?;
+    let (release_bump, _) = version::calculate_version_bump(
+        repo, release_base.tag_commit(), &head, parser, &options.bump_map, walk, progress,
+    )?;
+    let next_version = walk.next_version(&release_base.version, &release_bump);
+
+    let commits = summary.commits.iter().map(commit_json).collect::<Vec<_>>();
+    let breaking_changes = summary.breaking_changes().count();
+    let changelog = if options.show_changelog {
+        let mut plan = ReleasePlan {
+            current_version: release_base.version.clone(),
+            next_version: next_version.clone(),
+            bump,
+            version_override: None,
+            summary,
+            repo_info: git::get_repo_info_from_remote(repo, options.walk.remote_name()),
+            base_commit: base.unwrap_or_else(|| git::root_commit(&head).id()),
+            head_commit: new,
+            previous_tag: release_base.latest_tag.as_ref().map(|(name, _)| name.clone()),
+            working_tree: Default::default(),
+            changelog: None,
+        };
+        Some(release::render_changelog(&mut plan, options)?)
+    } else {
+        None
+    };
+    Ok(serde_json::json!({
+        "ref": update.refname,
+        "old_sha": sha(update.old),
+        "new_sha": new.to_string(),
+        "base_sha": sha(base),
+        "bump": bump.level(),
+        "commit_count": commits.len(),
+        "breaking_changes": breaking_changes,
+        "commits": commits,
+        "current_version": walk.scheme.render(&release_base.version),
+        "version": walk.scheme.render(&next_version),
+        "changelog": changelog,
+    }))
+}
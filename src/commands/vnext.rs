@@ -2,118 +2,363 @@
 
 use crate::models::error::VNextError;
 use crate::core::git;
-use crate::core::version;
+use crate::core::calculator::{ReleasePlan, VersionCalculator};
 use crate::core::changelog;
-use crate::parsers::{ParserFactory, ParserStrategy};
+use crate::core::ignore_revs;
+use crate::core::progress::ProgressReporter;
+use crate::core::post_process;
+use crate::core::provenance;
+use crate::core::release;
+use crate::core::version;
+use crate::models::changeset::{ChangesetSummary, ReleaseCounts};
+use crate::models::options::{FetchMode, OutputFormat, VNextOptions};
+use crate::models::repo::WorkingTreeStatus;
+use crate::utils::date;
+use crate::utils::color;
+use crate::utils::fs;
+use crate::utils::github_output;
+use crate::utils::output::OutputWriter;
+use std::path::Path;
 
 /// Run the vnext command
-pub fn run_vnext_command(
-    parser_name: &str,
-    breaking_pattern: &str,
-    type_pattern: &str,
-    title_pattern: &str,
-    body_pattern: &str,
-    scope_pattern: &str,
-    major_commit_types: &str,
-    minor_commit_types: &str,
-    noop_commit_types: &str,
-    show_changelog: bool,
-    no_header_scaling: bool,
-    current: bool,
-) -> Result<(), VNextError> {
-    // Parse comma-separated commit types
-    let major_types: Vec<&str> = major_commit_types.split(',').map(|s| s.trim()).collect();
-    let minor_types: Vec<&str> = minor_commit_types.split(',').map(|s| s.trim()).collect();
-    let noop_types: Vec<&str> = noop_commit_types.split(',').map(|s| s.trim()).collect();
-    
-    log::debug!("Using commit types:");
-    log::debug!("  Major types: {:?}", major_types);
-    log::debug!("  Minor types: {:?}", minor_types);
-    log::debug!("  No-op types: {:?}", noop_types);
+pub fn run_vnext_command(options: &VNextOptions) -> Result<(), VNextError> {
+    let show_changelog = options.show_changelog;
+    let progress = ProgressReporter::from_options(options);
+    fetch_release_tags(options)?;
+    let decisions = ignore_revs::load_for_run(options)?;
+    let calculator = VersionCalculator::from_options(".", options).with_ignore_revs(decisions.clone());
+
+    // If --current flag is set, output the current version and return early
+    if options.current {
+        return match calculator.current_version() {
+            Ok(current_version) => {
+                let json = changelog::result_json(&current_version, &current_version, None, &options.walk.scheme);
+                let text = options.walk.scheme.render(&current_version);
+                OutputWriter::stdout(options.format).result(&colored(options, text, &[]), json)
+            }
+            Err(e) if is_unborn_head(&e) || (options.lenient && is_missing_repository(&e)) => {
+                log::debug!("No Git repository or HEAD found: {}. Assuming the initial version.", e);
+                output_fallback(options)
+            }
+            Err(e) => Err(e),
+        };
+    }
+
+    // Calculate version
+    let mut plan = match calculator.run() {
+        Ok(plan) => plan,
+        Err(e @ (VNextError::Cancelled | VNextError::ConfigError(_))) => return Err(e),
+        // Nothing is committed yet in a freshly initialized repository
+        Err(e) if is_unborn_head(&e) || (options.lenient && is_missing_repository(&e)) => {
+            log::debug!("No Git repository or HEAD found: {}. Assuming the initial version.", e);
+            return output_fallback(options);
+        }
+        Err(e) if options.lenient => {
+            log::error!("Failed to calculate version: {}", e);
+            return output_fallback(options);
+        }
+        Err(e) => return Err(e),
+    };
+    // Releasing from a dirty working tree usually means a misconfigured pipeline
+    check_working_tree(options, &plan.working_tree)?;
+    check_warnings(options, &plan.summary)?;
+    let repo = git::open_repository_with(".", options.git_dir.as_deref())?;
+    let render = show_changelog || options.changelog_out.is_some() || options.github_output;
+    let labels_affect_bump = options.labels_affect_bump && !options.changelog_exclude_labels.is_empty();
+    if render || labels_affect_bump {
+        release::attribute_commits(&repo, &mut plan, options, &progress)?;
+    }
+    // Pull request labels are only known once the commits are attributed
+    if labels_affect_bump {
+        release::exclude_labeled_from_bump(Path::new("."), &mut plan, options, &decisions)?;
+    }
+    // A re-run release job must not report a version whose tag is already taken
+    check_release_tag(&repo, options, &plan)?;
     
-    // Create the appropriate parser based on the strategy
-    log::debug!("Using parser strategy: {}", parser_name);
+    // Record provenance before changelog filters drop any commits
+    let provenance = match &options.provenance_out {
+        Some(_) => {
+            let head_time = repo.find_commit(plan.head_commit)?.time().seconds();
+            let provenance = provenance::build_provenance(&plan, options, head_time);
+            Some(serde_json::to_string_pretty(&provenance).map_err(|e| VNextError::Other(e.to_string()))?)
+        }
+        None => None,
+    };
     
-    let strategy = match parser_name {
-        "conventional" => {
-            log::debug!("Selected conventional commit parser strategy");
-            ParserStrategy::Conventional
-        },
-        "custom" => {
-            log::debug!("Selected custom regex parser strategy");
-            ParserStrategy::CustomRegex {
-                commit_type_pattern: type_pattern.to_string(),
-                title_pattern: title_pattern.to_string(),
-                body_pattern: body_pattern.to_string(),
-                breaking_pattern: breaking_pattern.to_string(),
-                scope_pattern: scope_pattern.to_string(),
-            }
-        },
-        _ => {
-            log::warn!("Unknown parser strategy '{}', falling back to conventional", parser_name);
-            ParserStrategy::Conventional
+    // Counted before changelog filters drop any commits
+    let range = commit_range(&plan);
+    let breaking_headers = changelog::breaking_entry_headers(&plan.summary, &options.changelog);
+    let commit_types = plan.summary.type_counts();
+    let breaking_changes = plan.summary.breaking_changes().count();
+    let (stats, release_counts) = match options.format {
+        OutputFormat::Json => {
+            // Authors are only resolved for changelogs, so attribute a copy of the commits
+            let mut attributed = plan.summary.clone();
+            git::populate_local_authors(&repo, &mut attributed);
+            let mut stats = release_stats(&repo, &attributed, plan.previous_tag.as_deref(), plan.head_commit)?;
+            // Sections count the commits the changelog lists, after its filters
+            let mut listed = attributed.clone();
+            release::filter_changelog_commits(&mut listed, options);
+            stats["sections"] = serde_json::json!(changelog::section_counts(&listed));
+            (Some(stats), attributed.release_counts())
         }
+        OutputFormat::Text => (None, ReleaseCounts::default()),
     };
     
-    let parser = ParserFactory::create(&strategy);
-    log::debug!("Parser initialized: {}", parser.name());
+    // Build metadata only decorates the released version; the changelog heading keeps the bare one
+    let mut released_version = plan.next_version.clone();
+    if let Some(template) = &options.build_metadata {
+        let release_date = options.changelog.release_date.clone().unwrap_or_else(date::today_utc);
+        released_version.build = version::render_build_metadata(template, &plan.head_commit.to_string(), plan.summary.commits.len(), &release_date)
+            .map_err(VNextError::ConfigError)?;
+    }
+    
+    // Render and post-process the changelog once so stdout and the changelog file match
+    let changelog_text = if render {
+        Some(release::render_changelog(&mut plan, options)?)
+    } else {
+        None
+    };
+    let ReleasePlan { current_version, bump, version_override, summary, working_tree, .. } = plan;
+    
+    // Write the version and changelog files before printing so a single run produces both
+    write_output_files(options, &options.walk.scheme.render(&released_version), changelog_text.as_deref())?;
+    if let (Some(path), Some(json)) = (&options.provenance_out, &provenance) {
+        fs::write_atomic(path, &options.line_ending.apply(&format!("{}\n", json)))?;
+    }
+    write_github_output(
+        options,
+        &options.walk.scheme.render(&released_version),
+        &options.walk.scheme.render(&current_version),
+        bump.level(),
+        changelog_text.as_deref(),
+    )?;
+    
+    // Output result
+    let changelog_text = changelog_text.filter(|_| show_changelog);
+    let mut json = changelog::result_json(&released_version, &current_version, changelog_text.as_deref(), &options.walk.scheme);
+    json["bump"] = bump.level().into();
+    json["forced"] = version_override.as_ref().map(|o| o.kind()).into();
+    json["commit_types"] = serde_json::json!(commit_types);
+    json["breaking_changes"] = breaking_changes.into();
+    json["stats"] = stats.into();
+    json["truncated"] = summary.truncated.into();
+    if summary.truncated {
+        json["omitted_commits"] = summary.omitted_commits.into();
+    }
+    json["warnings"] = serde_json::json!(summary.diagnostics);
+    json["summary"] = summary_json(&release_counts);
+    json["dirty"] = working_tree.is_dirty(options.allow_untracked).into();
+    let mut text = changelog::compose_sections(&options.print_sections(), &options.walk.scheme.render(&released_version), changelog_text.as_deref());
+    if options.show_range {
+        text.push('\n');
+        for (key, value) in &range {
+            let value = value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string());
+            text.push_str(&format!("\n{}: {}", key, value));
+        }
+    }
+    for (key, value) in range {
+        json[key] = value;
+    }
+    OutputWriter::stdout(options.format).result(&colored(options, text, &breaking_headers), json)?;
+    // Warnings come last so they are not lost above a long changelog
+    summary.diagnostics.log();
+    Ok(())
+}
 
-    // Open repository and handle errors
-    let repo = match git::open_repository() {
-        Ok(repo) => repo,
-        Err(e) => {
-            log::debug!("No Git repository found: {}. Assuming version 0.0.0.", e);
-            changelog::output_fallback(show_changelog);
-            return Ok(());
+/// The commit range the plan analyzed: `base_sha..head_sha` in Git's range notation.
+/// Without a previous release the whole history up to HEAD is analyzed, and the base and
+/// its tag are null. The count excludes commits outside a monorepo `--path`.
+fn commit_range(plan: &ReleasePlan) -> Vec<(&'static str, serde_json::Value)> {
+    let base_sha = plan.previous_tag.as_ref().map(|_| plan.base_commit.to_string());
+    vec![
+        ("base_sha", base_sha.into()),
+        ("head_sha", plan.head_commit.to_string().into()),
+        ("base_ref", plan.previous_tag.clone().into()),
+        ("commit_count", (plan.summary.commits.len() + plan.summary.omitted_commits).into()),
+    ]
+}
+
+/// Statistics of the release range for the JSON output: the dates of the first and last
+/// commit, the days from the previous release to HEAD and the commits of each author.
+/// `summary` must have its authors populated
+fn release_stats(
+    repo: &git2::Repository,
+    summary: &ChangesetSummary,
+    previous_tag: Option<&str>,
+    head_commit: git2::Oid,
+) -> Result<serde_json::Value, VNextError> {
+    let range = summary.commit_time_range();
+    let authored = summary.author_time_range();
+    let days_since_previous_release = match previous_tag {
+        Some(tag) => {
+            let tag_time = repo.revparse_single(&format!("refs/tags/{}", tag))?.peel_to_commit()?.time().seconds();
+            let head_time = repo.find_commit(head_commit)?.time().seconds();
+            Some((head_time - tag_time).div_euclid(86_400))
         }
+        None => None,
     };
+    Ok(serde_json::json!({
+        "first_commit_at": range.map(|(first, _)| date::format_epoch_timestamp(first)),
+        "last_commit_at": range.map(|(_, last)| date::format_epoch_timestamp(last)),
+        "first_authored_at": authored.map(|(first, _)| date::format_epoch_timestamp(first)),
+        "last_authored_at": authored.map(|(_, last)| date::format_epoch_timestamp(last)),
+        "days_since_previous_release": days_since_previous_release,
+        "commits_per_author": summary.commits_per_author(),
+    }))
+}
 
-    // Resolve HEAD and handle errors
-    let head = match git::resolve_head(&repo) {
-        Ok(head) => head,
+/// Fetch tags from `origin` with `--fetch`, so tags pushed after the checkout count
+fn fetch_release_tags(options: &VNextOptions) -> Result<(), VNextError> {
+    let Some(mode) = options.fetch else { return Ok(()) };
+    match git::open_repository_with(".", options.git_dir.as_deref()).and_then(|repo| git::fetch_tags(&repo, options.ssh_key.as_deref())) {
+        Ok(()) => Ok(()),
+        Err(e) if mode == FetchMode::Required => Err(e),
         Err(e) => {
-            log::debug!("Failed to resolve HEAD: {}. Assuming version 0.0.0.", e);
-            changelog::output_fallback(show_changelog);
-            return Ok(());
+            log::warn!("Failed to fetch tags from origin, using the local tags: {}", e);
+            Ok(())
         }
+    }
+}
+
+/// Fail when the release tag of the next version exists on another commit, unless
+/// `--allow-existing-tag` is set; a tag at HEAD means the release is already cut.
+///
+/// Without a bump the next version is the current one, whose tag is fine as long as
+/// HEAD contains it; a current release tagged on another branch is refused too.
+fn check_release_tag(repo: &git2::Repository, options: &VNextOptions, plan: &ReleasePlan) -> Result<(), VNextError> {
+    let name = options.walk.release_tag_name(&plan.next_version);
+    let tagged = match repo.find_reference(&format!("refs/tags/{}", name)).and_then(|r| r.peel_to_commit()) {
+        Ok(commit) => commit.id(),
+        // No tag, or one that does not name a commit
+        Err(_) => return Ok(()),
     };
-    log::debug!("HEAD commit: {}", head.id());
+    if tagged == plan.head_commit {
+        log::warn!("Tag {} already points at HEAD; this release is already cut", name);
+    } else if !options.allow_existing_tag
+        && (plan.next_version != plan.current_version || !repo.graph_descendant_of(plan.head_commit, tagged)?)
+    {
+        return Err(VNextError::TagExists(format!(
+            "{} points at {}, not HEAD; delete the tag or pass --allow-existing-tag",
+            name, &tagged.to_string()[..7]
+        )));
+    }
+    Ok(())
+}
 
-    // If --current flag is set, output the current version and return early
-    let (current_version, base_commit) = version::find_version_base(&repo, &head);
-    if current {
-        println!("{}", current_version);
+/// Fail with `--deny-warnings` when a commit message raised a warning, before any file is written
+fn check_warnings(options: &VNextOptions, summary: &ChangesetSummary) -> Result<(), VNextError> {
+    if !options.deny_warnings || summary.diagnostics.is_empty() {
         return Ok(());
     }
+    summary.diagnostics.log();
+    Err(VNextError::CommitWarnings(format!(
+        "{} warning{} with --deny-warnings; reword the commits or drop the flag",
+        summary.diagnostics.len(),
+        if summary.diagnostics.len() == 1 { "" } else { "s" }
+    )))
+}
 
-    // Calculate version
-    let (next_version, mut summary) = match version::calculate_version(
-        &repo, &head, &current_version, &base_commit, &*parser,
-        &major_types, &minor_types, &noop_types
-    ) {
-        Ok(result) => result,
-        Err(e) => {
-            log::error!("Failed to calculate version: {}", e);
-            changelog::output_fallback(show_changelog);
-            return Ok(());
-        }
+/// Warn about uncommitted changes, or fail with `--require-clean`
+fn check_working_tree(options: &VNextOptions, status: &WorkingTreeStatus) -> Result<(), VNextError> {
+    if !status.is_dirty(options.allow_untracked) {
+        return Ok(());
+    }
+    let description = format!("{} modified and {} untracked files", status.modified, status.untracked);
+    if options.require_clean {
+        Err(VNextError::RepositoryState(format!(
+            "Working tree is dirty: {}; commit or stash them{}",
+            description,
+            if status.modified == 0 { ", or pass --allow-untracked" } else { "" }
+        )))
+    } else {
+        log::warn!("Working tree has uncommitted changes ({})", description);
+        Ok(())
+    }
+}
+
+/// Write the `--version-file-out` and `--changelog-out` files if requested
+fn write_output_files(options: &VNextOptions, version: &str, changelog_text: Option<&str>) -> Result<(), VNextError> {
+    if let Some(path) = &options.version_file_out {
+        fs::write_atomic(path, &options.line_ending.apply(&format!("{}\n", version)))?;
+    }
+    if let (Some(path), Some(text)) = (&options.changelog_out, changelog_text) {
+        fs::write_atomic(path, &options.line_ending.apply(&format!("{}\n", text)))?;
+    }
+    Ok(())
+}
+
+/// Append the `--github-output` step outputs, warning instead when `GITHUB_OUTPUT` is not set
+fn write_github_output(
+    options: &VNextOptions,
+    version: &str,
+    current_version: &str,
+    bump: &str,
+    changelog_text: Option<&str>,
+) -> Result<(), VNextError> {
+    if !options.github_output {
+        return Ok(());
+    }
+    let Some(path) = std::env::var_os(github_output::GITHUB_OUTPUT_ENV).filter(|path| !path.is_empty()) else {
+        log::warn!("--github-output is set but {} is not; skipping the step outputs", github_output::GITHUB_OUTPUT_ENV);
+        return Ok(());
     };
-    
-    // Get repository information
-    let repo_info = git::get_repo_info(&repo);
-    
-    // Use GitHub integration if repository is on GitHub
-    let use_github = repo_info.is_github_repo;
-    
-    // Handle GitHub integration if needed
-    if show_changelog && use_github {
-        if let Err(e) = crate::core::github::enhance_with_github_info(&repo_info, &mut summary) {
-            log::warn!("Failed to fetch author information from GitHub API: {}", e);
+    let mut outputs = vec![("version", version), ("current_version", current_version), ("bump", bump)];
+    if let Some(changelog) = changelog_text {
+        outputs.push(("changelog", changelog));
+    }
+    github_output::append_outputs(Path::new(&path), &outputs)
+}
+
+/// The release summary for the JSON output: its counts and the rendered `text` line
+fn summary_json(counts: &ReleaseCounts) -> serde_json::Value {
+    let mut json = serde_json::json!(counts);
+    json["text"] = counts.to_string().into();
+    json
+}
+
+/// Output the fallback result (the initial version, 0.0.0 by default) and write any requested files
+fn output_fallback(options: &VNextOptions) -> Result<(), VNextError> {
+    let version = options.walk.initial_base_version();
+    let changelog_text = if options.show_changelog || options.changelog_out.is_some() || options.github_output {
+        Some(post_process::apply_post_process(options, changelog::fallback_changelog(&options.changelog, &version))?)
+    } else {
+        None
+    };
+    if !options.current {
+        let rendered = options.walk.scheme.render(&version);
+        write_output_files(options, &rendered, changelog_text.as_deref())?;
+        write_github_output(options, &rendered, &rendered, "none", changelog_text.as_deref())?;
+        if let Some(path) = &options.provenance_out {
+            log::warn!("No release to describe; not writing provenance to {}", path.display());
         }
     }
-    
-    // Output result
-    changelog::output_result(&next_version, &summary, show_changelog, no_header_scaling, &current_version, &repo_info);
-    
-    Ok(())
-}
\ No newline at end of file
+    let changelog_text = changelog_text.filter(|_| options.show_changelog);
+    let mut json = changelog::result_json(&version, &version, changelog_text.as_deref(), &options.walk.scheme);
+    json["summary"] = summary_json(&ReleaseCounts::default());
+    let text = changelog::compose_sections(&options.print_sections(), &options.walk.scheme.render(&version), changelog_text.as_deref());
+    OutputWriter::stdout(options.format).result(&colored(options, text, &[]), json)
+}
+
+/// Text result colored for a terminal under `--color`, with the entries of `breaking`
+/// commits in red; files never go through this
+fn colored(options: &VNextOptions, text: String, breaking: &[String]) -> String {
+    if options.format == OutputFormat::Text && color::color_stdout(options.color) {
+        color::colorize(&text, breaking)
+    } else {
+        text
+    }
+}
+
+/// Whether an error means there is no repository or no commit to calculate from
+fn is_missing_repository(error: &VNextError) -> bool {
+    matches!(error, VNextError::NotARepository(_)) || is_unborn_head(error)
+}
+
+/// Whether an error means the repository has no commits yet
+fn is_unborn_head(error: &VNextError) -> bool {
+    matches!(
+        error,
+        VNextError::GitError(e) if e.code() == git2::ErrorCode::UnbornBranch
+    )
+}
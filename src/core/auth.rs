@@ -0,0 +1,178 @@
+//! GitHub token resolution shared by every code path calling the GitHub API
+
+use crate::core::github::GITHUB_API_URL;
+use crate::models::error::VNextError;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Environment variables holding a GitHub token, in order of precedence
+pub const TOKEN_ENV_VARS: [&str; 2] = ["GITHUB_TOKEN", "GH_TOKEN"];
+
+/// How to authenticate with GitHub. Tokens are looked up in this order:
+///
+/// 1. `token` (`--github-token`)
+/// 2. the `GITHUB_TOKEN` and `GH_TOKEN` environment variables
+/// 3. `gh auth token`, when the GitHub CLI is installed and logged in
+/// 4. an installation token of the GitHub App in `app` (with the `github-app` feature)
+#[derive(Clone, Debug, Default)]
+pub struct GitHubAuth {
+    /// Token given on the command line
+    pub token: Option<String>,
+    /// GitHub App an installation token is created for
+    pub app: Option<GitHubAppCredentials>,
+}
+
+/// A GitHub App and one of its installations
+#[derive(Clone, Debug)]
+pub struct GitHubAppCredentials {
+    /// App ID (or client ID), the issuer of the app's JSON Web Tokens
+    pub app_id: String,
+    /// PEM file with the app's private key, as downloaded from GitHub
+    pub private_key: PathBuf,
+    /// ID of the installation the token is created for
+    pub installation_id: u64,
+}
+
+/// Where a resolved token came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenSource {
+    /// `--github-token`
+    Flag,
+    /// The named environment variable
+    Environment(&'static str),
+    /// `gh auth token`
+    GhCli,
+    /// An installation token of a GitHub App
+    GitHubApp,
+}
+
+impl GitHubAuth {
+    /// Resolve the token, if any source provides one
+    pub fn resolve_token(&self) -> Option<String> {
+        self.resolve().map(|(token, _)| token)
+    }
+
+    /// Resolve the token and its source, if any source provides one
+    pub fn resolve(&self) -> Option<(String, TokenSource)> {
+        let resolved = self.resolve_with(&|name| std::env::var(name).ok(), &gh_auth_token, &|app| {
+            installation_token(app, GITHUB_API_URL)
+                .map_err(|e| log::warn!("Failed to create a GitHub App installation token: {}", e))
+                .ok()
+        });
+        if let Some((_, source)) = &resolved {
+            log::debug!("Using the GitHub token from {:?}", source);
+        }
+        resolved
+    }
+
+    /// Resolve the token with the given environment lookup, GitHub CLI and GitHub App
+    /// token exchange; empty values are skipped
+    pub fn resolve_with(
+        &self,
+        env: &dyn Fn(&str) -> Option<String>,
+        gh: &dyn Fn() -> Option<String>,
+        exchange: &dyn Fn(&GitHubAppCredentials) -> Option<String>,
+    ) -> Option<(String, TokenSource)> {
+        let non_empty = |token: Option<String>| token.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+        if let Some(token) = non_empty(self.token.clone()) {
+            return Some((token, TokenSource::Flag));
+        }
+        for name in TOKEN_ENV_VARS {
+            if let Some(token) = non_empty(env(name)) {
+                return Some((token, TokenSource::Environment(name)));
+            }
+        }
+        if let Some(token) = non_empty(gh()) {
+            return Some((token, TokenSource::GhCli));
+        }
+        let app = self.app.as_ref()?;
+        non_empty(exchange(app)).map(|token| (token, TokenSource::GitHubApp))
+    }
+}
+
+/// Token of the GitHub CLI's logged-in account, if `gh` is installed and logged in
+pub fn gh_auth_token() -> Option<String> {
+    let output = Command::new("gh")
+        .args(["auth", "token"])
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        log::debug!("gh auth token failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Create an installation token of the GitHub App in `app` through the API at `api_url`
+#[cfg(feature = "github-app")]
+pub fn installation_token(app: &GitHubAppCredentials, api_url: &str) -> Result<String, VNextError> {
+    let pem = std::fs::read_to_string(&app.private_key).map_err(|e| {
+        VNextError::ConfigError(format!("Failed to read the GitHub App key {}: {}", app.private_key.display(), e))
+    })?;
+    let jwt = app_jwt(&pem, &app.app_id, crate::utils::date::now_epoch())?;
+    let response = reqwest::blocking::Client::new()
+        .post(format!("{}/app/installations/{}/access_tokens", api_url.trim_end_matches('/'), app.installation_id))
+        .header("Authorization", format!("Bearer {}", jwt))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "vnext-cli")
+        .send()
+        .map_err(|e| VNextError::GithubError(format!("Request failed: {}", e)))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().unwrap_or_default();
+        return Err(VNextError::GithubError(format!(
+            "Failed to create an installation token: status {}: {}",
+            status,
+            body.trim()
+        )));
+    }
+    let body: serde_json::Value = response
+        .json()
+        .map_err(|e| VNextError::GithubError(format!("Failed to parse response: {}", e)))?;
+    body["token"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| VNextError::GithubError("The installation token response has no token".to_string()))
+}
+
+/// Without the `github-app` feature, GitHub App credentials cannot be used
+#[cfg(not(feature = "github-app"))]
+pub fn installation_token(_app: &GitHubAppCredentials, _api_url: &str) -> Result<String, VNextError> {
+    Err(VNextError::ConfigError(
+        "GitHub App authentication needs vnext built with the github-app feature".to_string(),
+    ))
+}
+
+/// JSON Web Token (RS256) authenticating as the GitHub App `app_id` with the PEM private
+/// key `pem` (PKCS#1 or PKCS#8), valid from a minute before `now` for nine minutes
+#[cfg(feature = "github-app")]
+pub fn app_jwt(pem: &str, app_id: &str, now: i64) -> Result<String, VNextError> {
+    use rsa::pkcs1::DecodeRsaPrivateKey;
+    use rsa::pkcs8::DecodePrivateKey;
+    use rsa::signature::{SignatureEncoding, Signer};
+
+    let key = rsa::RsaPrivateKey::from_pkcs1_pem(pem)
+        .or_else(|_| rsa::RsaPrivateKey::from_pkcs8_pem(pem))
+        .map_err(|e| VNextError::ConfigError(format!("The GitHub App key is not an RSA private key: {}", e)))?;
+    let header = serde_json::json!({ "alg": "RS256", "typ": "JWT" });
+    // GitHub rejects tokens issued in the future, so allow for clock drift
+    let claims = serde_json::json!({ "iat": now - 60, "exp": now + 540, "iss": app_id });
+    let signing_input = format!("{}.{}", base64url(header.to_string().as_bytes()), base64url(claims.to_string().as_bytes()));
+    let signature = rsa::pkcs1v15::SigningKey::<rsa::sha2::Sha256>::new(key).sign(signing_input.as_bytes());
+    Ok(format!("{}.{}", signing_input, base64url(&signature.to_bytes())))
+}
+
+/// Unpadded URL-safe base64, as used in JSON Web Tokens
+#[cfg(feature = "github-app")]
+fn base64url(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+        }
+    }
+    encoded
+}
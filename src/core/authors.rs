@@ -0,0 +1,79 @@
+//! Author mapping from a vendored authors file
+
+use crate::models::authors::AuthorMapping;
+use crate::models::changeset::ChangesetSummary;
+use crate::models::error::VNextError;
+use regex::Regex;
+use std::path::Path;
+
+/// Email-to-author mapping loaded from an authors file.
+///
+/// The file is a JSON array of `{"email": ..., "name": ..., "username": ...}` entries.
+/// Exact emails take precedence over globs; globs are tried in file order.
+pub struct AuthorsFile {
+    exact: Vec<AuthorMapping>,
+    globs: Vec<(Regex, AuthorMapping)>,
+}
+
+impl AuthorsFile {
+    /// Load an authors file from `path`
+    pub fn load(path: &Path) -> Result<Self, VNextError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            VNextError::ConfigError(format!("Failed to read authors file {}: {}", path.display(), e))
+        })?;
+        Self::parse(&contents).map_err(|e| match e {
+            VNextError::ConfigError(message) => VNextError::ConfigError(format!("{}: {}", path.display(), message)),
+            other => other,
+        })
+    }
+
+    /// Parse the JSON contents of an authors file
+    pub fn parse(contents: &str) -> Result<Self, VNextError> {
+        let entries: Vec<AuthorMapping> = serde_json::from_str(contents)
+            .map_err(|e| VNextError::ConfigError(format!("Invalid authors file: {}", e)))?;
+        Self::from_entries(entries)
+    }
+
+    /// Build the mapping from a list of entries
+    pub fn from_entries(entries: Vec<AuthorMapping>) -> Result<Self, VNextError> {
+        let mut exact = Vec::new();
+        let mut globs = Vec::new();
+        for entry in entries {
+            if entry.email.contains('*') {
+                let pattern = entry.email.split('*').map(regex::escape).collect::<Vec<_>>().join(".*");
+                globs.push((Regex::new(&format!("(?i)^{}$", pattern))?, entry));
+            } else {
+                exact.push(entry);
+            }
+        }
+        Ok(AuthorsFile { exact, globs })
+    }
+
+    /// Find the mapping for an email address
+    pub fn lookup(&self, email: &str) -> Option<&AuthorMapping> {
+        self.exact
+            .iter()
+            .find(|entry| entry.email.eq_ignore_ascii_case(email))
+            .or_else(|| self.globs.iter().find(|(glob, _)| glob.is_match(email)).map(|(_, entry)| entry))
+    }
+
+    /// Apply the mapping to the authors in `summary`.
+    ///
+    /// Authors that already have a username from forge enrichment are left alone unless
+    /// `wins` is set. Unmatched emails are left unchanged.
+    pub fn apply(&self, summary: &mut ChangesetSummary, wins: bool) {
+        for author in summary.commits.iter_mut().filter_map(|c| c.author.as_mut()) {
+            if author.username.is_some() && !wins {
+                continue;
+            }
+            if let Some(mapping) = self.lookup(&author.email) {
+                if let Some(name) = &mapping.name {
+                    author.name = name.clone();
+                }
+                if let Some(username) = &mapping.username {
+                    author.username = Some(username.clone());
+                }
+            }
+        }
+    }
+}
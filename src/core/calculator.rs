@@ -0,0 +1,287 @@
+//! High-level version calculation API for library consumers
+
+use crate::core::git;
+use crate::core::progress::ProgressReporter;
+use crate::core::version;
+use crate::models::changeset::ChangesetSummary;
+use crate::models::error::VNextError;
+use crate::models::options::{ChangelogOptions, VNextOptions, WalkOptions};
+use crate::models::repo::{RepoInfo, WorkingTreeStatus};
+use crate::models::review::IgnoreRevs;
+use crate::models::version::{BumpLevel, BumpMap, VersionBump, VersionOverride, VersionScheme};
+use crate::parsers::{ParserHandle, ParserStrategy};
+use semver::Version;
+use std::path::{Path, PathBuf};
+
+/// Outcome of a version calculation
+pub struct ReleasePlan {
+    /// Version of the latest release (the initial version when there is none)
+    pub current_version: Version,
+    /// Version the pending commits bump to, or the forced version
+    pub next_version: Version,
+    /// Highest bump found in the pending commits, or the forced bump
+    pub bump: VersionBump,
+    /// Override applied instead of the calculated bump, if any
+    pub version_override: Option<VersionOverride>,
+    /// Pending commits and their bump counts
+    pub summary: ChangesetSummary,
+    /// Remote repository information
+    pub repo_info: RepoInfo,
+    /// Commit the pending commits were compared against
+    pub base_commit: git2::Oid,
+    /// Commit HEAD pointed to
+    pub head_commit: git2::Oid,
+    /// Name of the latest release tag, if there is one
+    pub previous_tag: Option<String>,
+    /// Uncommitted changes in the working tree
+    pub working_tree: WorkingTreeStatus,
+    /// Rendered changelog, only set by `calculate_release` when asked for
+    pub changelog: Option<String>,
+}
+
+/// Builder for calculating the next version of a repository.
+///
+/// ```no_run
+/// use vnext::{ParserStrategy, VersionCalculator};
+///
+/// let plan = VersionCalculator::for_repo(".")
+///     .with_parser(ParserStrategy::Conventional)
+///     .with_types(&["major"], &["feat", "minor"], &["chore", "noop"])
+///     .run()?;
+/// println!("{} -> {}", plan.current_version, plan.next_version);
+/// # Ok::<(), vnext::VNextError>(())
+/// ```
+pub struct VersionCalculator<'a> {
+    path: PathBuf,
+    git_dir: Option<PathBuf>,
+    parser: ParserHandle,
+    bump_map: BumpMap,
+    walk: WalkOptions,
+    changelog: ChangelogOptions,
+    version_override: Option<VersionOverride>,
+    allow_downgrade: bool,
+    first_release: bool,
+    progress: ProgressReporter<'a>,
+}
+
+impl<'a> VersionCalculator<'a> {
+    /// Create a calculator for the repository at `path` with the default settings
+    pub fn for_repo(path: impl AsRef<Path>) -> Self {
+        let defaults = VNextOptions::default();
+        VersionCalculator {
+            path: path.as_ref().to_path_buf(),
+            git_dir: None,
+            parser: defaults.parser,
+            bump_map: defaults.bump_map,
+            walk: defaults.walk,
+            changelog: defaults.changelog,
+            version_override: None,
+            allow_downgrade: false,
+            first_release: false,
+            progress: ProgressReporter::none(),
+        }
+    }
+
+    /// Create a calculator for the repository at `path` using the settings of a vnext run
+    pub fn from_options(path: impl AsRef<Path>, options: &'a VNextOptions) -> Self {
+        VersionCalculator {
+            path: path.as_ref().to_path_buf(),
+            git_dir: options.git_dir.clone(),
+            parser: options.parser.clone(),
+            bump_map: options.bump_map.clone(),
+            walk: options.walk.clone(),
+            changelog: options.changelog.clone(),
+            version_override: options.version_override.clone(),
+            allow_downgrade: options.allow_downgrade,
+            first_release: options.first_release,
+            progress: ProgressReporter::from_options(options),
+        }
+    }
+
+    /// Open the repository from `git_dir` instead of searching from the path (e.g. for
+    /// `git init --separate-git-dir` checkouts)
+    pub fn with_git_dir(mut self, git_dir: impl AsRef<Path>) -> Self {
+        self.git_dir = Some(git_dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set the strategy used to parse commit messages
+    pub fn with_parser(mut self, strategy: ParserStrategy) -> Self {
+        self.parser = ParserHandle::new(strategy);
+        self
+    }
+
+    /// Share an already created parser handle, so the parser is only built once
+    pub fn with_parser_handle(mut self, parser: ParserHandle) -> Self {
+        self.parser = parser;
+        self
+    }
+
+    /// Set the commit types that trigger major, minor and no-op bumps; every other type bumps the patch version
+    pub fn with_types(mut self, major: &[&str], minor: &[&str], noop: &[&str]) -> Self {
+        self.bump_map = BumpMap::from_types(major, minor, noop);
+        self
+    }
+
+    /// Set the version bump each commit type triggers
+    pub fn with_bump_map(mut self, bump_map: BumpMap) -> Self {
+        self.bump_map = bump_map;
+        self
+    }
+
+    /// Set the options controlling how commits are classified during the revwalk
+    pub fn with_walk_options(mut self, walk: WalkOptions) -> Self {
+        self.walk = walk;
+        self
+    }
+
+    /// Only consider release tags starting with `prefix` (e.g. `api-v` for `api-v1.2.3`)
+    pub fn with_tag_prefix(mut self, prefix: &str) -> Self {
+        self.walk.tag_prefix = Some(prefix.to_string());
+        self.changelog.tag_prefix = Some(prefix.to_string());
+        self
+    }
+
+    /// Only count commits touching files under `path`
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.walk.path = Some(path.to_string());
+        self
+    }
+
+    /// Use `trunk` as the trunk branch instead of detecting it
+    pub fn with_trunk(mut self, trunk: &str) -> Self {
+        self.walk.trunk = Some(trunk.to_string());
+        self
+    }
+
+    /// Only consider release tags under `namespace` (e.g. `releases/`)
+    pub fn with_tag_namespace(mut self, namespace: &str) -> Self {
+        self.walk.tag_namespace = Some(git::normalize_tag_namespace(namespace));
+        self.changelog.tag_namespace = Some(git::normalize_tag_namespace(namespace));
+        self
+    }
+
+    /// Set the options used to render the changelog of the plan
+    pub fn with_changelog_options(mut self, changelog: ChangelogOptions) -> Self {
+        self.changelog = changelog;
+        self
+    }
+
+    /// Options for rendering the changelog of the plan, with the tag namespace and prefix of the calculator
+    pub fn changelog_options(&self) -> &ChangelogOptions {
+        &self.changelog
+    }
+
+    /// Hide commits or force them to no-ops according to review decisions
+    pub fn with_ignore_revs(mut self, ignore_revs: IgnoreRevs) -> Self {
+        self.walk.ignore_revs = ignore_revs;
+        self
+    }
+
+    /// Release with this bump or version instead of the one the commits call for
+    pub fn with_version_override(mut self, version_override: VersionOverride) -> Self {
+        self.version_override = Some(version_override);
+        self
+    }
+
+    /// Allow a forced version that is not greater than the current version
+    pub fn with_allow_downgrade(mut self, allow_downgrade: bool) -> Self {
+        self.allow_downgrade = allow_downgrade;
+        self
+    }
+
+    /// Start versioning from `version` when no release tag exists
+    pub fn with_initial_version(mut self, version: Version) -> Self {
+        self.walk.initial_version = Some(version);
+        self
+    }
+
+    /// Stop the walk after `max` commits; the summary records how many were left out
+    pub fn with_max_commits(mut self, max: usize) -> Self {
+        self.walk.max_commits = Some(max);
+        self
+    }
+
+    /// Release the initial version as is, whatever the commit types; fails once a release tag exists
+    pub fn with_first_release(mut self, first_release: bool) -> Self {
+        self.first_release = first_release;
+        self
+    }
+
+    /// Report progress and check for cancellation through `progress`
+    pub fn with_progress(mut self, progress: ProgressReporter<'a>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Find the version of the latest release without walking the pending commits
+    pub fn current_version(&self) -> Result<Version, VNextError> {
+        let repo = git::open_repository_with(&self.path, self.git_dir.as_deref())?;
+        git::check_shallow(&repo, self.walk.allow_shallow)?;
+        let head = git::resolve_head(&repo)?;
+        let base = version::find_version_base(&repo, &head, &self.walk, &self.progress)?;
+        Ok(base.version)
+    }
+
+    /// Calculate the next version and the changes leading to it
+    pub fn run(&self) -> Result<ReleasePlan, VNextError> {
+        log::debug!("Using commit types:");
+        for (commit_type, level) in self.bump_map.entries() {
+            log::debug!("  {}: {}", commit_type, level);
+        }
+
+        let parser = self.parser.parser()?;
+
+        let repo = git::open_repository_with(&self.path, self.git_dir.as_deref())?;
+        git::check_shallow(&repo, self.walk.allow_shallow)?;
+        let head = git::resolve_head(&repo)?;
+        log::debug!("HEAD commit: {}", head.id());
+
+        let base = version::find_version_base(&repo, &head, &self.walk, &self.progress)?;
+        let (bump, summary) = version::calculate_version_bump(
+            &repo, base.tag_commit(), &head, &*parser,
+            &self.bump_map, &self.walk, &self.progress,
+        )?;
+        let current_version = base.version;
+        let previous_tag = base.latest_tag.map(|(name, _)| name);
+        let (next_version, bump) = if self.first_release {
+            if let Some(tag) = &previous_tag {
+                return Err(VNextError::ConfigError(format!(
+                    "--first-release only applies before the first release, but {} is already tagged; drop the flag",
+                    tag
+                )));
+            }
+            // Without a tag the current version is the initial version
+            (current_version.clone(), VersionBump::from(BumpLevel::None))
+        } else {
+            let (next_version, bump) = version::apply_version_override(
+                &current_version, bump, self.version_override.as_ref(), self.allow_downgrade,
+            )?;
+            // Calendar versions take their period from the release date, whatever the bump level
+            match (&self.walk.scheme, &self.version_override) {
+                (VersionScheme::Calver(_), None | Some(VersionOverride::Bump(_))) => (self.walk.next_version(&current_version, &bump), bump),
+                _ => (next_version, bump),
+            }
+        };
+
+        log::debug!(
+            "Version bump: major={}, minor={}, patch={}",
+            bump.major, bump.minor, bump.patch
+        );
+        log::debug!("Next version: {}", next_version);
+
+        Ok(ReleasePlan {
+            current_version,
+            next_version,
+            bump,
+            version_override: self.version_override.clone(),
+            summary,
+            repo_info: git::get_repo_info_from_remote(&repo, self.walk.remote_name()),
+            base_commit: base.base_commit.id(),
+            head_commit: head.id(),
+            previous_tag,
+            working_tree: git::working_tree_status(&repo)?,
+            changelog: None,
+        })
+    }
+}
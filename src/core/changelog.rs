@@ -1,8 +1,14 @@
 //! Changelog generation
 
-use crate::models::changeset::ChangesetSummary;
+use crate::models::changeset::{pluralize, ChangesetSummary};
+use crate::models::commit::{Commit, CommitAuthor};
+use crate::models::options::{ChangelogOptions, ChangelogOrder, PrintSection, DEFAULT_CHANGELOG_HEADING, DEFAULT_HEADER_SCALING_OFFSET};
 use crate::models::repo::RepoInfo;
+use crate::models::version::VersionScheme;
+use crate::parsers::trailers;
+use crate::utils::date;
 use semver::Version;
+use std::collections::BTreeMap;
 
 /// Format a changelog from a commit summary
 ///
@@ -15,124 +21,698 @@ pub fn format_changelog(
     current_version: &Version,
     repo_info: &RepoInfo,
 ) -> String {
-    let mut changelog = format!("### What's changed in v{}\n\n", next_version);
+    let options = ChangelogOptions {
+        no_header_scaling,
+        ..ChangelogOptions::default()
+    };
+    format_changelog_with_options(summary, next_version, current_version, repo_info, &options)
+}
+
+/// Render the changelog heading, substituting the `{version}`, `{previous_version}`
+/// and `{date}` placeholders
+pub fn render_heading(
+    options: &ChangelogOptions,
+    next_version: &Version,
+    current_version: &Version,
+) -> String {
+    let template = options.heading.as_deref().unwrap_or(DEFAULT_CHANGELOG_HEADING);
+    let mut heading = template
+        .replace("{version}", &options.scheme.render(next_version))
+        .replace("{previous_version}", &options.scheme.render(current_version));
+    if heading.contains("{date}") {
+        let release_date = options.release_date.clone().unwrap_or_else(date::today_utc);
+        heading = heading.replace("{date}", &release_date);
+    }
+    heading
+}
+
+/// Format a changelog from a commit summary using the given changelog options
+pub fn format_changelog_with_options(
+    summary: &ChangesetSummary,
+    next_version: &Version,
+    current_version: &Version,
+    repo_info: &RepoInfo,
+    options: &ChangelogOptions,
+) -> String {
+    let scaling_offset = if options.no_header_scaling {
+        0
+    } else {
+        options.header_scaling_offset.unwrap_or(DEFAULT_HEADER_SCALING_OFFSET)
+    };
+    let mut changelog = format!("{}\n\n", render_heading(options, next_version, current_version));
+    // Grouped changelogs list breaking changes in their own section instead
+    if !options.no_highlight_breaking && !options.group_sections {
+        changelog.push_str(&format_breaking_changes(summary, scaling_offset, !options.no_escape));
+    }
     if summary.commits.is_empty() {
         changelog.push_str("* No changes\n");
+    } else if options.group_sections {
+        changelog.push_str(&format_sections(summary, options, scaling_offset));
     } else {
-        // Reverse the commits to display them in chronological order (oldest first)
-        let mut commits = summary.commits.clone();
-        commits.reverse();
-        for commit in &commits {
-            // Format the first line with the commit title
-            let first_line = if let Some(scope) = &commit.scope {
-                format!("* {}({}): {}", commit.commit_type, scope, commit.title)
-            } else {
-                format!("* {}: {}", commit.commit_type, commit.title)
-            };
-            
-            // Add author information if available
-            let line_with_author = if let Some(author_info) = &commit.author {
-                if let Some(username) = &author_info.username {
-                    format!("{} (by @{})\n", first_line, username)
-                } else {
-                    format!("{} (by {})\n", first_line, author_info.name)
-                }
-            } else {
-                format!("{}\n", first_line)
-            };
-            
-            changelog.push_str(&line_with_author);
-            
-            // Add the commit body if present
-            if let Some(body) = &commit.body {
-                // If this is a breaking change, add the prefix
-                let body_with_prefix = if commit.has_breaking_change && !body.starts_with("BREAKING CHANGE:") {
-                    format!("BREAKING CHANGE: {}", body)
-                } else {
-                    body.clone()
-                };
-                
-                // Format the body with proper indentation and header scaling
-                let formatted_body = format_commit_body(&body_with_prefix, no_header_scaling);
-                if !formatted_body.is_empty() {
-                    // Add a single newline before the body
-                    changelog.push_str("\n");
-                    changelog.push_str(&formatted_body);
-                    changelog.push('\n');
-                }
-            }
-
-            changelog.push_str("\n");
+        for commit in ordered_commits(summary, options.order) {
+            changelog.push_str(&format_entry(commit, options, scaling_offset));
+        }
+    }
+    if summary.truncated {
+        changelog.push_str(&format!("* ...and {} more commits\n\n", summary.omitted_commits));
+    }
+    if options.section_counts {
+        changelog.push_str(&format!("{}\n", format_totals(summary)));
+    }
+    
+    if options.contributors {
+        changelog.push_str(&format_contributors(summary));
+    }
+    if options.summary_line {
+        if !changelog.ends_with("\n\n") {
+            changelog.push('\n');
         }
+        changelog.push_str(&format!("{}\n", summary.release_counts()));
     }
     
-    // Add comparison link if it's a GitHub repository and current version is not 0.0.0
-    if repo_info.is_github_repo && (current_version.major > 0 || current_version.minor > 0 || current_version.patch > 0) {
+    // Add comparison link if it's a GitHub repository, current version is not 0.0.0 and
+    // there is a next version to compare with (unreleased notes have none)
+    if repo_info.is_github_repo
+        && (current_version.major > 0 || current_version.minor > 0 || current_version.patch > 0)
+        && next_version != current_version
+    {
         changelog.push('\n');
-        changelog.push_str(&format!("See full diff: [v{}...v{}](https://github.com/{}/{}/compare/v{}...v{})",
-            current_version, next_version, repo_info.owner, repo_info.name, current_version, next_version));
+        let prefix = format!(
+            "{}{}",
+            options.tag_namespace.as_deref().unwrap_or(""),
+            options.tag_prefix.as_deref().unwrap_or("v")
+        );
+        let (current, next) = (options.scheme.render(current_version), options.scheme.render(next_version));
+        changelog.push_str(&format!("See full diff: [{p}{}...{p}{}](https://github.com/{}/{}/compare/{p}{}...{p}{})",
+            current, next, repo_info.owner, repo_info.name, current, next, p = prefix));
     }
     
     changelog
 }
 
+/// A changelog entry: the header line with its credits and annotations, then the
+/// indented body, followed by a blank line
+fn format_entry(commit: &Commit, options: &ChangelogOptions, scaling_offset: u8) -> String {
+    let mut entry = String::new();
+    // Format the first line with the commit title
+    let mut first_line = format!("* {}", entry_header(commit, options));
+    if commit.is_empty {
+        first_line.push_str(" (empty)");
+    }
+    if commit.skip_release {
+        first_line.push_str(" (skip release)");
+    }
+    let issues = issue_references(commit, options.issue_references);
+    if !issues.is_empty() {
+        first_line.push_str(&format!(" (refs {})", issues.join(", ")));
+    }
+    
+    // Parsers keep footers out of the body, but a body built by hand may still end in
+    // git trailers such as Signed-off-by; split those off unless they should be kept
+    let (stripped_body, body_trailers) = match &commit.body {
+        Some(body) => trailers::split_trailers(body),
+        None => (String::new(), Vec::new()),
+    };
+    let mut body = if options.keep_trailers {
+        with_footers(commit.body.clone(), &commit.footers)
+    } else if body_trailers.is_empty() {
+        commit.body.clone()
+    } else if stripped_body.is_empty() {
+        None
+    } else {
+        Some(stripped_body)
+    };
+    let mut trailers = body_trailers;
+    trailers.extend(commit.footers.iter().cloned());
+    
+    // A body starting with the breaking change token is the note itself
+    let note = commit.breaking_change_note.as_ref();
+    if note.is_some() && body.as_ref() == note {
+        body = None;
+    }
+    // Only the description is shortened, never the breaking change note
+    if options.first_line_only {
+        body = None;
+    } else if let Some(max_lines) = options.max_body_lines {
+        body = body.map(|body| truncate_body(&body, max_lines));
+    }
+    
+    // A breaking change footer after the description is shown below it, unless the
+    // breaking changes section above the commit list already shows it
+    let highlighted = !options.no_highlight_breaking && !options.group_sections;
+    if let Some(note) = note.filter(|_| !highlighted) {
+        body = Some(match body {
+            Some(body) => format!("{}\n\nBREAKING CHANGE: {}", body, note),
+            None => format!("BREAKING CHANGE: {}", note),
+        });
+    }
+    
+    // Add author information if available
+    let mut credits = Vec::new();
+    if let Some(author_info) = &commit.author {
+        if let Some(username) = &author_info.username {
+            credits.push(format!("@{}", username));
+        } else {
+            credits.push(author_info.name.clone());
+        }
+    }
+    if options.credit_coauthors {
+        for name in trailers::coauthor_names(&trailers) {
+            if !credits.contains(&name) {
+                credits.push(name);
+            }
+        }
+    }
+    let mut line_with_author = if credits.is_empty() {
+        first_line
+    } else {
+        format!("{} (by {})", first_line, credits.join(", "))
+    };
+    if let Some(time) = commit.timestamp.filter(|_| options.show_dates) {
+        line_with_author.push_str(&format!(" ({})", date::format_epoch_date(time)));
+    }
+    line_with_author.push('\n');
+    
+    entry.push_str(&line_with_author);
+    
+    // Add the commit body if present
+    if let Some(body) = &body {
+        // If this is a breaking change without a footer, add the prefix
+        let body_with_prefix = if commit.has_breaking_change && commit.breaking_change_note.is_none() && !body.starts_with("BREAKING CHANGE:") {
+            format!("BREAKING CHANGE: {}", body)
+        } else {
+            body.clone()
+        };
+        
+        // Format the body with proper indentation and header scaling
+        let formatted_body = format_commit_body(&body_with_prefix, scaling_offset, !options.no_escape);
+        if !formatted_body.is_empty() {
+            // Add a single newline before the body
+            entry.push_str("\n");
+            entry.push_str(&formatted_body);
+            entry.push('\n');
+        }
+    }
+
+    entry.push_str("\n");
+    entry
+}
+
+/// Sections of a grouped changelog, in the order they are listed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangelogSection {
+    Breaking,
+    Features,
+    Fixes,
+    Other,
+}
+
+impl ChangelogSection {
+    pub const ALL: [ChangelogSection; 4] =
+        [ChangelogSection::Breaking, ChangelogSection::Features, ChangelogSection::Fixes, ChangelogSection::Other];
+
+    /// Section `commit` is listed under; breaking changes go first whatever their type
+    pub fn of(commit: &Commit) -> Self {
+        if commit.has_breaking_change {
+            ChangelogSection::Breaking
+        } else if commit.type_matches(&["feat"]) {
+            ChangelogSection::Features
+        } else if commit.type_matches(&["fix"]) {
+            ChangelogSection::Fixes
+        } else {
+            ChangelogSection::Other
+        }
+    }
+
+    /// Key of the section in the JSON output
+    pub fn key(self) -> &'static str {
+        match self {
+            ChangelogSection::Breaking => "breaking",
+            ChangelogSection::Features => "features",
+            ChangelogSection::Fixes => "fixes",
+            ChangelogSection::Other => "other",
+        }
+    }
+
+    fn heading(self) -> &'static str {
+        match self {
+            ChangelogSection::Breaking => "⚠ Breaking changes",
+            ChangelogSection::Features => "✨ Features",
+            ChangelogSection::Fixes => "🐛 Fixes",
+            ChangelogSection::Other => "🧰 Other changes",
+        }
+    }
+}
+
+/// Number of listed commits in each section, keyed by `ChangelogSection::key`
+pub fn section_counts(summary: &ChangesetSummary) -> BTreeMap<&'static str, usize> {
+    let mut counts: BTreeMap<&'static str, usize> = ChangelogSection::ALL.iter().map(|section| (section.key(), 0)).collect();
+    for commit in &summary.commits {
+        *counts.entry(ChangelogSection::of(commit).key()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Entries under a heading per section, leaving out empty sections; with
+/// `options.section_counts` each heading shows its number of entries
+fn format_sections(summary: &ChangesetSummary, options: &ChangelogOptions, scaling_offset: u8) -> String {
+    let commits = ordered_commits(summary, options.order);
+    let mut text = String::new();
+    for section in ChangelogSection::ALL {
+        let entries: Vec<&Commit> = commits.iter().copied().filter(|commit| ChangelogSection::of(commit) == section).collect();
+        if entries.is_empty() {
+            continue;
+        }
+        text.push_str(&format!("#### {}", section.heading()));
+        if options.section_counts {
+            text.push_str(&format!(" ({})", entries.len()));
+        }
+        text.push_str("\n\n");
+        for commit in entries {
+            text.push_str(&format_entry(commit, options, scaling_offset));
+        }
+    }
+    text
+}
+
+/// Totals line of a grouped changelog, e.g. `37 commits from 9 contributors`; commits
+/// past the commit limit count, and contributors are left out when no author is known
+pub fn format_totals(summary: &ChangesetSummary) -> String {
+    let commits = pluralize(summary.commits.len() + summary.omitted_commits, "commit", "commits");
+    match summary.contributors().len() {
+        0 => commits,
+        contributors => format!("{} from {}", commits, pluralize(contributors, "contributor", "contributors")),
+    }
+}
+
+/// Commits in the order they are listed in the changelog.
+///
+/// Commits are collected newest first, so without an explicit order they are listed in
+/// reverse walk order, which approximates chronological order. The sorts are stable;
+/// commits without a timestamp count as the newest.
+fn ordered_commits(summary: &ChangesetSummary, order: Option<ChangelogOrder>) -> Vec<&Commit> {
+    let mut commits: Vec<&Commit> = summary.commits.iter().rev().collect();
+    let time = |commit: &Commit| commit.timestamp.unwrap_or(i64::MAX);
+    match order {
+        None => {}
+        Some(ChangelogOrder::Chronological) => commits.sort_by_key(|commit| time(commit)),
+        Some(ChangelogOrder::Reverse) => {
+            commits.sort_by_key(|commit| time(commit));
+            commits.reverse();
+        }
+        Some(ChangelogOrder::Type) => commits.sort_by_key(|commit| (ChangelogSection::of(commit) as u8, time(commit))),
+    }
+    commits
+}
+
+/// Header of a changelog entry: the commit header, or with `prefer_pr_titles` the title
+/// and number of the pull request the commit was merged through, when known
+fn entry_header(commit: &Commit, options: &ChangelogOptions) -> String {
+    match commit.pr_title.as_deref().filter(|_| options.prefer_pr_titles) {
+        Some(title) => {
+            let title = if options.no_escape { title.to_string() } else { escape_markdown(title) };
+            match commit.pr_number {
+                Some(number) => format!("{} (#{})", title, number),
+                None => title,
+            }
+        }
+        None => commit_header(commit, !options.no_escape),
+    }
+}
+
+/// Headers breaking commits are listed under, in the breaking changes section and in the
+/// commit list, so the terminal coloring can pick out their entries
+pub fn breaking_entry_headers(summary: &ChangesetSummary, options: &ChangelogOptions) -> Vec<String> {
+    let mut headers = Vec::new();
+    for commit in summary.breaking_changes() {
+        headers.push(commit_header(commit, !options.no_escape));
+        headers.push(entry_header(commit, options));
+    }
+    headers.dedup();
+    headers
+}
+
+/// Header of a commit as listed in the changelog: `type(scope): title`, or the subject
+/// of a commit that does not follow the commit format. With `escape`, markdown in the
+/// scope and title is escaped
+fn commit_header(commit: &Commit, escape: bool) -> String {
+    let text = |text: &str| if escape { escape_markdown(text) } else { text.to_string() };
+    if commit.commit_type.is_empty() {
+        return text(commit.display_title());
+    }
+    let scope = if commit.scopes.is_empty() { commit.scope.clone() } else { Some(commit.scopes.join(",")) };
+    match &scope {
+        Some(scope) => format!("{}({}): {}", commit.commit_type, text(scope), text(&commit.title)),
+        None => format!("{}: {}", commit.commit_type, text(&commit.title)),
+    }
+}
+
+/// Characters escaped by `escape_markdown`
+const MARKDOWN_CONTROL_CHARS: &[char] = &['\\', '*', '_', '[', ']', '<', '>', '#', '|', '~'];
+
+/// Backslash-escape markdown control characters so the text renders literally.
+///
+/// Code spans (text between a pair of backticks) are kept as they are; an unmatched
+/// backtick is escaped.
+pub fn escape_markdown(text: &str) -> String {
+    let segments: Vec<&str> = text.split('`').collect();
+    // An odd number of segments means every backtick is paired
+    let unmatched = segments.len().is_multiple_of(2);
+    let mut escaped = String::with_capacity(text.len());
+    for (i, segment) in segments.iter().enumerate() {
+        let is_last_unmatched = unmatched && i == segments.len() - 1;
+        if i > 0 {
+            escaped.push_str(if is_last_unmatched { "\\`" } else { "`" });
+        }
+        if i % 2 == 1 && !is_last_unmatched {
+            escaped.push_str(segment);
+            continue;
+        }
+        for c in segment.chars() {
+            if MARKDOWN_CONTROL_CHARS.contains(&c) {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+/// Whether a line would render as a thematic break (`---`, `***`, `___`) or turn the
+/// line above into a heading (`===`, `---`)
+fn is_break_line(line: &str) -> bool {
+    let chars: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    match chars.first() {
+        Some(&first) if chars.iter().all(|&c| c == first) => match first {
+            '-' | '=' => true,
+            '*' | '_' => chars.len() >= 3,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Format the breaking changes section shown above the commit list: every breaking
+/// commit, oldest first, with the text of its breaking change footer below it.
+/// Empty when the release has no breaking changes.
+pub fn format_breaking_changes(summary: &ChangesetSummary, scaling_offset: u8, escape: bool) -> String {
+    let breaking: Vec<&Commit> = summary.breaking_changes().collect();
+    if breaking.is_empty() {
+        return String::new();
+    }
+    let mut section = String::from("#### ⚠ Breaking changes\n\n");
+    for commit in breaking.into_iter().rev() {
+        section.push_str(&format!("* {}\n", commit_header(commit, escape)));
+        if let Some(note) = commit.breaking_change_note.as_deref().filter(|note| !note.is_empty()) {
+            section.push('\n');
+            section.push_str(&format_commit_body(note, scaling_offset, escape));
+            section.push('\n');
+        }
+        section.push('\n');
+    }
+    section
+}
+
+/// Footer tokens whose values are rendered as issue references
+pub const ISSUE_FOOTER_TOKENS: [&str; 4] = ["Refs", "Closes", "Fixes", "Resolves"];
+
+/// Issue references of a commit: those linked by footer rules and, when
+/// `from_footers` is set, the values of its `Refs`/`Closes`/`Fixes`/`Resolves` footers
+fn issue_references(commit: &Commit, from_footers: bool) -> Vec<String> {
+    let mut issues = commit.issues.clone();
+    if from_footers {
+        for (token, value) in &commit.footers {
+            let is_issue_token = ISSUE_FOOTER_TOKENS.iter().any(|t| t.eq_ignore_ascii_case(token));
+            if is_issue_token && !issues.contains(value) {
+                issues.push(value.clone());
+            }
+        }
+    }
+    issues
+}
+
+/// Append the footers (except breaking change footers, which are rendered as a note)
+/// to a commit body as `Token: value` lines
+fn with_footers(body: Option<String>, footers: &[(String, String)]) -> Option<String> {
+    let lines: Vec<String> = footers
+        .iter()
+        .filter(|(token, _)| !trailers::is_breaking_token(token))
+        .map(|(token, value)| format!("{}: {}", token, value))
+        .collect();
+    match body {
+        _ if lines.is_empty() => body,
+        Some(body) => Some(format!("{}\n\n{}", body, lines.join("\n"))),
+        None => Some(lines.join("\n")),
+    }
+}
+
+/// Filter the commits that should be listed in the changelog
+///
+/// Only `summary.commits` is modified; the bump counters are left untouched so
+/// excluded commits are still counted (e.g. in `summary.noop`). Breaking changes
+/// are never excluded as no-ops, and commits forced in by a footer rule are always
+/// kept. An empty `include_types` list allows all types.
+pub fn filter_changelog_commits(
+    summary: &mut ChangesetSummary,
+    exclude_noop: bool,
+    noop_types: &[&str],
+    include_types: &[&str],
+) {
+    summary.commits.retain(|commit| {
+        if commit.force_include {
+            return true;
+        }
+        if exclude_noop && !commit.has_breaking_change && commit.is_noop_change(noop_types) {
+            return false;
+        }
+        include_types.is_empty() || commit.type_matches(include_types)
+    });
+}
+
+/// Remove commits whose pull request has one of `labels` from the changelog.
+///
+/// Like `filter_changelog_commits`, the bump counters are left untouched and commits
+/// forced in by a footer rule are kept. Nothing is removed unless the labels of the
+/// commits were fetched (`summary.labels_known`).
+pub fn exclude_labeled_commits(summary: &mut ChangesetSummary, labels: &[String]) {
+    if labels.is_empty() || !summary.labels_known {
+        return;
+    }
+    summary.commits.retain(|commit| commit.force_include || !commit.has_any_label(labels));
+}
+
+/// Format the contributors section of the changelog
+///
+/// Contributors are listed as `@username` when known and by name otherwise,
+/// deduplicated case-insensitively and sorted alphabetically. When
+/// `summary.known_contributors` is set, authors whose email and name do not appear
+/// in it are also listed as new contributors.
+pub fn format_contributors(summary: &ChangesetSummary) -> String {
+    let contributors = summary.contributors();
+    if contributors.is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::from("\n## Contributors\n\n");
+    for author in contributors {
+        section.push_str(&format!("* {}\n", contributor_display(author)));
+    }
+    let new_contributors = summary.new_contributors();
+    if !new_contributors.is_empty() {
+        section.push_str("\n### New contributors\n\n");
+        for author in new_contributors {
+            section.push_str(&format!("* {} made their first contribution\n", contributor_display(author)));
+        }
+    }
+    section
+}
+
+/// A contributor as listed in the changelog: `@username` when known, the name otherwise
+fn contributor_display(author: &CommitAuthor) -> String {
+    match &author.username {
+        Some(username) => format!("@{}", username),
+        None => author.name.clone(),
+    }
+}
+
 /// Format a commit body with proper indentation and header scaling
-fn format_commit_body(body: &str, no_header_scaling: bool) -> String {
+///
+/// Every line is indented by two spaces so the body stays part of the commit's list
+/// item. Headings are scaled down by `scaling_offset` levels, except inside fenced code
+/// blocks (``` or ~~~), whose lines are kept verbatim. Indented code is never scaled
+/// since headings start the line. With `escape`, lines such as `---` that would end the
+/// entry with a horizontal rule or turn the line above into a heading are escaped; the
+/// body is otherwise kept.
+fn format_commit_body(body: &str, scaling_offset: u8, escape: bool) -> String {
     let mut formatted = String::new();
-    let lines: Vec<&str> = body.lines().collect();
+    let mut fences = FenceTracker::default();
     
     // Process each line
-    for (i, line) in lines.iter().enumerate() {
+    for (i, line) in body.lines().enumerate() {
+        if fences.track(line) {
+            if !line.is_empty() {
+                formatted.push_str("  ");
+                formatted.push_str(line);
+            }
+            formatted.push('\n');
+            continue;
+        }
+        let line = line.trim_end();
         if line.is_empty() {
             // Only add a newline for empty lines if it's not the first line
             if i > 0 {
                 formatted.push('\n');
             }
-        } else {
-            let processed_line = if !no_header_scaling {
-                // Scale down headers in commit body (h1->h4, h2->h5, h3->h6)
-                if line.starts_with("# ") {
-                    format!("#### {}", &line[2..])
-                } else if line.starts_with("## ") {
-                    format!("##### {}", &line[3..])
-                } else if line.starts_with("### ") {
-                    format!("###### {}", &line[4..])
-                } else {
-                    line.to_string()
-                }
-            } else {
-                // No header scaling
-                line.to_string()
-            };
-            formatted.push_str(&format!("  {}\n", processed_line));
+            continue;
         }
+        
+        let processed_line = if escape && is_break_line(line) {
+            let marker = line.trim_start();
+            format!("{}\\{}", &line[..line.len() - marker.len()], marker)
+        } else {
+            scale_heading(line, scaling_offset).unwrap_or_else(|| line.to_string())
+        };
+        formatted.push_str(&format!("  {}\n", processed_line));
     }
     
     // Remove trailing newlines to match expected format
     formatted.trim_end().to_string()
 }
 
-/// Output the result of the version calculation
-pub fn output_result(
-    next_version: &Version,
-    summary: &ChangesetSummary,
-    show_changelog: bool,
-    no_header_scaling: bool,
-    current_version: &Version,
-    repo_info: &RepoInfo,
-) {
-    if show_changelog {
-        println!("{}", format_changelog(summary, next_version, no_header_scaling, current_version, repo_info));
+/// Truncate a commit body after `max_lines` lines, ending it with a line noting how many
+/// were omitted. A cut inside a fenced code block moves before the block's opening fence,
+/// so no code block is left open
+pub fn truncate_body(body: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = body.lines().collect();
+    if lines.len() <= max_lines {
+        return body.to_string();
+    }
+    let mut cut = max_lines;
+    let mut fences = FenceTracker::default();
+    let mut block_start = 0;
+    for (i, line) in lines[..max_lines].iter().enumerate() {
+        let was_open = fences.is_open();
+        fences.track(line);
+        if !was_open && fences.is_open() {
+            block_start = i;
+        }
+    }
+    if fences.is_open() {
+        cut = block_start;
+    }
+    let kept = lines[..cut].join("\n");
+    let kept = kept.trim_end();
+    let omitted = lines.len() - cut;
+    let marker = format!("... ({} more {})", omitted, if omitted == 1 { "line" } else { "lines" });
+    if kept.is_empty() {
+        marker
     } else {
-        println!("{}", next_version);
+        format!("{}\n\n{}", kept, marker)
     }
 }
 
-/// Output a fallback result when an error occurs
-pub fn output_fallback(show_changelog: bool) {
-    if show_changelog {
-        println!("## What's changed in 0.0.0\n\n* No changes\n\n---");
+/// Fence of a fenced code block: three or more backticks or tildes
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Fence {
+    marker: char,
+    len: usize,
+}
+
+impl Fence {
+    /// The fence a line starts with, and the text after it (the info string). A line
+    /// like ```` ```code``` ```` is inline code, not a fence
+    fn parse(line: &str) -> Option<(Fence, &str)> {
+        let trimmed = line.trim_start();
+        let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+        let len = trimmed.chars().take_while(|c| *c == marker).count();
+        let rest = &trimmed[len..];
+        (len >= 3 && !(marker == '`' && rest.contains('`'))).then_some((Fence { marker, len }, rest))
+    }
+}
+
+/// Line-by-line state of fenced code blocks.
+///
+/// A block is closed by a fence of the same character that is at least as long as the
+/// opening one and has no info string, so a ```` ```rust ```` line inside a ```` ```` ````
+/// block or a `~~~` block stays code.
+#[derive(Default)]
+struct FenceTracker {
+    open: Option<Fence>,
+}
+
+impl FenceTracker {
+    /// Whether a code block is open after the lines tracked so far
+    fn is_open(&self) -> bool {
+        self.open.is_some()
+    }
+
+    /// Track the next line, returning whether it belongs to a code block, fences included
+    fn track(&mut self, line: &str) -> bool {
+        match (self.open, Fence::parse(line)) {
+            (None, Some((fence, _))) => {
+                self.open = Some(fence);
+                true
+            }
+            (Some(open), Some((fence, rest)))
+                if fence.marker == open.marker && fence.len >= open.len && rest.trim().is_empty() =>
+            {
+                self.open = None;
+                true
+            }
+            (open, _) => open.is_some(),
+        }
+    }
+}
+
+/// Scale a markdown heading down by `offset` levels, capped at h6, or `None` if the
+/// line is not a heading
+fn scale_heading(line: &str, offset: u8) -> Option<String> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if offset == 0 || level == 0 || level > 6 || !line[level..].starts_with(' ') {
+        return None;
+    }
+    let scaled = (level + offset as usize).min(6);
+    Some(format!("{}{}", "#".repeat(scaled), &line[level..]))
+}
+
+/// JSON form of a version calculation result with the versions rendered in `scheme`;
+/// `changelog` is `null` unless requested
+pub fn result_json(next_version: &Version, current_version: &Version, changelog: Option<&str>, scheme: &VersionScheme) -> serde_json::Value {
+    serde_json::json!({
+        "version": scheme.render(next_version),
+        "current_version": scheme.render(current_version),
+        "changelog": changelog,
+    })
+}
+
+/// Line separating the sections of the text output
+pub const SECTION_SEPARATOR: &str = "---";
+
+/// Text output made of `sections` in order, separated by `SECTION_SEPARATOR` lines: the
+/// `version` line and the `changelog`, which is left out when it was not rendered.
+/// Trailing blank lines of a section are dropped before a separator
+pub fn compose_sections(sections: &[PrintSection], version: &str, changelog: Option<&str>) -> String {
+    let texts: Vec<&str> = sections
+        .iter()
+        .filter_map(|section| match section {
+            PrintSection::Version => Some(version),
+            PrintSection::Changelog => changelog,
+        })
+        .collect();
+    let last = texts.len().saturating_sub(1);
+    texts
+        .iter()
+        .enumerate()
+        .map(|(index, text)| if index < last { text.trim_end() } else { text })
+        .collect::<Vec<_>>()
+        .join(&format!("\n{}\n", SECTION_SEPARATOR))
+}
+
+/// Render the changelog used when no version could be calculated, for the initial `version`
+pub fn fallback_changelog(options: &ChangelogOptions, version: &Version) -> String {
+    if options.heading.is_some() {
+        format!("{}\n\n* No changes\n\n---", render_heading(options, version, version))
     } else {
-        println!("0.0.0");
+        format!("## What's changed in {}\n\n* No changes\n\n---", options.scheme.render(version))
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,386 @@
+//! Deploy key and Actions secret API of GitHub repositories
+
+use crate::core::auth::GitHubAuth;
+use crate::core::github::{GitHubClient, GITHUB_API_URL};
+use crate::models::deploy_key::{
+    DeployKeyList, DeployKeyResponse, RepositoryResponse, SecretList, SecretPublicKey, SelectedRepositories,
+};
+use crate::models::error::VNextError;
+use crate::models::repo::RepoSlug;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use crypto_box::aead::OsRng;
+use reqwest::blocking::{Client, RequestBuilder};
+use serde::de::DeserializeOwned;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// The deploy key and secret operations the `deploy-key` command needs, so the command
+/// logic can be tested without GitHub.
+///
+/// `org_secret` names the organization holding the secret; without it the secret
+/// belongs to the repository.
+pub trait DeployKeyApi {
+    /// List the deploy keys of `repo`
+    fn list_keys(&self, repo: &RepoSlug) -> Result<Vec<DeployKeyResponse>, VNextError>;
+
+    /// Add `public_key` (an `authorized_keys` line) to `repo` as a deploy key titled
+    /// `title`, with write access unless `read_only` is set
+    fn add_key(&self, repo: &RepoSlug, title: &str, public_key: &str, read_only: bool) -> Result<DeployKeyResponse, VNextError>;
+
+    /// Delete the deploy key `key_id` of `repo`; a key that is already gone is not an error
+    fn delete_key(&self, repo: &RepoSlug, key_id: u64) -> Result<(), VNextError>;
+
+    /// Check whether the Actions secret `name` exists
+    fn secret_exists(&self, repo: &RepoSlug, name: &str, org_secret: Option<&str>) -> Result<bool, VNextError>;
+
+    /// Create or update the Actions secret `name`.
+    ///
+    /// An organization secret is visible to the `readers` repositories. Without
+    /// `readers`, an existing secret stays visible to the repositories it was, and a new
+    /// one is visible to `repo` only.
+    fn set_secret(&self, repo: &RepoSlug, name: &str, value: &str, org_secret: Option<&str>, readers: &[RepoSlug]) -> Result<(), VNextError>;
+
+    /// Delete the Actions secret `name`
+    fn delete_secret(&self, repo: &RepoSlug, name: &str, org_secret: Option<&str>) -> Result<(), VNextError>;
+}
+
+/// Talks to the GitHub REST API when a token is set, and through the GitHub CLI (`gh`)
+/// otherwise.
+///
+/// The API only accepts secret values encrypted for the repository's or organization's
+/// public key, so secrets are sealed with `seal_secret` before they are sent.
+pub struct DeployKeyClient {
+    client: Client,
+    api_url: String,
+    token: Option<String>,
+}
+
+impl DeployKeyClient {
+    /// Create a client for the API at `api_url`; without `token` every call goes through `gh`
+    pub fn new(api_url: &str, token: Option<String>) -> Self {
+        DeployKeyClient {
+            client: Client::new(),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token,
+        }
+    }
+
+    /// Create a client for api.github.com, authenticated with the token resolved from
+    /// `auth` when there is one
+    pub fn from_auth(auth: &GitHubAuth) -> Self {
+        DeployKeyClient::new(GITHUB_API_URL, auth.resolve_token())
+    }
+
+    /// Authenticated request to `path` (relative to the API root)
+    fn request(&self, method: reqwest::Method, path: &str, token: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{}/{}", self.api_url, path))
+            .header("Authorization", format!("token {}", token))
+            .header("User-Agent", "vnext-cli")
+    }
+
+    /// GET `path` and parse the JSON response, failing with an error mentioning `action`
+    fn get_json<T: DeserializeOwned>(&self, path: &str, token: &str, action: &str) -> Result<T, VNextError> {
+        let response = self.request(reqwest::Method::GET, path, token)
+            .send()
+            .map_err(|e| VNextError::GithubError(format!("Request failed: {}", e)))?;
+        check_response(response, action)?
+            .json()
+            .map_err(|e| VNextError::GithubError(format!("Failed to parse response to {}: {}", action, e)))
+    }
+
+    /// Repositories the existing organization secret `name` of `org` is visible to, or
+    /// `repo` alone when there is no such secret yet
+    fn secret_readers(&self, repo: &RepoSlug, org: &str, name: &str) -> Result<Vec<RepoSlug>, VNextError> {
+        let body = match &self.token {
+            Some(token) => {
+                let response = self.request(reqwest::Method::GET, &secret_repositories_path(org, name), token)
+                    .send()
+                    .map_err(|e| VNextError::GithubError(format!("Request failed: {}", e)))?;
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    return Ok(vec![repo.clone()]);
+                }
+                check_response(response, "list the repositories of the secret")?
+                    .text()
+                    .map_err(|e| VNextError::GithubError(format!("Failed to read response: {}", e)))?
+            }
+            None => match self.gh(&secret_repositories_args(org, name), None) {
+                Err(VNextError::GithubError(error)) if error.contains("404") => return Ok(vec![repo.clone()]),
+                result => result?,
+            },
+        };
+        let selected: SelectedRepositories = serde_json::from_str(&body)
+            .map_err(|e| VNextError::GithubError(format!("Failed to parse secret repositories response: {}", e)))?;
+        selected
+            .repositories
+            .iter()
+            .map(|repository| RepoSlug::parse(&repository.full_name).map_err(VNextError::GithubError))
+            .collect()
+    }
+
+    /// Run `gh` with `args`, returning its stdout or an error with its stderr
+    fn gh(&self, args: &[String], stdin: Option<&str>) -> Result<String, VNextError> {
+        let output = run_gh(args, stdin)?;
+        if !output.status.success() {
+            return Err(VNextError::GithubError(format!(
+                "gh {} failed: {}",
+                args.first().map(String::as_str).unwrap_or_default(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// Encrypt `value` as a libsodium sealed box for `public_key`, the base64 key of a
+/// secrets API, returning the base64 `encrypted_value` the API expects
+pub fn seal_secret(public_key: &str, value: &str) -> Result<String, VNextError> {
+    let key: [u8; crypto_box::KEY_SIZE] = BASE64
+        .decode(public_key.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| VNextError::GithubError("The secrets public key is not a base64 Curve25519 key".to_string()))?;
+    let sealed = crypto_box::PublicKey::from(key)
+        .seal(&mut OsRng, value.as_bytes())
+        .map_err(|e| VNextError::Other(format!("Failed to encrypt the secret: {}", e)))?;
+    Ok(BASE64.encode(sealed))
+}
+
+/// Arguments of `gh` listing the deploy keys of `repo`
+pub fn list_keys_args(repo: &RepoSlug) -> Vec<String> {
+    to_args(&["api", &keys_path(repo)])
+}
+
+/// Arguments of `gh` adding a deploy key; the title and key are passed as raw strings
+pub fn add_key_args(repo: &RepoSlug, title: &str, public_key: &str, read_only: bool) -> Vec<String> {
+    to_args(&[
+        "api",
+        &keys_path(repo),
+        "--raw-field",
+        &format!("title={}", title),
+        "--raw-field",
+        &format!("key={}", public_key.trim()),
+        // --field turns true and false into JSON booleans
+        "--field",
+        &format!("read_only={}", read_only),
+    ])
+}
+
+/// Arguments of `gh` deleting the deploy key `key_id`
+pub fn delete_key_args(repo: &RepoSlug, key_id: u64) -> Vec<String> {
+    to_args(&["api", "-X", "DELETE", &format!("{}/{}", keys_path(repo), key_id)])
+}
+
+/// Arguments of `gh` listing the Actions secrets of `repo`, or of `org_secret` when set
+pub fn list_secrets_args(repo: &RepoSlug, org_secret: Option<&str>) -> Vec<String> {
+    to_args(&["api", &secrets_path(repo, org_secret)])
+}
+
+/// Arguments of `gh` listing the repositories the organization secret `name` is visible to
+pub fn secret_repositories_args(org: &str, name: &str) -> Vec<String> {
+    to_args(&["api", &secret_repositories_path(org, name)])
+}
+
+/// Arguments of `gh` setting the secret `name`, whose value is read from stdin; an
+/// organization secret is visible to the `readers` repositories, or to `repo` without them
+pub fn set_secret_args(repo: &RepoSlug, name: &str, org_secret: Option<&str>, readers: &[RepoSlug]) -> Vec<String> {
+    let mut args = to_args(&["secret", "set", name]);
+    match org_secret {
+        Some(org) => {
+            let repos = match readers {
+                [] => repo.name.clone(),
+                _ => readers.iter().map(|reader| reader.name.as_str()).collect::<Vec<_>>().join(","),
+            };
+            args.extend(to_args(&["--org", org, "--visibility", "selected", "--repos", &repos]))
+        }
+        None => args.extend(to_args(&["--repo", &repo.to_string()])),
+    }
+    args.extend(to_args(&["--app", "actions"]));
+    args
+}
+
+/// Arguments of `gh` deleting the secret `name`
+pub fn delete_secret_args(repo: &RepoSlug, name: &str, org_secret: Option<&str>) -> Vec<String> {
+    let mut args = to_args(&["secret", "delete", name]);
+    match org_secret {
+        Some(org) => args.extend(to_args(&["--org", org])),
+        None => args.extend(to_args(&["--repo", &repo.to_string()])),
+    }
+    args.extend(to_args(&["--app", "actions"]));
+    args
+}
+
+fn to_args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+/// API path of the deploy keys of `repo`
+fn keys_path(repo: &RepoSlug) -> String {
+    format!("repos/{}/{}/keys", repo.owner, repo.name)
+}
+
+/// Run the GitHub CLI without a shell, passing `stdin` (e.g. a secret value) on its
+/// standard input
+fn run_gh(args: &[String], stdin: Option<&str>) -> Result<Output, VNextError> {
+    let mut child = Command::new("gh")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| VNextError::GithubError(format!("Failed to execute the GitHub CLI (gh): {}", e)))?;
+    if let (Some(mut pipe), Some(input)) = (child.stdin.take(), stdin) {
+        pipe.write_all(input.as_bytes())?;
+    }
+    Ok(child.wait_with_output()?)
+}
+
+/// API path of the secrets of `repo`, or of `org_secret` when set
+fn secrets_path(repo: &RepoSlug, org_secret: Option<&str>) -> String {
+    match org_secret {
+        Some(org) => format!("orgs/{}/actions/secrets", org),
+        None => format!("repos/{}/{}/actions/secrets", repo.owner, repo.name),
+    }
+}
+
+/// API path of the repositories the organization secret `name` of `org` is visible to
+fn secret_repositories_path(org: &str, name: &str) -> String {
+    format!("orgs/{}/actions/secrets/{}/repositories", org, name)
+}
+
+/// Turn a failed API response into an error mentioning `action`
+fn check_response(response: reqwest::blocking::Response, action: &str) -> Result<reqwest::blocking::Response, VNextError> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status();
+    let body = response.text().unwrap_or_default();
+    Err(VNextError::GithubError(format!("Failed to {}: status {}: {}", action, status, body.trim())))
+}
+
+impl DeployKeyApi for DeployKeyClient {
+    fn list_keys(&self, repo: &RepoSlug) -> Result<Vec<DeployKeyResponse>, VNextError> {
+        let body = match &self.token {
+            Some(token) => {
+                let response = self.request(reqwest::Method::GET, &keys_path(repo), token)
+                    .send()
+                    .map_err(|e| VNextError::GithubError(format!("Request failed: {}", e)))?;
+                check_response(response, "list deploy keys")?
+                    .text()
+                    .map_err(|e| VNextError::GithubError(format!("Failed to read response: {}", e)))?
+            }
+            None => self.gh(&list_keys_args(repo), None)?,
+        };
+        let keys: DeployKeyList = serde_json::from_str(&body)
+            .map_err(|e| VNextError::GithubError(format!("Failed to parse deploy keys response: {}", e)))?;
+        Ok(keys.0)
+    }
+
+    fn add_key(&self, repo: &RepoSlug, title: &str, public_key: &str, read_only: bool) -> Result<DeployKeyResponse, VNextError> {
+        let body = match &self.token {
+            Some(token) => {
+                let response = self.request(reqwest::Method::POST, &keys_path(repo), token)
+                    .json(&serde_json::json!({ "title": title, "key": public_key.trim(), "read_only": read_only }))
+                    .send()
+                    .map_err(|e| VNextError::GithubError(format!("Request failed: {}", e)))?;
+                check_response(response, "add deploy key")?
+                    .text()
+                    .map_err(|e| VNextError::GithubError(format!("Failed to read response: {}", e)))?
+            }
+            None => self.gh(&add_key_args(repo, title, public_key, read_only), None)?,
+        };
+        serde_json::from_str(&body)
+            .map_err(|e| VNextError::GithubError(format!("Failed to parse deploy key response: {}", e)))
+    }
+
+    fn delete_key(&self, repo: &RepoSlug, key_id: u64) -> Result<(), VNextError> {
+        match &self.token {
+            Some(token) => {
+                let path = format!("{}/{}", keys_path(repo), key_id);
+                let response = self.request(reqwest::Method::DELETE, &path, token)
+                    .send()
+                    .map_err(|e| VNextError::GithubError(format!("Request failed: {}", e)))?;
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    log::info!("Deploy key with ID {} not found (may have been deleted already)", key_id);
+                    return Ok(());
+                }
+                check_response(response, "delete deploy key")?;
+            }
+            None => match self.gh(&delete_key_args(repo, key_id), None) {
+                // If we get a 404, the key might have been deleted already
+                Err(VNextError::GithubError(error)) if error.contains("404") => {
+                    log::info!("Deploy key with ID {} not found (may have been deleted already)", key_id);
+                }
+                result => {
+                    result?;
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn secret_exists(&self, repo: &RepoSlug, name: &str, org_secret: Option<&str>) -> Result<bool, VNextError> {
+        if self.token.is_some() {
+            let client = GitHubClient::new(&self.api_url, self.token.clone());
+            return match org_secret {
+                Some(org) => client.org_secret_exists(org, name),
+                None => client.secret_exists(&repo.owner, &repo.name, name),
+            };
+        }
+        let body = self.gh(&list_secrets_args(repo, org_secret), None)?;
+        let secrets: SecretList = serde_json::from_str(&body)
+            .map_err(|e| VNextError::GithubError(format!("Failed to parse secrets response: {}", e)))?;
+        Ok(secrets.secrets.iter().any(|secret| secret.name == name))
+    }
+
+    fn set_secret(&self, repo: &RepoSlug, name: &str, value: &str, org_secret: Option<&str>, readers: &[RepoSlug]) -> Result<(), VNextError> {
+        let readers = match org_secret {
+            Some(org) if readers.is_empty() => self.secret_readers(repo, org, name)?,
+            _ => readers.to_vec(),
+        };
+        let Some(token) = &self.token else {
+            // The value goes through stdin so it never appears in a command line or a file
+            return self.gh(&set_secret_args(repo, name, org_secret, &readers), Some(value)).map(|_| ());
+        };
+        let path = secrets_path(repo, org_secret);
+        let public_key: SecretPublicKey = self.get_json(&format!("{}/public-key", path), token, "fetch the secrets public key")?;
+        let mut body = serde_json::json!({
+            "encrypted_value": seal_secret(&public_key.key, value)?,
+            "key_id": public_key.key_id,
+        });
+        if org_secret.is_some() {
+            let ids = readers
+                .iter()
+                .map(|reader| {
+                    let path = format!("repos/{}/{}", reader.owner, reader.name);
+                    self.get_json::<RepositoryResponse>(&path, token, "look up the repository").map(|repository| repository.id)
+                })
+                .collect::<Result<Vec<u64>, VNextError>>()?;
+            body["visibility"] = serde_json::json!("selected");
+            body["selected_repository_ids"] = serde_json::json!(ids);
+        }
+        let response = self.request(reqwest::Method::PUT, &format!("{}/{}", path, name), token)
+            .json(&body)
+            .send()
+            .map_err(|e| VNextError::GithubError(format!("Request failed: {}", e)))?;
+        check_response(response, "set secret")?;
+        Ok(())
+    }
+
+    fn delete_secret(&self, repo: &RepoSlug, name: &str, org_secret: Option<&str>) -> Result<(), VNextError> {
+        match &self.token {
+            Some(token) => {
+                let path = format!("{}/{}", secrets_path(repo, org_secret), name);
+                let response = self.request(reqwest::Method::DELETE, &path, token)
+                    .send()
+                    .map_err(|e| VNextError::GithubError(format!("Request failed: {}", e)))?;
+                if response.status() != reqwest::StatusCode::NOT_FOUND {
+                    check_response(response, "delete secret")?;
+                }
+                Ok(())
+            }
+            None => self.gh(&delete_secret_args(repo, name, org_secret), None).map(|_| ()),
+        }
+    }
+}
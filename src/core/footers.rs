@@ -0,0 +1,72 @@
+//! Commit classification driven by Conventional Commits footers
+
+use crate::models::commit::Commit;
+use crate::models::footer::{FooterAction, FooterRule};
+
+/// Parse a footer rule of the form `TOKEN[:VALUE]=ACTION`, where the action is
+/// `classify-as:TYPE`, `force-include` or `link-as-issue`.
+///
+/// For example `Reverts=classify-as:revert` or `Refs:RELEASE-BLOCKER=force-include`.
+pub fn parse_footer_rule(value: &str) -> Result<FooterRule, String> {
+    let (footer, action) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected TOKEN[:VALUE]=ACTION, got '{}'", value))?;
+    let (token, footer_value) = match footer.split_once(':') {
+        Some((token, footer_value)) => (token.trim(), Some(footer_value.trim().to_string())),
+        None => (footer.trim(), None),
+    };
+    if token.is_empty() {
+        return Err(format!("missing footer token in '{}'", value));
+    }
+
+    let action = match action.trim().split_once(':') {
+        Some(("classify-as", commit_type)) if !commit_type.trim().is_empty() => {
+            FooterAction::ClassifyAs(commit_type.trim().to_lowercase())
+        }
+        None if action.trim() == "force-include" => FooterAction::ForceInclude,
+        None if action.trim() == "link-as-issue" => FooterAction::LinkAsIssue,
+        _ => {
+            return Err(format!(
+                "unknown footer action '{}', expected classify-as:TYPE, force-include or link-as-issue",
+                action.trim()
+            ))
+        }
+    };
+
+    Ok(FooterRule {
+        token: token.to_string(),
+        value: footer_value.filter(|v| !v.is_empty()),
+        action,
+    })
+}
+
+/// Apply the footer rules to a parsed commit, based on its footers.
+///
+/// Rules are evaluated in order; a later `classify-as` rule overrides an earlier one.
+pub fn apply_footer_rules(commit: &mut Commit, rules: &[FooterRule]) {
+    let footers = commit.footers.clone();
+    for rule in rules {
+        for (token, value) in &footers {
+            if !token.eq_ignore_ascii_case(&rule.token) {
+                continue;
+            }
+            if let Some(expected) = &rule.value {
+                if !value.eq_ignore_ascii_case(expected) {
+                    continue;
+                }
+            }
+            match &rule.action {
+                FooterAction::ClassifyAs(commit_type) => {
+                    log::debug!("Footer {} classifies commit {} as {}", token, commit.commit_id, commit_type);
+                    commit.commit_type = commit_type.clone();
+                }
+                FooterAction::ForceInclude => commit.force_include = true,
+                FooterAction::LinkAsIssue => {
+                    if !commit.issues.contains(value) {
+                        commit.issues.push(value.clone());
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,13 +1,46 @@
 //! Git repository operations
 
-use git2::{Commit, Repository};
+use git2::{Commit, Mailmap, Remote, Repository};
+use crate::core::progress::ProgressReporter;
+use crate::core::version::VersionParser;
+use crate::models::changeset::ChangesetSummary;
+use crate::models::commit::CommitAuthor;
 use crate::models::error::VNextError;
-use crate::models::repo::RepoInfo;
+use crate::models::options::{Phase, PrereleaseBase};
+use crate::models::repo::{RepoInfo, WorkingTreeStatus};
+use std::collections::HashSet;
 use url::Url;
 
-/// Find the main branch ("main" or "master").
+/// Maximum number of commits walked when collecting previous contributors
+pub const MAX_CONTRIBUTOR_HISTORY: usize = 10_000;
+
+/// Remote the repository owner and name are read from unless another is configured
+pub const DEFAULT_REMOTE: &str = "origin";
+
+/// Conventional trunk branch names, in order of preference
+pub const TRUNK_BRANCH_CANDIDATES: [&str; 4] = ["main", "master", "trunk", "develop"];
+
+/// Find the trunk branch.
+///
+/// Consults `refs/remotes/origin/HEAD` first, then the remote-tracking branches on
+/// origin, then the local branches, trying the names in `TRUNK_BRANCH_CANDIDATES`.
+/// In a bare repository (e.g. a mirror) the branch HEAD points to is the default
+/// branch and is used when there is no `origin/HEAD`.
 pub fn find_trunk_branch(repo: &Repository) -> Option<String> {
-    for branch in ["main", "master"] {
+    if let Some(branch) = origin_head_branch(repo) {
+        return Some(branch);
+    }
+    if repo.is_bare() {
+        if let Some(branch) = repo.head().ok().and_then(|head| head.shorthand().map(|s| s.to_string())) {
+            return Some(branch);
+        }
+    }
+    for branch in TRUNK_BRANCH_CANDIDATES {
+        if repo.find_branch(&format!("origin/{}", branch), git2::BranchType::Remote).is_ok() {
+            return Some(branch.to_string());
+        }
+    }
+    for branch in TRUNK_BRANCH_CANDIDATES {
         if repo.find_branch(branch, git2::BranchType::Local).is_ok() {
             return Some(branch.to_string());
         }
@@ -15,30 +48,357 @@ pub fn find_trunk_branch(repo: &Repository) -> Option<String> {
     None
 }
 
+/// Name of the branch `refs/remotes/origin/HEAD` points to, if set
+fn origin_head_branch(repo: &Repository) -> Option<String> {
+    let reference = repo.find_reference("refs/remotes/origin/HEAD").ok()?;
+    let target = reference.symbolic_target()?;
+    target.strip_prefix("refs/remotes/origin/").map(|branch| branch.to_string())
+}
+
 /// Find the latest semver tag in the repo, returning (tag_name, commit).
 pub fn find_latest_tag(repo: &Repository) -> Option<(String, Commit)> {
-    let tags = repo.tag_names(None).expect("Failed to get tag names");
-    let mut latest: Option<(String, Commit)> = None;
-    let mut max_version = crate::core::version::parse_version("0.0.0").unwrap();
-
-    for tag in tags.iter().flatten() {
-        if let Ok(reference) = repo.find_reference(&format!("refs/tags/{}", tag)) {
-            if let Ok(commit) = reference.peel_to_commit() {
-                if let Ok(version) = crate::core::version::parse_version(tag) {
-                    if version > max_version {
-                        max_version = version;
-                        latest = Some((tag.to_string(), commit));
-                    }
-                }
+    find_latest_tag_with_progress(repo, &ProgressReporter::none()).unwrap_or_else(|e| {
+        log::debug!("Failed to scan tags: {}", e);
+        None
+    })
+}
+
+/// Find the latest semver tag in the repo, reporting progress for each scanned tag.
+///
+/// Returns `VNextError::Cancelled` if cancellation is requested during the scan.
+pub fn find_latest_tag_with_progress<'repo>(
+    repo: &'repo Repository,
+    progress: &ProgressReporter,
+) -> Result<Option<(String, Commit<'repo>)>, VNextError> {
+    find_latest_prefixed_tag(repo, None, progress)
+}
+
+/// Find the latest stable semver tag starting with `prefix` (e.g. `releases/` or `api-v`),
+/// returning the full tag name and its commit.
+///
+/// Only the highest candidates of `list_semver_tags` are peeled, until one points at a
+/// commit, so a scan costs one peel in the common case. Tags of the same version on
+/// different commits are resolved as by `find_release_tags` from HEAD.
+pub fn find_latest_prefixed_tag<'repo>(
+    repo: &'repo Repository,
+    prefix: Option<&str>,
+    progress: &ProgressReporter,
+) -> Result<Option<(String, Commit<'repo>)>, VNextError> {
+    let head = repo.head().and_then(|head| head.peel_to_commit()).ok();
+    let parser = VersionParser::new().with_prefix(prefix.unwrap_or(""));
+    Ok(find_release_tags(repo, &parser, head.as_ref(), false, progress)?.stable)
+}
+
+/// Latest release tags found by one tag scan, as (tag name, commit)
+pub struct LatestTags<'repo> {
+    /// Highest version, prereleases included
+    pub any: Option<(String, Commit<'repo>)>,
+    /// Highest version without a prerelease part
+    pub stable: Option<(String, Commit<'repo>)>,
+}
+
+impl<'repo> LatestTags<'repo> {
+    /// Latest tag under `policy`
+    pub fn for_policy(self, policy: PrereleaseBase) -> Option<(String, Commit<'repo>)> {
+        match policy {
+            PrereleaseBase::Include => self.any,
+            PrereleaseBase::Exclude => self.stable,
+        }
+    }
+}
+
+/// Find the latest release tags `parser` accepts, with and without prereleases,
+/// skipping tags that do not point at a commit.
+///
+/// When several tags name the highest version (e.g. `v1.2.3` and `1.2.3`) on different
+/// commits, the first one reachable from `head` wins and the ambiguity is logged, or
+/// reported as an error when `strict` is set.
+pub fn find_release_tags<'repo>(
+    repo: &'repo Repository,
+    parser: &VersionParser,
+    head: Option<&Commit<'repo>>,
+    strict: bool,
+    progress: &ProgressReporter,
+) -> Result<LatestTags<'repo>, VNextError> {
+    let tags = list_semver_tags(repo, parser, progress)?;
+    let any = highest_tag(repo, tags.iter(), head, strict)?;
+    let stable = match &any {
+        Some((version, name, commit)) if version.pre.is_empty() => Some((name.clone(), commit.clone())),
+        _ => highest_tag(repo, tags.iter().filter(|(version, _)| version.pre.is_empty()), head, strict)?
+            .map(|(_, name, commit)| (name, commit)),
+    };
+    Ok(LatestTags { any: any.map(|(_, name, commit)| (name, commit)), stable })
+}
+
+/// First tag of `tags` (sorted as by `list_semver_tags`) that points at a commit,
+/// resolving tags of the same version on different commits by reachability from `head`
+fn highest_tag<'repo, 'a>(
+    repo: &'repo Repository,
+    tags: impl Iterator<Item = &'a (semver::Version, String)>,
+    head: Option<&Commit<'repo>>,
+    strict: bool,
+) -> Result<Option<(semver::Version, String, Commit<'repo>)>, VNextError> {
+    let mut tied: Vec<(semver::Version, String, Commit<'repo>)> = Vec::new();
+    for (version, name) in tags {
+        if tied.first().is_some_and(|(highest, _, _)| tag_precedence(highest) != tag_precedence(version)) {
+            break;
+        }
+        if let Some(commit) = peel_tag(repo, name) {
+            tied.push((version.clone(), name.clone(), commit));
+        }
+    }
+    if tied.iter().all(|(_, _, commit)| commit.id() == tied[0].2.id()) {
+        return Ok(tied.into_iter().next());
+    }
+
+    let names = tied.iter().map(|(_, name, _)| name.as_str()).collect::<Vec<_>>().join(", ");
+    if strict {
+        return Err(VNextError::ConfigError(format!(
+            "tags {} name the same version on different commits; delete all but one, or drop --strict-tags",
+            names
+        )));
+    }
+    let reachable = tied.iter().position(|(_, _, commit)| {
+        head.is_some_and(|head| head.id() == commit.id() || repo.graph_descendant_of(head.id(), commit.id()).unwrap_or(false))
+    });
+    let tag = tied.swap_remove(reachable.unwrap_or(0));
+    match reachable {
+        Some(_) => log::warn!("Tags {} name the same version on different commits; using {}, which is reachable from HEAD", names, tag.1),
+        None => log::warn!("Tags {} name the same version on different commits and none is reachable from HEAD; using {}", names, tag.1),
+    }
+    Ok(Some(tag))
+}
+
+/// List the tags `parser` accepts with their versions, from the highest version to the
+/// lowest.
+///
+/// The namespace and prefix of the parser are stripped before the version is parsed;
+/// without them only plain `v1.2.3` / `1.2.3` tags are considered. Versions are parsed
+/// from the ref names alone, so the tags may still point at trees or blobs; see `peel_tag`.
+pub fn list_semver_tags(
+    repo: &Repository,
+    parser: &VersionParser,
+    progress: &ProgressReporter,
+) -> Result<Vec<(semver::Version, String)>, VNextError> {
+    let started = std::time::Instant::now();
+    let names: Vec<String> = repo
+        .references_glob("refs/tags/*")?
+        .filter_map(|reference| reference.ok()?.name()?.strip_prefix("refs/tags/").map(str::to_string))
+        .collect();
+    let total = names.len() as u64;
+
+    let mut candidates = Vec::new();
+    for (i, name) in names.into_iter().enumerate() {
+        progress.check_cancelled()?;
+        if let Ok(version) = parser.parse(&name) {
+            // 0.0.0 is the baseline without tags, not a release
+            if tag_precedence(&version) > tag_precedence(&semver::Version::new(0, 0, 0)) {
+                candidates.push((version, name));
             }
         }
+        progress.report(Phase::TagScan, i as u64 + 1, Some(total));
+    }
+
+    // The sort is stable, so the first of equal versions wins as before
+    candidates.sort_by_key(|(version, _)| std::cmp::Reverse(tag_precedence(version)));
+    log::debug!("Scanned {} tags in {:?}", total, started.elapsed());
+    Ok(candidates)
+}
+
+/// Precedence of a tagged version. Build metadata has no precedence, so `v1.2.0+build.9`
+/// never outranks `v1.3.0+build.1`
+pub fn tag_precedence(version: &semver::Version) -> (u64, u64, u64, semver::Prerelease) {
+    (version.major, version.minor, version.patch, version.pre.clone())
+}
+
+/// Commit the tag `name` points at; tags of trees or blobs are not releases
+pub fn peel_tag<'repo>(repo: &'repo Repository, name: &str) -> Option<Commit<'repo>> {
+    match repo.find_reference(&format!("refs/tags/{}", name)).and_then(|r| r.peel_to_commit()) {
+        Ok(commit) => Some(commit),
+        Err(e) => {
+            log::debug!("Skipping tag {} that does not point at a commit: {}", name, e);
+            None
+        }
+    }
+}
+
+/// Root commit reached from `commit` by following the first parent chain
+pub fn root_commit<'repo>(commit: &Commit<'repo>) -> Commit<'repo> {
+    let mut current = commit.clone();
+    while let Some(parent) = current.parents().next() {
+        current = parent;
+    }
+    current
+}
+
+/// Count the uncommitted changes in the working tree.
+///
+/// Bare repositories have no working tree and are reported as clean.
+pub fn working_tree_status(repo: &Repository) -> Result<WorkingTreeStatus, VNextError> {
+    let mut status = WorkingTreeStatus::default();
+    if repo.is_bare() {
+        return Ok(status);
+    }
+    let mut options = git2::StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(false)
+        .include_ignored(false)
+        .exclude_submodules(true);
+    for entry in repo.statuses(Some(&mut options))?.iter() {
+        let flags = entry.status();
+        if flags == git2::Status::WT_NEW {
+            status.untracked += 1;
+        } else if !flags.is_empty() && !flags.contains(git2::Status::IGNORED) {
+            status.modified += 1;
+        }
+    }
+    Ok(status)
+}
+
+/// Check whether a commit changes any file under `path` compared to its first parent
+pub fn commit_touches_path(repo: &Repository, commit: &Commit, path: &str) -> Result<bool, VNextError> {
+    let tree = commit.tree()?;
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+    let mut options = git2::DiffOptions::new();
+    options.pathspec(path.trim_end_matches('/'));
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut options))?;
+    Ok(diff.deltas().len() > 0)
+}
+
+/// Normalize a tag namespace so it always ends with a `/`
+pub fn normalize_tag_namespace(namespace: &str) -> String {
+    let namespace = namespace.trim().trim_start_matches("refs/tags/");
+    if namespace.is_empty() || namespace.ends_with('/') {
+        namespace.to_string()
+    } else {
+        format!("{}/", namespace)
     }
-    latest
 }
 
-/// Open the Git repository in the current directory
+/// Fail on shallow clones unless `allow_shallow` is set.
+///
+/// A shallow clone ends at a grafted boundary, so tags and the root commit may be
+/// missing and the calculated version would be wrong.
+pub fn check_shallow(repo: &Repository, allow_shallow: bool) -> Result<(), VNextError> {
+    if !repo.is_shallow() {
+        return Ok(());
+    }
+    if allow_shallow {
+        log::warn!("Repository is a shallow clone; the version is calculated from the available history only");
+        Ok(())
+    } else {
+        Err(VNextError::ConfigError(
+            "Repository is a shallow clone; fetch the full history (git fetch --unshallow, or fetch-depth: 0 in actions/checkout) or pass --allow-shallow".to_string(),
+        ))
+    }
+}
+
+/// Fetch the tags of `origin` into the repository, replacing local tags that moved.
+///
+/// SSH remotes authenticate with `ssh_key` or else the SSH agent, HTTPS remotes with the
+/// configured credential helpers.
+pub fn fetch_tags(repo: &Repository, ssh_key: Option<&std::path::Path>) -> Result<(), VNextError> {
+    let mut remote = repo.find_remote("origin")?;
+    let config = repo.config()?;
+    let mut attempts = 0;
+    let mut callbacks = git2::RemoteCallbacks::new();
+    callbacks.credentials(move |url, username, allowed| {
+        // libgit2 asks again after rejected credentials; give up instead of looping
+        attempts += 1;
+        if attempts > 3 {
+            return Err(git2::Error::from_str("authentication failed"));
+        }
+        if allowed.contains(git2::CredentialType::SSH_KEY) {
+            let username = username.unwrap_or("git");
+            match ssh_key {
+                Some(key) => git2::Cred::ssh_key(username, None, key, None),
+                None => git2::Cred::ssh_key_from_agent(username),
+            }
+        } else if allowed.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+            git2::Cred::credential_helper(&config, url, username)
+        } else if allowed.contains(git2::CredentialType::USERNAME) {
+            git2::Cred::username(username.unwrap_or("git"))
+        } else {
+            git2::Cred::default()
+        }
+    });
+    let mut options = git2::FetchOptions::new();
+    options.remote_callbacks(callbacks).download_tags(git2::AutotagOption::None);
+
+    let started = std::time::Instant::now();
+    remote.fetch(&["+refs/tags/*:refs/tags/*"], Some(&mut options), None)?;
+    log::debug!(
+        "Fetched tags from {} ({} objects) in {:?}",
+        remote.url().unwrap_or("origin"), remote.stats().received_objects(), started.elapsed()
+    );
+    Ok(())
+}
+
+/// Open the Git repository of the current directory: the git directory named by the
+/// `GIT_DIR` environment variable when it is set, like git itself, or else the repository
+/// containing the current directory
 pub fn open_repository() -> Result<Repository, VNextError> {
-    Repository::open(".").map_err(|e| e.into())
+    open_repository_with(".", env_git_dir().as_deref())
+}
+
+/// The git directory named by the `GIT_DIR` environment variable, if it is set and not empty
+pub fn env_git_dir() -> Option<std::path::PathBuf> {
+    std::env::var_os("GIT_DIR").filter(|dir| !dir.is_empty()).map(std::path::PathBuf::from)
+}
+
+/// Open `git_dir` when it is given (`--git-dir`), or else the Git repository containing `path`
+pub fn open_repository_with(path: impl AsRef<std::path::Path>, git_dir: Option<&std::path::Path>) -> Result<Repository, VNextError> {
+    match git_dir {
+        Some(git_dir) => open_git_dir(git_dir),
+        None => open_repository_at(path),
+    }
+}
+
+/// Open the Git repository containing `path`.
+///
+/// Parent directories are searched, so `path` may be a subdirectory of a checkout, a
+/// linked worktree or a bare repository (e.g. a `repo.git` mirror). `GIT_DIR` is not
+/// consulted; separate git directories are opened with `open_git_dir`.
+pub fn open_repository_at(path: impl AsRef<std::path::Path>) -> Result<Repository, VNextError> {
+    let path = path.as_ref();
+    let repo = Repository::open_ext(path, git2::RepositoryOpenFlags::empty(), std::iter::empty::<&std::ffi::OsStr>())
+        .map_err(|e| {
+            let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+            open_error(e, &format!("{} and its parent directories", path.display()))
+        })?;
+    log_opened(&repo);
+    Ok(repo)
+}
+
+/// Open the git directory `git_dir` without searching, which supports separate git
+/// directories (`git init --separate-git-dir`)
+pub fn open_git_dir(git_dir: &std::path::Path) -> Result<Repository, VNextError> {
+    let repo = Repository::open(git_dir)
+        .map_err(|e| open_error(e, &format!("the git directory {}", git_dir.display())))?;
+    log_opened(&repo);
+    Ok(repo)
+}
+
+fn open_error(e: git2::Error, searched: &str) -> VNextError {
+    if e.code() == git2::ErrorCode::NotFound {
+        VNextError::NotARepository(format!("No Git repository found in {}: {}", searched, e.message()))
+    } else {
+        VNextError::GitError(git2::Error::new(
+            e.code(), e.class(), format!("Failed to open the Git repository in {}: {}", searched, e.message()),
+        ))
+    }
+}
+
+fn log_opened(repo: &Repository) {
+    log::debug!(
+        "Opened {} repository at {}",
+        if repo.is_bare() { "bare" } else if repo.is_worktree() { "linked worktree" } else { "checkout" },
+        repo.path().display()
+    );
 }
 
 /// Resolve the HEAD reference to a commit
@@ -49,37 +409,52 @@ pub fn resolve_head(repo: &Repository) -> Result<Commit, VNextError> {
 }
 
 /// Extract repository information from a git remote URL
-/// Returns (host, owner, name) if successful
+/// Returns (host, owner, name) if successful. For nested groups (e.g. GitLab subgroups)
+/// the owner contains every namespace segment joined with `/`.
+///
+/// Accepts `https://`, `http://`, `ssh://` and `git://` URLs, with or without user info
+/// and a port, as well as scp-like `[user@]host:owner/repo` remotes. Trailing slashes and
+/// a single trailing `.git` are dropped. Azure DevOps remotes
+/// (`https://dev.azure.com/org/project/_git/repo`, `git@ssh.dev.azure.com:v3/org/project/repo`)
+/// map to the owner `org/project`.
 pub fn extract_repo_info(remote_url: &str) -> Option<(String, String, String)> {
-    // Handle SSH URLs like git@github.com:owner/repo.git or git@gitlab.com:owner/repo.git
-    if remote_url.starts_with("git@") && remote_url.contains(':') {
-        let host_part = remote_url.split('@').nth(1)?.split(':').next()?;
-        let path = remote_url.split(':').nth(1)?;
-        let path = path.trim_end_matches(".git");
-        let parts: Vec<&str> = path.split('/').collect();
-        if parts.len() >= 2 {
-            return Some((host_part.to_string(), parts[0].to_string(), parts[1].to_string()));
+    let remote_url = remote_url.trim();
+    let (host, path) = match Url::parse(remote_url) {
+        // The host excludes user info and the port
+        Ok(url) if url.has_host() => (url.host_str()?.to_string(), url.path().to_string()),
+        // scp-like remotes are not URLs, and neither is `ssh://host:owner/repo`
+        _ => {
+            let remote = remote_url.split_once("://").map_or(remote_url, |(_, rest)| rest);
+            let (authority, path) = remote.split_once(':')?;
+            let host = authority.rsplit('@').next()?;
+            (host.to_string(), path.to_string())
         }
+    };
+    if host.is_empty() {
+        return None;
     }
-    
-    // Handle HTTPS URLs like https://github.com/owner/repo.git or https://gitlab.com/owner/repo.git
-    if let Ok(url) = Url::parse(remote_url) {
-        let host = url.host_str()?;
-        let path = url.path().trim_start_matches('/').trim_end_matches(".git");
-        let parts: Vec<&str> = path.split('/').collect();
-        if parts.len() >= 2 {
-            return Some((host.to_string(), parts[0].to_string(), parts[1].to_string()));
-        }
+
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let mut parts: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+    // Azure DevOps puts `_git` before the repository name and `v3` before the organization over SSH
+    parts.retain(|part| *part != "_git");
+    if host == "ssh.dev.azure.com" && parts.first() == Some(&"v3") {
+        parts.remove(0);
     }
-    
-    None
+    // Everything before the last segment is the owner, so nested groups are kept
+    let (name, owner) = parts.split_last()?;
+    if owner.is_empty() {
+        return None;
+    }
+    Some((host, owner.join("/"), name.to_string()))
 }
 
 /// Get repository information from a git repository
 ///
 /// This function extracts the owner, name, and repository type from a git repository.
 /// It uses the `extract_repo_info` function to extract the repository information
-/// from the remote URL.
+/// from the URL of the `origin` remote.
 ///
 /// # Arguments
 ///
@@ -89,10 +464,16 @@ pub fn extract_repo_info(remote_url: &str) -> Option<(String, String, String)> {
 ///
 /// A `RepoInfo` struct containing the repository information
 pub fn get_repo_info(repo: &Repository) -> RepoInfo {
+    get_repo_info_from_remote(repo, DEFAULT_REMOTE)
+}
+
+/// Get repository information from the URL of the remote `remote_name`, falling back to
+/// the first configured remote when there is no remote by that name (see `find_remote_or_first`)
+pub fn get_repo_info_from_remote(repo: &Repository, remote_name: &str) -> RepoInfo {
     let mut repo_info = RepoInfo::new();
     
     // Check repository host
-    if let Ok(remote) = repo.find_remote("origin") {
+    if let Some(remote) = find_remote_or_first(repo, remote_name) {
         if let Some(url) = remote.url() {
             if let Some((host, repo_owner, repo_name)) = extract_repo_info(url) {
                 repo_info.owner = repo_owner;
@@ -115,4 +496,72 @@ pub fn get_repo_info(repo: &Repository) -> RepoInfo {
     }
     
     repo_info
+}
+
+/// The remote named `name`, or the first configured remote when there is none by that
+/// name, logging which one is used instead
+pub fn find_remote_or_first<'repo>(repo: &'repo Repository, name: &str) -> Option<Remote<'repo>> {
+    if let Ok(remote) = repo.find_remote(name) {
+        return Some(remote);
+    }
+    let remotes = repo.remotes().ok()?;
+    let first = remotes.iter().flatten().next()?;
+    log::info!("No remote named {}; reading the repository from {} instead", name, first);
+    repo.find_remote(first).ok()
+}
+
+/// Build a `CommitAuthor` from the author signature of a local commit,
+/// resolving the identity through the repository's mailmap when one is given
+pub fn local_author(commit: &Commit, mailmap: Option<&Mailmap>) -> CommitAuthor {
+    let raw = commit.author();
+    let signature = match mailmap.map(|mailmap| mailmap.resolve_signature(&raw)) {
+        Some(Ok(resolved)) => resolved,
+        _ => raw.to_owned(),
+    };
+    CommitAuthor {
+        name: signature.name().unwrap_or("").to_string(),
+        email: signature.email().unwrap_or("").to_string(),
+        username: None,
+    }
+}
+
+/// Fill in author information from the local repository for commits that have none.
+///
+/// Identities are resolved through `.mailmap` so renamed or consolidated authors
+/// appear under their canonical name.
+pub fn populate_local_authors(repo: &Repository, summary: &mut ChangesetSummary) {
+    let mailmap = repo.mailmap().ok();
+    for commit in summary.commits.iter_mut().filter(|c| c.author.is_none()) {
+        let git_commit = git2::Oid::from_str(&commit.commit_id).and_then(|oid| repo.find_commit(oid));
+        if let Ok(git_commit) = git_commit {
+            commit.author = Some(local_author(&git_commit, mailmap.as_ref()));
+        }
+    }
+}
+
+/// Collect the lowercased author emails and names of the history up to and including `base`.
+///
+/// The walk is bounded by `limit` commits so very long histories stay fast.
+pub fn collect_previous_contributors(
+    repo: &Repository,
+    base: &Commit,
+    limit: usize,
+) -> Result<HashSet<String>, VNextError> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(base.id())?;
+
+    let mailmap = repo.mailmap().ok();
+    let mut contributors = HashSet::new();
+    for oid in revwalk.take(limit) {
+        let commit = repo.find_commit(oid?)?;
+        let author = local_author(&commit, mailmap.as_ref());
+        if !author.email.is_empty() {
+            contributors.insert(author.email.to_lowercase());
+        }
+        if !author.name.is_empty() {
+            contributors.insert(author.name.to_lowercase());
+        }
+    }
+    log::debug!("Found {} previous contributor identities", contributors.len());
+    Ok(contributors)
 }
\ No newline at end of file
@@ -4,96 +4,512 @@ use crate::models::error::VNextError;
 use crate::models::repo::RepoInfo;
 use crate::models::commit::CommitAuthor;
 use crate::models::changeset::ChangesetSummary;
-use crate::models::github::GitHubCommit;
+use crate::models::github::{CommitMetadata, GitHubCommit, PullRequestInfo};
+use crate::models::options::Phase;
+use crate::core::auth::GitHubAuth;
+use crate::core::progress::ProgressReporter;
 use reqwest::blocking::Client;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
 
-/// Enhance commit summary with GitHub author information
+/// Default API endpoint for github.com
+pub const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// Number of commit lookups run in parallel unless configured otherwise
+pub const DEFAULT_GITHUB_CONCURRENCY: usize = 4;
+
+/// Default directory of the author cache: `$XDG_CACHE_HOME/vnext`, falling back to
+/// `~/.cache/vnext`
+pub fn default_cache_dir() -> Option<PathBuf> {
+    let non_empty = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+    non_empty("XDG_CACHE_HOME")
+        .or_else(|| non_empty("HOME").map(|home| home.join(".cache")))
+        .map(|dir| dir.join("vnext"))
+}
+
+/// Time a single API request may take before it fails
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Commits looked up by a single GraphQL query
+pub const GRAPHQL_BATCH_SIZE: usize = 100;
+
+/// Fields of a commit read by the GraphQL lookup
+const GRAPHQL_COMMIT_FRAGMENT: &str = "fragment commitMetadata on Commit { \
+    author { name email user { login } } \
+    associatedPullRequests(first: 1) { nodes { number title labels(first: 20) { nodes { name } } } } }";
+
+/// Source of commit metadata from a forge, used to attribute changelog entries.
+///
+/// `GitHubClient` is the HTTP implementation; tests and library users can plug in
+/// their own provider.
+pub trait CommitMetadataProvider {
+    /// Look up the authors of `commit_ids` in the repository, keyed by commit ID.
+    ///
+    /// Commits the forge does not know are left out of the map; an error means the
+    /// lookup as a whole failed.
+    fn fetch_authors(
+        &self,
+        repo: &RepoInfo,
+        commit_ids: &[String],
+        progress: &ProgressReporter,
+    ) -> Result<HashMap<String, CommitAuthor>, VNextError>;
+
+    /// Look up the authors and pull requests of `commit_ids`, keyed by commit ID.
+    ///
+    /// Providers that only know authors can keep the default, which wraps `fetch_authors`.
+    fn fetch_metadata(
+        &self,
+        repo: &RepoInfo,
+        commit_ids: &[String],
+        progress: &ProgressReporter,
+    ) -> Result<HashMap<String, CommitMetadata>, VNextError> {
+        Ok(authors_metadata(self.fetch_authors(repo, commit_ids, progress)?))
+    }
+
+    /// Whether `fetch_metadata` returns the pull request labels of the commits
+    fn provides_labels(&self) -> bool {
+        false
+    }
+}
+
+/// Metadata holding only the author of each commit
+fn authors_metadata(authors: HashMap<String, CommitAuthor>) -> HashMap<String, CommitMetadata> {
+    authors
+        .into_iter()
+        .map(|(commit_id, author)| (commit_id, CommitMetadata { author: Some(author), pull_request: None }))
+        .collect()
+}
+
+/// Minimal GitHub API client
+pub struct GitHubClient {
+    client: Client,
+    api_url: String,
+    token: Option<String>,
+    concurrency: usize,
+    cache_dir: Option<PathBuf>,
+    graphql: bool,
+}
+
+impl GitHubClient {
+    /// Create a client for the given API endpoint (e.g. `https://github.example.com/api/v3`)
+    pub fn new(api_url: &str, token: Option<String>) -> Self {
+        GitHubClient {
+            client: Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token,
+            concurrency: DEFAULT_GITHUB_CONCURRENCY,
+            cache_dir: None,
+            graphql: false,
+        }
+    }
+
+    /// Create a client for github.com with the token of the environment or the GitHub CLI
+    pub fn from_env() -> Self {
+        GitHubClient::from_auth(&GitHubAuth::default())
+    }
+
+    /// Create a client for github.com with the token resolved from `auth`
+    pub fn from_auth(auth: &GitHubAuth) -> Self {
+        GitHubClient::new(GITHUB_API_URL, auth.resolve_token())
+    }
+
+    /// Run up to `concurrency` commit lookups in parallel (at least one)
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Cache resolved authors under `dir`, one JSON file per `owner/repo/sha`.
+    ///
+    /// Commits are immutable, so entries never expire; unreadable entries are
+    /// fetched again and overwritten.
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Look up authors and pull requests through the GraphQL API, `GRAPHQL_BATCH_SIZE`
+    /// commits per query. Needs a token; without one the REST lookup is used.
+    ///
+    /// GraphQL results are not cached, since pull request labels can change.
+    pub fn with_graphql(mut self, graphql: bool) -> Self {
+        self.graphql = graphql;
+        self
+    }
+
+    /// Endpoint of the GraphQL API: `/graphql` on github.com, `/api/graphql` for the
+    /// `/api/v3` endpoint of GitHub Enterprise Server
+    fn graphql_url(&self) -> String {
+        format!("{}/graphql", self.api_url.strip_suffix("/v3").unwrap_or(&self.api_url))
+    }
+
+    /// Fetch the author and pull request of every commit with GraphQL, keyed by commit ID.
+    ///
+    /// Each query looks up to `GRAPHQL_BATCH_SIZE` commits; commits the API does not
+    /// know, and IDs that are not object IDs, are left out of the map.
+    pub fn fetch_commit_metadata_graphql(
+        &self,
+        repo_owner: &str,
+        repo_name: &str,
+        commit_ids: &[String],
+        progress: &ProgressReporter,
+    ) -> Result<HashMap<String, CommitMetadata>, VNextError> {
+        let token = self.token.as_deref().ok_or_else(|| {
+            VNextError::GithubError("The GraphQL API needs a token; set GITHUB_TOKEN, pass --github-token or log in with gh auth login".to_string())
+        })?;
+        let object_ids: Vec<&String> = commit_ids
+            .iter()
+            .filter(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_hexdigit()))
+            .collect();
+        let total = commit_ids.len() as u64;
+        let mut received = (commit_ids.len() - object_ids.len()) as u64;
+        let mut metadata = HashMap::new();
+        for batch in object_ids.chunks(GRAPHQL_BATCH_SIZE) {
+            progress.check_cancelled()?;
+            let variables = serde_json::json!({ "owner": repo_owner, "name": repo_name });
+            let data = self.graphql_request(token, &graphql_commits_query(batch), variables)?;
+            for (index, commit_id) in batch.iter().enumerate() {
+                let object = &data["repository"][format!("c{}", index)];
+                if !object.is_null() {
+                    metadata.insert((*commit_id).clone(), graphql_commit_metadata(object));
+                }
+            }
+            received += batch.len() as u64;
+            progress.report(Phase::Enrichment, received, Some(total));
+        }
+        log::debug!("Fetched metadata of {} commits in {} GraphQL queries", metadata.len(), object_ids.len().div_ceil(GRAPHQL_BATCH_SIZE));
+        Ok(metadata)
+    }
+
+    /// Send a GraphQL query and return its `data`; errors only fail the query when no
+    /// data came back
+    fn graphql_request(&self, token: &str, query: &str, variables: serde_json::Value) -> Result<serde_json::Value, VNextError> {
+        let response = self.client
+            .post(self.graphql_url())
+            .header("User-Agent", "vnext-cli")
+            .header("Authorization", format!("bearer {}", token))
+            .json(&serde_json::json!({ "query": query, "variables": variables }))
+            .send()
+            .map_err(|e| VNextError::GithubError(format!("Request failed: {}", e)))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(VNextError::GithubError(format!("GraphQL query failed with status {}", status)));
+        }
+        let mut body: serde_json::Value = response.json()
+            .map_err(|e| VNextError::GithubError(format!("Failed to parse response: {}", e)))?;
+        let errors: Vec<String> = body["errors"]
+            .as_array()
+            .map(|errors| errors.iter().filter_map(|e| e["message"].as_str().map(str::to_string)).collect())
+            .unwrap_or_default();
+        match body.get_mut("data").map(serde_json::Value::take) {
+            Some(data) if !data.is_null() => {
+                for error in errors {
+                    log::debug!("GraphQL query returned an error: {}", error);
+                }
+                Ok(data)
+            }
+            _ => Err(VNextError::GithubError(format!("GraphQL query failed: {}", errors.join("; ")))),
+        }
+    }
+
+    /// Fetch the author of every commit, in the order of `commit_ids`.
+    ///
+    /// Commits the API does not know (e.g. not pushed yet) get no author, and so
+    /// do commits whose request failed: each failure is logged as a warning and the
+    /// remaining lookups carry on. Progress is reported and cancellation checked on
+    /// the calling thread as results arrive.
+    pub fn fetch_commit_authors(
+        &self,
+        repo_owner: &str,
+        repo_name: &str,
+        commit_ids: &[String],
+        progress: &ProgressReporter,
+    ) -> Result<Vec<(String, Option<CommitAuthor>)>, VNextError> {
+        let total = commit_ids.len() as u64;
+        let next = AtomicUsize::new(0);
+        let stop = AtomicBool::new(false);
+        let mut authors: Vec<Option<CommitAuthor>> = vec![None; commit_ids.len()];
+        let mut failed = 0;
+        let (sender, receiver) = mpsc::channel();
+
+        // Only commits missing from the cache are requested
+        let mut pending = Vec::new();
+        for (index, commit_id) in commit_ids.iter().enumerate() {
+            match self.cached_author(repo_owner, repo_name, commit_id) {
+                Some(author) => authors[index] = Some(author),
+                None => pending.push(index),
+            }
+        }
+        log::debug!("Found {} of {} commit authors in the cache", commit_ids.len() - pending.len(), commit_ids.len());
+
+        let outcome = std::thread::scope(|scope| {
+            for _ in 0..self.concurrency.min(pending.len()) {
+                let sender = sender.clone();
+                let (next, stop, pending) = (&next, &stop, &pending);
+                scope.spawn(move || {
+                    // Each worker takes the next commit until all are taken or the lookup stops
+                    while !stop.load(Ordering::SeqCst) {
+                        let Some(&index) = pending.get(next.fetch_add(1, Ordering::SeqCst)) else { break };
+                        let result = self.fetch_commit_author(repo_owner, repo_name, &commit_ids[index]);
+                        if sender.send((index, result)).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(sender);
+
+            let mut received = (commit_ids.len() - pending.len()) as u64;
+            for (index, result) in receiver {
+                if let Err(e) = progress.check_cancelled() {
+                    stop.store(true, Ordering::SeqCst);
+                    return Err(e);
+                }
+                match result {
+                    Ok(author) => {
+                        // Failing to cache only costs a request next time
+                        if let Some(author) = &author {
+                            if let Err(e) = self.store_cached_author(repo_owner, repo_name, &commit_ids[index], author) {
+                                log::debug!("Failed to cache the author of {}: {}", commit_ids[index], e);
+                            }
+                        }
+                        authors[index] = author;
+                    }
+                    // One failed lookup leaves its commit without an author, the rest carry on
+                    Err(e) => {
+                        log::warn!("Failed to look up the author of commit {}: {}", commit_ids[index], e);
+                        failed += 1;
+                    }
+                }
+                received += 1;
+                progress.report(Phase::Enrichment, received, Some(total));
+            }
+            Ok(())
+        });
+        outcome?;
+        if failed > 0 {
+            log::warn!("Looked up {} of {} commit authors, {} lookups failed", commit_ids.len() - failed, commit_ids.len(), failed);
+        }
+
+        Ok(commit_ids.iter().cloned().zip(authors).collect())
+    }
+
+    /// Path of the cache entry for a commit, when caching is enabled
+    fn cache_path(&self, repo_owner: &str, repo_name: &str, commit_id: &str) -> Option<PathBuf> {
+        let dir = self.cache_dir.as_ref()?;
+        Some(dir.join(repo_owner).join(repo_name).join(format!("{}.json", commit_id)))
+    }
+
+    /// Read a cached author; missing and corrupted entries are treated alike
+    fn cached_author(&self, repo_owner: &str, repo_name: &str, commit_id: &str) -> Option<CommitAuthor> {
+        let path = self.cache_path(repo_owner, repo_name, commit_id)?;
+        let contents = std::fs::read_to_string(&path).ok()?;
+        match serde_json::from_str(&contents) {
+            Ok(author) => Some(author),
+            Err(e) => {
+                log::debug!("Ignoring corrupted cache entry {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Cache a resolved author, when caching is enabled
+    fn store_cached_author(&self, repo_owner: &str, repo_name: &str, commit_id: &str, author: &CommitAuthor) -> Result<(), VNextError> {
+        let Some(path) = self.cache_path(repo_owner, repo_name, commit_id) else { return Ok(()) };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string(author).map_err(|e| VNextError::Other(e.to_string()))?;
+        crate::utils::fs::write_atomic(&path, &json)
+    }
+
+    /// Fetch the author of a single commit, `None` when the API has no such commit
+    fn fetch_commit_author(&self, repo_owner: &str, repo_name: &str, commit_id: &str) -> Result<Option<CommitAuthor>, VNextError> {
+        let url = format!("{}/repos/{}/{}/commits/{}", self.api_url, repo_owner, repo_name, commit_id);
+
+        let mut request = self.client
+            .get(&url)
+            .header("User-Agent", "vnext-cli");
+
+        // Add authorization header if a token is available
+        if let Some(token) = &self.token {
+            log::debug!("Using GITHUB_TOKEN for authentication");
+            request = request.header("Authorization", format!("token {}", token));
+        }
+
+        let response = request.send()
+            .map_err(|e| VNextError::GithubError(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            log::debug!("Failed to fetch commit {} from GitHub API: {}", commit_id, response.status());
+            log::debug!("This probably means that {} exists in your current repository but has not been pushed to the remote.", commit_id);
+            return Ok(None);
+        }
+
+        let commit: GitHubCommit = response.json()
+            .map_err(|e| VNextError::GithubError(format!("Failed to parse response: {}", e)))?;
+        Ok(Some(CommitAuthor {
+            name: commit.commit.author.name,
+            email: commit.commit.author.email,
+            username: commit.author.map(|a| a.login),
+        }))
+    }
+
+    /// Check whether the Actions secret `secret_name` exists in the repository.
+    ///
+    /// Needs a token allowed to read the repository's secrets.
+    pub fn secret_exists(&self, repo_owner: &str, repo_name: &str, secret_name: &str) -> Result<bool, VNextError> {
+        self.lookup_secret(&format!("repos/{}/{}", repo_owner, repo_name), secret_name)
+    }
+
+    /// Check whether the Actions secret `secret_name` exists in the organization `org`.
+    ///
+    /// Needs a token allowed to read the organization's secrets.
+    pub fn org_secret_exists(&self, org: &str, secret_name: &str) -> Result<bool, VNextError> {
+        self.lookup_secret(&format!("orgs/{}", org), secret_name)
+    }
+
+    /// Look up the Actions secret `secret_name` under `scope` (`repos/o/n` or `orgs/o`)
+    fn lookup_secret(&self, scope: &str, secret_name: &str) -> Result<bool, VNextError> {
+        let token = self.token.as_deref().ok_or_else(|| {
+            VNextError::GithubError("Reading Actions secrets needs a token; set GITHUB_TOKEN, pass --github-token or log in with gh auth login".to_string())
+        })?;
+        let url = format!("{}/{}/actions/secrets/{}", self.api_url, scope, secret_name);
+        let response = self.client
+            .get(&url)
+            .header("User-Agent", "vnext-cli")
+            .header("Authorization", format!("token {}", token))
+            .send()
+            .map_err(|e| VNextError::GithubError(format!("Request failed: {}", e)))?;
+        match response.status() {
+            status if status.is_success() => Ok(true),
+            reqwest::StatusCode::NOT_FOUND => Ok(false),
+            status => Err(VNextError::GithubError(format!("Failed to look up secret {}: status {}", secret_name, status))),
+        }
+    }
+}
+
+impl CommitMetadataProvider for GitHubClient {
+    fn fetch_authors(
+        &self,
+        repo: &RepoInfo,
+        commit_ids: &[String],
+        progress: &ProgressReporter,
+    ) -> Result<HashMap<String, CommitAuthor>, VNextError> {
+        let authors = self.fetch_commit_authors(&repo.owner, &repo.name, commit_ids, progress)?;
+        Ok(authors.into_iter().filter_map(|(commit_id, author)| Some((commit_id, author?))).collect())
+    }
+
+    fn fetch_metadata(
+        &self,
+        repo: &RepoInfo,
+        commit_ids: &[String],
+        progress: &ProgressReporter,
+    ) -> Result<HashMap<String, CommitMetadata>, VNextError> {
+        if self.graphql {
+            if self.token.is_some() {
+                return self.fetch_commit_metadata_graphql(&repo.owner, &repo.name, commit_ids, progress);
+            }
+            log::warn!("--github-graphql needs a GitHub token; fetching authors through the REST API instead");
+        }
+        Ok(authors_metadata(self.fetch_authors(repo, commit_ids, progress)?))
+    }
+
+    fn provides_labels(&self) -> bool {
+        self.graphql && self.token.is_some()
+    }
+}
+
+/// Query looking up `commit_ids` as the aliases `c0`, `c1`, ... of the repository
+fn graphql_commits_query(commit_ids: &[&String]) -> String {
+    let mut query = String::from("query($owner: String!, $name: String!) {\n  repository(owner: $owner, name: $name) {\n");
+    for (index, commit_id) in commit_ids.iter().enumerate() {
+        query.push_str(&format!("    c{}: object(oid: \"{}\") {{ ...commitMetadata }}\n", index, commit_id));
+    }
+    query.push_str("  }\n}\n");
+    query.push_str(GRAPHQL_COMMIT_FRAGMENT);
+    query
+}
+
+/// Author and pull request of a commit object from a GraphQL response
+fn graphql_commit_metadata(object: &serde_json::Value) -> CommitMetadata {
+    let author = &object["author"];
+    let pull_request = &object["associatedPullRequests"]["nodes"][0];
+    CommitMetadata {
+        author: author["name"].as_str().map(|name| CommitAuthor {
+            name: name.to_string(),
+            email: author["email"].as_str().unwrap_or_default().to_string(),
+            username: author["user"]["login"].as_str().map(str::to_string),
+        }),
+        pull_request: pull_request["number"].as_u64().map(|number| PullRequestInfo {
+            number,
+            title: pull_request["title"].as_str().unwrap_or_default().to_string(),
+            labels: pull_request["labels"]["nodes"]
+                .as_array()
+                .map(|labels| labels.iter().filter_map(|label| label["name"].as_str().map(str::to_string)).collect())
+                .unwrap_or_default(),
+        }),
+    }
+}
+
+/// Enhance commit summary with author and pull request information from a forge
 pub fn enhance_with_github_info(
+    provider: &dyn CommitMetadataProvider,
     repo_info: &RepoInfo,
     summary: &mut ChangesetSummary,
+    progress: &ProgressReporter,
 ) -> Result<(), VNextError> {
-    log::debug!("GitHub integration enabled, fetching commit author information");
+    log::debug!("Forge integration enabled, fetching commit metadata");
     
     // Extract commit IDs from the summary
     let commit_ids: Vec<String> = summary.commits.iter()
         .map(|commit| commit.commit_id.clone())
         .collect();
     
-    // Fetch author information from GitHub API
-    match fetch_commit_authors(&repo_info.owner, &repo_info.name, &commit_ids) {
-        Ok(authors) => {
-            log::debug!("Attempted to retrieve author information for {} commits", authors.len());
-            log::debug!("Found authors for {} commits", authors.iter().filter(|(_, author)| author.is_some()).count());
-            // Create a map of commit IDs to authors
-            let mut author_map = HashMap::new();
-            for (commit_id, author) in authors {
-                author_map.insert(commit_id, author);
-            }
+    match provider.fetch_metadata(repo_info, &commit_ids, progress) {
+        Ok(metadata) => {
+            log::debug!("Found metadata for {} of {} commits", metadata.len(), commit_ids.len());
             
-            // Update the summary with author information
+            // Update the summary with author and pull request information
             for commit in &mut summary.commits {
-                if let Some(author) = author_map.get(&commit.commit_id) {
-                    if let Some(author_info) = author {
-                        log::debug!("Adding author information for commit {}: {}", commit.commit_id, author_info.name);
-                        commit.author = Some(author_info.clone());
-                    }
+                let Some(metadata) = metadata.get(&commit.commit_id) else { continue };
+                if let Some(author_info) = &metadata.author {
+                    log::debug!("Adding author information for commit {}: {}", commit.commit_id, author_info.name);
+                    // Keep a locally resolved (mailmapped) identity and only add the username
+                    commit.author = Some(match commit.author.take() {
+                        Some(local) => CommitAuthor { username: author_info.username.clone(), ..local },
+                        None => author_info.clone(),
+                    });
+                }
+                if let Some(pull_request) = &metadata.pull_request {
+                    commit.pr_number = Some(pull_request.number);
+                    commit.pr_title = Some(pull_request.title.clone());
+                    commit.labels = pull_request.labels.clone();
                 }
             }
+            summary.labels_known = provider.provides_labels();
             Ok(())
         }
+        Err(VNextError::Cancelled) => Err(VNextError::Cancelled),
+        Err(e @ VNextError::GitlabError(_)) => Err(e),
         Err(e) => {
             Err(VNextError::GithubError(format!("Failed to fetch author information: {}", e)))
         }
     }
 }
 
-/// Fetch commit author information from GitHub API
+/// Fetch commit author information from the GitHub API with a client for github.com
 pub fn fetch_commit_authors(
     repo_owner: &str,
     repo_name: &str,
     commit_ids: &[String],
+    progress: &ProgressReporter,
 ) -> Result<Vec<(String, Option<CommitAuthor>)>, VNextError> {
-    let client = Client::new();
-    let mut results = Vec::new();
-
-    for commit_id in commit_ids {
-        let url = format!(
-            "https://api.github.com/repos/{}/{}/commits/{}",
-            repo_owner, repo_name, commit_id
-        );
-
-        // Check for GITHUB_TOKEN environment variable
-        let mut request = client
-            .get(&url)
-            .header("User-Agent", "vnext-cli");
-            
-        // Add authorization header if GITHUB_TOKEN is available
-        if let Ok(token) = std::env::var("GITHUB_TOKEN") {
-            log::debug!("Using GITHUB_TOKEN for authentication");
-            request = request.header("Authorization", format!("token {}", token));
-        }
-        
-        let response = request.send()
-            .map_err(|e| VNextError::GithubError(format!("Request failed: {}", e)))?;
-
-        if response.status().is_success() {
-            let commit: GitHubCommit = response.json()
-                .map_err(|e| VNextError::GithubError(format!("Failed to parse response: {}", e)))?;
-            
-            let author = CommitAuthor {
-                name: commit.commit.author.name,
-                email: commit.commit.author.email,
-                username: commit.author.map(|a| a.login),
-            };
-            
-            results.push((commit_id.clone(), Some(author)));
-        } else {
-            log::debug!("Failed to fetch commit {} from GitHub API: {}", commit_id, response.status());
-            log::debug!("This probably means that {} exists in your current repository but has not been pushed to the remote.", commit_id);
-            results.push((commit_id.clone(), None));
-        }
-    }
-
-    Ok(results)
-}
\ No newline at end of file
+    GitHubClient::from_env().fetch_commit_authors(repo_owner, repo_name, commit_ids, progress)
+}
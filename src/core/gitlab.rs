@@ -0,0 +1,155 @@
+//! GitLab API integration
+
+use crate::core::github::CommitMetadataProvider;
+use crate::core::progress::ProgressReporter;
+use crate::models::commit::CommitAuthor;
+use crate::models::error::VNextError;
+use crate::models::github::{CommitMetadata, PullRequestInfo};
+use crate::models::gitlab::{GitLabMergeRequest, GitLabProject};
+use crate::models::options::Phase;
+use crate::models::repo::RepoInfo;
+use reqwest::blocking::{Client, RequestBuilder};
+use std::collections::HashMap;
+
+/// Default API endpoint for gitlab.com
+pub const GITLAB_API_URL: &str = "https://gitlab.com/api/v4";
+
+/// Minimal GitLab API client
+pub struct GitLabClient {
+    client: Client,
+    api_url: String,
+    token: Option<String>,
+}
+
+impl GitLabClient {
+    /// Create a client for the given API endpoint (e.g. `https://gitlab.example.com/api/v4`)
+    pub fn new(api_url: &str, token: Option<String>) -> Self {
+        GitLabClient {
+            client: Client::new(),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token,
+        }
+    }
+
+    /// Create a client for gitlab.com using the `GITLAB_TOKEN` environment variable
+    pub fn from_env() -> Self {
+        GitLabClient::new(GITLAB_API_URL, std::env::var("GITLAB_TOKEN").ok())
+    }
+
+    /// GET request for `path` under the API endpoint, with the token when one is set
+    fn get(&self, path: &str) -> RequestBuilder {
+        let mut request = self.client
+            .get(format!("{}/{}", self.api_url, path))
+            .header("User-Agent", "vnext-cli");
+
+        // Add authorization header if a token is available
+        if let Some(token) = &self.token {
+            log::debug!("Using GitLab token for authentication");
+            request = request.header("PRIVATE-TOKEN", token);
+        }
+        request
+    }
+
+    /// Look up the numeric project ID for a `namespace/project` path
+    pub fn resolve_project_id(&self, project_path: &str) -> Result<u64, VNextError> {
+        let response = self.get(&format!("projects/{}", encode_project_path(project_path)))
+            .send()
+            .map_err(|e| VNextError::GitlabError(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(VNextError::GitlabError(format!(
+                "Failed to look up project {}: {}", project_path, response.status()
+            )));
+        }
+
+        let project: GitLabProject = response.json()
+            .map_err(|e| VNextError::GitlabError(format!("Failed to parse response: {}", e)))?;
+        log::debug!("Resolved GitLab project {} to ID {}", project.path_with_namespace, project.id);
+        Ok(project.id)
+    }
+
+    /// Resolve and cache the project ID on the repository information.
+    ///
+    /// If the lookup fails the ID stays unset and API calls fall back to the
+    /// URL-encoded project path.
+    pub fn resolve_project(&self, repo_info: &mut RepoInfo) {
+        if repo_info.project_id.is_some() {
+            return;
+        }
+        let project_path = format!("{}/{}", repo_info.owner, repo_info.name);
+        match self.resolve_project_id(&project_path) {
+            Ok(id) => repo_info.project_id = Some(id),
+            Err(e) => log::warn!("Falling back to the encoded project path for {}: {}", project_path, e),
+        }
+    }
+
+    /// The merge request that brought `commit_id` in, preferring a merged one; `None`
+    /// when the commit has no merge request or the API does not know it
+    pub fn commit_merge_request(&self, repo_info: &RepoInfo, commit_id: &str) -> Result<Option<GitLabMergeRequest>, VNextError> {
+        let path = format!("projects/{}/repository/commits/{}/merge_requests", project_identifier(repo_info), commit_id);
+        let response = self.get(&path)
+            .send()
+            .map_err(|e| VNextError::GitlabError(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            log::debug!("Failed to fetch the merge requests of commit {} from GitLab API: {}", commit_id, response.status());
+            return Ok(None);
+        }
+
+        let merge_requests: Vec<GitLabMergeRequest> = response.json()
+            .map_err(|e| VNextError::GitlabError(format!("Failed to parse response: {}", e)))?;
+        let merged = merge_requests.iter().position(|merge_request| merge_request.state == "merged");
+        Ok(merge_requests.into_iter().nth(merged.unwrap_or(0)))
+    }
+}
+
+impl CommitMetadataProvider for GitLabClient {
+    /// GitLab does not link commits to user accounts, so commits keep their local authors
+    fn fetch_authors(
+        &self,
+        _repo: &RepoInfo,
+        _commit_ids: &[String],
+        _progress: &ProgressReporter,
+    ) -> Result<HashMap<String, CommitAuthor>, VNextError> {
+        Ok(HashMap::new())
+    }
+
+    /// The merge request of each commit, through the numeric project ID when it is
+    /// resolved on `repo`
+    fn fetch_metadata(
+        &self,
+        repo: &RepoInfo,
+        commit_ids: &[String],
+        progress: &ProgressReporter,
+    ) -> Result<HashMap<String, CommitMetadata>, VNextError> {
+        let total = commit_ids.len() as u64;
+        let mut metadata = HashMap::new();
+        for (index, commit_id) in commit_ids.iter().enumerate() {
+            progress.check_cancelled()?;
+            if let Some(merge_request) = self.commit_merge_request(repo, commit_id)? {
+                let pull_request = PullRequestInfo { number: merge_request.iid, title: merge_request.title, labels: merge_request.labels };
+                metadata.insert(commit_id.clone(), CommitMetadata { author: None, pull_request: Some(pull_request) });
+            }
+            progress.report(Phase::Enrichment, index as u64 + 1, Some(total));
+        }
+        Ok(metadata)
+    }
+
+    fn provides_labels(&self) -> bool {
+        true
+    }
+}
+
+/// URL-encode a `namespace/project` path for use as a GitLab project identifier
+pub fn encode_project_path(project_path: &str) -> String {
+    url::form_urlencoded::byte_serialize(project_path.as_bytes()).collect()
+}
+
+/// Identifier to use for a project in GitLab API paths: the numeric ID when known,
+/// otherwise the URL-encoded `namespace/project` path
+pub fn project_identifier(repo_info: &RepoInfo) -> String {
+    match repo_info.project_id {
+        Some(id) => id.to_string(),
+        None => encode_project_path(&format!("{}/{}", repo_info.owner, repo_info.name)),
+    }
+}
@@ -0,0 +1,90 @@
+//! Persistence of `vnext review` decisions in an ignore-revs file
+//!
+//! The file lists one commit per line as `<sha> [hide|noop]`, in the spirit of
+//! `.git-blame-ignore-revs`; a bare SHA hides the commit. Blank lines and lines
+//! starting with `#` are ignored.
+
+use crate::core::git;
+use crate::models::error::VNextError;
+use crate::models::options::VNextOptions;
+use crate::models::review::{IgnoreRevs, RevDecision, DEFAULT_IGNORE_REVS_FILE};
+use crate::utils::fs;
+use std::path::{Path, PathBuf};
+
+/// Parse the contents of an ignore-revs file
+pub fn parse_ignore_revs(contents: &str) -> Result<IgnoreRevs, String> {
+    let mut revs = IgnoreRevs::new();
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let rev = fields.next().unwrap_or_default();
+        if rev.len() < 4 || !rev.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("line {}: '{}' is not a commit SHA", number + 1, rev));
+        }
+        let decision = match fields.next() {
+            None | Some("hide") => RevDecision::Hide,
+            Some("noop") => RevDecision::Noop,
+            Some(other) => {
+                return Err(format!("line {}: unknown decision '{}', expected hide or noop", number + 1, other))
+            }
+        };
+        revs.set(rev, Some(decision));
+    }
+    Ok(revs)
+}
+
+/// Render decisions in the ignore-revs file format
+pub fn format_ignore_revs(revs: &IgnoreRevs) -> String {
+    let mut contents = String::from("# Commits reviewed with `vnext review`: <sha> hide|noop\n");
+    for (rev, decision) in revs.iter() {
+        contents.push_str(&format!("{} {}\n", rev, decision.as_str()));
+    }
+    contents
+}
+
+/// Load the decisions from `path`; a missing file holds no decisions
+pub fn load_ignore_revs(path: &Path) -> Result<IgnoreRevs, VNextError> {
+    if !path.exists() {
+        return Ok(IgnoreRevs::new());
+    }
+    let contents = std::fs::read_to_string(path)?;
+    parse_ignore_revs(&contents)
+        .map_err(|e| VNextError::ConfigError(format!("Invalid ignore-revs file {}: {}", path.display(), e)))
+}
+
+/// Write the decisions to `path`
+pub fn save_ignore_revs(path: &Path, revs: &IgnoreRevs) -> Result<(), VNextError> {
+    fs::write_atomic(path, &format_ignore_revs(revs))
+}
+
+/// Path of the ignore-revs file of a run: `--ignore-revs-file`, or `.vnext-ignore-revs`.
+///
+/// A relative path is resolved against the working directory of the repository, so the
+/// same file is used from any subdirectory of the checkout.
+pub fn ignore_revs_path(options: &VNextOptions) -> PathBuf {
+    let path = options
+        .ignore_revs_file
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_IGNORE_REVS_FILE));
+    // Without a repository the path is used as is; the calculation reports the missing
+    // repository, or tolerates it with --lenient
+    let workdir = git::open_repository_with(".", options.git_dir.as_deref()).ok().and_then(|repo| repo.workdir().map(Path::to_path_buf));
+    match workdir {
+        Some(workdir) if path.is_relative() => workdir.join(path),
+        _ => path,
+    }
+}
+
+/// Decisions of a run: those in `options.walk` plus the ones in the ignore-revs file
+pub fn load_for_run(options: &VNextOptions) -> Result<IgnoreRevs, VNextError> {
+    let mut revs = options.walk.ignore_revs.clone();
+    for (rev, decision) in load_ignore_revs(&ignore_revs_path(options))?.iter() {
+        if revs.decision(rev).is_none() {
+            revs.set(rev, Some(decision));
+        }
+    }
+    Ok(revs)
+}
@@ -0,0 +1,70 @@
+//! Soft lint rules for commit messages.
+//!
+//! Rules only raise warnings; they never change how a commit bumps the version. Each rule
+//! is an entry of `RULES`, so a new rule is a name and a check function.
+
+use crate::models::commit::Commit;
+use crate::models::diagnostics::Diagnostics;
+use crate::models::version::BumpMap;
+
+/// Longest subject line, in characters, the `subject-length` rule accepts
+pub const MAX_SUBJECT_LENGTH: usize = 72;
+
+/// What a rule sees of a commit: the parsed commit, its raw message and the type lists
+pub struct LintInput<'a> {
+    pub commit: &'a Commit,
+    pub message: &'a str,
+    pub bump_map: &'a BumpMap,
+}
+
+/// A named check returning a warning message when the commit breaks the rule
+pub struct LintRule {
+    pub name: &'static str,
+    pub check: fn(&LintInput) -> Option<String>,
+}
+
+/// Rules run on every analyzed commit, in order
+pub const RULES: &[LintRule] = &[
+    LintRule { name: "subject-length", check: subject_length },
+    LintRule { name: "subject-body-separator", check: subject_body_separator },
+    LintRule { name: "unknown-type", check: unknown_type },
+];
+
+/// Rule named `name`
+pub fn rule(name: &str) -> Option<&'static LintRule> {
+    RULES.iter().find(|rule| rule.name == name)
+}
+
+/// Run every rule on `commit`, whose raw message is `message`, recording the warnings in
+/// `diagnostics`
+pub fn lint_commit(commit: &Commit, message: &str, bump_map: &BumpMap, diagnostics: &mut Diagnostics) {
+    let input = LintInput { commit, message, bump_map };
+    for rule in RULES {
+        if let Some(warning) = (rule.check)(&input) {
+            diagnostics.warn(&commit.commit_id, rule.name, warning);
+        }
+    }
+}
+
+/// The subject is longer than `MAX_SUBJECT_LENGTH` characters
+fn subject_length(input: &LintInput) -> Option<String> {
+    let length = input.message.lines().next().unwrap_or_default().trim_end().chars().count();
+    (length > MAX_SUBJECT_LENGTH)
+        .then(|| format!("subject is {} characters, over the limit of {}", length, MAX_SUBJECT_LENGTH))
+}
+
+/// The line after the subject is not blank
+fn subject_body_separator(input: &LintInput) -> Option<String> {
+    let second_line = input.message.lines().nth(1)?;
+    (!second_line.trim().is_empty()).then(|| "no blank line between the subject and the body".to_string())
+}
+
+/// The type is in none of the type lists. `fix` is exempt, since it is the type the patch
+/// fallback exists for; messages without a type are left to the parser
+fn unknown_type(input: &LintInput) -> Option<String> {
+    let commit_type = input.commit.commit_type.as_str();
+    if commit_type.is_empty() || commit_type.eq_ignore_ascii_case("fix") || input.bump_map.level(commit_type).is_some() {
+        return None;
+    }
+    Some(format!("type '{}' is not in any configured type list, so it bumps the patch version", commit_type))
+}
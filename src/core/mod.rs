@@ -5,11 +5,32 @@
 
 pub mod git;
 pub mod github;
+pub mod gitlab;
 pub mod version;
+pub mod calculator;
 pub mod changelog;
+pub mod progress;
+pub mod authors;
+pub mod footers;
+pub mod ignore_revs;
+pub mod post_process;
+pub mod provenance;
+pub mod release;
+pub mod deploy_key;
+pub mod auth;
+pub mod push;
+pub mod lint;
 
 // Re-export commonly used functions
 pub use git::{extract_repo_info, find_latest_tag, find_trunk_branch, open_repository, resolve_head, get_repo_info};
 pub use github::enhance_with_github_info;
 pub use version::{calculate_next_version, calculate_version_bump, parse_version, calculate_version};
-pub use changelog::{output_result, output_fallback, format_changelog};
\ No newline at end of file
+pub use changelog::{fallback_changelog, format_changelog, format_changelog_with_options, render_heading, format_contributors, filter_changelog_commits};
+pub use calculator::{ReleasePlan, VersionCalculator};
+pub use authors::AuthorsFile;
+pub use footers::{apply_footer_rules, parse_footer_rule};
+pub use ignore_revs::{load_ignore_revs, save_ignore_revs};
+pub use progress::ProgressReporter;
+pub use post_process::{apply_post_process, run_post_process_command};
+pub use provenance::{build_provenance, configuration_fingerprint, Provenance};
+pub use release::{calculate_release, ReleaseOptions};
\ No newline at end of file
@@ -0,0 +1,102 @@
+//! Changelog post-processing through external commands and embedder hooks
+
+use crate::models::error::VNextError;
+use crate::models::options::VNextOptions;
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Default time an external post-process command may run before it is killed
+pub const DEFAULT_POST_PROCESS_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Apply the configured post-processing to a rendered changelog.
+///
+/// The external command (if any) runs first, followed by the library hook.
+pub fn apply_post_process(options: &VNextOptions, changelog: String) -> Result<String, VNextError> {
+    let changelog = match &options.changelog_post_process {
+        Some(command) => run_post_process_command(command, &changelog, options.changelog_post_process_timeout)?,
+        None => changelog,
+    };
+    Ok(match &options.post_process {
+        Some(hook) => hook(changelog),
+        None => changelog,
+    })
+}
+
+/// Pipe `input` through `command` (run by the platform shell) and return its stdout.
+///
+/// Fails if the command cannot be started, exits unsuccessfully, or runs longer than `timeout`.
+pub fn run_post_process_command(command: &str, input: &str, timeout: Duration) -> Result<String, VNextError> {
+    log::debug!("Post-processing changelog with: {}", command);
+
+    let mut child = shell_command(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| VNextError::PostProcessError(format!("Failed to run '{}': {}", command, e)))?;
+
+    // Feed stdin and drain the pipes on separate threads so a large changelog cannot deadlock
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = input.to_string();
+    let writer = thread::spawn(move || {
+        let _ = stdin.write_all(input.as_bytes());
+    });
+    let stdout_reader = read_pipe(child.stdout.take().expect("stdout is piped"));
+    let stderr_reader = read_pipe(child.stderr.take().expect("stderr is piped"));
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(VNextError::PostProcessError(format!(
+                "'{}' timed out after {}s",
+                command,
+                timeout.as_secs()
+            )));
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    let _ = writer.join();
+    let stdout = stdout_reader.join().unwrap_or_default();
+    let stderr = stderr_reader.join().unwrap_or_default();
+
+    if !status.success() {
+        return Err(VNextError::PostProcessError(format!(
+            "'{}' exited with {}: {}",
+            command,
+            status,
+            stderr.trim()
+        )));
+    }
+
+    Ok(stdout.trim_end_matches(['\r', '\n']).to_string())
+}
+
+fn read_pipe<R: Read + Send + 'static>(mut pipe: R) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut contents = String::new();
+        let _ = pipe.read_to_string(&mut contents);
+        contents
+    })
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
@@ -0,0 +1,49 @@
+//! Progress reporting and cancellation for long-running phases
+
+use crate::models::error::VNextError;
+use crate::models::options::{CancellationToken, Phase, ProgressCallback, VNextOptions};
+
+/// Borrowed view of the progress callback and cancellation token of a run.
+///
+/// Core functions take a reporter rather than the full options so they can be
+/// used without a progress hook via `ProgressReporter::none()`.
+#[derive(Clone, Copy, Default)]
+pub struct ProgressReporter<'a> {
+    callback: Option<&'a ProgressCallback>,
+    cancellation: Option<&'a CancellationToken>,
+}
+
+impl<'a> ProgressReporter<'a> {
+    /// Create a reporter from an optional callback and cancellation token
+    pub fn new(callback: Option<&'a ProgressCallback>, cancellation: Option<&'a CancellationToken>) -> Self {
+        ProgressReporter { callback, cancellation }
+    }
+
+    /// Create a reporter that does nothing and never cancels
+    pub fn none() -> Self {
+        ProgressReporter { callback: None, cancellation: None }
+    }
+
+    /// Create a reporter from the progress hook and cancellation token in the options
+    pub fn from_options(options: &'a VNextOptions) -> Self {
+        ProgressReporter::new(options.progress.as_ref(), options.cancellation.as_ref())
+    }
+
+    /// Report progress for a phase
+    pub fn report(&self, phase: Phase, current: u64, total: Option<u64>) {
+        if let Some(callback) = self.callback {
+            callback(phase, current, total);
+        }
+    }
+
+    /// Return `VNextError::Cancelled` if cancellation has been requested
+    pub fn check_cancelled(&self) -> Result<(), VNextError> {
+        match self.cancellation {
+            Some(token) if token.is_cancelled() => {
+                log::debug!("Cancellation requested, aborting");
+                Err(VNextError::Cancelled)
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Release provenance metadata for supply-chain tooling
+
+use crate::core::calculator::ReleasePlan;
+use crate::models::options::VNextOptions;
+use crate::utils::date;
+use serde::Serialize;
+
+/// Version of the provenance schema; bumped on incompatible changes
+pub const PROVENANCE_SCHEMA_VERSION: u32 = 1;
+
+/// Provenance of a calculated release, written by `--provenance-out`.
+///
+/// Every field is always present; optional values are `null`.
+#[derive(Serialize, Debug, Clone)]
+pub struct Provenance {
+    /// Always `PROVENANCE_SCHEMA_VERSION`
+    pub schema_version: u32,
+    /// `owner/name` of the `--remote` remote (`origin` by default), if it could be determined
+    pub repository: Option<String>,
+    /// Tag the release will be published under (e.g. `v1.3.0`)
+    pub tag: String,
+    /// Latest existing release tag, if there is one
+    pub previous_tag: Option<String>,
+    /// Version of the previous release (0.0.0 when there is none)
+    pub current_version: String,
+    /// Version of the release
+    pub next_version: String,
+    /// Full SHA of the commit HEAD pointed to
+    pub head_sha: String,
+    /// Full SHA of the commit the release was compared against
+    pub baseline_sha: String,
+    /// Number of commits in the release, before changelog filters
+    pub commit_count: usize,
+    /// Tool that produced the metadata
+    pub tool: ProvenanceTool,
+    /// Hash of the settings that affect the calculated version
+    pub configuration_fingerprint: String,
+    /// Timestamps, as RFC 3339 UTC strings
+    pub timestamps: ProvenanceTimestamps,
+}
+
+/// Name and version of the tool that produced the provenance
+#[derive(Serialize, Debug, Clone)]
+pub struct ProvenanceTool {
+    pub name: String,
+    pub version: String,
+}
+
+/// Timestamps recorded in the provenance
+#[derive(Serialize, Debug, Clone)]
+pub struct ProvenanceTimestamps {
+    /// When the provenance was generated
+    pub generated_at: String,
+    /// Commit time of HEAD
+    pub head_committed_at: String,
+}
+
+/// Build the provenance of a release plan.
+///
+/// `head_time` is the commit time of HEAD in seconds since the Unix epoch.
+pub fn build_provenance(plan: &ReleasePlan, options: &VNextOptions, head_time: i64) -> Provenance {
+    let repository = if plan.repo_info.owner.is_empty() || plan.repo_info.name.is_empty() {
+        None
+    } else {
+        Some(format!("{}/{}", plan.repo_info.owner, plan.repo_info.name))
+    };
+
+    Provenance {
+        schema_version: PROVENANCE_SCHEMA_VERSION,
+        repository,
+        tag: options.walk.release_tag_name(&plan.next_version),
+        previous_tag: plan.previous_tag.clone(),
+        current_version: options.walk.scheme.render(&plan.current_version),
+        next_version: options.walk.scheme.render(&plan.next_version),
+        head_sha: plan.head_commit.to_string(),
+        baseline_sha: plan.base_commit.to_string(),
+        commit_count: plan.summary.commits.len(),
+        tool: ProvenanceTool {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        },
+        configuration_fingerprint: configuration_fingerprint(options),
+        timestamps: ProvenanceTimestamps {
+            generated_at: date::format_epoch_timestamp(date::now_epoch()),
+            head_committed_at: date::format_epoch_timestamp(head_time),
+        },
+    }
+}
+
+/// Fingerprint of the settings that affect the calculated version.
+///
+/// This is a 64-bit FNV-1a hash in hex over a canonical description of the settings, so
+/// it is stable across runs and platforms for the same vnext version and configuration.
+pub fn configuration_fingerprint(options: &VNextOptions) -> String {
+    let mut aliases: Vec<_> = options.parser.type_aliases().iter().collect();
+    aliases.sort();
+    let canonical = format!(
+        "parser={:?}\naliases={:?}\nbump_map={:?}\nempty_commits={:?}\ntag_namespace={:?}\ntag_prefix={:?}\npath={:?}\nscopes={:?}\nexclude_unscoped={}\nfooter_rules={:?}\nversion_override={:?}\ninitial_version={:?}\nfirst_release={}\nmax_commits={:?}",
+        options.parser.strategy(),
+        aliases,
+        options.bump_map.entries(),
+        options.walk.empty_commits,
+        options.walk.tag_namespace,
+        options.walk.tag_prefix,
+        options.walk.path,
+        options.walk.scopes,
+        options.walk.exclude_unscoped,
+        options.walk.footer_rules,
+        options.version_override,
+        options.walk.initial_version,
+        options.first_release,
+        options.walk.max_commits,
+    );
+
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in canonical.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
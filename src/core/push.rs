@@ -0,0 +1,71 @@
+//! Ref updates of a push, as server-side Git hooks receive them on stdin
+
+use crate::models::error::VNextError;
+use git2::{Oid, Repository};
+
+/// One `<old> <new> <ref>` line of a pre-receive or post-receive hook
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RefUpdate {
+    /// Commit the ref pointed to, `None` for a new ref
+    pub old: Option<Oid>,
+    /// Commit the ref is updated to, `None` when it is deleted
+    pub new: Option<Oid>,
+    /// Full name of the ref, e.g. `refs/heads/main`
+    pub refname: String,
+}
+
+/// Parse the `<old> <new> <ref>` lines of a hook's stdin; blank lines are skipped
+pub fn parse_ref_updates(input: &str) -> Result<Vec<RefUpdate>, VNextError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            parse_ref_update(line).map_err(|e| VNextError::ConfigError(format!("stdin line {}: {}", i + 1, e)))
+        })
+        .collect()
+}
+
+fn parse_ref_update(line: &str) -> Result<RefUpdate, String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [old, new, refname] = fields[..] else {
+        return Err(format!("expected '<old> <new> <ref>', got '{}'", line.trim()));
+    };
+    Ok(RefUpdate { old: parse_sha(old)?, new: parse_sha(new)?, refname: refname.to_string() })
+}
+
+/// Parse a full commit ID; the all-zero ID stands for a missing commit
+fn parse_sha(sha: &str) -> Result<Option<Oid>, String> {
+    if !matches!(sha.len(), 40 | 64) || !sha.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("'{}' is not a full commit ID", sha));
+    }
+    let oid = Oid::from_str(sha).map_err(|e| format!("'{}' is not a full commit ID: {}", sha, e))?;
+    Ok((!oid.is_zero()).then_some(oid))
+}
+
+/// Commit whose descendants up to `update.new` are the pushed commits: the old commit of
+/// the ref, or for a new ref its merge base with the `trunk` branch. `None` means the
+/// whole history of the new commit, when a new ref has no trunk to branch from.
+pub fn range_base(repo: &Repository, update: &RefUpdate, trunk: Option<&str>) -> Result<Option<Oid>, VNextError> {
+    if update.old.is_some() {
+        return Ok(update.old);
+    }
+    let Some(new) = update.new else { return Ok(None) };
+    let Some(trunk) = trunk else {
+        log::debug!("No trunk branch for the new ref {}, using its whole history", update.refname);
+        return Ok(None);
+    };
+    // The trunk of a bare repository is a local branch, of a checkout possibly only a remote one
+    let tip = [format!("refs/heads/{}", trunk), format!("refs/remotes/origin/{}", trunk)]
+        .iter()
+        .find_map(|name| repo.refname_to_id(name).ok());
+    match tip.map(|tip| repo.merge_base(new, tip)) {
+        Some(Ok(base)) => Ok(Some(base)),
+        Some(Err(e)) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Some(Err(e)) => Err(e.into()),
+        None => {
+            log::debug!("Trunk branch {} not found, using the whole history of {}", trunk, update.refname);
+            Ok(None)
+        }
+    }
+}
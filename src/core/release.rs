@@ -0,0 +1,278 @@
+//! One-call release calculation for embedders, driving the same code as the CLI
+
+use crate::core::authors::AuthorsFile;
+use crate::core::calculator::{ReleasePlan, VersionCalculator};
+use crate::core::changelog;
+use crate::core::git;
+use crate::core::github::GitHubClient;
+use crate::core::gitlab::GitLabClient;
+use crate::core::post_process;
+use crate::core::progress::ProgressReporter;
+use crate::models::changeset::ChangesetSummary;
+use crate::models::error::VNextError;
+use crate::models::options::{ChangelogOptions, VNextOptions, WalkOptions};
+use crate::models::review::{IgnoreRevs, RevDecision};
+use crate::models::version::{BumpLevel, BumpMap};
+use crate::parsers::{ParserHandle, ParserStrategy};
+use std::path::Path;
+
+/// Owned configuration of `calculate_release`; the defaults match the CLI defaults
+#[derive(Clone, Debug)]
+pub struct ReleaseOptions {
+    /// Strategy used to parse commit messages
+    pub parser: ParserStrategy,
+    /// Commit types that trigger a major bump
+    pub major_types: Vec<String>,
+    /// Commit types that trigger a minor bump
+    pub minor_types: Vec<String>,
+    /// Commit types that trigger no bump; every other type bumps the patch version
+    pub noop_types: Vec<String>,
+    /// Only consider release tags under this namespace (e.g. `releases/`)
+    pub tag_namespace: Option<String>,
+    /// Only consider release tags starting with this prefix after the namespace (e.g. `api-v`)
+    pub tag_prefix: Option<String>,
+    /// Only count commits that touch files under this repository-relative path
+    pub path: Option<String>,
+    /// Render the changelog into `ReleasePlan::changelog`
+    pub changelog: bool,
+    /// Omit no-op commits from the changelog
+    pub changelog_exclude_noop: bool,
+    /// Commit types to show in the changelog (all types when empty)
+    pub changelog_include_types: Vec<String>,
+    /// Options controlling how the changelog is rendered
+    pub changelog_options: ChangelogOptions,
+}
+
+impl Default for ReleaseOptions {
+    fn default() -> Self {
+        let bump_map = BumpMap::default();
+        let types = |level: BumpLevel| bump_map.types_at(level).into_iter().map(str::to_string).collect();
+        ReleaseOptions {
+            parser: ParserStrategy::Conventional,
+            major_types: types(BumpLevel::Major),
+            minor_types: types(BumpLevel::Minor),
+            noop_types: types(BumpLevel::None),
+            tag_namespace: None,
+            tag_prefix: None,
+            path: None,
+            changelog: false,
+            changelog_exclude_noop: false,
+            changelog_include_types: Vec::new(),
+            changelog_options: ChangelogOptions::default(),
+        }
+    }
+}
+
+impl ReleaseOptions {
+    /// The run options of a vnext invocation with these settings
+    pub fn to_options(&self) -> VNextOptions {
+        VNextOptions {
+            parser: ParserHandle::new(self.parser.clone()),
+            bump_map: BumpMap::from_types(&self.major_types, &self.minor_types, &self.noop_types),
+            walk: WalkOptions {
+                tag_namespace: self.tag_namespace.as_deref().map(git::normalize_tag_namespace),
+                tag_prefix: self.tag_prefix.clone(),
+                path: self.path.clone(),
+                ..WalkOptions::default()
+            },
+            show_changelog: self.changelog,
+            changelog_exclude_noop: self.changelog_exclude_noop,
+            changelog_include_types: self.changelog_include_types.clone(),
+            changelog: ChangelogOptions {
+                tag_namespace: self.tag_namespace.as_deref().map(git::normalize_tag_namespace),
+                tag_prefix: self.tag_prefix.clone(),
+                ..self.changelog_options.clone()
+            },
+            ..VNextOptions::default()
+        }
+    }
+}
+
+/// Calculate the next release of the repository at `repo_path`, with the changelog
+/// rendered into `ReleasePlan::changelog` when `options.changelog` is set.
+///
+/// Like the CLI, commits of GitHub repositories are attributed through the GitHub API
+/// (authenticated with the token of `GITHUB_TOKEN`, `GH_TOKEN` or the GitHub CLI) before
+/// the changelog is rendered.
+///
+/// ```
+/// # use std::process::Command;
+/// # let dir = tempfile::tempdir().unwrap();
+/// # let git = |args: &[&str]| {
+/// #     Command::new("git").args(args).current_dir(dir.path()).output().unwrap();
+/// # };
+/// # git(&["init"]);
+/// # git(&["config", "user.name", "Test"]);
+/// # git(&["config", "user.email", "test@example.com"]);
+/// # std::fs::write(dir.path().join("README.md"), "# Test").unwrap();
+/// # git(&["add", "."]);
+/// # git(&["commit", "-m", "feat: Initial commit"]);
+/// # git(&["tag", "v1.0.0"]);
+/// # std::fs::write(dir.path().join("export.txt"), "export").unwrap();
+/// # git(&["add", "."]);
+/// # git(&["commit", "-m", "feat: Add export"]);
+/// use vnext::core::{calculate_release, ReleaseOptions};
+///
+/// let options = ReleaseOptions { changelog: true, ..ReleaseOptions::default() };
+/// let plan = calculate_release(dir.path(), &options)?;
+///
+/// assert_eq!(plan.next_version.to_string(), "1.1.0");
+/// assert_eq!(plan.bump.level(), "minor");
+/// let changelog = plan.changelog.unwrap();
+/// assert!(changelog.starts_with("### What's changed in v1.1.0"));
+/// assert!(changelog.contains("* feat: Add export"));
+/// # Ok::<(), vnext::VNextError>(())
+/// ```
+pub fn calculate_release(repo_path: &Path, options: &ReleaseOptions) -> Result<ReleasePlan, VNextError> {
+    let options = options.to_options();
+    let progress = ProgressReporter::from_options(&options);
+    let mut plan = VersionCalculator::from_options(repo_path, &options).run()?;
+    if options.show_changelog {
+        let repo = git::open_repository_with(repo_path, options.git_dir.as_deref())?;
+        attribute_commits(&repo, &mut plan, &options, &progress)?;
+        let changelog = render_changelog(&mut plan, &options)?;
+        plan.changelog = Some(changelog);
+    }
+    Ok(plan)
+}
+
+/// Attribute the commits of `plan.summary` locally (honoring `.mailmap`), through the
+/// GitHub API for GitHub remotes, with merge requests from the GitLab API for GitLab
+/// remotes and through the authors file, and look up earlier
+/// contributors when the changelog lists them. Local attribution also runs for the
+/// summary and totals lines, which count the contributors.
+pub fn attribute_commits(
+    repo: &git2::Repository,
+    plan: &mut ReleasePlan,
+    options: &VNextOptions,
+    progress: &ProgressReporter,
+) -> Result<(), VNextError> {
+    let summary = &mut plan.summary;
+    let use_github = plan.repo_info.is_github_repo;
+
+    // Attribute commits from the local repository (honoring .mailmap) before any forge lookup
+    if use_github || options.changelog.contributors || options.changelog.summary_line || options.changelog.section_counts || options.authors_file.is_some() {
+        git::populate_local_authors(repo, summary);
+    }
+
+    // Handle GitHub integration if needed
+    if use_github {
+        let started = std::time::Instant::now();
+        let mut client = GitHubClient::from_auth(&options.github_auth)
+            .with_concurrency(options.github_concurrency)
+            .with_graphql(options.github_graphql);
+        if let Some(dir) = &options.github_cache {
+            client = client.with_cache_dir(dir);
+        }
+        match crate::core::github::enhance_with_github_info(&client, &plan.repo_info, summary, progress) {
+            Ok(()) => {}
+            Err(VNextError::Cancelled) => return Err(VNextError::Cancelled),
+            Err(VNextError::GithubError(e)) if options.require_github => return Err(VNextError::GithubRequired(e)),
+            Err(e) if options.require_github => return Err(VNextError::GithubRequired(e.to_string())),
+            Err(e) => log::warn!("Failed to fetch author information from GitHub API: {}", e),
+        }
+        log::debug!("GitHub enrichment took {:?}", started.elapsed());
+    } else if options.require_github {
+        return Err(VNextError::GithubRequired(
+            "the repository has no GitHub remote".to_string(),
+        ));
+    } else if plan.repo_info.is_gitlab_repo {
+        // Resolve the numeric project ID once so every commit lookup uses it
+        let started = std::time::Instant::now();
+        let client = GitLabClient::from_env();
+        client.resolve_project(&mut plan.repo_info);
+        match crate::core::github::enhance_with_github_info(&client, &plan.repo_info, summary, progress) {
+            Ok(()) => {}
+            Err(VNextError::Cancelled) => return Err(VNextError::Cancelled),
+            Err(e) => log::warn!("Failed to fetch merge requests: {}", e),
+        }
+        log::debug!("GitLab enrichment took {:?}", started.elapsed());
+    }
+
+    // Apply the vendored author mapping on top of local and forge attribution
+    if let Some(path) = &options.authors_file {
+        AuthorsFile::load(path)?.apply(summary, options.authors_file_wins);
+    }
+
+    // Look up earlier contributors for the contributors section
+    if options.changelog.contributors && plan.previous_tag.is_some() {
+        let base_commit = repo.find_commit(plan.base_commit)?;
+        match git::collect_previous_contributors(repo, &base_commit, git::MAX_CONTRIBUTOR_HISTORY) {
+            Ok(known) => summary.known_contributors = Some(known),
+            Err(e) => log::warn!("Failed to collect previous contributors: {}", e),
+        }
+    }
+
+    if !options.changelog_exclude_labels.is_empty() && !summary.labels_known {
+        log::warn!("Pull request labels are only fetched for GitHub remotes with --github-graphql and GITHUB_TOKEN, and for GitLab remotes; no commits are excluded by label");
+    }
+    Ok(())
+}
+
+/// Count the commits of `plan` whose pull request has one of the excluded labels as
+/// no-ops, on top of the review `decisions`, and recalculate the bump and next version.
+///
+/// Nothing changes unless the labels of the commits were fetched by `attribute_commits`.
+pub fn exclude_labeled_from_bump(
+    repo_path: &Path,
+    plan: &mut ReleasePlan,
+    options: &VNextOptions,
+    decisions: &IgnoreRevs,
+) -> Result<(), VNextError> {
+    let labels = &options.changelog_exclude_labels;
+    if !plan.summary.labels_known {
+        return Ok(());
+    }
+    let mut decisions = decisions.clone();
+    let mut excluded = 0;
+    for commit in plan.summary.commits.iter().filter(|commit| commit.has_any_label(labels)) {
+        // A commit hidden during review stays hidden
+        if decisions.decision(&commit.commit_id).is_none() {
+            decisions.set(&commit.commit_id, Some(RevDecision::Noop));
+            excluded += 1;
+        }
+    }
+    if excluded == 0 {
+        return Ok(());
+    }
+    log::debug!("Recalculating the bump with {} commits excluded by label", excluded);
+    let recalculated = VersionCalculator::from_options(repo_path, options)
+        .with_ignore_revs(decisions)
+        .run()?;
+    plan.next_version = recalculated.next_version;
+    plan.bump = recalculated.bump;
+    let summary = &mut plan.summary;
+    (summary.major, summary.minor, summary.patch, summary.noop) = (
+        recalculated.summary.major,
+        recalculated.summary.minor,
+        recalculated.summary.patch,
+        recalculated.summary.noop,
+    );
+    Ok(())
+}
+
+/// Filter and render the changelog of the attributed `plan`, then run it through the
+/// post-process steps of `options`.
+pub fn render_changelog(plan: &mut ReleasePlan, options: &VNextOptions) -> Result<String, VNextError> {
+    let summary = &mut plan.summary;
+    filter_changelog_commits(summary, options);
+
+    let rendered = changelog::format_changelog_with_options(
+        summary, &plan.next_version, &plan.current_version, &plan.repo_info, &options.changelog,
+    );
+    post_process::apply_post_process(options, rendered)
+}
+
+/// Drop the commits that should not appear in the changelog under `options`
+pub fn filter_changelog_commits(summary: &mut ChangesetSummary, options: &VNextOptions) {
+    let noop_types = options.bump_map.types_at(BumpLevel::None);
+    let include_types: Vec<&str> = options.changelog_include_types.iter().map(|s| s.as_str()).collect();
+    changelog::filter_changelog_commits(summary, options.changelog_exclude_noop, &noop_types, &include_types);
+    if options.changelog_scope_filter {
+        summary.commits.retain(|commit| !commit.out_of_scope || commit.force_include);
+    }
+    if options.changelog_hide_skipped {
+        summary.commits.retain(|commit| !commit.skip_release || commit.force_include);
+    }
+    changelog::exclude_labeled_commits(summary, &options.changelog_exclude_labels);
+}
@@ -3,9 +3,14 @@
 use semver::{BuildMetadata, Prerelease, Version};
 use git2::{Commit, Repository};
 use crate::models::error::VNextError;
-use crate::models::version::VersionBump;
+use crate::models::version::{BumpLevel, BumpMap, BumpReason, CalverFormat, CommitBump, VersionBump, VersionOverride, VersionScheme};
+use std::collections::HashMap;
 use crate::models::changeset::ChangesetSummary;
+use crate::models::options::{EmptyCommitPolicy, Phase, WalkOptions};
+use crate::models::review::RevDecision;
+use crate::core::progress::ProgressReporter;
 use log::debug;
+use std::fmt;
 
 /// Enum representing the type of version bump
 #[derive(Debug, PartialEq)]
@@ -16,6 +21,18 @@ pub enum VersionBumpType {
     NoOp,
 }
 
+impl VersionBumpType {
+    /// Bump level of this commit classification
+    pub fn level(&self) -> BumpLevel {
+        match self {
+            VersionBumpType::Major => BumpLevel::Major,
+            VersionBumpType::Minor => BumpLevel::Minor,
+            VersionBumpType::Patch => BumpLevel::Patch,
+            VersionBumpType::NoOp => BumpLevel::None,
+        }
+    }
+}
+
 /// Determine the type of version bump for a commit based on configurable commit types
 pub fn determine_version_bump(
     commit: &crate::models::commit::Commit,
@@ -23,28 +40,199 @@ pub fn determine_version_bump(
     minor_types: &[&str],
     noop_types: &[&str]
 ) -> VersionBumpType {
+    classify_commit(commit, &BumpMap::from_types(major_types, minor_types, noop_types))
+}
+
+/// Determine the type of version bump for a commit from the level its type is mapped to
+pub fn classify_commit(commit: &crate::models::commit::Commit, bump_map: &BumpMap) -> VersionBumpType {
+    explain_commit(commit, bump_map).0
+}
+
+/// Determine the type of version bump for a commit together with the rule that decided it
+pub fn explain_commit(commit: &crate::models::commit::Commit, bump_map: &BumpMap) -> (VersionBumpType, BumpReason) {
     // Breaking changes always trigger a major bump
     if commit.has_breaking_change {
-        return VersionBumpType::Major;
+        let reason = if header_has_bang(&commit.raw_message) { BumpReason::BreakingBang } else { BumpReason::BreakingFooter };
+        return (VersionBumpType::Major, reason);
     }
-    
-    // Check commit type against configurable lists
-    if major_types.contains(&commit.commit_type.as_str()) {
-        VersionBumpType::Major
-    } else if minor_types.contains(&commit.commit_type.as_str()) {
-        VersionBumpType::Minor
-    } else if noop_types.contains(&commit.commit_type.as_str()) {
-        VersionBumpType::NoOp
-    } else {
+
+    match bump_map.level(&commit.commit_type) {
+        Some(BumpLevel::Major) => (VersionBumpType::Major, BumpReason::TypeList),
+        Some(BumpLevel::Minor) => (VersionBumpType::Minor, BumpReason::TypeList),
+        Some(BumpLevel::Patch) => (VersionBumpType::Patch, BumpReason::TypeList),
+        Some(BumpLevel::None) => (VersionBumpType::NoOp, BumpReason::TypeList),
         // Default to patch for any other commit type
-        VersionBumpType::Patch
+        None => (VersionBumpType::Patch, BumpReason::UnknownFallback),
+    }
+}
+
+/// Whether the header of `message` marks a breaking change with `!` before the colon
+fn header_has_bang(message: &str) -> bool {
+    let header = message.lines().next().unwrap_or("");
+    header.split_once(':').is_some_and(|(prefix, _)| prefix.trim_end().ends_with('!'))
+}
+
+/// Parse a comma-separated list of `type=level` pairs (e.g. `feat=minor,fix=patch,docs=none`).
+///
+/// Types are lowercased; levels are `major`, `minor`, `patch` or `none` (`noop`).
+pub fn parse_bump_map(value: &str) -> Result<HashMap<String, BumpLevel>, String> {
+    let mut levels = HashMap::new();
+    for pair in value.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+        let (commit_type, level) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("expected type=level, got '{}'", pair))?;
+        let commit_type = commit_type.trim().to_lowercase();
+        if commit_type.is_empty() {
+            return Err(format!("expected type=level, got '{}'", pair));
+        }
+        let level = BumpLevel::parse(level).ok_or_else(|| format!(
+            "unknown bump level '{}' for type '{}', expected major, minor, patch or none",
+            level.trim(), commit_type
+        ))?;
+        levels.insert(commit_type, level);
+    }
+    Ok(levels)
+}
+
+/// Why a tag or version string could not be parsed
+#[derive(Debug)]
+pub enum VersionParseError {
+    /// The input does not start with the configured namespace and prefix
+    WrongPrefix { input: String, expected: String },
+    /// The input has the expected prefix but the rest is not a semantic version
+    InvalidSemver { input: String, source: semver::Error },
+}
+
+impl fmt::Display for VersionParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionParseError::WrongPrefix { input, expected } => {
+                write!(f, "'{}' does not start with the prefix '{}'", input, expected)
+            }
+            VersionParseError::InvalidSemver { input, source } => {
+                write!(f, "'{}' is not a semantic version: {}", input, source)
+            }
+        }
     }
 }
 
-/// Parse a version string into a semver Version
-pub fn parse_version(tag: &str) -> Result<Version, semver::Error> {
-    let cleaned_tag = tag.trim_start_matches('v');
-    Version::parse(cleaned_tag)
+impl std::error::Error for VersionParseError {}
+
+/// Parser for release tags and version strings, configured once with the tag
+/// namespace, tag prefix and leniency of a run.
+///
+/// The namespace and prefix are stripped first, then a single optional `v`. In lenient
+/// mode surrounding whitespace is ignored and missing minor and patch components are
+/// filled in with zeros (`1.2` parses as `1.2.0`).
+#[derive(Clone, Debug, Default)]
+pub struct VersionParser {
+    namespace: Option<String>,
+    prefix: Option<String>,
+    lenient: bool,
+    scheme: VersionScheme,
+}
+
+impl VersionParser {
+    /// Create a strict parser for plain `v1.2.3` / `1.2.3` versions
+    pub fn new() -> Self {
+        VersionParser::default()
+    }
+
+    /// Create a parser for the release tags selected by the walk options
+    pub fn from_walk_options(walk: &WalkOptions) -> Self {
+        VersionParser {
+            namespace: walk.tag_namespace.clone(),
+            prefix: walk.tag_prefix.clone(),
+            lenient: false,
+            scheme: walk.scheme,
+        }
+    }
+
+    /// Require the tag namespace (e.g. `releases/`)
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(crate::core::git::normalize_tag_namespace(namespace));
+        self
+    }
+
+    /// Require the tag prefix after the namespace (e.g. `api-v`)
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = Some(prefix.to_string());
+        self
+    }
+
+    /// Parse versions of `scheme`; calendar versions may have zero-padded segments (`2026.01.3`)
+    pub fn with_scheme(mut self, scheme: VersionScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    /// Accept surrounding whitespace and versions with missing minor or patch components
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// The full prefix expected before the version: the namespace followed by the prefix
+    pub fn expected_prefix(&self) -> String {
+        format!("{}{}", self.namespace.as_deref().unwrap_or(""), self.prefix.as_deref().unwrap_or(""))
+    }
+
+    /// Parse a tag or version string
+    pub fn parse(&self, input: &str) -> Result<Version, VersionParseError> {
+        let trimmed = if self.lenient { input.trim() } else { input };
+        let expected = self.expected_prefix();
+        let rest = trimmed.strip_prefix(expected.as_str()).ok_or_else(|| VersionParseError::WrongPrefix {
+            input: input.to_string(),
+            expected: expected.clone(),
+        })?;
+        let rest = rest.strip_prefix('v').unwrap_or(rest);
+        let mut version = if self.lenient { pad_version(rest) } else { rest.to_string() };
+        if let VersionScheme::Calver(_) = self.scheme {
+            version = strip_leading_zeros(&version);
+        }
+        Version::parse(&version).map_err(|source| VersionParseError::InvalidSemver {
+            input: input.to_string(),
+            source,
+        })
+    }
+}
+
+/// Fill in missing minor and patch components (`1` → `1.0.0`, `1.2-rc.1` → `1.2.0-rc.1`)
+fn pad_version(version: &str) -> String {
+    let core_end = version.find(['-', '+']).unwrap_or(version.len());
+    let (core, suffix) = version.split_at(core_end);
+    let components = core.split('.').count();
+    if core.is_empty() || components >= 3 {
+        return version.to_string();
+    }
+    format!("{}{}{}", core, ".0".repeat(3 - components), suffix)
+}
+
+/// Drop the zero padding of the numeric segments of a calendar version (`2026.01.3` -> `2026.1.3`)
+fn strip_leading_zeros(version: &str) -> String {
+    let core_end = version.find(['-', '+']).unwrap_or(version.len());
+    let (core, suffix) = version.split_at(core_end);
+    let core: Vec<&str> = core
+        .split('.')
+        .map(|segment| match segment.trim_start_matches('0') {
+            "" if !segment.is_empty() => "0",
+            trimmed => trimmed,
+        })
+        .collect();
+    format!("{}{}", core.join("."), suffix)
+}
+
+/// Parse a version string into a semver Version, allowing a leading `v`
+pub fn parse_version(tag: &str) -> Result<Version, VersionParseError> {
+    VersionParser::new().parse(tag)
+}
+
+/// Parse a tag into a semver Version after stripping `prefix` (e.g. `api-v` or `releases/`).
+///
+/// A leading `v` left after the prefix is trimmed as in `parse_version`. Returns `None`
+/// when the tag does not start with the prefix or is not a version.
+pub fn parse_version_with_prefix(tag: &str, prefix: &str) -> Option<Version> {
+    VersionParser::new().with_prefix(prefix).parse(tag).ok()
 }
 
 /// Calculate the next version based on the current version and the version bump
@@ -53,117 +241,364 @@ pub fn calculate_next_version(current: &Version, bump: &VersionBump) -> Version
     next.pre = Prerelease::EMPTY;
     next.build = BuildMetadata::EMPTY;
 
-    if bump.major {
-        next.major += 1;
-        next.minor = 0;
-        next.patch = 0;
-    } else if bump.minor {
-        next.minor += 1;
-        next.patch = 0;
-    } else if bump.patch {
-        next.patch += 1;
+    match bump.bump_level() {
+        BumpLevel::Major => {
+            next.major += 1;
+            next.minor = 0;
+            next.patch = 0;
+        }
+        BumpLevel::Minor => {
+            next.minor += 1;
+            next.patch = 0;
+        }
+        BumpLevel::Patch => next.patch += 1,
+        BumpLevel::None => {}
     }
 
     next
 }
 
-/// Calculate how the version should bump between `from` and `to` commits.
-/// Uses a revwalk to include or exclude the base commit as appropriate.
+/// Calculate the next version under `scheme`.
+///
+/// Semantic versions are bumped as by `calculate_next_version`. Calendar versions take
+/// their year and month from `date` (YYYY-MM-DD); the micro segment counts up from the
+/// current version in the same period and restarts at 0 in a new one. Without a bump
+/// the version stays the same.
+pub fn calculate_next_version_with_scheme(current: &Version, bump: &VersionBump, scheme: &VersionScheme, date: &str) -> Version {
+    match scheme {
+        VersionScheme::Semver => calculate_next_version(current, bump),
+        VersionScheme::Calver(format) => calculate_next_calver(current, bump, format, date),
+    }
+}
+
+fn calculate_next_calver(current: &Version, bump: &VersionBump, format: &CalverFormat, date: &str) -> Version {
+    let mut next = current.clone();
+    next.pre = Prerelease::EMPTY;
+    next.build = BuildMetadata::EMPTY;
+    if bump.bump_level() == BumpLevel::None {
+        return next;
+    }
+    let Some((year, month)) = format.period(date) else {
+        log::warn!("Release date {} is not a YYYY-MM-DD date; keeping the period of {}", date, current);
+        next.patch += 1;
+        return next;
+    };
+    if (current.major, current.minor) == (year, month) {
+        next.patch += 1;
+    } else {
+        next = Version::new(year, month, 0);
+    }
+    next
+}
+
+/// Apply a command line override to the calculated bump, returning the next version and
+/// the bump it amounts to.
+///
+/// A forced version must be greater than `current` unless `allow_downgrade` is set.
+pub fn apply_version_override(
+    current: &Version,
+    bump: VersionBump,
+    version_override: Option<&VersionOverride>,
+    allow_downgrade: bool,
+) -> Result<(Version, VersionBump), VNextError> {
+    match version_override {
+        None => Ok((calculate_next_version(current, &bump), bump)),
+        Some(VersionOverride::Bump(level)) => {
+            debug!("Forcing a {} bump instead of {}", level, bump.level());
+            let bump = VersionBump::from(*level);
+            Ok((calculate_next_version(current, &bump), bump))
+        }
+        Some(VersionOverride::Version(version)) => {
+            if version <= current && !allow_downgrade {
+                return Err(VNextError::ConfigError(format!(
+                    "--force-version {} is not greater than the current version {}; pass --allow-downgrade to release it anyway",
+                    version, current
+                )));
+            }
+            debug!("Forcing version {} instead of a {} bump", version, bump.level());
+            let level = if version.major != current.major {
+                BumpLevel::Major
+            } else if version.minor != current.minor {
+                BumpLevel::Minor
+            } else if version.patch != current.patch {
+                BumpLevel::Patch
+            } else {
+                BumpLevel::None
+            };
+            Ok((version.clone(), VersionBump::from(level)))
+        }
+    }
+}
+
+/// Render a `--build-metadata` template into semver build metadata.
+///
+/// Supports the `{sha}`, `{short-sha}`, `{commits}` and `{date}` placeholders. The result
+/// must be dot-separated identifiers of ASCII letters, digits and hyphens.
+pub fn render_build_metadata(template: &str, sha: &str, commits: usize, date: &str) -> Result<BuildMetadata, String> {
+    let rendered = template
+        .replace("{short-sha}", &sha[..7.min(sha.len())])
+        .replace("{sha}", sha)
+        .replace("{commits}", &commits.to_string())
+        .replace("{date}", date);
+    BuildMetadata::new(&rendered).map_err(|e| format!(
+        "build metadata '{}' is invalid ({}); use dot-separated identifiers of letters, digits and hyphens with the {{sha}}, {{short-sha}}, {{commits}} and {{date}} placeholders",
+        rendered, e
+    ))
+}
+
+/// Calculate how the version should bump for the commits reachable from `to` but not
+/// from `latest_tag`, the commit of the previous release (see `find_version_base`).
 pub fn calculate_version_bump(
     repo: &Repository,
-    _from: &Commit,
+    latest_tag: Option<&Commit>,
     to: &Commit,
     parser: &dyn crate::models::commit::CommitParser,
-    major_types: &[&str],
-    minor_types: &[&str],
-    noop_types: &[&str],
+    bump_map: &BumpMap,
+    walk: &WalkOptions,
+    progress: &ProgressReporter,
 ) -> Result<(VersionBump, ChangesetSummary), VNextError> {
     log::debug!("Calculating version bump using parser: {}", parser.name());
     
-    let mut bump = VersionBump { major: false, minor: false, patch: false };
+    let mut level = BumpLevel::None;
     let mut summary = ChangesetSummary::new();
+    let skip_markers = walk.skip_release_markers();
 
     // Build a revwalk starting from HEAD.
+    let started = std::time::Instant::now();
     let mut revwalk = repo.revwalk()?;
     revwalk.push(to.id())?;
 
     // If a previous tag exists, hide it so we walk only the newer commits.
-    if let Some((_, tag_commit)) = crate::core::git::find_latest_tag(repo) {
+    if let Some(tag_commit) = latest_tag {
         revwalk.hide(tag_commit.id())?;
     }
 
     // Iterate commits (newest first). We collect and then reverse for changelog display.
-    for oid in revwalk {
+    let mut revwalk = revwalk.enumerate();
+    for (walked, oid) in revwalk.by_ref() {
+        progress.check_cancelled()?;
         let oid = oid?;
+        
+        // Past the limit the remaining commits are only counted, without reading them
+        if walk.max_commits.is_some_and(|max| summary.commits.len() >= max) {
+            summary.truncated = true;
+            summary.omitted_commits = 1 + revwalk.count();
+            log::warn!(
+                "Stopped after {} commits; {} older commits are not listed or counted toward the bump",
+                summary.commits.len(), summary.omitted_commits
+            );
+            break;
+        }
         let git_commit = repo.find_commit(oid)?;
-        let message = git_commit.message().unwrap_or("").to_string();
+        
+        // In a monorepo only commits touching the package path count
+        if let Some(path) = &walk.path {
+            if !crate::core::git::commit_touches_path(repo, &git_commit, path)? {
+                log::debug!("Skipping commit {} outside of {}", oid, path);
+                progress.report(Phase::Revwalk, walked as u64 + 1, None);
+                continue;
+            }
+        }
+        
+        let raw_message = git_commit.message().unwrap_or("");
+        let message = crate::models::commit::normalize_message(raw_message);
+        if message.is_empty() {
+            log::debug!("Commit {} has an empty message", oid);
+        }
         
         // Parse the commit message into a structured Commit object FIRST
         // This avoids parsing the same message multiple times
-        let commit = parser.parse_commit(oid.to_string(), message);
+        let mut commit = parser.parse_commit(oid.to_string(), message);
+        commit.timestamp = Some(git_commit.time().seconds());
+        commit.author_timestamp = Some(git_commit.author().when().seconds());
+        crate::core::footers::apply_footer_rules(&mut commit, &walk.footer_rules);
         
-        // Determine the version bump using the new function
-        match determine_version_bump(&commit, major_types, minor_types, noop_types) {
-            VersionBumpType::Major => {
-                bump.major = true;
-                summary.major += 1;
-                log::debug!("Detected major change in commit: {}", commit.commit_id);
-            },
-            VersionBumpType::Minor => {
-                bump.minor = true;
-                summary.minor += 1;
-                log::debug!("Detected minor change in commit: {}", commit.commit_id);
-            },
-            VersionBumpType::Patch => {
-                bump.patch = true;
-                summary.patch += 1;
-                log::debug!("Detected patch change in commit: {}", commit.commit_id);
-            },
-            VersionBumpType::NoOp => {
-                summary.noop += 1;
-                log::debug!("Detected no-op change in commit: {}", commit.commit_id);
+        // Commits hidden during review are left out entirely
+        let decision = walk.ignore_revs.decision(&commit.commit_id);
+        if decision == Some(RevDecision::Hide) {
+            log::debug!("Skipping commit {} hidden in review", oid);
+            progress.report(Phase::Revwalk, walked as u64 + 1, None);
+            continue;
+        }
+        crate::core::lint::lint_commit(&commit, raw_message, bump_map, &mut summary.diagnostics);
+        
+        // Empty commits (same tree as the first parent) can be forced to no-op
+        if walk.empty_commits == EmptyCommitPolicy::Noop {
+            if let Ok(parent) = git_commit.parent(0) {
+                commit.is_empty = parent.tree_id() == git_commit.tree_id();
             }
         }
         
+        // Determine the version bump using the new function
+        // Commits outside the scope filter belong to other packages
+        commit.out_of_scope = !commit_in_scope(&commit, walk);
+        commit.skip_release = has_skip_marker(&commit, &skip_markers);
+        
+        let (bump_type, reason) = if decision == Some(RevDecision::Noop) {
+            log::debug!("Commit {} is marked as a no-op in review", commit.commit_id);
+            (VersionBumpType::NoOp, BumpReason::Review)
+        } else if commit.skip_release {
+            log::debug!("Commit {} has a skip-release marker, treating it as a no-op", commit.commit_id);
+            (VersionBumpType::NoOp, BumpReason::SkipRelease)
+        } else if commit.out_of_scope {
+            log::debug!("Commit {} is outside the scope filter, treating it as a no-op", commit.commit_id);
+            (VersionBumpType::NoOp, BumpReason::OutOfScope)
+        } else if commit.is_empty {
+            log::debug!("Commit {} changes no files, treating it as a no-op", commit.commit_id);
+            (VersionBumpType::NoOp, BumpReason::EmptyCommit)
+        } else {
+            explain_commit(&commit, bump_map)
+        };
+        summary.bumps.push(CommitBump { commit_id: commit.commit_id.clone(), level: bump_type.level(), reason });
+        record_bump(&mut level, &mut summary, bump_type, &commit.commit_id);
+        
         // Add the commit to the summary
         summary.commits.push(commit);
+        progress.report(Phase::Revwalk, walked as u64 + 1, None);
     }
+    summary.total_commits = summary.commits.len() + summary.omitted_commits;
+    log::debug!("Walked {} commits in {:?}", summary.commits.len(), started.elapsed());
 
-    Ok((bump, summary))
+    Ok((VersionBump::from(level), summary))
 }
 
-/// Find the version base (main branch, latest tag, base commit)
-pub fn find_version_base<'repo, 'head>(repo: &'repo Repository, head: &'head Commit<'repo>) -> (Version, Commit<'repo>) {
-    let main_branch = crate::core::git::find_trunk_branch(repo).expect("Failed to find main branch");
-    debug!("Trunk branch detected: {}", main_branch);
+/// Whether the subject or body of a commit matches one of the skip-release `markers`
+pub fn has_skip_marker(commit: &crate::models::commit::Commit, markers: &[regex::Regex]) -> bool {
+    markers.iter().any(|marker| marker.is_match(&commit.raw_message))
+}
+
+/// Whether a commit counts toward the release under the scope filter of `walk`.
+///
+/// Every commit is in scope when no scopes are set. Otherwise one of the commit's
+/// scopes, or a parent of a nested scope (`api` for `api/auth`), must match a
+/// configured scope, compared case-insensitively with `*` matching any text. Commits
+/// without a scope are in scope unless `walk.exclude_unscoped` is set.
+pub fn commit_in_scope(commit: &crate::models::commit::Commit, walk: &WalkOptions) -> bool {
+    if walk.scopes.is_empty() {
+        return true;
+    }
+    // Commits built by hand may only have the display string
+    let scopes = if commit.scopes.is_empty() {
+        commit.scope.as_deref().map(crate::models::commit::parse_scopes).unwrap_or_default()
+    } else {
+        commit.scopes.clone()
+    };
+    if scopes.is_empty() {
+        return !walk.exclude_unscoped;
+    }
+    scopes.iter().any(|scope| {
+        let parents = scope.match_indices('/').map(|(index, _)| &scope[..index]);
+        std::iter::once(scope.as_str())
+            .chain(parents)
+            .any(|candidate| walk.scopes.iter().any(|pattern| scope_matches(candidate, pattern)))
+    })
+}
+
+/// Match a scope against a pattern where `*` matches any text, ignoring case
+fn scope_matches(scope: &str, pattern: &str) -> bool {
+    let scope = scope.to_lowercase();
+    let pattern = pattern.trim().to_lowercase();
+    if !pattern.contains('*') {
+        return scope == pattern;
+    }
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = scope.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let last = parts.pop().unwrap_or_default();
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
 
-    let (start_version, last_tag_commit) = match crate::core::git::find_latest_tag(repo) {
+/// Record a commit's bump type in the highest bump level so far and the summary counters
+pub fn record_bump(level: &mut BumpLevel, summary: &mut ChangesetSummary, bump_type: VersionBumpType, commit_id: &str) {
+    *level = (*level).max(bump_type.level());
+    match bump_type {
+        VersionBumpType::Major => {
+            summary.major += 1;
+            log::debug!("Detected major change in commit: {}", commit_id);
+        },
+        VersionBumpType::Minor => {
+            summary.minor += 1;
+            log::debug!("Detected minor change in commit: {}", commit_id);
+        },
+        VersionBumpType::Patch => {
+            summary.patch += 1;
+            log::debug!("Detected patch change in commit: {}", commit_id);
+        },
+        VersionBumpType::NoOp => {
+            summary.noop += 1;
+            log::debug!("Detected no-op change in commit: {}", commit_id);
+        }
+    }
+}
+
+/// Starting point of a release: the current version, the base commit and the latest tag
+pub struct VersionBase<'repo> {
+    /// Version of the latest release, or the initial version without one
+    pub version: Version,
+    /// Merge base of HEAD and the latest tag, or the root commit without a tag
+    pub base_commit: Commit<'repo>,
+    /// Name and commit of the latest release tag
+    pub latest_tag: Option<(String, Commit<'repo>)>,
+}
+
+impl<'repo> VersionBase<'repo> {
+    /// Commit of the latest release tag, hidden from the revwalk of `calculate_version_bump`.
+    ///
+    /// Hiding the tag hides everything reachable from both HEAD and the tag, which is the
+    /// history of `base_commit` when the tag sits on an unmerged release branch, and never
+    /// less than it when there are several merge bases.
+    pub fn tag_commit(&self) -> Option<&Commit<'repo>> {
+        self.latest_tag.as_ref().map(|(_, commit)| commit)
+    }
+}
+
+/// Find the version base (main branch, latest tag, base commit).
+///
+/// This is the only place a run scans the tags, which is slow in repositories with
+/// thousands of them; pass the returned tag on rather than looking it up again.
+pub fn find_version_base<'repo>(
+    repo: &'repo Repository,
+    head: &Commit<'repo>,
+    walk: &WalkOptions,
+    progress: &ProgressReporter,
+) -> Result<VersionBase<'repo>, VNextError> {
+    // The revwalk does not need the trunk branch; CI checkouts are often detached
+    if repo.head_detached().unwrap_or(false) {
+        debug!("HEAD is detached, skipping trunk branch detection");
+    } else {
+        match walk.trunk.clone().or_else(|| crate::core::git::find_trunk_branch(repo)) {
+            Some(main_branch) => debug!("Trunk branch: {}", main_branch),
+            None => debug!("No trunk branch found"),
+        }
+    }
+
+    let parser = VersionParser::from_walk_options(walk);
+    let latest_tag = crate::core::git::find_release_tags(repo, &parser, Some(head), walk.strict_tags, progress)?
+        .for_policy(walk.prerelease_base);
+    let has_tag = latest_tag.is_some();
+    let (start_version, last_tag_commit) = match &latest_tag {
         Some((tag, commit)) => {
-            let version = parse_version(&tag).unwrap_or_else(|_| Version::new(0, 0, 0));
+            let version = parser
+                .parse(tag)
+                .unwrap_or_else(|_| Version::new(0, 0, 0));
             debug!("Last release: {} at commit {}", tag, commit.id());
-            (version, commit)
+            (version, commit.clone())
         }
         None => {
-            debug!("No previous release tags found, starting from 0.0.0");
-            let version = Version::new(0, 0, 0);
+            let version = walk.initial_base_version();
+            debug!("No previous release tags found, starting from {}", version);
             
             // Find the initial commit in the repository
-            let mut current = head.clone();
-            let initial_commit;
-            
-            // Traverse to the root commit by following the first parent chain
-            loop {
-                let parents = current.parents();
-                if parents.count() == 0 {
-                    // We've reached a commit with no parents (the initial commit)
-                    initial_commit = current;
-                    break;
-                }
-                
-                // Move to the first parent and continue
-                current = current.parents().next().unwrap();
-            }
-            
+            let initial_commit = crate::core::git::root_commit(head);
             debug!("Found initial commit: {}", initial_commit.id());
             (version, initial_commit)
         }
@@ -171,38 +606,35 @@ pub fn find_version_base<'repo, 'head>(repo: &'repo Repository, head: &'head Com
     debug!("Last tag or base commit: {}", last_tag_commit.id());
 
     // Determine the base commit: use merge base with main if tag exists, otherwise use the initial commit
-    let base_commit = if crate::core::git::find_latest_tag(repo).is_some() {
-        let merge_base = repo
-            .merge_base(head.id(), last_tag_commit.id())
-            .expect("Failed to find merge base between HEAD and tag");
-        repo.find_commit(merge_base)
-            .expect("Failed to find merge base commit")
+    let base_commit = if has_tag {
+        let merge_base = repo.merge_base(head.id(), last_tag_commit.id())?;
+        repo.find_commit(merge_base)?
     } else {
         // When no tags exist, we want to analyze all commits from the initial commit to HEAD
         last_tag_commit.clone()
     };
     debug!("Base commit for analysis: {}", base_commit.id());
     
-    (start_version, base_commit)
+    Ok(VersionBase { version: start_version, base_commit, latest_tag })
 }
 
-/// Calculate the next version based on commit history
+/// Calculate the next version based on commit history since `latest_tag`
 pub fn calculate_version(
     repo: &Repository,
     head: &Commit,
     current_version: &Version,
-    base_commit: &Commit,
+    latest_tag: Option<&Commit>,
     parser: &dyn crate::models::commit::CommitParser,
-    major_types: &[&str],
-    minor_types: &[&str],
-    noop_types: &[&str],
+    bump_map: &BumpMap,
+    walk: &WalkOptions,
+    progress: &ProgressReporter,
 ) -> Result<(Version, ChangesetSummary), VNextError> {
     // Calculate version bump
     let (bump, summary) = calculate_version_bump(
-        repo, base_commit, head, parser, major_types, minor_types, noop_types)?;
+        repo, latest_tag, head, parser, bump_map, walk, progress)?;
     
     // Calculate next version
-    let next_version = calculate_next_version(&current_version, &bump);
+    let next_version = walk.next_version(current_version, &bump);
     
     log::debug!(
         "Version bump: major={}, minor={}, patch={}",
@@ -1,4 +1,45 @@
 //! A library for calculating the next version based on conventional commits.
+//!
+//! The simplest entry point is [`VersionCalculator`], which opens a repository,
+//! finds the latest release tag and classifies the commits since then:
+//!
+//! ```
+//! # use std::process::Command;
+//! # let dir = tempfile::tempdir().unwrap();
+//! # let git = |args: &[&str]| {
+//! #     Command::new("git").args(args).current_dir(dir.path()).output().unwrap();
+//! # };
+//! # git(&["init"]);
+//! # git(&["config", "user.name", "Test"]);
+//! # git(&["config", "user.email", "test@example.com"]);
+//! # std::fs::write(dir.path().join("README.md"), "# Test").unwrap();
+//! # git(&["add", "."]);
+//! # git(&["commit", "-m", "feat: Initial commit"]);
+//! # git(&["tag", "v1.0.0"]);
+//! # std::fs::write(dir.path().join("fix.txt"), "fix").unwrap();
+//! # git(&["add", "."]);
+//! # git(&["commit", "-m", "fix: Fix a bug"]);
+//! use vnext::{ParserStrategy, VersionCalculator};
+//!
+//! let plan = VersionCalculator::for_repo(dir.path())
+//!     .with_parser(ParserStrategy::Conventional)
+//!     .with_types(&["major"], &["feat", "minor"], &["chore", "noop"])
+//!     .run()?;
+//!
+//! assert_eq!(plan.current_version.to_string(), "1.0.0");
+//! assert_eq!(plan.next_version.to_string(), "1.0.1");
+//! assert_eq!(plan.bump.level(), "patch");
+//! assert_eq!(plan.summary.commits.len(), 1);
+//! # Ok::<(), vnext::VNextError>(())
+//! ```
+//!
+//! Errors are returned as [`VNextError`]; a directory that is not a Git repository
+//! yields a `VNextError::GitError` rather than a panic:
+//!
+//! ```
+//! let dir = tempfile::tempdir().unwrap();
+//! assert!(vnext::VersionCalculator::for_repo(dir.path()).run().is_err());
+//! ```
 
 pub mod models;
 pub mod utils;
@@ -10,36 +51,44 @@ pub mod parsers;
 // Re-export commonly used types and functions
 pub use cli::Cli;
 pub use models::error::VNextError;
-pub use models::version::VersionBump;
+pub use models::version::{BumpLevel, BumpMap, BumpReason, CalverFormat, CalverYear, CommitBump, VersionBump, VersionOverride, VersionScheme};
 pub use models::commit::{Commit, CommitAuthor};
-pub use models::changeset::ChangesetSummary;
+pub use models::changeset::{ChangesetSummary, ReleaseCounts};
+pub use models::diagnostics::{Diagnostic, Diagnostics};
 pub use models::repo::RepoInfo;
 pub use core::git::{extract_repo_info, find_latest_tag, find_trunk_branch, open_repository, resolve_head, get_repo_info};
 pub use core::github::enhance_with_github_info;
+pub use core::calculator::{ReleasePlan, VersionCalculator};
+pub use core::release::{calculate_release, ReleaseOptions};
+pub use core::footers::{apply_footer_rules, parse_footer_rule};
+pub use models::footer::{FooterAction, FooterRule};
+pub use models::review::{IgnoreRevs, RevDecision};
+pub use parsers::{ParserHandle, ParserStrategy};
 pub use core::version::{calculate_next_version, calculate_version_bump, parse_version, calculate_version};
-pub use core::changelog::{output_result, output_fallback, format_changelog};
+pub use core::changelog::{fallback_changelog, format_changelog, format_changelog_with_options, render_heading, format_contributors, filter_changelog_commits};
 pub use parsers::conventional::{parse_conventional_commit, CONVENTIONAL_COMMIT_REGEX_STR};
 
 // Re-export for backward compatibility with tests
 pub mod version {
-    pub use crate::models::version::VersionBump;
+    pub use crate::models::version::{BumpLevel, BumpMap, BumpReason, CalverFormat, CalverYear, CommitBump, VersionBump, VersionOverride, VersionScheme};
     pub use crate::models::commit::{Commit, CommitAuthor};
     pub use crate::models::changeset::ChangesetSummary;
-    pub use crate::core::version::{calculate_next_version, calculate_version_bump, parse_version, calculate_version, find_version_base};
+    pub use crate::core::version::{calculate_next_version, calculate_version_bump, parse_version, parse_version_with_prefix, calculate_version, find_version_base, VersionBase, commit_in_scope, classify_commit, explain_commit, parse_bump_map, VersionParser, VersionParseError};
 }
 
 pub mod git {
-    pub use crate::core::git::{extract_repo_info, find_latest_tag, find_trunk_branch, open_repository, resolve_head};
+    pub use crate::core::git::{extract_repo_info, find_latest_tag, find_trunk_branch, open_repository, open_repository_at, open_repository_with, open_git_dir, env_git_dir, resolve_head, collect_previous_contributors, populate_local_authors, find_latest_prefixed_tag, find_release_tags, LatestTags, normalize_tag_namespace, get_repo_info, get_repo_info_from_remote, find_remote_or_first, DEFAULT_REMOTE};
 }
 
 pub mod changelog {
     pub use crate::models::repo::RepoInfo;
     pub use crate::core::git::get_repo_info;
-    pub use crate::core::changelog::{output_result, output_fallback, format_changelog};
+    pub use crate::core::changelog::{fallback_changelog, format_changelog, format_changelog_with_options, render_heading, format_contributors, filter_changelog_commits, section_counts, format_totals, ChangelogSection, exclude_labeled_commits, truncate_body, escape_markdown, compose_sections, SECTION_SEPARATOR};
 }
 
 pub mod github {
-    pub use crate::core::github::enhance_with_github_info;
+    pub use crate::core::github::{default_cache_dir, enhance_with_github_info, fetch_commit_authors, CommitMetadataProvider, GitHubClient, DEFAULT_GITHUB_CONCURRENCY, GITHUB_API_URL, GRAPHQL_BATCH_SIZE};
+    pub use crate::models::github::{CommitMetadata, PullRequestInfo};
 }
 
 pub mod error {
@@ -8,17 +8,17 @@ use vnext::utils::logging;
 
 /// Main function
 fn main() {
+    // Parse command line arguments first, since they configure logging
+    let cli = Cli::parse();
+
     // Initialize logging
-    logging::init_logging().expect("Failed to setup logging");
+    logging::init_logging_with(cli.log_level(), cli.log_format).expect("Failed to setup logging");
     debug!("Starting vnext");
 
-    // Parse command line arguments
-    let cli = Cli::parse();
-
     // Run the CLI
     if let Err(e) = run(cli) {
         eprintln!("Error: {}", e);
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
     }
 
     debug!("vnext completed successfully");
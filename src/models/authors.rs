@@ -0,0 +1,16 @@
+//! Author mapping data structures
+
+use serde::{Deserialize, Serialize};
+
+/// One entry of an authors file, mapping an email (or `*` glob) to a display name and handle
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AuthorMapping {
+    /// Email address or glob such as `*@example.com`
+    pub email: String,
+    /// Display name to use instead of the git signature name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Handle rendered as `@username`
+    #[serde(default)]
+    pub username: Option<String>,
+}
@@ -1,14 +1,37 @@
 //! Changeset-related data structures
 
-use crate::models::commit::Commit;
+use crate::models::commit::{Commit, CommitAuthor};
+use crate::models::diagnostics::Diagnostics;
+use crate::models::version::{BumpLevel, BumpMap, CommitBump};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
 
 /// Represents a summary of changes for version calculation
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ChangesetSummary {
     pub major: u32,
     pub minor: u32,
     pub patch: u32,
     pub noop: u32,
     pub commits: Vec<Commit>,
+    /// Lowercased emails and names of authors of commits before the release range,
+    /// used to detect new contributors (`None` when not computed)
+    pub known_contributors: Option<HashSet<String>>,
+    /// Whether the walk stopped at the commit limit before the end of the range
+    pub truncated: bool,
+    /// Number of walked commits plus the ones past the commit limit
+    pub total_commits: usize,
+    /// Number of commits past the commit limit, which are neither listed nor counted
+    /// toward the bump. They are not read, so path filters do not apply to the count
+    pub omitted_commits: usize,
+    /// Whether the pull request labels of the commits were fetched, so a commit without
+    /// labels has none rather than unknown ones
+    pub labels_known: bool,
+    /// Bump level and reason of each walked commit, newest first
+    pub bumps: Vec<CommitBump>,
+    /// Commit message warnings raised while walking the commits
+    pub diagnostics: Diagnostics,
 }
 
 impl ChangesetSummary {
@@ -19,8 +42,170 @@ impl ChangesetSummary {
             patch: 0,
             noop: 0,
             commits: Vec::new(),
+            known_contributors: None,
+            truncated: false,
+            total_commits: 0,
+            omitted_commits: 0,
+            labels_known: false,
+            bumps: Vec::new(),
+            diagnostics: Diagnostics::new(),
         }
     }
 
     // The format_changelog method has been moved to the changelog service
-}
\ No newline at end of file
+
+    /// Commits with a breaking change, newest first
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &Commit> {
+        self.commits.iter().filter(|commit| commit.has_breaking_change)
+    }
+
+    /// Bump level and reason recorded for the commit `commit_id`
+    pub fn bump_of(&self, commit_id: &str) -> Option<&CommitBump> {
+        self.bumps.iter().find(|bump| bump.commit_id == commit_id)
+    }
+
+    /// Commits of the given type (ignoring case), newest first
+    pub fn by_type<'a>(&'a self, commit_type: &'a str) -> impl Iterator<Item = &'a Commit> {
+        self.commits.iter().filter(move |commit| commit.commit_type.eq_ignore_ascii_case(commit_type))
+    }
+
+    /// Number of commits of each type, keyed by the lowercased type; commits that do not
+    /// follow the commit format are counted under the empty type
+    pub fn type_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for commit in &self.commits {
+            *counts.entry(commit.commit_type.to_lowercase()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Highest bump the listed commits call for under `bump_map`; empty, out-of-scope and
+    /// skipped commits count as no-ops
+    pub fn highest_bump(&self, bump_map: &BumpMap) -> BumpLevel {
+        self.commits
+            .iter()
+            .filter(|commit| !commit.is_empty && !commit.out_of_scope && !commit.skip_release)
+            .map(|commit| crate::core::version::classify_commit(commit, bump_map).level())
+            .max()
+            .unwrap_or(BumpLevel::None)
+    }
+
+    /// Commit times of the oldest and newest commit, for commits read from a repository
+    pub fn commit_time_range(&self) -> Option<(i64, i64)> {
+        let times = self.commits.iter().filter_map(|commit| commit.timestamp);
+        let first = times.clone().min()?;
+        Some((first, times.max().unwrap_or(first)))
+    }
+
+    /// Author times of the earliest and latest authored commit, for commits read from a
+    /// repository. Rebased or squash-merged commits are authored before they are committed
+    pub fn author_time_range(&self) -> Option<(i64, i64)> {
+        let times = self.commits.iter().filter_map(|commit| commit.author_timestamp);
+        let first = times.clone().min()?;
+        Some((first, times.max().unwrap_or(first)))
+    }
+
+    /// Number of commits of each author, keyed by name; commits without an author are
+    /// not counted
+    pub fn commits_per_author(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for author in self.commits.iter().filter_map(|commit| commit.author.as_ref()) {
+            *counts.entry(author.name.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Distinct commit authors, sorted case-insensitively by username, or by name when
+    /// the username is unknown. Authors with neither are left out
+    pub fn contributors(&self) -> Vec<&CommitAuthor> {
+        self.contributor_groups().into_values().map(|authors| authors[0]).collect()
+    }
+
+    /// Contributors with an identity (email or name) missing from `known_contributors`,
+    /// sorted like `contributors`; empty when `known_contributors` is not computed
+    pub fn new_contributors(&self) -> Vec<&CommitAuthor> {
+        let Some(known) = &self.known_contributors else { return Vec::new() };
+        let is_new = |author: &&CommitAuthor| {
+            !known.contains(&author.email.to_lowercase()) && !known.contains(&author.name.to_lowercase())
+        };
+        self.contributor_groups()
+            .into_values()
+            .filter_map(|authors| authors.into_iter().find(is_new))
+            .collect()
+    }
+
+    /// Counts of the one-line release summary. Commits past the commit limit count toward
+    /// the commits only, and contributors are only counted once authors are populated
+    pub fn release_counts(&self) -> ReleaseCounts {
+        ReleaseCounts {
+            commits: self.commits.len() + self.omitted_commits,
+            features: self.by_type("feat").count(),
+            fixes: self.by_type("fix").count(),
+            breaking_changes: self.breaking_changes().count(),
+            contributors: self.contributors().len(),
+        }
+    }
+
+    /// Commit authors grouped by their lowercased username or name, newest commit first
+    fn contributor_groups(&self) -> BTreeMap<String, Vec<&CommitAuthor>> {
+        let mut groups: BTreeMap<String, Vec<&CommitAuthor>> = BTreeMap::new();
+        for author in self.commits.iter().filter_map(|commit| commit.author.as_ref()) {
+            let key = author.username.as_deref().unwrap_or(&author.name).to_lowercase();
+            if !key.is_empty() {
+                groups.entry(key).or_default().push(author);
+            }
+        }
+        groups
+    }
+}
+
+impl fmt::Display for ChangesetSummary {
+    /// Commit count and the number of commits at each bump level
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} commits (major: {}, minor: {}, patch: {}, noop: {})",
+            self.commits.len(), self.major, self.minor, self.patch, self.noop
+        )?;
+        if self.truncated {
+            write!(f, ", {} omitted", self.omitted_commits)?;
+        }
+        Ok(())
+    }
+}
+
+/// Counts behind the release summary line, e.g.
+/// `12 commits, 3 features, 5 fixes, 1 breaking change, by 4 contributors`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseCounts {
+    pub commits: usize,
+    pub features: usize,
+    pub fixes: usize,
+    pub breaking_changes: usize,
+    pub contributors: usize,
+}
+
+impl fmt::Display for ReleaseCounts {
+    /// The commit count always, then the non-zero categories, pluralized
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", pluralize(self.commits, "commit", "commits"))?;
+        for (count, one, many) in [
+            (self.features, "feature", "features"),
+            (self.fixes, "fix", "fixes"),
+            (self.breaking_changes, "breaking change", "breaking changes"),
+        ] {
+            if count > 0 {
+                write!(f, ", {}", pluralize(count, one, many))?;
+            }
+        }
+        if self.contributors > 0 {
+            write!(f, ", by {}", pluralize(self.contributors, "contributor", "contributors"))?;
+        }
+        Ok(())
+    }
+}
+
+/// `count` followed by the singular `one` or the plural `many`
+pub(crate) fn pluralize(count: usize, one: &str, many: &str) -> String {
+    format!("{} {}", count, if count == 1 { one } else { many })
+}
@@ -1,7 +1,10 @@
 //! Commit-related data structures
 
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
 /// Represents a commit author
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CommitAuthor {
     pub name: String,
     #[allow(dead_code)]
@@ -10,16 +13,73 @@ pub struct CommitAuthor {
 }
 
 /// Represents a parsed conventional commit message
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Commit {
     pub commit_id: String,
     pub raw_message: String,
     pub commit_type: String,
+    /// Scopes joined with `,` for display, e.g. `api,web`
     pub scope: Option<String>,
+    /// Individual scopes of the header; nested scopes like `api/auth` are kept whole
+    pub scopes: Vec<String>,
     pub has_breaking_change: bool,  // Single flag for breaking changes
     pub title: String,
     pub body: Option<String>,
+    /// Text of the `BREAKING CHANGE:` footer, kept apart from the body
+    pub breaking_change_note: Option<String>,
+    /// Footers from the trailer block at the end of the message, as (token, value) pairs;
+    /// the body does not include them
+    pub footers: Vec<(String, String)>,
     pub author: Option<CommitAuthor>,
+    /// Whether the commit changes no files (only detected with the `noop` empty-commit policy)
+    pub is_empty: bool,
+    /// Whether the commit's scope is outside the scope filter, so it counts as a no-op
+    pub out_of_scope: bool,
+    /// Whether the message carries a skip-release marker (e.g. `[skip release]`), so it counts as a no-op
+    pub skip_release: bool,
+    /// Always list the commit in the changelog (set by a `force-include` footer rule)
+    pub force_include: bool,
+    /// Issue references from `link-as-issue` footer rules
+    pub issues: Vec<String>,
+    /// Commit time in seconds since the epoch; `None` for commits not read from a repository
+    pub timestamp: Option<i64>,
+    /// Author time in seconds since the epoch; `None` for commits not read from a repository
+    pub author_timestamp: Option<i64>,
+    /// Number of the pull request the commit was merged through (only known with GraphQL enrichment)
+    pub pr_number: Option<u64>,
+    /// Title of that pull request
+    pub pr_title: Option<String>,
+    /// Labels of that pull request
+    pub labels: Vec<String>,
+}
+
+impl fmt::Display for CommitAuthor {
+    /// `Name <email>`, followed by ` (@username)` when the forge username is known
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} <{}>", self.name, self.email)?;
+        match &self.username {
+            Some(username) => write!(f, " (@{})", username),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Normalize a raw commit message: CRLF line endings become LF, trailing whitespace is
+/// removed from every line and leading/trailing blank lines are dropped
+pub fn normalize_message(message: &str) -> String {
+    message.lines().map(str::trim_end).collect::<Vec<_>>().join("\n").trim().to_string()
+}
+
+/// Split a header scope such as `api, web` or `api/auth` into its scopes.
+///
+/// Scopes are separated by commas; slashes denote nesting, so `api/auth` is a single
+/// scope with surrounding whitespace removed from each segment.
+pub fn parse_scopes(scope: &str) -> Vec<String> {
+    scope
+        .split(',')
+        .map(|s| s.split('/').map(str::trim).collect::<Vec<_>>().join("/"))
+        .filter(|s| !s.is_empty())
+        .collect()
 }
 
 impl Commit {
@@ -30,10 +90,23 @@ impl Commit {
             raw_message,
             commit_type: String::new(),
             scope: None,
+            scopes: Vec::new(),
             has_breaking_change: false,
             title: String::new(),
             body: None,
+            breaking_change_note: None,
+            footers: Vec::new(),
             author: None,
+            is_empty: false,
+            out_of_scope: false,
+            skip_release: false,
+            force_include: false,
+            issues: Vec::new(),
+            timestamp: None,
+            author_timestamp: None,
+            pr_number: None,
+            pr_title: None,
+            labels: Vec::new(),
         }
     }
     
@@ -45,23 +118,36 @@ impl Commit {
         if let Some(parsed) = crate::parsers::conventional::parse_conventional_commit(&message) {
             commit.commit_type = parsed.commit_type;
             commit.scope = parsed.scope;
+            commit.scopes = parsed.scopes;
             // Set has_breaking_change if either flag or body indicates a breaking change
             commit.has_breaking_change = parsed.breaking_change_flag || parsed.breaking_change_body;
             commit.title = parsed.title;
             commit.body = parsed.body;
+            commit.breaking_change_note = parsed.breaking_change_note;
+            commit.footers = parsed.footers;
         }
         
         commit
     }
     
+    /// Title to show for the commit: the parsed title, or the subject (first line of the
+    /// raw message) when the message did not match the commit format
+    pub fn display_title(&self) -> &str {
+        if self.commit_type.is_empty() {
+            self.raw_message.lines().next().unwrap_or("").trim()
+        } else {
+            &self.title
+        }
+    }
+
     /// Check if this commit represents a major change
     pub fn is_major_change(&self, major_types: &[&str]) -> bool {
-        self.has_breaking_change || major_types.contains(&self.commit_type.as_str())
+        self.has_breaking_change || self.type_matches(major_types)
     }
     
     /// Check if this commit represents a minor change
     pub fn is_minor_change(&self, minor_types: &[&str]) -> bool {
-        minor_types.contains(&self.commit_type.as_str())
+        self.type_matches(minor_types)
     }
     
     /// Check if this commit represents a patch change
@@ -71,7 +157,25 @@ impl Commit {
     
     /// Check if this commit represents a no-op change
     pub fn is_noop_change(&self, noop_types: &[&str]) -> bool {
-        noop_types.contains(&self.commit_type.as_str())
+        self.type_matches(noop_types)
+    }
+
+    /// Check if the commit type is one of `types`, ignoring case
+    pub fn type_matches(&self, types: &[&str]) -> bool {
+        types.iter().any(|t| t.eq_ignore_ascii_case(&self.commit_type))
+    }
+
+    /// Check if the pull request of the commit has one of `labels`, ignoring case
+    pub fn has_any_label(&self, labels: &[String]) -> bool {
+        self.labels.iter().any(|label| labels.iter().any(|l| l.eq_ignore_ascii_case(label)))
+    }
+}
+
+impl fmt::Display for Commit {
+    /// The short commit ID followed by the header of the message
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let short_id = &self.commit_id[..7.min(self.commit_id.len())];
+        write!(f, "{} {}", short_id, self.raw_message.lines().next().unwrap_or(""))
     }
 }
 
@@ -84,7 +188,10 @@ impl Commit {
 /// Implementations of this trait should be able to:
 /// - Parse a commit message into a structured Commit object
 /// - Provide a name for identification
-pub trait CommitParser {
+///
+/// Parsers are `Send + Sync` so one parser, with its compiled regexes, can be shared
+/// by threads analyzing different repositories.
+pub trait CommitParser: Send + Sync {
     /// Parse a commit message into a structured Commit object.
     ///
     /// This method parses a commit message into a structured Commit object, which
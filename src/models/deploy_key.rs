@@ -3,7 +3,7 @@
 use serde::{Deserialize, Serialize};
 
 /// Response from GitHub API when creating or fetching a deploy key
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DeployKeyResponse {
     pub id: u64,
     pub key: String,
@@ -12,6 +12,9 @@ pub struct DeployKeyResponse {
     pub verified: bool,
     pub created_at: String,
     pub read_only: bool,
+    /// When the key was last used, if GitHub reports it
+    #[serde(default)]
+    pub last_used: Option<String>,
 }
 
 /// Represents a list of deploy keys
@@ -31,4 +34,27 @@ pub struct Secret {
     pub name: String,
     pub created_at: String,
     pub updated_at: String,
-}
\ No newline at end of file
+}
+
+/// Public key that Actions secrets of a repository or organization are encrypted for
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SecretPublicKey {
+    /// ID of the key, sent along with the encrypted value
+    pub key_id: String,
+    /// The Curve25519 public key, base64 encoded
+    pub key: String,
+}
+
+/// The part of a repository response the secrets API needs
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RepositoryResponse {
+    pub id: u64,
+    pub full_name: String,
+}
+
+/// Repositories an organization secret with `selected` visibility is visible to
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SelectedRepositories {
+    pub total_count: u64,
+    pub repositories: Vec<RepositoryResponse>,
+}
@@ -0,0 +1,62 @@
+//! Warnings collected while analyzing commits
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A commit message warning raised by a lint rule
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub commit_id: String,
+    /// Name of the rule that raised the warning, e.g. `subject-length`
+    pub rule: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    /// `abc1234: message (rule)`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} ({})", &self.commit_id[..7.min(self.commit_id.len())], self.message, self.rule)
+    }
+}
+
+/// Warnings of a run, in the order they were raised. They never change the bump.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Diagnostics {
+    warnings: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Diagnostics::default()
+    }
+
+    /// Record a warning of `rule` about the commit `commit_id`
+    pub fn warn(&mut self, commit_id: &str, rule: &str, message: impl Into<String>) {
+        self.warnings.push(Diagnostic { commit_id: commit_id.to_string(), rule: rule.to_string(), message: message.into() });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.warnings.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.warnings.iter()
+    }
+
+    /// Names of the rules that raised warnings about `commit_id`
+    pub fn rules_for<'a>(&'a self, commit_id: &'a str) -> impl Iterator<Item = &'a str> {
+        self.warnings.iter().filter(move |warning| warning.commit_id == commit_id).map(|warning| warning.rule.as_str())
+    }
+
+    /// Log every warning at the warn level, so `--quiet` silences them
+    pub fn log(&self) {
+        for warning in &self.warnings {
+            log::warn!("{}", warning);
+        }
+    }
+}
@@ -7,14 +7,33 @@ use std::fmt;
 pub enum VNextError {
     /// Git-related errors
     GitError(git2::Error),
+    /// No Git repository was found where one was searched for
+    NotARepository(String),
     /// IO-related errors
     IoError(std::io::Error),
     /// Regex-related errors
     RegexError(regex::Error),
     /// GitHub API-related errors
     GithubError(String),
+    /// GitHub enrichment failed and `--require-github` asked for it
+    GithubRequired(String),
+    /// GitLab API-related errors
+    GitlabError(String),
     /// Version parsing errors
     VersionError(semver::Error),
+    /// Missing or invalid configuration, naming the flag that fixes it
+    ConfigError(String),
+    /// The repository is in a state the run refuses, such as a dirty working tree when a
+    /// clean one is required
+    RepositoryState(String),
+    /// The release tag of the next version already exists on another commit
+    TagExists(String),
+    /// Changelog post-processing errors
+    PostProcessError(String),
+    /// Commit messages raised warnings and `--deny-warnings` is set
+    CommitWarnings(String),
+    /// The operation was cancelled through a cancellation token
+    Cancelled,
     /// Other errors
     Other(String),
 }
@@ -23,10 +42,19 @@ impl fmt::Display for VNextError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             VNextError::GitError(e) => write!(f, "Git error: {}", e),
+            VNextError::NotARepository(e) => write!(f, "Not a Git repository: {}", e),
             VNextError::IoError(e) => write!(f, "IO error: {}", e),
             VNextError::RegexError(e) => write!(f, "Regex error: {}", e),
             VNextError::GithubError(e) => write!(f, "GitHub API error: {}", e),
+            VNextError::GithubRequired(e) => write!(f, "GitHub enrichment required by --require-github failed: {}", e),
+            VNextError::GitlabError(e) => write!(f, "GitLab API error: {}", e),
             VNextError::VersionError(e) => write!(f, "Version parsing error: {}", e),
+            VNextError::ConfigError(e) => write!(f, "Configuration error: {}", e),
+            VNextError::RepositoryState(e) => write!(f, "Repository state error: {}", e),
+            VNextError::TagExists(e) => write!(f, "Release tag already exists: {}", e),
+            VNextError::PostProcessError(e) => write!(f, "Changelog post-processing error: {}", e),
+            VNextError::CommitWarnings(e) => write!(f, "Commit message warnings: {}", e),
+            VNextError::Cancelled => write!(f, "Operation cancelled"),
             VNextError::Other(e) => write!(f, "{}", e),
         }
     }
@@ -34,6 +62,27 @@ impl fmt::Display for VNextError {
 
 impl std::error::Error for VNextError {}
 
+impl VNextError {
+    /// Process exit code for the error:
+    ///
+    /// - 1: any other failure
+    /// - 2: usage or configuration error
+    /// - 3: no version bump needed (reserved for a fail-on-no-bump check)
+    /// - 4: not a Git repository
+    /// - 5: Git or repository state error, such as a dirty working tree or an existing release tag
+    /// - 6: GitHub API error during enrichment when `--require-github` is set; other GitHub
+    ///   API errors, e.g. of `deploy-key` or GitHub App tokens, are 1
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            VNextError::ConfigError(_) => 2,
+            VNextError::NotARepository(_) => 4,
+            VNextError::GitError(_) | VNextError::RepositoryState(_) | VNextError::TagExists(_) => 5,
+            VNextError::GithubRequired(_) => 6,
+            _ => 1,
+        }
+    }
+}
+
 // Implement conversions from other error types
 impl From<git2::Error> for VNextError {
     fn from(err: git2::Error) -> Self {
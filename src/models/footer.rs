@@ -0,0 +1,23 @@
+//! Footer rule data structures
+
+/// What a matching commit footer does to the commit
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FooterAction {
+    /// Classify the commit as if it had this type (e.g. `revert` or `noop`)
+    ClassifyAs(String),
+    /// Always list the commit in the changelog, regardless of changelog filters
+    ForceInclude,
+    /// Show the footer value as an issue reference next to the commit in the changelog
+    LinkAsIssue,
+}
+
+/// A footer token (optionally with a required value) and the action it triggers
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FooterRule {
+    /// Footer token such as `Refs` or `Reverts`, matched case-insensitively
+    pub token: String,
+    /// Only match footers with this value (e.g. `RELEASE-BLOCKER`), matched case-insensitively
+    pub value: Option<String>,
+    /// Action applied to commits with a matching footer
+    pub action: FooterAction,
+}
@@ -1,5 +1,6 @@
 //! GitHub-related data structures
 
+use crate::models::commit::CommitAuthor;
 use serde::{Deserialize, Serialize};
 
 /// GitHub commit information
@@ -29,4 +30,34 @@ pub struct GitCommitAuthor {
 pub struct GitHubAccountInfo {
     pub login: String,
     // html_url field removed as it's not used anywhere in the codebase
-}
\ No newline at end of file
+}
+
+/// Pull request a commit was merged through
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PullRequestInfo {
+    pub number: u64,
+    pub title: String,
+    pub labels: Vec<String>,
+}
+
+/// Metadata a forge knows about a commit
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommitMetadata {
+    pub author: Option<CommitAuthor>,
+    pub pull_request: Option<PullRequestInfo>,
+}
+
+/// The parts of a GitHub Actions event payload (the file `GITHUB_EVENT_PATH` names)
+/// vnext reads
+#[derive(Deserialize, Debug)]
+pub struct GitHubEvent {
+    /// Pull request of `pull_request` and `pull_request_target` events
+    pub pull_request: Option<GitHubEventPullRequest>,
+}
+
+/// Pull request of an event payload
+#[derive(Deserialize, Debug)]
+pub struct GitHubEventPullRequest {
+    pub number: u64,
+    pub title: String,
+}
@@ -0,0 +1,21 @@
+//! GitLab-related data structures
+
+use serde::{Deserialize, Serialize};
+
+/// GitLab project information returned by `GET /projects/:id`
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GitLabProject {
+    pub id: u64,
+    pub path_with_namespace: String,
+}
+
+/// Merge request returned by `GET /projects/:id/repository/commits/:sha/merge_requests`
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GitLabMergeRequest {
+    pub iid: u64,
+    pub title: String,
+    /// `opened`, `closed`, `locked` or `merged`
+    pub state: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
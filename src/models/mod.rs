@@ -8,12 +8,22 @@ pub mod commit;
 pub mod error;
 pub mod repo;
 pub mod github;
+pub mod gitlab;
 pub mod deploy_key;
+pub mod authors;
+pub mod footer;
+pub mod review;
 pub mod changeset;
+pub mod diagnostics;
+pub mod options;
 
 // Re-export commonly used types
-pub use version::VersionBump;
+pub use version::{BumpLevel, BumpMap, VersionBump, VersionOverride};
 pub use commit::{Commit, CommitAuthor};
-pub use changeset::ChangesetSummary;
+pub use changeset::{ChangesetSummary, ReleaseCounts};
+pub use diagnostics::{Diagnostic, Diagnostics};
 pub use error::VNextError;
-pub use repo::RepoInfo;
\ No newline at end of file
+pub use repo::{RepoInfo, WorkingTreeStatus};
+pub use footer::{FooterAction, FooterRule};
+pub use review::{IgnoreRevs, RevDecision};
+pub use options::{VNextOptions, ChangelogOptions, WalkOptions, EmptyCommitPolicy, OutputFormat, PrintSection, Phase, ProgressCallback, PostProcessHook, CancellationToken};
\ No newline at end of file
@@ -0,0 +1,525 @@
+//! Run options for vnext, shared by the CLI and library embedders
+
+use crate::core::auth::GitHubAuth;
+use crate::models::footer::FooterRule;
+use crate::models::repo::RepoSlug;
+use crate::models::review::IgnoreRevs;
+use crate::models::version::{BumpMap, VersionBump, VersionOverride, VersionScheme};
+use crate::parsers::ParserHandle;
+use regex::Regex;
+use semver::Version;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Phases of a vnext run that report progress
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// Scanning the repository tags for the latest release
+    TagScan,
+    /// Walking the commits since the last release
+    Revwalk,
+    /// Fetching author information from the forge API
+    Enrichment,
+}
+
+/// Callback invoked with the current phase, the number of items processed so far,
+/// and the total number of items when it is known up front
+pub type ProgressCallback = Box<dyn Fn(Phase, u64, Option<u64>) + Send>;
+
+/// Hook applied to the rendered changelog before it is written or printed
+pub type PostProcessHook = Box<dyn Fn(String) -> String + Send>;
+
+/// Cooperative cancellation flag shared between an embedder and a running calculation.
+///
+/// Cloning the token shares the underlying flag, so an embedder can keep one clone
+/// and hand the other to `VNextOptions`.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a new, non-cancelled token
+    pub fn new() -> Self {
+        CancellationToken(Arc::new(AtomicBool::new(false)))
+    }
+
+    /// Request cancellation of the running calculation
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Check whether cancellation has been requested
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// How commits that change no files are classified
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum EmptyCommitPolicy {
+    /// Classify empty commits by their message like any other commit
+    #[default]
+    Classify,
+    /// Treat empty commits as no-ops and mark them as empty in the changelog
+    Noop,
+}
+
+/// Whether prerelease tags (e.g. `v1.3.0-rc.1`) can be the release the next version is calculated from
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum PrereleaseBase {
+    /// The latest tag of any version, prereleases included
+    Include,
+    /// The latest tag of a stable version
+    #[default]
+    Exclude,
+}
+
+/// Whether release tags are fetched from `origin` before the calculation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum FetchMode {
+    /// Fetch tags, warning and carrying on with the local tags when the fetch fails
+    Optional,
+    /// Fetch tags and fail when the fetch fails
+    Required,
+}
+
+/// Line endings of the files a run writes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LineEnding {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`
+    Crlf,
+    /// `\r\n` on Windows, `\n` elsewhere
+    Native,
+}
+
+impl LineEnding {
+    /// The line separator
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+            LineEnding::Native if cfg!(windows) => "\r\n",
+            LineEnding::Native => "\n",
+        }
+    }
+
+    /// Convert the line endings of `text`, whether `\n` or `\r\n`, to this one
+    pub fn apply(self, text: &str) -> String {
+        let normalized = text.replace("\r\n", "\n");
+        match self.as_str() {
+            "\n" => normalized,
+            separator => normalized.replace('\n', separator),
+        }
+    }
+}
+
+/// Output format of commands that support machine-readable output
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// A single JSON object
+    Json,
+}
+
+/// When text output is colored
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// On a terminal, unless `NO_COLOR` is set
+    #[default]
+    Auto,
+    /// Always, even when stdout is piped
+    Always,
+    /// Never
+    Never,
+}
+
+/// Section of the text output of a run
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum PrintSection {
+    /// The next version on a line of its own
+    Version,
+    /// The rendered changelog
+    Changelog,
+}
+
+/// Order of the commits in the changelog
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ChangelogOrder {
+    /// Oldest first, by commit time
+    Chronological,
+    /// Newest first, by commit time
+    Reverse,
+    /// Breaking changes, then features, then fixes, then the rest, each oldest first
+    Type,
+}
+
+/// Options controlling which commits are walked and how they are classified
+#[derive(Clone, Debug, Default)]
+pub struct WalkOptions {
+    /// How commits that change no files are classified
+    pub empty_commits: EmptyCommitPolicy,
+    /// Calculate from a shallow clone on a best-effort basis instead of failing
+    pub allow_shallow: bool,
+    /// Only consider release tags under this namespace (e.g. `releases/`)
+    pub tag_namespace: Option<String>,
+    /// Only consider release tags starting with this prefix after the namespace (e.g. `api-v`)
+    pub tag_prefix: Option<String>,
+    /// Only count commits that touch files under this repository-relative path
+    pub path: Option<String>,
+    /// Trunk branch name, skipping detection when set
+    pub trunk: Option<String>,
+    /// Remote the repository owner and name are read from (`origin` when unset)
+    pub remote: Option<String>,
+    /// Footer rules evaluated on each commit after parsing
+    pub footer_rules: Vec<FooterRule>,
+    /// Review decisions hiding commits or forcing them to no-ops
+    pub ignore_revs: IgnoreRevs,
+    /// Only count commits whose scope matches one of these values or globs (all commits when empty)
+    pub scopes: Vec<String>,
+    /// Treat commits without a scope as out of scope when `scopes` is set
+    pub exclude_unscoped: bool,
+    /// Version to start from when no release tag exists (0.0.0 when unset)
+    pub initial_version: Option<Version>,
+    /// Stop the walk after this many commits, counting the rest as omitted (unlimited when unset)
+    pub max_commits: Option<usize>,
+    /// Patterns marking a commit as not releasable when found in its message
+    /// (`DEFAULT_SKIP_MARKERS` when unset)
+    pub skip_markers: Option<Vec<Regex>>,
+    /// Fail instead of picking one when several tags name the latest version on different commits
+    pub strict_tags: bool,
+    /// Whether a prerelease tag can be the latest release
+    pub prerelease_base: PrereleaseBase,
+    /// How versions are numbered
+    pub scheme: VersionScheme,
+    /// Date (YYYY-MM-DD) deciding the period of calendar versions (today in UTC when unset)
+    pub release_date: Option<String>,
+}
+
+impl WalkOptions {
+    /// Name of the remote the repository owner and name are read from
+    pub fn remote_name(&self) -> &str {
+        self.remote.as_deref().unwrap_or(crate::core::git::DEFAULT_REMOTE)
+    }
+
+    /// Version the commits bump from when no release tag exists
+    pub fn initial_base_version(&self) -> Version {
+        self.initial_version.clone().unwrap_or_else(|| Version::new(0, 0, 0))
+    }
+
+    /// Patterns of the skip-release markers, the defaults unless overridden
+    pub fn skip_release_markers(&self) -> Vec<Regex> {
+        match &self.skip_markers {
+            Some(markers) => markers.clone(),
+            None => DEFAULT_SKIP_MARKERS
+                .iter()
+                .map(|marker| Regex::new(marker).expect("default skip markers are valid"))
+                .collect(),
+        }
+    }
+
+    /// Name of the tag a release of `version` is published under (`v` prefix when unset)
+    pub fn release_tag_name(&self, version: &Version) -> String {
+        format!("{}{}", self.release_tag_prefix().unwrap_or_else(|| "v".to_string()), self.scheme.render(version))
+    }
+
+    /// Version `bump` leads to from `current` under the version scheme
+    pub fn next_version(&self, current: &Version, bump: &VersionBump) -> Version {
+        let date = self.release_date.clone().unwrap_or_else(crate::utils::date::today_utc);
+        crate::core::version::calculate_next_version_with_scheme(current, bump, &self.scheme, &date)
+    }
+
+    /// Full prefix of release tags before the version: the namespace followed by the tag prefix
+    pub fn release_tag_prefix(&self) -> Option<String> {
+        match (&self.tag_namespace, &self.tag_prefix) {
+            (None, None) => None,
+            (namespace, prefix) => Some(format!(
+                "{}{}",
+                namespace.as_deref().unwrap_or(""),
+                prefix.as_deref().unwrap_or("")
+            )),
+        }
+    }
+}
+
+/// Markers that keep a commit from triggering a release, matched case-insensitively
+pub const DEFAULT_SKIP_MARKERS: &[&str] = &[r"(?i)\[skip release\]", r"(?i)\[no release\]"];
+
+/// Default heading of the changelog
+pub const DEFAULT_CHANGELOG_HEADING: &str = "### What's changed in v{version}";
+
+/// Number of levels headings in commit bodies are scaled down by (h1 -> h4)
+pub const DEFAULT_HEADER_SCALING_OFFSET: u8 = 3;
+
+/// Options controlling how the changelog is rendered
+#[derive(Clone, Debug, Default)]
+pub struct ChangelogOptions {
+    /// Disable header scaling in commit bodies
+    pub no_header_scaling: bool,
+    /// Levels headings in commit bodies are scaled down by (`DEFAULT_HEADER_SCALING_OFFSET`
+    /// when unset); headings that would go past h6 become h6
+    pub header_scaling_offset: Option<u8>,
+    /// Heading template supporting `{version}`, `{previous_version}` and `{date}`
+    /// placeholders (`DEFAULT_CHANGELOG_HEADING` when unset)
+    pub heading: Option<String>,
+    /// Release date used for the `{date}` placeholder (today in UTC when unset)
+    pub release_date: Option<String>,
+    /// Keep git trailers (e.g. `Signed-off-by:`) at the end of commit bodies
+    pub keep_trailers: bool,
+    /// Credit `Co-authored-by` trailers as additional authors on the changelog line
+    pub credit_coauthors: bool,
+    /// Append a contributors section (and new contributors when known)
+    pub contributors: bool,
+    /// Append a line counting the commits, features, fixes, breaking changes and contributors
+    pub summary_line: bool,
+    /// List the entries under a heading per section: breaking changes, features, fixes
+    /// and other changes
+    pub group_sections: bool,
+    /// Show the number of entries in each section heading and end the changelog with a
+    /// totals line (with `group_sections`)
+    pub section_counts: bool,
+    /// Namespace of the release tags used in compare links (e.g. `releases/`)
+    pub tag_namespace: Option<String>,
+    /// Prefix of the release tags before the version in compare links (`v` when unset)
+    pub tag_prefix: Option<String>,
+    /// Version scheme the heading and compare links render versions in
+    pub scheme: VersionScheme,
+    /// Show `Refs`, `Closes`, `Fixes` and `Resolves` footers as issue references
+    pub issue_references: bool,
+    /// Leave out the breaking changes section above the commit list
+    pub no_highlight_breaking: bool,
+    /// Truncate commit bodies after this many lines (unlimited when unset)
+    pub max_body_lines: Option<usize>,
+    /// List only the header of each commit, without its body
+    pub first_line_only: bool,
+    /// Render commit titles and bodies verbatim instead of escaping markdown in them
+    pub no_escape: bool,
+    /// Order of the listed commits (the reverse of the walk, oldest first, when unset)
+    pub order: Option<ChangelogOrder>,
+    /// Append the commit date (YYYY-MM-DD, UTC) to each entry
+    pub show_dates: bool,
+    /// List commits merged through a known pull request under the pull request title
+    pub prefer_pr_titles: bool,
+}
+
+/// Options controlling a vnext run
+pub struct VNextOptions {
+    /// Commit message parser, shared by every step of the run
+    pub parser: ParserHandle,
+    /// Version bump each commit type triggers; unmapped types bump the patch version
+    pub bump_map: BumpMap,
+    /// Options controlling how commits are classified during the revwalk
+    pub walk: WalkOptions,
+    /// Output the changelog instead of the bare version
+    pub show_changelog: bool,
+    /// Sections of the text output, in order, separated by `---` lines (the changelog with
+    /// `show_changelog`, otherwise the version, when empty)
+    pub print: Vec<PrintSection>,
+    /// Omit no-op commits from the changelog
+    pub changelog_exclude_noop: bool,
+    /// Omit commits outside the scope filter from the changelog
+    pub changelog_scope_filter: bool,
+    /// Omit commits with a skip-release marker from the changelog
+    pub changelog_hide_skipped: bool,
+    /// Commit types to show in the changelog (all types when empty)
+    pub changelog_include_types: Vec<String>,
+    /// Omit commits whose pull request has one of these labels from the changelog
+    pub changelog_exclude_labels: Vec<String>,
+    /// Count the commits excluded by label as no-ops toward the bump
+    pub labels_affect_bump: bool,
+    /// Options controlling how the changelog is rendered
+    pub changelog: ChangelogOptions,
+    /// Output the current version instead of the next one
+    pub current: bool,
+    /// Release with this bump or version instead of the one the commits call for
+    pub version_override: Option<VersionOverride>,
+    /// Allow a forced version that is not greater than the current version
+    pub allow_downgrade: bool,
+    /// Report the next version even when its release tag already exists on another commit
+    pub allow_existing_tag: bool,
+    /// Release the initial version as is, whatever the commit types, when no release tag exists
+    pub first_release: bool,
+    /// Build metadata template appended to the next version, supporting `{sha}`,
+    /// `{short-sha}`, `{commits}` and `{date}` placeholders
+    pub build_metadata: Option<String>,
+    /// Format of the result written to stdout
+    pub format: OutputFormat,
+    /// When the text result is colored; files are never colored
+    pub color: ColorChoice,
+    /// Append the analyzed commit range to the text output
+    pub show_range: bool,
+    /// Also write the bare next version to this file
+    pub version_file_out: Option<PathBuf>,
+    /// Also write the rendered changelog to this file
+    pub changelog_out: Option<PathBuf>,
+    /// Also write release provenance metadata (JSON) to this file
+    pub provenance_out: Option<PathBuf>,
+    /// Also append the version, current version, bump and changelog to the GitHub Actions
+    /// step output file named by `GITHUB_OUTPUT`
+    pub github_output: bool,
+    /// Line endings of the version, changelog and provenance files
+    pub line_ending: LineEnding,
+    /// Fail instead of warning when the working tree has uncommitted changes
+    pub require_clean: bool,
+    /// Do not treat untracked files as uncommitted changes
+    pub allow_untracked: bool,
+    /// Fail instead of warning when a commit message breaks a lint rule
+    pub deny_warnings: bool,
+    /// JSON file mapping author emails (or globs) to display names and handles
+    pub authors_file: Option<PathBuf>,
+    /// Let the authors file override forge enrichment
+    pub authors_file_wins: bool,
+    /// Number of GitHub commit lookups run in parallel
+    pub github_concurrency: usize,
+    /// Directory GitHub commit authors are cached in between runs (no cache when unset)
+    pub github_cache: Option<PathBuf>,
+    /// Fetch authors and pull requests with the GitHub GraphQL API instead of one REST
+    /// request per commit
+    pub github_graphql: bool,
+    /// How the GitHub token is found
+    pub github_auth: GitHubAuth,
+    /// Fetch tags from `origin` before the calculation (no fetch when unset)
+    pub fetch: Option<FetchMode>,
+    /// SSH private key used to authenticate the fetch (the SSH agent when unset)
+    pub ssh_key: Option<PathBuf>,
+    /// Fail when GitHub author information cannot be fetched for the changelog
+    pub require_github: bool,
+    /// Output the initial version instead of failing when no repository is found or the
+    /// calculation fails
+    pub lenient: bool,
+    /// File review decisions are read from and written to (`.vnext-ignore-revs` when unset)
+    pub ignore_revs_file: Option<PathBuf>,
+    /// Git directory of the repository (`--git-dir` or `GIT_DIR`); the repository is
+    /// searched from the current directory when unset
+    pub git_dir: Option<PathBuf>,
+    /// External command the rendered changelog is piped through (stdin to stdout)
+    pub changelog_post_process: Option<String>,
+    /// Time the post-process command may run before it is killed
+    pub changelog_post_process_timeout: Duration,
+    /// Optional hook applied to the rendered changelog after the external command
+    pub post_process: Option<PostProcessHook>,
+    /// Optional progress callback
+    pub progress: Option<ProgressCallback>,
+    /// Optional cancellation token checked periodically during long-running phases
+    pub cancellation: Option<CancellationToken>,
+}
+
+impl VNextOptions {
+    /// Sections of the text output, in order
+    pub fn print_sections(&self) -> Vec<PrintSection> {
+        match (self.print.is_empty(), self.show_changelog) {
+            (false, _) => self.print.clone(),
+            (true, true) => vec![PrintSection::Changelog],
+            (true, false) => vec![PrintSection::Version],
+        }
+    }
+}
+
+impl Default for VNextOptions {
+    fn default() -> Self {
+        VNextOptions {
+            parser: ParserHandle::default(),
+            bump_map: BumpMap::default(),
+            walk: WalkOptions::default(),
+            show_changelog: false,
+            print: Vec::new(),
+            changelog_exclude_noop: false,
+            changelog_scope_filter: false,
+            changelog_hide_skipped: false,
+            changelog_include_types: Vec::new(),
+            changelog_exclude_labels: Vec::new(),
+            labels_affect_bump: false,
+            changelog: ChangelogOptions::default(),
+            current: false,
+            version_override: None,
+            allow_downgrade: false,
+            allow_existing_tag: false,
+            first_release: false,
+            build_metadata: None,
+            format: OutputFormat::Text,
+            color: ColorChoice::Auto,
+            show_range: false,
+            version_file_out: None,
+            changelog_out: None,
+            provenance_out: None,
+            github_output: false,
+            line_ending: LineEnding::Lf,
+            require_clean: false,
+            allow_untracked: false,
+            deny_warnings: false,
+            authors_file: None,
+            authors_file_wins: false,
+            github_concurrency: crate::core::github::DEFAULT_GITHUB_CONCURRENCY,
+            github_graphql: false,
+            github_auth: GitHubAuth::default(),
+            github_cache: None,
+            fetch: None,
+            ssh_key: None,
+            require_github: false,
+            lenient: false,
+            ignore_revs_file: None,
+            git_dir: None,
+            changelog_post_process: None,
+            changelog_post_process_timeout: crate::core::post_process::DEFAULT_POST_PROCESS_TIMEOUT,
+            post_process: None,
+            progress: None,
+            cancellation: None,
+        }
+    }
+}
+
+/// Split a comma-separated list into trimmed, non-empty entries
+pub fn split_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Rules of the `check` command
+#[derive(Clone, Debug, Default)]
+pub struct CheckOptions {
+    /// Allowed commit types; empty for the types of the bump map and the common
+    /// conventional commit types
+    pub allowed_types: Vec<String>,
+    /// Allowed scopes; empty to allow any scope
+    pub allowed_scopes: Vec<String>,
+    /// Maximum length of the title in characters
+    pub max_length: Option<usize>,
+}
+
+/// Options of the `deploy-key` command actions
+#[derive(Clone, Debug, Default)]
+pub struct DeployKeyOptions {
+    /// Repository owner (detected or prompted for when unset)
+    pub owner: Option<String>,
+    /// Repository name (detected or prompted for when unset)
+    pub name: Option<String>,
+    /// Title of the deploy key and name of the secret
+    pub key_name: String,
+    /// Replace an existing deploy key and secret without asking
+    pub overwrite: bool,
+    /// Answer every prompt with yes
+    pub yes: bool,
+    /// Also write the key pair to files
+    pub keep_files: bool,
+    /// Set up these repositories instead of a single one, or with `org_secret` the
+    /// repositories allowed to read the organization secret
+    pub repos: Vec<RepoSlug>,
+    /// Store the private key as an Actions secret of this organization instead of the repository
+    pub org_secret: Option<String>,
+    /// Give the deploy key write access, e.g. to push release tags
+    pub write_access: bool,
+    /// How the GitHub token is found
+    pub github_auth: GitHubAuth,
+    /// Remote the current repository is detected from (`origin` when unset)
+    pub remote: Option<String>,
+    /// Git directory the current repository is opened from (searched from the current directory when unset)
+    pub git_dir: Option<PathBuf>,
+}
@@ -1,12 +1,18 @@
 //! Repository information data structures
 
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
 /// Repository information structure
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RepoInfo {
     pub owner: String,
     pub name: String,
     pub is_github_repo: bool,
     pub is_gitlab_repo: bool,
     pub is_bitbucket_repo: bool,
+    /// Numeric GitLab project ID, resolved lazily for API calls
+    pub project_id: Option<u64>,
 }
 
 impl RepoInfo {
@@ -18,6 +24,74 @@ impl RepoInfo {
             is_github_repo: false,
             is_gitlab_repo: false,
             is_bitbucket_repo: false,
+            project_id: None,
+        }
+    }
+}
+
+impl fmt::Display for RepoInfo {
+    /// `owner/name`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.owner, self.name)
+    }
+}
+
+/// A GitHub repository named as `owner/name`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RepoSlug {
+    pub owner: String,
+    pub name: String,
+}
+
+/// Check that `value` is a valid GitHub owner, organization or repository name:
+/// letters, digits, `_`, `.` and `-`, and neither `.` nor `..`.
+///
+/// Names are interpolated into API paths and passed to the GitHub CLI, so anything
+/// else is rejected before any call is made.
+pub fn validate_github_name(value: &str) -> Result<(), String> {
+    let valid_chars = value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+    if value.is_empty() || !valid_chars || value == "." || value == ".." {
+        return Err(format!("'{}' is not a valid GitHub name; use letters, digits, '_', '.' and '-'", value));
+    }
+    Ok(())
+}
+
+impl RepoSlug {
+    /// Create a repository slug, validating both names
+    pub fn new(owner: &str, name: &str) -> Result<Self, String> {
+        validate_github_name(owner)?;
+        validate_github_name(name)?;
+        Ok(RepoSlug { owner: owner.to_string(), name: name.to_string() })
+    }
+
+    /// Parse `owner/name`, rejecting invalid names and extra slashes
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().split_once('/') {
+            Some((owner, name)) if !owner.is_empty() && !name.is_empty() && !name.contains('/') => RepoSlug::new(owner, name),
+            _ => Err(format!("'{}' is not a repository; expected owner/name", value.trim())),
         }
     }
-}
\ No newline at end of file
+}
+
+impl fmt::Display for RepoSlug {
+    /// `owner/name`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.owner, self.name)
+    }
+}
+
+/// Uncommitted changes in the working tree
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WorkingTreeStatus {
+    /// Modified, staged, deleted or renamed tracked files
+    pub modified: usize,
+    /// Untracked files (ignored files are not counted)
+    pub untracked: usize,
+}
+
+impl WorkingTreeStatus {
+    /// Whether the working tree has changes, optionally ignoring untracked files
+    pub fn is_dirty(&self, allow_untracked: bool) -> bool {
+        self.modified > 0 || (!allow_untracked && self.untracked > 0)
+    }
+}
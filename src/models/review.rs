@@ -0,0 +1,67 @@
+//! Commit review decision data structures
+
+use std::collections::BTreeMap;
+
+/// File `vnext review` stores its decisions in, relative to the repository root
+pub const DEFAULT_IGNORE_REVS_FILE: &str = ".vnext-ignore-revs";
+
+/// Decision recorded for a commit during `vnext review`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RevDecision {
+    /// Leave the commit out of the version calculation and the changelog
+    Hide,
+    /// List the commit in the changelog, but never bump the version for it
+    Noop,
+}
+
+impl RevDecision {
+    /// Name of the decision in the ignore-revs file
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RevDecision::Hide => "hide",
+            RevDecision::Noop => "noop",
+        }
+    }
+}
+
+/// Review decisions keyed by commit SHA; abbreviated SHAs match as prefixes
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IgnoreRevs {
+    decisions: BTreeMap<String, RevDecision>,
+}
+
+impl IgnoreRevs {
+    /// Create an empty set of decisions
+    pub fn new() -> Self {
+        IgnoreRevs::default()
+    }
+
+    /// Decision recorded for the commit with the full SHA `commit_id`
+    pub fn decision(&self, commit_id: &str) -> Option<RevDecision> {
+        let commit_id = commit_id.to_lowercase();
+        self.decisions
+            .iter()
+            .find(|(rev, _)| commit_id.starts_with(rev.as_str()))
+            .map(|(_, decision)| *decision)
+    }
+
+    /// Record the decision for a commit, or clear it with `None`
+    pub fn set(&mut self, commit_id: &str, decision: Option<RevDecision>) {
+        let commit_id = commit_id.to_lowercase();
+        // Abbreviated entries matching the commit would shadow the new decision
+        self.decisions.retain(|rev, _| !commit_id.starts_with(rev.as_str()));
+        if let Some(decision) = decision {
+            self.decisions.insert(commit_id, decision);
+        }
+    }
+
+    /// Iterate the decisions in SHA order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, RevDecision)> {
+        self.decisions.iter().map(|(rev, decision)| (rev.as_str(), *decision))
+    }
+
+    /// Whether no decisions are recorded
+    pub fn is_empty(&self) -> bool {
+        self.decisions.is_empty()
+    }
+}
@@ -1,8 +1,369 @@
 //! Version-related data structures
 
-/// Represents the type of version bump to apply
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Represents the type of version bump to apply.
+///
+/// Only the highest set flag matters; `BumpLevel` is the canonical form and
+/// `VersionBump::from(level)` sets the flag of that level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct VersionBump {
     pub major: bool,
     pub minor: bool,
     pub patch: bool,
-}
\ No newline at end of file
+}
+
+impl VersionBump {
+    /// Highest bump level set
+    pub fn bump_level(&self) -> BumpLevel {
+        if self.major {
+            BumpLevel::Major
+        } else if self.minor {
+            BumpLevel::Minor
+        } else if self.patch {
+            BumpLevel::Patch
+        } else {
+            BumpLevel::None
+        }
+    }
+
+    /// Name of the highest bump level ("major", "minor", "patch" or "none")
+    pub fn level(&self) -> &'static str {
+        self.bump_level().as_str()
+    }
+}
+
+impl fmt::Display for VersionBump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.level())
+    }
+}
+
+impl From<BumpLevel> for VersionBump {
+    fn from(level: BumpLevel) -> Self {
+        VersionBump {
+            major: level == BumpLevel::Major,
+            minor: level == BumpLevel::Minor,
+            patch: level == BumpLevel::Patch,
+        }
+    }
+}
+
+/// Version bump a commit type triggers, ordered from no bump to a major bump
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BumpLevel {
+    /// No version bump
+    None,
+    /// Bump the patch version
+    Patch,
+    /// Bump the minor version
+    Minor,
+    /// Bump the major version
+    Major,
+}
+
+impl BumpLevel {
+    /// Name of the level ("none", "patch", "minor" or "major")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BumpLevel::None => "none",
+            BumpLevel::Patch => "patch",
+            BumpLevel::Minor => "minor",
+            BumpLevel::Major => "major",
+        }
+    }
+
+    /// Parse a level name, accepting `noop` as an alias of `none`
+    pub fn parse(name: &str) -> Option<BumpLevel> {
+        match name.trim().to_lowercase().as_str() {
+            "none" | "noop" => Some(BumpLevel::None),
+            "patch" => Some(BumpLevel::Patch),
+            "minor" => Some(BumpLevel::Minor),
+            "major" => Some(BumpLevel::Major),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BumpLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Mapping of commit types (lowercase) to the bump they trigger.
+///
+/// Types missing from the map bump the patch version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BumpMap {
+    levels: HashMap<String, BumpLevel>,
+}
+
+impl BumpMap {
+    /// Create an empty map, under which every commit type bumps the patch version
+    pub fn new() -> Self {
+        BumpMap { levels: HashMap::new() }
+    }
+
+    /// Build a map from major, minor and no-op type lists.
+    ///
+    /// A type listed more than once gets the highest of its levels.
+    pub fn from_types<S: AsRef<str>>(major: &[S], minor: &[S], noop: &[S]) -> Self {
+        let mut map = BumpMap::new();
+        for (types, level) in [(noop, BumpLevel::None), (minor, BumpLevel::Minor), (major, BumpLevel::Major)] {
+            for commit_type in types {
+                map.insert(commit_type.as_ref(), level);
+            }
+        }
+        map
+    }
+
+    /// Map `commit_type` to `level`, replacing any previous level
+    pub fn insert(&mut self, commit_type: &str, level: BumpLevel) {
+        self.levels.insert(commit_type.trim().to_lowercase(), level);
+    }
+
+    /// Level `commit_type` is mapped to, ignoring case
+    pub fn level(&self, commit_type: &str) -> Option<BumpLevel> {
+        self.levels.get(&commit_type.to_lowercase()).copied()
+    }
+
+    /// Commit types mapped to `level`, sorted
+    pub fn types_at(&self, level: BumpLevel) -> Vec<&str> {
+        let mut types: Vec<&str> = self.levels.iter()
+            .filter(|(_, l)| **l == level)
+            .map(|(t, _)| t.as_str())
+            .collect();
+        types.sort_unstable();
+        types
+    }
+
+    /// All mapped commit types with their level, sorted by type
+    pub fn entries(&self) -> Vec<(&str, BumpLevel)> {
+        let mut entries: Vec<(&str, BumpLevel)> = self.levels.iter().map(|(t, l)| (t.as_str(), *l)).collect();
+        entries.sort_unstable();
+        entries
+    }
+}
+
+impl Default for BumpMap {
+    /// `major` and `breaking` bump major, `feat` and `minor` bump minor, `chore` and `noop`
+    /// bump nothing
+    fn default() -> Self {
+        BumpMap::from_types(&["major", "breaking"], &["feat", "minor"], &["chore", "noop"])
+    }
+}
+
+impl Extend<(String, BumpLevel)> for BumpMap {
+    fn extend<I: IntoIterator<Item = (String, BumpLevel)>>(&mut self, entries: I) {
+        for (commit_type, level) in entries {
+            self.insert(&commit_type, level);
+        }
+    }
+}
+
+/// Override of the calculated next version
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VersionOverride {
+    /// Apply this bump instead of the one the commits call for
+    Bump(BumpLevel),
+    /// Release this exact version
+    Version(Version),
+}
+
+impl VersionOverride {
+    /// Name of the override kind ("bump" or "version")
+    pub fn kind(&self) -> &'static str {
+        match self {
+            VersionOverride::Bump(_) => "bump",
+            VersionOverride::Version(_) => "version",
+        }
+    }
+}
+
+/// Rule that decided the bump level of a commit
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BumpReason {
+    /// `!` after the type or scope of the header
+    BreakingBang,
+    /// A `BREAKING CHANGE:` footer (or a match of the custom breaking pattern)
+    BreakingFooter,
+    /// The commit type is in the bump map
+    TypeList,
+    /// The commit type is not in the bump map, so it bumps the patch version
+    UnknownFallback,
+    /// Marked as a no-op during review
+    Review,
+    /// The message carries a skip-release marker
+    SkipRelease,
+    /// The scope is outside the scope filter
+    OutOfScope,
+    /// The commit changes no files
+    EmptyCommit,
+}
+
+impl BumpReason {
+    /// Name of the reason, e.g. `breaking-bang` or `type-list`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BumpReason::BreakingBang => "breaking-bang",
+            BumpReason::BreakingFooter => "breaking-footer",
+            BumpReason::TypeList => "type-list",
+            BumpReason::UnknownFallback => "unknown-fallback",
+            BumpReason::Review => "review",
+            BumpReason::SkipRelease => "skip-release",
+            BumpReason::OutOfScope => "out-of-scope",
+            BumpReason::EmptyCommit => "empty-commit",
+        }
+    }
+}
+
+impl fmt::Display for BumpReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Bump level a walked commit was classified at, and why
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitBump {
+    pub commit_id: String,
+    pub level: BumpLevel,
+    pub reason: BumpReason,
+}
+
+/// How versions are numbered
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VersionScheme {
+    /// Semantic versioning: the commits decide between major, minor and patch bumps
+    #[default]
+    Semver,
+    /// Calendar versioning: the release date decides the first two segments and the
+    /// third counts the releases of the period
+    Calver(CalverFormat),
+}
+
+impl VersionScheme {
+    /// Parse `semver`, `calver` or `calver:<format>` (e.g. `calver:YY.MM.MICRO`)
+    pub fn parse(value: &str) -> Result<VersionScheme, String> {
+        match value.trim() {
+            "semver" => Ok(VersionScheme::Semver),
+            "calver" => Ok(VersionScheme::Calver(CalverFormat::default())),
+            other => match other.strip_prefix("calver:") {
+                Some(format) => CalverFormat::parse(format).map(VersionScheme::Calver),
+                None => Err(format!("'{}' is not a version scheme, expected semver, calver or calver:<format>", value)),
+            },
+        }
+    }
+
+    /// Version as it is released: semantic versions as is, calendar versions with their
+    /// zero-padded segments
+    pub fn render(&self, version: &Version) -> String {
+        match self {
+            VersionScheme::Semver => version.to_string(),
+            VersionScheme::Calver(format) => format.render(version),
+        }
+    }
+}
+
+/// Year segment of a calendar version
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalverYear {
+    /// `YYYY`: the full year, e.g. 2026
+    Full,
+    /// `YY`: the year without the century, e.g. 6 for 2006
+    Short,
+    /// `0Y`: the year without the century, zero-padded, e.g. 06
+    PaddedShort,
+}
+
+/// Format of a calendar version: a year segment, a month segment and the micro counter,
+/// such as `YYYY.0M.MICRO` for 2026.01.0
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CalverFormat {
+    pub year: CalverYear,
+    /// Zero-pad the month (`0M`) rather than writing it as is (`MM`)
+    pub padded_month: bool,
+}
+
+impl Default for CalverFormat {
+    /// `YYYY.0M.MICRO`
+    fn default() -> Self {
+        CalverFormat { year: CalverYear::Full, padded_month: true }
+    }
+}
+
+impl CalverFormat {
+    /// Parse a format of a year (`YYYY`, `YY` or `0Y`), a month (`MM` or `0M`) and `MICRO`,
+    /// separated by dots
+    pub fn parse(format: &str) -> Result<CalverFormat, String> {
+        let segments: Vec<&str> = format.trim().split('.').collect();
+        let error = || format!(
+            "'{}' is not a calendar version format, expected a year (YYYY, YY or 0Y), a month (MM or 0M) and MICRO, e.g. YYYY.0M.MICRO",
+            format
+        );
+        let [year, month, "MICRO"] = segments.as_slice() else {
+            return Err(error());
+        };
+        let year = match *year {
+            "YYYY" => CalverYear::Full,
+            "YY" => CalverYear::Short,
+            "0Y" => CalverYear::PaddedShort,
+            _ => return Err(error()),
+        };
+        let padded_month = match *month {
+            "MM" => false,
+            "0M" => true,
+            _ => return Err(error()),
+        };
+        Ok(CalverFormat { year, padded_month })
+    }
+
+    /// Year and month segments of the period containing `date` (YYYY-MM-DD)
+    pub fn period(&self, date: &str) -> Option<(u64, u64)> {
+        let mut parts = date.split('-');
+        let year: u64 = parts.next()?.parse().ok()?;
+        let month: u64 = parts.next()?.parse().ok()?;
+        let year = match self.year {
+            CalverYear::Full => year,
+            CalverYear::Short | CalverYear::PaddedShort => year % 100,
+        };
+        Some((year, month))
+    }
+
+    /// `version` with the padding of this format, e.g. 2026.01.3
+    pub fn render(&self, version: &Version) -> String {
+        let mut rendered = match self.year {
+            CalverYear::PaddedShort => format!("{:02}", version.major),
+            CalverYear::Full | CalverYear::Short => version.major.to_string(),
+        };
+        if self.padded_month {
+            rendered.push_str(&format!(".{:02}.{}", version.minor, version.patch));
+        } else {
+            rendered.push_str(&format!(".{}.{}", version.minor, version.patch));
+        }
+        if !version.pre.is_empty() {
+            rendered.push_str(&format!("-{}", version.pre));
+        }
+        if !version.build.is_empty() {
+            rendered.push_str(&format!("+{}", version.build));
+        }
+        rendered
+    }
+}
+
+impl fmt::Display for CalverFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let year = match self.year {
+            CalverYear::Full => "YYYY",
+            CalverYear::Short => "YY",
+            CalverYear::PaddedShort => "0Y",
+        };
+        write!(f, "{}.{}.MICRO", year, if self.padded_month { "0M" } else { "MM" })
+    }
+}
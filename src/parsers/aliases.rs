@@ -0,0 +1,50 @@
+//! Commit type aliases (e.g. `feature` → `feat`)
+
+use crate::models::commit::{Commit, CommitParser};
+use std::collections::HashMap;
+
+/// Parse a comma-separated list of `alias=type` pairs (e.g. `feature=feat,bugfix=fix`).
+///
+/// Both sides are trimmed and lowercased.
+pub fn parse_type_aliases(value: &str) -> Result<HashMap<String, String>, String> {
+    let mut aliases = HashMap::new();
+    for pair in value.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+        let (alias, commit_type) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("expected alias=type, got '{}'", pair))?;
+        let (alias, commit_type) = (alias.trim().to_lowercase(), commit_type.trim().to_lowercase());
+        if alias.is_empty() || commit_type.is_empty() {
+            return Err(format!("expected alias=type, got '{}'", pair));
+        }
+        aliases.insert(alias, commit_type);
+    }
+    Ok(aliases)
+}
+
+/// Parser wrapper that rewrites aliased commit types to their canonical type
+pub struct AliasingParser<P: ?Sized> {
+    aliases: HashMap<String, String>,
+    inner: Box<P>,
+}
+
+impl<P: CommitParser + ?Sized> AliasingParser<P> {
+    /// Wrap `inner`, rewriting commit types found in `aliases`
+    pub fn new(inner: Box<P>, aliases: HashMap<String, String>) -> Self {
+        AliasingParser { aliases, inner }
+    }
+}
+
+impl<P: CommitParser + ?Sized> CommitParser for AliasingParser<P> {
+    fn parse_commit(&self, commit_id: String, message: String) -> Commit {
+        let mut commit = self.inner.parse_commit(commit_id, message);
+        if let Some(commit_type) = self.aliases.get(&commit.commit_type.to_lowercase()) {
+            log::debug!("Commit type '{}' is an alias of '{}'", commit.commit_type, commit_type);
+            commit.commit_type = commit_type.clone();
+        }
+        commit
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+}
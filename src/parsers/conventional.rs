@@ -1,57 +1,103 @@
 //! Regex pattern compilation and validation for conventional commits
 
-use crate::models::commit::{Commit, CommitParser};
+use crate::models::commit::{normalize_message, parse_scopes, Commit, CommitParser};
+use crate::parsers::trailers;
 pub use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::OnceLock;
 
-// Regex for parsing conventional commits
-pub const CONVENTIONAL_COMMIT_REGEX_STR: &str = r"^([\w-]+)(?:\(([^\)]+)\))?(!)?:\s*(.*)\n*((BREAKING CHANGE:)?\s?([\s\S]*))?";
+// Regex for parsing the header (first line) of a conventional commit: type(scope)!: title
+pub const CONVENTIONAL_COMMIT_REGEX_STR: &str = r"^([\w-]+)(?:\(([^\)]+)\))?(!)?:[ \t]*(.*?)[ \t]*$";
+
+// Tokens that make a body starting with them a breaking change note
+const BREAKING_BODY_TOKENS: [&str; 2] = ["BREAKING CHANGE:", "BREAKING-CHANGE:"];
 
 /// Represents the parsed components of a conventional commit message
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ParsedCommit {
     pub commit_type: String,
     pub scope: Option<String>,
+    /// Individual scopes, see `parse_scopes`
+    pub scopes: Vec<String>,
     pub breaking_change_flag: bool,
     pub title: String,
     pub body: Option<String>,
     pub breaking_change_body: bool,
+    /// Text of the `BREAKING CHANGE:` (or `BREAKING-CHANGE:`) footer, if any
+    pub breaking_change_note: Option<String>,
+    /// Footers from the trailer block at the end of the body, as (token, value) pairs
+    pub footers: Vec<(String, String)>,
+}
+
+impl fmt::Display for ParsedCommit {
+    /// The header: `type(scope)!: title`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.commit_type)?;
+        if let Some(scope) = &self.scope {
+            write!(f, "({})", scope)?;
+        }
+        if self.breaking_change_flag {
+            f.write_str("!")?;
+        }
+        write!(f, ": {}", self.title)
+    }
 }
 
 /// Parse a conventional commit message into its components
 pub fn parse_conventional_commit(message: &str) -> Option<ParsedCommit> {
     log::debug!("Conventional Commit Parser - Message: {}", message);
-    // Master regex for the entire commit message including header and body
-    // Format: type(scope)?!?: title\n*(BREAKING CHANGE:)?\s?([\s\S]*)
-    let commit_regex = Regex::new(CONVENTIONAL_COMMIT_REGEX_STR).ok()?;
+    static HEADER_REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+    let header_regex = HEADER_REGEX.get_or_init(|| Regex::new(CONVENTIONAL_COMMIT_REGEX_STR).ok()).as_ref()?;
     
-    // Parse using the regex
-    let captures = commit_regex.captures(message)?;
+    // Split the header (first line) from the body before analyzing either
+    let message = normalize_message(message);
+    let (header, rest) = message.split_once('\n').unwrap_or((message.as_str(), ""));
+    let captures = header_regex.captures(header)?;
     
-    let commit_type = captures.get(1)?.as_str().to_string();
-    let scope = captures.get(2).map(|m| m.as_str().to_string());
+    let commit_type = captures.get(1)?.as_str().to_lowercase();
+    let scopes = captures.get(2).map(|m| parse_scopes(m.as_str())).unwrap_or_default();
+    let scope = (!scopes.is_empty()).then(|| scopes.join(","));
     let breaking_change_flag = captures.get(3).is_some();
     let title = captures.get(4)?.as_str().to_string();
     
-    // Get body from capture group 7 (if it exists)
-    let body = captures.get(7).map(|m| {
-        let body_str = m.as_str().trim_start();
-        if body_str.is_empty() {
-            None
-        } else {
-            Some(body_str.to_string())
-        }
-    }).flatten();
+    // A body starting with a breaking change token is itself the breaking change note
+    let body_text = rest.trim();
+    let breaking_token = BREAKING_BODY_TOKENS.iter().find(|token| body_text.starts_with(*token));
+    let body_text = match breaking_token {
+        Some(token) => body_text[token.len()..].trim_start(),
+        None => body_text,
+    };
     
-    // Check for breaking change in body using capture group 6
-    let breaking_change_body = captures.get(6).is_some();
+    // Keep the footer block out of the body
+    let (body, mut footers) = if body_text.is_empty() {
+        (None, Vec::new())
+    } else {
+        let (description, footers) = trailers::split_footers(body_text);
+        (Some(description).filter(|d| !d.is_empty()), footers)
+    };
+    
+    // Otherwise look for a breaking change footer in the trailer block
+    let breaking_change_note = match breaking_token {
+        Some(token) => {
+            let note = body.clone().unwrap_or_default();
+            footers.insert(0, (token.trim_end_matches(':').to_string(), note.clone()));
+            Some(note)
+        }
+        None => trailers::breaking_change_note(&footers),
+    };
+    let breaking_change_body = breaking_change_note.is_some();
     
     Some(ParsedCommit {
         commit_type,
         scope,
+        scopes,
         breaking_change_flag,
         title,
         body,
         breaking_change_body,
+        breaking_change_note,
+        footers,
     })
 }
 
@@ -71,10 +117,13 @@ impl CommitParser for ConventionalCommitParser {
         if let Some(parsed) = parse_conventional_commit(&message) {
             commit.commit_type = parsed.commit_type;
             commit.scope = parsed.scope;
+            commit.scopes = parsed.scopes;
             // Set has_breaking_change if either flag or body indicates a breaking change
             commit.has_breaking_change = parsed.breaking_change_flag || parsed.breaking_change_body;
             commit.title = parsed.title;
             commit.body = parsed.body;
+            commit.breaking_change_note = parsed.breaking_change_note;
+            commit.footers = parsed.footers;
         } else {
             log::debug!("Conventional parser: Could not parse commit message: {}", message.lines().next().unwrap_or(""));
         }
@@ -1,15 +1,98 @@
 //! Custom regex-based commit parser implementation
 
-use crate::models::commit::{Commit, CommitParser};
+use crate::models::commit::{normalize_message, parse_scopes, Commit, CommitParser};
+use crate::parsers::trailers;
 use regex::Regex;
+use std::fmt;
 
-// Default regex patterns
-pub const COMMIT_TYPE_REGEX_STR: &str = r"^([\w-]+)(?:.*)?!?:.*";
-pub const TITLE_REGEX_STR: &str = r"^[\w-]+(?:.*)?!?:\s(.*)";
-pub const BODY_REGEX_STR: &str = r"^[\w-]+(?:.*)?!?:\s.*\n\s*(?:BREAKING CHANGE:)?\s*([\s\S]*)";
-pub const SCOPE_REGEX_STR: &str = r"^[\w-]+(?:\((.*)\))?!?:.*";
-pub const BREAKING_REGEX_STR: &str = r"(?:^[^\n]*\n\nBREAKING CHANGE:.*|^[\w-]+(?:.*)?!:.*)";
-// Regex for extracting scope from commit message
+// Default regex patterns; the header is `type(scope)!: title`, where the scope and the
+// `!` marking a breaking change of any type are optional
+pub const COMMIT_TYPE_REGEX_STR: &str = r"^([\w-]+)(?:\([^)\n]*\))?!?:.*";
+pub const TITLE_REGEX_STR: &str = r"^[\w-]+(?:\([^)\n]*\))?!?:\s(.*)";
+pub const BODY_REGEX_STR: &str = r"^[\w-]+(?:\([^)\n]*\))?!?:\s.*\n\s*(?:BREAKING[ -]CHANGE:)?\s*([\s\S]*)";
+pub const SCOPE_REGEX_STR: &str = r"^[\w-]+(?:\(([^)\n]*)\))?!?:.*";
+pub const BREAKING_REGEX_STR: &str = r"(?:^[^\n]*\n\n(?:[\s\S]*\n\n)?BREAKING[ -]CHANGE:.*|^[\w-]+(?:\([^)\n]*\))?!:.*)";
+
+/// The patterns of a `CustomRegexParser`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatternName {
+    /// Extracts the commit type
+    Type,
+    /// Extracts the commit title
+    Title,
+    /// Extracts the commit body
+    Body,
+    /// Matches breaking changes
+    Breaking,
+    /// Extracts the commit scope
+    Scope,
+}
+
+impl PatternName {
+    /// Command line flag setting the pattern
+    pub fn flag(&self) -> &'static str {
+        match self {
+            PatternName::Type => "--type-pattern",
+            PatternName::Title => "--title-pattern",
+            PatternName::Body => "--body-pattern",
+            PatternName::Breaking => "--breaking-pattern",
+            PatternName::Scope => "--scope-pattern",
+        }
+    }
+
+    /// Capture groups the parser reads from the pattern; the extracted part is group 1
+    fn required_groups(&self) -> usize {
+        match self {
+            PatternName::Breaking => 0,
+            _ => 1,
+        }
+    }
+}
+
+/// What is wrong with a custom pattern
+#[derive(Debug)]
+pub enum PatternErrorKind {
+    /// The pattern does not compile; the regex error points at the offending position
+    Syntax(regex::Error),
+    /// The pattern has fewer capture groups than the parser reads
+    MissingCaptureGroup { required: usize, found: usize },
+}
+
+/// Error building a `CustomRegexParser`, naming the pattern that is invalid
+#[derive(Debug)]
+pub struct PatternError {
+    /// The invalid pattern
+    pub pattern: PatternName,
+    /// What is wrong with it
+    pub kind: PatternErrorKind,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            PatternErrorKind::Syntax(e) => write!(f, "{} does not compile:\n{}", self.pattern.flag(), e),
+            PatternErrorKind::MissingCaptureGroup { required, found } => write!(
+                f,
+                "{} needs {} capture group(s) but has {}; the extracted part must be capture group 1",
+                self.pattern.flag(), required, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+/// Compile a pattern and check it has the capture groups the parser reads
+fn compile(pattern: PatternName, source: &str) -> Result<Regex, PatternError> {
+    let regex = Regex::new(source).map_err(|e| PatternError { pattern, kind: PatternErrorKind::Syntax(e) })?;
+    // captures_len counts the implicit group 0 for the whole match
+    let found = regex.captures_len() - 1;
+    let required = pattern.required_groups();
+    if found < required {
+        return Err(PatternError { pattern, kind: PatternErrorKind::MissingCaptureGroup { required, found } });
+    }
+    Ok(regex)
+}
 
 /// Parser using custom regex patterns for commit parts
 pub struct CustomRegexParser {
@@ -27,13 +110,13 @@ impl CustomRegexParser {
         body_pattern: &str,
         breaking_pattern: &str,
         scope_pattern: &str,
-    ) -> Result<Self, regex::Error> {
+    ) -> Result<Self, PatternError> {
         Ok(CustomRegexParser {
-            commit_type_regex: Regex::new(commit_type_pattern)?,
-            title_regex: Regex::new(title_pattern)?,
-            body_regex: Regex::new(body_pattern)?,
-            breaking_regex: Regex::new(breaking_pattern)?,
-            scope_regex: Regex::new(scope_pattern)?,
+            commit_type_regex: compile(PatternName::Type, commit_type_pattern)?,
+            title_regex: compile(PatternName::Title, title_pattern)?,
+            body_regex: compile(PatternName::Body, body_pattern)?,
+            breaking_regex: compile(PatternName::Breaking, breaking_pattern)?,
+            scope_regex: compile(PatternName::Scope, scope_pattern)?,
         })
     }
     
@@ -52,6 +135,7 @@ impl CommitParser for CustomRegexParser {
     fn parse_commit(&self, commit_id: String, message: String) -> Commit {
         log::debug!("Customer Regex Parser - Message: {}", message);
         let mut commit = Commit::new(commit_id, message.clone());
+        let message = normalize_message(&message);
                 
         // Extract commit title using title_regex
         if let Some(captures) = self.title_regex.captures(&message) {
@@ -63,25 +147,18 @@ impl CommitParser for CustomRegexParser {
         // Extract commit type using commit_type_regex
         if let Some(captures) = self.commit_type_regex.captures(&message) {
             if let Some(type_match) = captures.get(1) {
-                commit.commit_type = type_match.as_str().to_string();
+                commit.commit_type = type_match.as_str().to_lowercase();
             }
         }
         
         // Extract scope using scope_regex
         if let Some(captures) = self.scope_regex.captures(&message) {
             if let Some(scope_match) = captures.get(1) {
-                commit.scope = Some(scope_match.as_str().to_string());
+                commit.scopes = parse_scopes(scope_match.as_str());
+                commit.scope = (!commit.scopes.is_empty()).then(|| commit.scopes.join(","));
             }
         }
         
-        // Extract title using title_regex
-        if let Some(captures) = self.title_regex.captures(&message) {
-            if let Some(title_match) = captures.get(1) {
-                commit.title = title_match.as_str().to_string();
-            }
-        }
-        
-        
         // Extract body using body_regex
         if let Some(captures) = self.body_regex.captures(&message) {
             if let Some(body_match) = captures.get(1) {
@@ -92,6 +169,16 @@ impl CommitParser for CustomRegexParser {
         // Set breaking change flag based on regex match
         commit.has_breaking_change = self.breaking_regex.is_match(&message);
         
+        // Keep the footer block, including any breaking change footer, apart from the body
+        if let Some(body) = commit.body.take() {
+            let (description, footers) = trailers::split_footers(&body);
+            commit.body = Some(description).filter(|d| !d.is_empty());
+            if commit.has_breaking_change {
+                commit.breaking_change_note = trailers::breaking_change_note(&footers);
+            }
+            commit.footers = footers;
+        }
+        
         // Log information about the commit for debugging
         log::debug!("Custom parser: Parsed commit: {}", message.lines().next().unwrap_or(""));
         log::debug!("  Type: {}", commit.commit_type);
@@ -1,8 +1,19 @@
 //! Factory for creating commit parsers based on the selected strategy
 
 use crate::models::commit::CommitParser;
+use crate::models::error::VNextError;
 use crate::parsers::conventional::ConventionalCommitParser;
-use crate::parsers::custom::CustomRegexParser;
+use crate::parsers::custom::{CustomRegexParser, PatternName, BODY_REGEX_STR, BREAKING_REGEX_STR, COMMIT_TYPE_REGEX_STR, SCOPE_REGEX_STR, TITLE_REGEX_STR};
+
+/// Names of the parsers selectable with `--parser`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ParserKind {
+    /// Conventional Commits
+    #[default]
+    Conventional,
+    /// Custom regex patterns
+    Custom,
+}
 
 /// Enum representing different commit parsing strategies.
 ///
@@ -54,7 +65,6 @@ pub struct ParserFactory;
 impl ParserFactory {
     /// Create a new commit parser based on the specified strategy.
     ///
-    /// This method creates a new commit parser based on the specified strategy.
     /// If the strategy is `ParserStrategy::Conventional`, it creates a `ConventionalCommitParser`.
     /// If the strategy is `ParserStrategy::CustomRegex`, it creates a `CustomRegexParser` with
     /// the specified regex patterns.
@@ -65,12 +75,13 @@ impl ParserFactory {
     ///
     /// # Returns
     ///
-    /// A boxed instance of a type that implements the `CommitParser` trait
-    pub fn create(strategy: &ParserStrategy) -> Box<dyn CommitParser> {
+    /// A boxed instance of a type that implements the `CommitParser` trait, or
+    /// `VNextError::ConfigError` when a custom regex pattern is invalid
+    pub fn create(strategy: &ParserStrategy) -> Result<Box<dyn CommitParser>, VNextError> {
         match strategy {
             ParserStrategy::Conventional => {
                 log::debug!("Using conventional commit parser");
-                Box::new(ConventionalCommitParser::new())
+                Ok(Box::new(ConventionalCommitParser::new()))
             },
             ParserStrategy::CustomRegex {
                 commit_type_pattern,
@@ -86,27 +97,37 @@ impl ParserFactory {
                 log::debug!("  Breaking pattern: {}", breaking_pattern);
                 log::debug!("  Scope pattern: {}", scope_pattern);
                 
-                match CustomRegexParser::new(
+                let parser = CustomRegexParser::new(
                     commit_type_pattern,
                     title_pattern,
                     body_pattern,
                     breaking_pattern,
                     scope_pattern
-                ) {
-                    Ok(parser) => Box::new(parser),
-                    Err(e) => {
-                        // Fall back to default patterns if custom patterns are invalid
-                        log::warn!("Invalid regex patterns, falling back to defaults: {}", e);
-                        log::debug!("Using default regex patterns:");
-                        log::debug!("  Commit type pattern: {}", crate::parsers::custom::COMMIT_TYPE_REGEX_STR);
-                        log::debug!("  Title pattern: {}", crate::parsers::custom::TITLE_REGEX_STR);
-                        log::debug!("  Body pattern: {}", crate::parsers::custom::BODY_REGEX_STR);
-                        log::debug!("  Breaking pattern: {}", crate::parsers::custom::BREAKING_REGEX_STR);
-                        log::debug!("  Scope pattern: {}", crate::parsers::custom::SCOPE_REGEX_STR);
-                        Box::new(CustomRegexParser::default())
-                    }
-                }
+                ).map_err(|e| VNextError::ConfigError(format!(
+                    "Invalid custom parser pattern: {}\nFix the pattern or pass --allow-regex-fallback to use its default",
+                    e
+                )))?;
+                Ok(Box::new(parser))
+            }
+        }
+    }
+
+    /// Replace each invalid pattern of a custom regex strategy by its default, logging
+    /// a warning naming the pattern. Valid patterns and other strategies are kept unchanged.
+    pub fn with_regex_fallback(mut strategy: ParserStrategy) -> ParserStrategy {
+        if let ParserStrategy::CustomRegex { commit_type_pattern, title_pattern, body_pattern, breaking_pattern, scope_pattern } = &mut strategy {
+            while let Err(e) = CustomRegexParser::new(commit_type_pattern, title_pattern, body_pattern, breaking_pattern, scope_pattern) {
+                log::warn!("Invalid custom parser pattern, falling back to its default: {}", e);
+                let (pattern, default) = match e.pattern {
+                    PatternName::Type => (&mut *commit_type_pattern, COMMIT_TYPE_REGEX_STR),
+                    PatternName::Title => (&mut *title_pattern, TITLE_REGEX_STR),
+                    PatternName::Body => (&mut *body_pattern, BODY_REGEX_STR),
+                    PatternName::Breaking => (&mut *breaking_pattern, BREAKING_REGEX_STR),
+                    PatternName::Scope => (&mut *scope_pattern, SCOPE_REGEX_STR),
+                };
+                *pattern = default.to_string();
             }
         }
+        strategy
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,105 @@
+//! Shared, lazily constructed commit parser
+
+use crate::models::commit::CommitParser;
+use crate::models::error::VNextError;
+use crate::parsers::aliases::AliasingParser;
+use crate::parsers::factory::{ParserFactory, ParserStrategy};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+
+/// A commit parser shared by everything in a run.
+///
+/// The parser (and its regexes) is built from the strategy the first time it is
+/// needed; clones share the same parser. If construction fails the error is cached
+/// and returned again on every later call instead of retrying.
+#[derive(Clone)]
+pub struct ParserHandle {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    strategy: ParserStrategy,
+    type_aliases: HashMap<String, String>,
+    parser: OnceLock<Result<Arc<dyn CommitParser>, String>>,
+    constructions: AtomicUsize,
+}
+
+impl ParserHandle {
+    /// Create a handle for `strategy` without building the parser yet
+    pub fn new(strategy: ParserStrategy) -> Self {
+        ParserHandle::with_type_aliases(strategy, HashMap::new())
+    }
+
+    /// Create a handle whose parser rewrites aliased commit types (e.g. `feature` → `feat`)
+    pub fn with_type_aliases(strategy: ParserStrategy, type_aliases: HashMap<String, String>) -> Self {
+        ParserHandle {
+            inner: Arc::new(Inner {
+                strategy,
+                type_aliases,
+                parser: OnceLock::new(),
+                constructions: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// The strategy the parser is built from
+    pub fn strategy(&self) -> &ParserStrategy {
+        &self.inner.strategy
+    }
+
+    /// The commit type aliases applied after parsing
+    pub fn type_aliases(&self) -> &HashMap<String, String> {
+        &self.inner.type_aliases
+    }
+
+    /// Get the parser, building it on first use.
+    ///
+    /// Returns `VNextError::ConfigError` when the strategy's patterns are invalid.
+    pub fn parser(&self) -> Result<Arc<dyn CommitParser>, VNextError> {
+        let parser = self.inner.parser.get_or_init(|| {
+            self.inner.constructions.fetch_add(1, Ordering::SeqCst);
+            let parser = ParserFactory::create(&self.inner.strategy).map(|parser| {
+                if self.inner.type_aliases.is_empty() {
+                    Arc::from(parser)
+                } else {
+                    Arc::new(AliasingParser::new(parser, self.inner.type_aliases.clone())) as Arc<dyn CommitParser>
+                }
+            });
+            match &parser {
+                Ok(parser) => log::debug!("Parser initialized: {}", parser.name()),
+                Err(e) => log::debug!("Parser construction failed: {}", e),
+            }
+            parser.map_err(|e| match e {
+                VNextError::ConfigError(message) => message,
+                other => other.to_string(),
+            })
+        });
+        match parser {
+            Ok(parser) => Ok(Arc::clone(parser)),
+            Err(message) => Err(VNextError::ConfigError(message.clone())),
+        }
+    }
+
+    /// Number of times the parser has been built through this handle or its clones
+    pub fn constructions(&self) -> usize {
+        self.inner.constructions.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for ParserHandle {
+    fn default() -> Self {
+        ParserHandle::new(ParserStrategy::default())
+    }
+}
+
+impl fmt::Debug for ParserHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParserHandle")
+            .field("strategy", &self.inner.strategy)
+            .field("type_aliases", &self.inner.type_aliases)
+            .field("constructions", &self.constructions())
+            .finish()
+    }
+}
@@ -1,8 +1,14 @@
+pub mod aliases;
 pub mod conventional;
 pub mod custom;
 pub mod factory;
+pub mod handle;
+pub mod trailers;
 
 // Re-export commonly used functions and types
 pub use conventional::{parse_conventional_commit, ParsedCommit, CONVENTIONAL_COMMIT_REGEX_STR, ConventionalCommitParser};
-pub use custom::{CustomRegexParser, COMMIT_TYPE_REGEX_STR, TITLE_REGEX_STR, BODY_REGEX_STR, BREAKING_REGEX_STR, SCOPE_REGEX_STR};
-pub use factory::{ParserFactory, ParserStrategy};
\ No newline at end of file
+pub use custom::{CustomRegexParser, PatternError, PatternErrorKind, PatternName, COMMIT_TYPE_REGEX_STR, TITLE_REGEX_STR, BODY_REGEX_STR, BREAKING_REGEX_STR, SCOPE_REGEX_STR};
+pub use factory::{ParserFactory, ParserKind, ParserStrategy};
+pub use handle::ParserHandle;
+pub use aliases::{parse_type_aliases, AliasingParser};
+pub use trailers::{split_trailers, split_footers, split_breaking_footer, coauthor_names};
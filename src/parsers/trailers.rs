@@ -0,0 +1,129 @@
+//! Detection of git trailers (`Token: value` and `Token #value` lines) at the end of commit bodies
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+// Regex for a single trailer line, e.g. `Signed-off-by: Jane <jane@example.com>` or `Closes #42`
+pub const TRAILER_REGEX_STR: &str = r"^(BREAKING CHANGE|[A-Za-z0-9][A-Za-z0-9-]*)(?::\s+(.*\S)| (#.*\S))\s*$";
+
+/// Split a commit body into the text before its trailer block and the parsed trailers.
+///
+/// The trailer block is the last paragraph of the body when every line in it is a
+/// `Token: value` trailer (or an indented continuation of the previous one). Breaking
+/// change footers are meaningful release notes, so they are kept in the returned body
+/// as well as being reported as trailers. If there is no trailer block the body is
+/// returned unchanged with no trailers.
+pub fn split_trailers(body: &str) -> (String, Vec<(String, String)>) {
+    let (mut remaining, trailers) = match trailer_block(body) {
+        Some(block) => block,
+        None => return (body.to_string(), Vec::new()),
+    };
+    for (token, value) in &trailers {
+        if is_breaking_token(token) {
+            if !remaining.is_empty() {
+                remaining.push_str("\n\n");
+            }
+            remaining.push_str(&format!("{}: {}", token, value));
+        }
+    }
+
+    (remaining, trailers)
+}
+
+/// Split a commit body into the text before its trailer block and the parsed trailers,
+/// removing the whole block (breaking change footers included) from the text.
+///
+/// If there is no trailer block the body is returned unchanged with no trailers.
+pub fn split_footers(body: &str) -> (String, Vec<(String, String)>) {
+    trailer_block(body).unwrap_or_else(|| (body.to_string(), Vec::new()))
+}
+
+/// Split the breaking change footers off a commit body.
+///
+/// Returns the body without its `BREAKING CHANGE:` / `BREAKING-CHANGE:` footers and the
+/// footer text (several footers are joined by blank lines), or the unchanged body and
+/// `None` when the trailer block has no breaking change footer. A `BREAKING CHANGE:`
+/// mention inside a description paragraph is not a footer.
+pub fn split_breaking_footer(body: &str) -> (String, Option<String>) {
+    let (mut remaining, trailers) = match trailer_block(body) {
+        Some(block) => block,
+        None => return (body.to_string(), None),
+    };
+    let note = match breaking_change_note(&trailers) {
+        Some(note) => note,
+        None => return (body.to_string(), None),
+    };
+    let others: Vec<_> = trailers.into_iter().filter(|(token, _)| !is_breaking_token(token)).collect();
+
+    if !others.is_empty() {
+        if !remaining.is_empty() {
+            remaining.push_str("\n\n");
+        }
+        let lines: Vec<String> = others.iter().map(|(token, value)| format!("{}: {}", token, value)).collect();
+        remaining.push_str(&lines.join("\n"));
+    }
+    (remaining, Some(note))
+}
+
+/// Join the values of the breaking change footers, or `None` when there are none
+pub fn breaking_change_note(footers: &[(String, String)]) -> Option<String> {
+    let notes: Vec<&str> = footers
+        .iter()
+        .filter(|(token, _)| is_breaking_token(token))
+        .map(|(_, value)| value.as_str())
+        .collect();
+    if notes.is_empty() {
+        None
+    } else {
+        Some(notes.join("\n\n"))
+    }
+}
+
+/// Parse the trailer block at the end of a body, returning the text before it and
+/// its trailers, or `None` when the last paragraph is not a trailer block
+fn trailer_block(body: &str) -> Option<(String, Vec<(String, String)>)> {
+    static TRAILER_REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+    let trailer_regex = TRAILER_REGEX.get_or_init(|| Regex::new(TRAILER_REGEX_STR).ok()).as_ref()?;
+
+    let lines: Vec<&str> = body.trim_end().lines().collect();
+    let block_start = lines.iter().rposition(|line| line.trim().is_empty()).map(|i| i + 1).unwrap_or(0);
+    let block = &lines[block_start..];
+    if block.is_empty() {
+        return None;
+    }
+
+    let mut trailers: Vec<(String, String)> = Vec::new();
+    for line in block {
+        if let Some(captures) = trailer_regex.captures(line) {
+            let value = captures.get(2).or_else(|| captures.get(3)).map(|m| m.as_str()).unwrap_or("");
+            trailers.push((captures[1].to_string(), value.to_string()));
+        } else if line.starts_with(char::is_whitespace) && !trailers.is_empty() {
+            // Continuation of the previous trailer's value
+            let last = trailers.len() - 1;
+            trailers[last].1.push(' ');
+            trailers[last].1.push_str(line.trim());
+        } else {
+            return None;
+        }
+    }
+
+    Some((lines[..block_start].join("\n").trim_end().to_string(), trailers))
+}
+
+/// Check whether a trailer token denotes a breaking change
+pub fn is_breaking_token(token: &str) -> bool {
+    token == "BREAKING CHANGE" || token == "BREAKING-CHANGE"
+}
+
+/// Extract the display names of co-authors from `Co-authored-by` trailers
+pub fn coauthor_names(trailers: &[(String, String)]) -> Vec<String> {
+    trailers
+        .iter()
+        .filter(|(token, _)| token.eq_ignore_ascii_case("Co-authored-by"))
+        .map(|(_, value)| match value.find('<') {
+            Some(pos) => value[..pos].trim().to_string(),
+            None => value.trim().to_string(),
+        })
+        .filter(|name| !name.is_empty())
+        .collect()
+}
@@ -0,0 +1,87 @@
+//! ANSI colors for text output on a terminal.
+//!
+//! Coloring is a layer over the rendered text: the changelog and version are formatted
+//! first, then decorated line by line, so files and piped output stay plain markdown.
+
+use crate::models::options::ColorChoice;
+use std::io::IsTerminal;
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const CYAN: &str = "\x1b[36m";
+
+/// Whether to color output under `choice`: always or never when forced, otherwise only on
+/// a terminal and when `NO_COLOR` is unset
+pub fn should_color(choice: ColorChoice, no_color: bool, is_terminal: bool) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => is_terminal && !no_color,
+    }
+}
+
+/// Whether to color stdout under `choice`, reading `NO_COLOR` and checking for a terminal
+pub fn color_stdout(choice: ColorChoice) -> bool {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    should_color(choice, no_color, std::io::stdout().is_terminal())
+}
+
+/// Color rendered output line by line: headings and bare version lines in bold, the
+/// breaking changes heading and entries starting with one of the `breaking` headers in
+/// red, and the `type(scope):` of other entries by kind
+pub fn colorize(text: &str, breaking: &[String]) -> String {
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        if line.starts_with('#') && line.to_lowercase().contains("breaking change") {
+            lines.push(paint(line, &format!("{}{}", BOLD, RED)));
+        } else if line.starts_with('#') || is_version_line(line) {
+            lines.push(paint(line, BOLD));
+        } else if let Some(entry) = line.strip_prefix("* ") {
+            if breaking.iter().any(|header| entry.starts_with(header.as_str())) {
+                lines.push(paint(line, RED));
+            } else {
+                lines.push(format!("* {}", color_entry(entry)));
+            }
+        } else if line.starts_with("See full diff:") || line == "---" {
+            lines.push(paint(line, DIM));
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+    let mut colored = lines.join("\n");
+    if text.ends_with('\n') {
+        colored.push('\n');
+    }
+    colored
+}
+
+/// `type(scope):` of an entry colored by type: features in green, fixes in yellow and
+/// anything else in cyan
+fn color_entry(entry: &str) -> String {
+    let Some((prefix, rest)) = entry.split_once(": ") else {
+        return entry.to_string();
+    };
+    if prefix.contains(' ') {
+        return entry.to_string();
+    }
+    let commit_type = prefix.split('(').next().unwrap_or_default().to_lowercase();
+    let color = match commit_type.as_str() {
+        "feat" => GREEN,
+        "fix" => YELLOW,
+        _ => CYAN,
+    };
+    format!("{}: {}", paint(prefix, color), rest)
+}
+
+/// A line holding only a version, as printed without the changelog
+fn is_version_line(line: &str) -> bool {
+    !line.is_empty() && line.starts_with(|c: char| c.is_ascii_digit()) && !line.contains(char::is_whitespace)
+}
+
+fn paint(text: &str, color: &str) -> String {
+    format!("{}{}{}", color, text, RESET)
+}
@@ -0,0 +1,61 @@
+//! Date helpers that avoid pulling in a full date/time library
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Current UTC date in ISO format (YYYY-MM-DD)
+pub fn today_utc() -> String {
+    format_epoch_date(now_epoch())
+}
+
+/// Format seconds since the Unix epoch as an ISO date (YYYY-MM-DD) in UTC
+pub fn format_epoch_date(seconds: i64) -> String {
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Current time in seconds since the Unix epoch
+pub fn now_epoch() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Format seconds since the Unix epoch as an RFC 3339 timestamp (YYYY-MM-DDTHH:MM:SSZ) in UTC
+pub fn format_epoch_timestamp(seconds: i64) -> String {
+    let time = seconds.rem_euclid(86_400);
+    format!(
+        "{}T{:02}:{:02}:{:02}Z",
+        format_epoch_date(seconds),
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+/// Check that a string is an ISO date (YYYY-MM-DD)
+pub fn is_iso_date(date: &str) -> bool {
+    let parts: Vec<&str> = date.split('-').collect();
+    if parts.len() != 3 || parts[0].len() != 4 || parts[1].len() != 2 || parts[2].len() != 2 {
+        return false;
+    }
+    match (parts[0].parse::<u32>(), parts[1].parse::<u32>(), parts[2].parse::<u32>()) {
+        (Ok(_), Ok(month), Ok(day)) => (1..=12).contains(&month) && (1..=31).contains(&day),
+        _ => false,
+    }
+}
+
+/// Convert days since the Unix epoch to a (year, month, day) civil date
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Algorithm from Howard Hinnant's "chrono-Compatible Low-Level Date Algorithms"
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
@@ -0,0 +1,23 @@
+//! File system helpers
+
+use crate::models::error::VNextError;
+use std::io::Write;
+use std::path::Path;
+
+/// Write `contents` to `path` atomically.
+///
+/// The data is written to a temporary file in the same directory and then renamed
+/// over the destination, so readers never observe a partially written file.
+pub fn write_atomic(path: &Path, contents: &str) -> Result<(), VNextError> {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    file.write_all(contents.as_bytes())?;
+    file.as_file().sync_all()?;
+    file.persist(path)
+        .map_err(|e| VNextError::Other(format!("Failed to write {}: {}", path.display(), e.error)))?;
+    log::debug!("Wrote {}", path.display());
+    Ok(())
+}
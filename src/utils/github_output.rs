@@ -0,0 +1,46 @@
+//! Step outputs for GitHub Actions, appended to the file named by `GITHUB_OUTPUT`
+
+use crate::models::error::VNextError;
+use std::io::Write;
+use std::path::Path;
+
+/// Environment variable GitHub Actions sets to the step output file
+pub const GITHUB_OUTPUT_ENV: &str = "GITHUB_OUTPUT";
+
+/// Delimiter of multi-line values unless the value contains it
+const DEFAULT_DELIMITER: &str = "EOF";
+
+/// Append `outputs` as (name, value) pairs to the step output file at `path`
+pub fn append_outputs(path: &Path, outputs: &[(&str, &str)]) -> Result<(), VNextError> {
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(format_outputs(outputs).as_bytes())?;
+    log::debug!("Appended {} step outputs to {}", outputs.len(), path.display());
+    Ok(())
+}
+
+/// Step output lines: `name=value` for single-line values, and a `name<<DELIMITER` block
+/// for multi-line ones. The delimiter is `EOF`, or a random `EOF_<hex>` token when the
+/// value contains `EOF`
+pub fn format_outputs(outputs: &[(&str, &str)]) -> String {
+    let mut text = String::new();
+    for (name, value) in outputs {
+        if value.contains('\n') {
+            let delimiter = delimiter_for(value);
+            text.push_str(&format!("{}<<{}\n{}\n{}\n", name, delimiter, value, delimiter));
+        } else {
+            text.push_str(&format!("{}={}\n", name, value));
+        }
+    }
+    text
+}
+
+/// A heredoc delimiter that `value` does not contain
+fn delimiter_for(value: &str) -> String {
+    let mut delimiter = DEFAULT_DELIMITER.to_string();
+    while value.contains(&delimiter) {
+        let mut token = [0u8; 8];
+        getrandom::getrandom(&mut token).expect("The operating system's random number generator failed");
+        delimiter = format!("{}_{:016x}", DEFAULT_DELIMITER, u64::from_ne_bytes(token));
+    }
+    delimiter
+}
@@ -5,19 +5,34 @@ use fern::Dispatch;
 use log::LevelFilter;
 use std::env;
 
-/// Initialize logging with fern
+/// Format of log lines written to stderr
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Colored, human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line with timestamp, level, target and message
+    Json,
+}
+
+/// Log level from the `LOG_LEVEL` environment variable, `info` when unset or invalid
+pub fn env_log_level() -> LevelFilter {
+    env::var("LOG_LEVEL")
+        .ok()
+        .and_then(|level| level.parse::<LevelFilter>().ok())
+        .unwrap_or(LevelFilter::Info)
+}
+
+/// Initialize logging with fern, at the level set by `LOG_LEVEL`
 pub fn init_logging() -> Result<(), fern::InitError> {
-    // Read the desired log level from the environment variable `LOG_LEVEL`
-    // Default to "info" if not set.
-    let log_level = env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string());
-    // Parse the environment variable into a LevelFilter. If parsing fails, default to Info.
-    let level_filter = log_level
-        .parse::<LevelFilter>()
-        .unwrap_or(LevelFilter::Info);
+    init_logging_with(env_log_level(), LogFormat::Text)
+}
 
-    Dispatch::new()
-        .level(level_filter)
-        .format(|out, message, record| {
+/// Initialize logging with fern at `level`, writing lines in `format` to stderr
+pub fn init_logging_with(level: LevelFilter, format: LogFormat) -> Result<(), fern::InitError> {
+    let dispatch = Dispatch::new().level(level);
+    let dispatch = match format {
+        LogFormat::Text => dispatch.format(|out, message, record| {
             let level = match record.level() {
                 log::Level::Error => format!("{:>12}", "Error").red().bold(),
                 log::Level::Warn => format!("{:>12}", "Warn").yellow().bold(),
@@ -26,8 +41,18 @@ pub fn init_logging() -> Result<(), fern::InitError> {
                 log::Level::Trace => format!("{:>12}", "Trace").normal().bold(),
             };
             out.finish(format_args!("{} {}", level, message))
-        })
-        .chain(std::io::stdout())
-        .apply()?;
+        }),
+        LogFormat::Json => dispatch.format(|out, message, record| {
+            let line = serde_json::json!({
+                "timestamp": crate::utils::date::format_epoch_timestamp(crate::utils::date::now_epoch()),
+                "level": record.level().as_str().to_lowercase(),
+                "target": record.target(),
+                "message": message.to_string(),
+            });
+            out.finish(format_args!("{}", line))
+        }),
+    };
+    // stdout is reserved for command results
+    dispatch.chain(std::io::stderr()).apply()?;
     Ok(())
-}
\ No newline at end of file
+}
@@ -3,7 +3,13 @@
 //! This module contains utility functions and helpers that are used across
 //! the application but don't represent core business logic.
 
+pub mod color;
+pub mod date;
+pub mod fs;
+pub mod github_output;
 pub mod logging;
+pub mod output;
 
 // Re-export commonly used functions
-pub use logging::init_logging;
\ No newline at end of file
+pub use logging::{init_logging, init_logging_with, LogFormat};
+pub use output::OutputWriter;
\ No newline at end of file
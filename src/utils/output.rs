@@ -0,0 +1,110 @@
+//! Writer for the machine-consumable output of commands
+
+use crate::models::error::VNextError;
+use crate::models::options::{OutputFormat, Phase, ProgressCallback};
+use std::io::{self, Write};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Writes command results in the requested format.
+///
+/// Results are the only thing vnext writes to stdout. Logs, warnings and interactive
+/// prompts go to stderr, so pipelines capturing stdout only ever see the result.
+pub struct OutputWriter {
+    format: OutputFormat,
+    sink: Box<dyn Write + Send>,
+}
+
+impl OutputWriter {
+    /// Create a writer for stdout
+    pub fn stdout(format: OutputFormat) -> Self {
+        OutputWriter::new(format, Box::new(io::stdout()))
+    }
+
+    /// Create a writer for any sink
+    pub fn new(format: OutputFormat, sink: Box<dyn Write + Send>) -> Self {
+        OutputWriter { format, sink }
+    }
+
+    /// Format results are written in
+    pub fn format(&self) -> OutputFormat {
+        self.format
+    }
+
+    /// Write a result: `text` in text format, or `json` on a single line in JSON format
+    pub fn result(&mut self, text: &str, json: serde_json::Value) -> Result<(), VNextError> {
+        match self.format {
+            OutputFormat::Text => writeln!(self.sink, "{}", text)?,
+            OutputFormat::Json => writeln!(self.sink, "{}", json)?,
+        }
+        self.sink.flush()?;
+        Ok(())
+    }
+}
+
+/// Create a progress callback drawing a single status line on stderr, e.g.
+/// `Walking commits: 1200`, rewritten in place with carriage returns.
+///
+/// The line is finished with a newline when the callback is dropped, so later
+/// output starts on a fresh line.
+pub fn stderr_progress() -> ProgressCallback {
+    status_line(Box::new(io::stderr()))
+}
+
+/// Create a progress callback drawing the status line on any sink.
+///
+/// When a phase ends, its line is kept with the time the phase took, e.g.
+/// `Walking commits: 1200 (85 ms)`.
+pub fn status_line(sink: Box<dyn Write + Send>) -> ProgressCallback {
+    let line = StatusLine { state: Mutex::new(StatusState { phase: None, sink }) };
+    Box::new(move |phase, current, total| line.update(phase, current, total))
+}
+
+/// Status line state: the phase drawn last and when it started, if any
+struct StatusLine {
+    state: Mutex<StatusState>,
+}
+
+struct StatusState {
+    phase: Option<(Phase, Instant)>,
+    sink: Box<dyn Write + Send>,
+}
+
+impl StatusState {
+    /// Keep the final count of the phase drawn last on screen, followed by its duration
+    fn finish_phase(&mut self) {
+        if let Some((_, started)) = self.phase.take() {
+            let _ = writeln!(self.sink, " ({} ms)", started.elapsed().as_millis());
+        }
+    }
+}
+
+impl StatusLine {
+    fn update(&self, phase: Phase, current: u64, total: Option<u64>) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.phase.is_some_and(|(last, _)| last != phase) {
+            state.finish_phase();
+        }
+        if state.phase.is_none() {
+            state.phase = Some((phase, Instant::now()));
+        }
+        let label = match phase {
+            Phase::TagScan => "Scanning tags",
+            Phase::Revwalk => "Walking commits",
+            Phase::Enrichment => "Looking up authors",
+        };
+        let _ = match total {
+            Some(total) => write!(state.sink, "\r{}: {}/{}", label, current, total),
+            None => write!(state.sink, "\r{}: {}", label, current),
+        };
+        let _ = state.sink.flush();
+    }
+}
+
+impl Drop for StatusLine {
+    fn drop(&mut self) {
+        let state = self.state.get_mut().unwrap_or_else(|e| e.into_inner());
+        state.finish_phase();
+        let _ = state.sink.flush();
+    }
+}
@@ -0,0 +1,119 @@
+use std::path::PathBuf;
+
+use vnext::core::auth::{GitHubAppCredentials, GitHubAuth, TokenSource};
+
+fn app() -> GitHubAppCredentials {
+    GitHubAppCredentials { app_id: "12345".to_string(), private_key: PathBuf::from("app.pem"), installation_id: 42 }
+}
+
+#[test]
+fn test_token_precedence() {
+    let env = |vars: &'static [(&'static str, &'static str)]| {
+        move |name: &str| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+    };
+    let gh = || Some("gh-token\n".to_string());
+    let no_gh = || None;
+    let exchange = |app: &GitHubAppCredentials| Some(format!("app-token-{}", app.installation_id));
+    let flag = GitHubAuth { token: Some("flag-token".to_string()), app: Some(app()) };
+    let with_app = GitHubAuth { token: None, app: Some(app()) };
+    let all_env = env(&[("GITHUB_TOKEN", "github-token"), ("GH_TOKEN", "gh-env-token")]);
+
+    assert_eq!(flag.resolve_with(&all_env, &gh, &exchange), Some(("flag-token".to_string(), TokenSource::Flag)));
+    assert_eq!(
+        with_app.resolve_with(&all_env, &gh, &exchange),
+        Some(("github-token".to_string(), TokenSource::Environment("GITHUB_TOKEN")))
+    );
+    // Empty values are skipped
+    assert_eq!(
+        with_app.resolve_with(&env(&[("GITHUB_TOKEN", ""), ("GH_TOKEN", "gh-env-token")]), &gh, &exchange),
+        Some(("gh-env-token".to_string(), TokenSource::Environment("GH_TOKEN")))
+    );
+    assert_eq!(with_app.resolve_with(&env(&[]), &gh, &exchange), Some(("gh-token".to_string(), TokenSource::GhCli)));
+    assert_eq!(
+        with_app.resolve_with(&env(&[]), &no_gh, &exchange),
+        Some(("app-token-42".to_string(), TokenSource::GitHubApp))
+    );
+    assert_eq!(GitHubAuth::default().resolve_with(&env(&[]), &no_gh, &exchange), None, "The app is only used when configured");
+    assert_eq!(with_app.resolve_with(&env(&[]), &no_gh, &|_| None), None);
+}
+
+#[test]
+fn test_token_resolution_from_the_environment() {
+    // The only test of this binary touching the process environment
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let gh = temp_dir.path().join("gh");
+    std::fs::write(&gh, "#!/bin/sh\n[ \"$1 $2\" = \"auth token\" ] && echo gh-cli-token\n").unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&gh, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    std::env::set_var("PATH", temp_dir.path());
+    std::env::set_var("GITHUB_TOKEN", "github-token");
+    std::env::set_var("GH_TOKEN", "gh-env-token");
+    let flag = GitHubAuth { token: Some("flag-token".to_string()), app: None };
+
+    assert_eq!(flag.resolve(), Some(("flag-token".to_string(), TokenSource::Flag)));
+    assert_eq!(GitHubAuth::default().resolve(), Some(("github-token".to_string(), TokenSource::Environment("GITHUB_TOKEN"))));
+    std::env::remove_var("GITHUB_TOKEN");
+    assert_eq!(GitHubAuth::default().resolve(), Some(("gh-env-token".to_string(), TokenSource::Environment("GH_TOKEN"))));
+    std::env::remove_var("GH_TOKEN");
+    #[cfg(unix)]
+    assert_eq!(GitHubAuth::default().resolve(), Some(("gh-cli-token".to_string(), TokenSource::GhCli)));
+
+    // Without a logged-in GitHub CLI there is no token
+    std::env::set_var("PATH", "");
+    assert_eq!(GitHubAuth::default().resolve_token(), None);
+}
+
+#[cfg(feature = "github-app")]
+mod mock_server;
+
+#[cfg(feature = "github-app")]
+#[test]
+fn test_github_app_installation_token() {
+    use mock_server::MockServer;
+    use rsa::pkcs1::EncodeRsaPrivateKey;
+    use rsa::pkcs1v15::{Signature, VerifyingKey};
+    use rsa::signature::Verifier;
+    use vnext::core::auth::{app_jwt, installation_token};
+
+    fn base64url_decode(value: &str) -> Vec<u8> {
+        const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+        let bits: Vec<u32> = value.bytes().map(|b| ALPHABET.iter().position(|&a| a == b).unwrap() as u32).collect();
+        let mut bytes = Vec::new();
+        for chunk in bits.chunks(4) {
+            let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | b << (18 - 6 * i));
+            bytes.extend(n.to_be_bytes()[1..chunk.len()].iter());
+        }
+        bytes
+    }
+
+    let key = rsa::RsaPrivateKey::new(&mut rsa::rand_core::OsRng, 1024).expect("Failed to generate an RSA key");
+    let pem = key.to_pkcs1_pem(rsa::pkcs1::LineEnding::LF).unwrap();
+
+    let jwt = app_jwt(&pem, "12345", 1_700_000_000).unwrap();
+    let parts: Vec<&str> = jwt.split('.').collect();
+    assert_eq!(parts.len(), 3);
+    let header: serde_json::Value = serde_json::from_slice(&base64url_decode(parts[0])).unwrap();
+    let claims: serde_json::Value = serde_json::from_slice(&base64url_decode(parts[1])).unwrap();
+    assert_eq!(header, serde_json::json!({ "alg": "RS256", "typ": "JWT" }));
+    assert_eq!(claims, serde_json::json!({ "iat": 1_699_999_940, "exp": 1_700_000_540, "iss": "12345" }));
+    let signature = Signature::try_from(base64url_decode(parts[2]).as_slice()).unwrap();
+    VerifyingKey::<rsa::sha2::Sha256>::new(key.to_public_key())
+        .verify(format!("{}.{}", parts[0], parts[1]).as_bytes(), &signature)
+        .expect("The token is signed with the app key");
+
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let key_path = temp_dir.path().join("app.pem");
+    std::fs::write(&key_path, pem.as_bytes()).unwrap();
+    let server = MockServer::start(|request| match request.path.as_str() {
+        "/app/installations/42/access_tokens" => (201, r#"{"token": "ghs_installation", "expires_at": "2030-01-01T00:00:00Z"}"#.to_string()),
+        _ => (404, r#"{"message": "Not Found"}"#.to_string()),
+    });
+    let credentials = GitHubAppCredentials { private_key: key_path, ..app() };
+    assert_eq!(installation_token(&credentials, &server.url).unwrap(), "ghs_installation");
+    let requests = server.requests.lock().unwrap();
+    assert_eq!(requests[0].method, "POST");
+    assert!(requests[0].header("Authorization").unwrap().starts_with("Bearer ey"));
+}
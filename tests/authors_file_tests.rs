@@ -0,0 +1,92 @@
+use std::fs;
+
+use vnext::core::authors::AuthorsFile;
+use vnext::version::{ChangesetSummary, Commit, CommitAuthor};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{run_and_show_command, run_vnext, run_vnext_output};
+
+const AUTHORS: &str = r#"[
+    {"email": "jane@example.com", "name": "Jane Doe", "username": "jane"},
+    {"email": "*@bots.example.com", "name": "Release Bot", "username": "release-bot"},
+    {"email": "*@example.com", "username": "example-team"}
+]"#;
+
+fn commit_by(id: &str, name: &str, email: &str, username: Option<&str>) -> Commit {
+    let mut commit = Commit::parse(id.to_string(), "fix: A fix".to_string());
+    commit.author = Some(CommitAuthor {
+        name: name.to_string(),
+        email: email.to_string(),
+        username: username.map(|u| u.to_string()),
+    });
+    commit
+}
+
+fn author(summary: &ChangesetSummary, index: usize) -> &CommitAuthor {
+    summary.commits[index].author.as_ref().unwrap()
+}
+
+#[test]
+fn test_exact_and_wildcard_entries() {
+    let authors = AuthorsFile::parse(AUTHORS).unwrap();
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(commit_by("c1", "jdoe", "JANE@example.com", None));
+    summary.commits.push(commit_by("c2", "ci", "deploy@bots.example.com", None));
+    summary.commits.push(commit_by("c3", "Sam", "sam@example.com", None));
+    summary.commits.push(commit_by("c4", "Alex", "alex@elsewhere.org", None));
+
+    authors.apply(&mut summary, false);
+
+    assert_eq!(author(&summary, 0).name, "Jane Doe", "Exact entries match case-insensitively");
+    assert_eq!(author(&summary, 0).username.as_deref(), Some("jane"));
+    assert_eq!(author(&summary, 1).username.as_deref(), Some("release-bot"), "Earlier globs win");
+    assert_eq!(author(&summary, 2).name, "Sam", "Entries without a name keep the signature name");
+    assert_eq!(author(&summary, 2).username.as_deref(), Some("example-team"));
+    assert_eq!(author(&summary, 3).name, "Alex", "Unmatched emails fall through unchanged");
+    assert_eq!(author(&summary, 3).username, None);
+}
+
+#[test]
+fn test_enrichment_wins_unless_authors_file_wins() {
+    let authors = AuthorsFile::parse(AUTHORS).unwrap();
+
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(commit_by("c1", "Jane", "jane@example.com", Some("jane-gh")));
+    authors.apply(&mut summary, false);
+    assert_eq!(author(&summary, 0).username.as_deref(), Some("jane-gh"));
+
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(commit_by("c1", "Jane", "jane@example.com", Some("jane-gh")));
+    authors.apply(&mut summary, true);
+    assert_eq!(author(&summary, 0).username.as_deref(), Some("jane"));
+}
+
+#[test]
+fn test_invalid_authors_file_is_a_config_error() {
+    let result = AuthorsFile::parse("{ not json");
+    assert!(matches!(result, Err(vnext::VNextError::ConfigError(_))));
+}
+
+#[test]
+fn test_changelog_uses_authors_file() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    run_and_show_command("git", &["init"], repo_path);
+    run_and_show_command("git", &["config", "user.name", "jdoe"], repo_path);
+    run_and_show_command("git", &["config", "user.email", "jane@example.com"], repo_path);
+    fs::write(repo_path.join("README.md"), "# Test Repository").expect("Failed to write README file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+    assert_eq!(run_vnext(repo_path), "0.1.0");
+
+    let out_dir = tempfile::tempdir().expect("Failed to create output directory");
+    let authors_path = out_dir.path().join("authors.json");
+    fs::write(&authors_path, AUTHORS).expect("Failed to write authors file");
+
+    let output = run_vnext_output(repo_path, &["--changelog", "--authors-file", authors_path.to_str().unwrap()]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(stdout.contains("* feat: Initial commit (by @jane)"), "{}", stdout);
+}
@@ -0,0 +1,67 @@
+use std::fs;
+use std::path::Path;
+
+use git2::Repository;
+use vnext::core::git::{find_trunk_branch, open_repository_at, working_tree_status};
+use vnext::VersionCalculator;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, run_and_show_command, run_vnext, run_vnext_with_args};
+
+/// Create a checkout at `<root>/work` released as 1.0.0 with a pending feature
+fn create_repo(root: &Path) -> std::path::PathBuf {
+    let repo_path = root.join("work");
+    fs::create_dir(&repo_path).expect("Failed to create repository directory");
+    run_and_show_command("git", &["init", "-b", "main"], &repo_path);
+    run_and_show_command("git", &["config", "user.name", "patrickleet"], &repo_path);
+    run_and_show_command("git", &["config", "user.email", "pat@patscott.io"], &repo_path);
+    commit_file(&repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], &repo_path);
+    fs::create_dir(repo_path.join("src")).expect("Failed to create src directory");
+    commit_file(&repo_path, "src/export.txt", "feat: Add export");
+    repo_path
+}
+
+#[test]
+fn test_bare_repository() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    create_repo(temp_dir.path());
+    run_and_show_command("git", &["clone", "--bare", "work", "repo.git"], temp_dir.path());
+    let bare_path = temp_dir.path().join("repo.git");
+    // Builds the binary
+    run_vnext(&bare_path);
+
+    assert_eq!(run_vnext_with_args(&bare_path, &[]), "1.1.0");
+    assert!(run_vnext_with_args(&bare_path, &["--changelog"]).contains("feat: Add export"));
+
+    let repo = open_repository_at(&bare_path).expect("Bare repositories should open");
+    assert!(repo.is_bare());
+    assert!(!working_tree_status(&repo).unwrap().is_dirty(false));
+    assert_eq!(find_trunk_branch(&repo).as_deref(), Some("main"));
+
+    let plan = VersionCalculator::for_repo(&bare_path).run().expect("Calculation should succeed");
+    assert_eq!(plan.next_version.to_string(), "1.1.0");
+}
+
+#[test]
+fn test_linked_worktree_and_subdirectory() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = create_repo(temp_dir.path());
+    // Builds the binary
+    run_vnext(&repo_path);
+
+    // Subdirectories of a checkout find the repository above them
+    assert_eq!(run_vnext_with_args(&repo_path.join("src"), &[]), "1.1.0");
+
+    run_and_show_command("git", &["worktree", "add", "-b", "fix-branch", "../linked"], &repo_path);
+    let worktree_path = temp_dir.path().join("linked");
+    commit_file(&worktree_path, "fix.txt", "fix: Handle empty files");
+
+    let repo = Repository::open(&worktree_path).unwrap();
+    assert!(repo.is_worktree());
+    assert_eq!(run_vnext_with_args(&worktree_path, &[]), "1.1.0");
+    assert!(run_vnext_with_args(&worktree_path, &["--changelog"]).contains("fix: Handle empty files"));
+    // The main checkout is unaffected by the worktree's branch
+    assert!(!run_vnext_with_args(&repo_path, &["--changelog"]).contains("fix: Handle empty files"));
+}
@@ -0,0 +1,72 @@
+use semver::Version;
+use vnext::changelog::{format_changelog_with_options, truncate_body, RepoInfo};
+use vnext::models::options::ChangelogOptions;
+use vnext::version::{ChangesetSummary, Commit};
+
+fn render(message: &str, options: &ChangelogOptions) -> String {
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(Commit::parse("abc123".to_string(), message.to_string()));
+    format_changelog_with_options(&summary, &Version::new(1, 1, 0), &Version::new(1, 0, 0), &RepoInfo::new(), options)
+}
+
+#[test]
+fn test_truncate_body() {
+    let body = "one\ntwo\nthree\nfour\nfive";
+    assert_eq!(truncate_body(body, 5), body, "Bodies within the limit are unchanged");
+    assert_eq!(truncate_body(body, 2), "one\ntwo\n\n... (3 more lines)");
+
+    // Blank lines before the cut are dropped
+    assert_eq!(truncate_body("one\n\nthree\nfour", 2), "one\n\n... (2 more lines)");
+}
+
+#[test]
+fn test_truncate_body_does_not_cut_inside_code_blocks() {
+    let body = "Summary\n\n```\nlog line 1\nlog line 2\nlog line 3\n```\nAfter";
+    // The cut moves before the opening fence
+    assert_eq!(truncate_body(body, 4), "Summary\n\n... (6 more lines)");
+    // A block closed before the cut is kept whole
+    assert_eq!(truncate_body(body, 7), "Summary\n\n```\nlog line 1\nlog line 2\nlog line 3\n```\n\n... (1 more line)");
+    // Fences only close with their own marker
+    let body = "~~~\n```\nstill code\n```\n~~~\nAfter";
+    assert_eq!(truncate_body(body, 4), "... (6 more lines)");
+    assert_eq!(truncate_body(body, 5), "~~~\n```\nstill code\n```\n~~~\n\n... (1 more line)");
+}
+
+#[test]
+fn test_changelog_body_limits() {
+    let message = "feat: Import PR description\n\n# Checklist\n\n- [x] Tests\n- [x] Docs\n- [x] Changelog\n\nSigned-off-by: Jane Smith <jane@example.com>";
+
+    // Trailers are stripped before counting, and headings are scaled after truncating
+    let options = ChangelogOptions { max_body_lines: Some(3), ..ChangelogOptions::default() };
+    assert_eq!(
+        render(message, &options),
+        "### What's changed in v1.1.0\n\n* feat: Import PR description\n\n  #### Checklist\n\n  - [x] Tests\n\n  ... (2 more lines)\n\n"
+    );
+
+    let options = ChangelogOptions { first_line_only: true, ..ChangelogOptions::default() };
+    assert_eq!(render(message, &options), "### What's changed in v1.1.0\n\n* feat: Import PR description\n\n");
+}
+
+#[test]
+fn test_breaking_note_is_never_truncated() {
+    let message = "feat: Rework the API\n\nOne\nTwo\nThree\n\nBREAKING CHANGE: The v1 endpoints are removed.";
+
+    let options = ChangelogOptions { max_body_lines: Some(2), no_highlight_breaking: true, ..ChangelogOptions::default() };
+    assert_eq!(
+        render(message, &options),
+        "### What's changed in v1.1.0\n\n* feat: Rework the API\n\n  One\n  Two\n\n  ... (1 more line)\n\n  \
+         BREAKING CHANGE: The v1 endpoints are removed.\n\n"
+    );
+
+    let options = ChangelogOptions { first_line_only: true, no_highlight_breaking: true, ..ChangelogOptions::default() };
+    assert_eq!(
+        render(message, &options),
+        "### What's changed in v1.1.0\n\n* feat: Rework the API\n\n  \
+         BREAKING CHANGE: The v1 endpoints are removed.\n\n"
+    );
+
+    // A note that is the whole body is kept in full as well
+    let message = "feat: Rework the API\n\nBREAKING CHANGE: One\nTwo\nThree";
+    let options = ChangelogOptions { max_body_lines: Some(1), no_highlight_breaking: true, ..ChangelogOptions::default() };
+    assert!(render(message, &options).ends_with("* feat: Rework the API\n\n  BREAKING CHANGE: One\n  Two\n  Three\n\n"));
+}
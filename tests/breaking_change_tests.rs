@@ -65,18 +65,36 @@ fn test_breaking_change_detection() {
     let tag_name = format!("v{}", version);
     run_and_show_command("git", &["tag", &tag_name], repo_path);
     
-    // 4. Add a commit with BREAKING CHANGE at the start of a line that is NOT the first line of the commit body
-    // This should NOT trigger a major version bump
+    // 4. Add a commit with a BREAKING CHANGE footer after the first paragraph of the commit body
+    // This SHOULD trigger a major version bump
     let file_path = repo_path.join("breaking-not-first-line.md");
     fs::write(&file_path, "# Breaking change not at first line").expect("Failed to write file");
     run_and_show_command("git", &["add", file_path.to_str().unwrap()], repo_path);
     run_and_show_command(
         "git",
-        &["commit", "-m", "feat: add another feature\n\nThis is the first line of the commit body.\n\nBREAKING CHANGE: This is not the first line of the body and should not trigger a major bump."],
+        &["commit", "-m", "feat: add another feature\n\nThis is the first line of the commit body.\n\nBREAKING CHANGE: This is a footer after the description and should trigger a major bump."],
         repo_path
     );
     
     let version = run_vnext(repo_path);
-    assert_eq!(version, "1.2.0", "Version should be 1.2.0 after BREAKING CHANGE not at first line");
-    println!("Asserted version {} is 1.2.0", version);
+    assert_eq!(version, "2.0.0", "Version should be 2.0.0 after a BREAKING CHANGE footer");
+    println!("Asserted version {} is 2.0.0", version);
+
+    let tag_name = format!("v{}", version);
+    run_and_show_command("git", &["tag", &tag_name], repo_path);
+
+    // 5. The hyphenated BREAKING-CHANGE footer is equivalent, even after other trailers
+    // This SHOULD trigger a major version bump
+    let file_path = repo_path.join("breaking-hyphenated-footer.md");
+    fs::write(&file_path, "# Hyphenated breaking change footer").expect("Failed to write file");
+    run_and_show_command("git", &["add", file_path.to_str().unwrap()], repo_path);
+    run_and_show_command(
+        "git",
+        &["commit", "-m", "fix: rework the config loader\n\nLoad the config lazily.\n\nReviewed-by: Jane Smith\nBREAKING-CHANGE: The config file is no longer read at startup."],
+        repo_path
+    );
+
+    let version = run_vnext(repo_path);
+    assert_eq!(version, "3.0.0", "Version should be 3.0.0 after a BREAKING-CHANGE footer");
+    println!("Asserted version {} is 3.0.0", version);
 }
\ No newline at end of file
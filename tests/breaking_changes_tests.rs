@@ -0,0 +1,94 @@
+use clap::Parser;
+use semver::Version;
+use vnext::changelog::{format_changelog_with_options, RepoInfo};
+use vnext::cli::Cli;
+use vnext::models::options::ChangelogOptions;
+use vnext::version::{ChangesetSummary, Commit};
+
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext, run_vnext_output, stdout};
+
+/// Summary of the commits given oldest first; the walk collects them newest first
+fn build_summary(messages: &[&str]) -> ChangesetSummary {
+    let mut summary = ChangesetSummary::new();
+    for (i, message) in messages.iter().enumerate().rev() {
+        summary.commits.push(Commit::parse(format!("c{}", i), message.to_string()));
+    }
+    summary
+}
+
+fn render(summary: &ChangesetSummary, options: &ChangelogOptions) -> String {
+    format_changelog_with_options(summary, &Version::new(2, 0, 0), &Version::new(1, 0, 0), &RepoInfo::new(), options)
+}
+
+#[test]
+fn test_breaking_changes_section() {
+    let summary = build_summary(&[
+        "refactor: Rename the config keys\n\nThe loader is simpler now.\n\nBREAKING CHANGE: `timeout` is now `request_timeout`.",
+        "fix: Handle empty payloads",
+        "feat(api)!: Drop the v1 endpoints",
+    ]);
+    assert_eq!(
+        render(&summary, &ChangelogOptions::default()),
+        concat!(
+            "### What's changed in v2.0.0\n\n",
+            "#### ⚠ Breaking changes\n\n",
+            "* refactor: Rename the config keys\n\n  `timeout` is now `request_timeout`.\n\n",
+            "* feat(api): Drop the v1 endpoints\n\n",
+            "* refactor: Rename the config keys\n\n  The loader is simpler now.\n\n",
+            "* fix: Handle empty payloads\n\n",
+            "* feat(api): Drop the v1 endpoints\n\n",
+        )
+    );
+}
+
+#[test]
+fn test_breaking_changes_section_can_be_disabled() {
+    let summary = build_summary(&["feat!: Drop the v1 endpoints"]);
+    let options = ChangelogOptions { no_highlight_breaking: true, ..ChangelogOptions::default() };
+    assert_eq!(render(&summary, &options), "### What's changed in v2.0.0\n\n* feat: Drop the v1 endpoints\n\n");
+
+    // Releases without breaking changes have no section
+    let summary = build_summary(&["feat: Add export"]);
+    assert_eq!(render(&summary, &ChangelogOptions::default()), "### What's changed in v2.0.0\n\n* feat: Add export\n\n");
+}
+
+#[test]
+fn test_highlight_breaking_flags() {
+    let disabled = |args: &[&str]| Cli::parse_from(args).to_options().changelog.no_highlight_breaking;
+    assert!(!disabled(&["vnext"]), "The section is shown by default");
+    assert!(disabled(&["vnext", "--no-highlight-breaking"]));
+    assert!(!disabled(&["vnext", "--no-highlight-breaking", "--highlight-breaking"]), "The last flag wins");
+}
+
+#[test]
+fn test_highlight_breaking_changes_the_output() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    commit_file(repo_path, "config.md", "refactor: Rename the config keys\n\nBREAKING CHANGE: `timeout` is now `request_timeout`.");
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let changelog = |args: &[&str]| stdout(&run_vnext_output(repo_path, &[&["--changelog"], args].concat()));
+    let highlighted = changelog(&["--no-highlight-breaking", "--highlight-breaking"]);
+    let inline = changelog(&["--no-highlight-breaking"]);
+    assert_ne!(highlighted, inline);
+    assert_eq!(changelog(&[]), highlighted, "The section is shown by default");
+
+    // Either way the note is rendered once
+    assert_eq!(
+        highlighted,
+        "### What's changed in v2.0.0\n\n\
+         #### ⚠ Breaking changes\n\n\
+         * refactor: Rename the config keys\n\n  `timeout` is now `request_timeout`.\n\n\
+         * refactor: Rename the config keys"
+    );
+    assert_eq!(
+        inline,
+        "### What's changed in v2.0.0\n\n\
+         * refactor: Rename the config keys\n\n  BREAKING CHANGE: `timeout` is now `request_timeout`."
+    );
+}
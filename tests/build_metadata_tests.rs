@@ -0,0 +1,75 @@
+use std::path::Path;
+
+use git2::Repository;
+use vnext::core::version::render_build_metadata;
+use vnext::git::find_latest_tag;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext, run_vnext_with_args};
+
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+}
+
+#[test]
+fn test_render_build_metadata() {
+    let sha = "a1b2c3d4e5f60718293a4b5c6d7e8f9012345678";
+    let metadata = render_build_metadata("build.{commits}.sha.{short-sha}", sha, 57, "2024-05-01").unwrap();
+    assert_eq!(metadata.as_str(), "build.57.sha.a1b2c3d");
+    let metadata = render_build_metadata("{date}.{sha}", sha, 0, "2024-05-01").unwrap();
+    assert_eq!(metadata.as_str(), format!("2024-05-01.{}", sha));
+
+    // Characters outside [0-9A-Za-z-], empty identifiers and unknown placeholders are rejected
+    assert!(render_build_metadata("build_{commits}", sha, 1, "2024-05-01").is_err());
+    assert!(render_build_metadata("build..{commits}", sha, 1, "2024-05-01").is_err());
+    let err = render_build_metadata("build.{branch}", sha, 1, "2024-05-01").unwrap_err();
+    assert!(err.contains("{short-sha}"), "The error lists the placeholders: {}", err);
+}
+
+#[test]
+fn test_latest_tag_ignores_build_metadata() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    commit_file(repo_path, "a.txt", "feat: First");
+    run_and_show_command("git", &["tag", "v1.2.0+build.1"], repo_path);
+    commit_file(repo_path, "b.txt", "feat: Second");
+    run_and_show_command("git", &["tag", "v1.10.0+build.2"], repo_path);
+    commit_file(repo_path, "c.txt", "feat: Third");
+    run_and_show_command("git", &["tag", "v1.9.0+build.99"], repo_path);
+
+    let repo = Repository::open(repo_path).expect("Failed to open repository");
+    let (tag, _) = find_latest_tag(&repo).expect("A release tag should be found");
+    assert_eq!(tag, "v1.10.0+build.2");
+}
+
+#[test]
+fn test_build_metadata_output() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.3.0"], repo_path);
+    commit_file(repo_path, "a.txt", "feat: Add export");
+    commit_file(repo_path, "b.txt", "fix: Handle empty files");
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let head = Repository::open(repo_path).unwrap().head().unwrap().peel_to_commit().unwrap().id().to_string();
+    let version = run_vnext_with_args(repo_path, &["--build-metadata", "build.{commits}.sha.{short-sha}"]);
+    assert_eq!(version, format!("1.4.0+build.2.sha.{}", &head[..7]));
+
+    let version = run_vnext_with_args(repo_path, &["--build-metadata", "d.{date}", "--release-date", "2024-05-01"]);
+    assert_eq!(version, "1.4.0+d.2024-05-01");
+
+    // The changelog heading keeps the bare version
+    let changelog = run_vnext_with_args(repo_path, &["--build-metadata", "build.{commits}", "--changelog"]);
+    assert!(changelog.contains("What's changed in v1.4.0\n"), "{}", changelog);
+
+    // --current shows the bare tag version
+    assert_eq!(run_vnext_with_args(repo_path, &["--current", "--build-metadata", "build.{commits}"]), "1.3.0");
+
+    // Invalid templates fail argument parsing
+    assert_eq!(run_vnext_with_args(repo_path, &["--build-metadata", "build_{commits}"]), "");
+}
@@ -0,0 +1,52 @@
+use semver::Version;
+use vnext::core::version::{record_bump, VersionBumpType};
+use vnext::version::{calculate_next_version, BumpLevel, ChangesetSummary, VersionBump};
+
+#[test]
+fn test_bump_level_ordering() {
+    assert!(BumpLevel::None < BumpLevel::Patch);
+    assert!(BumpLevel::Patch < BumpLevel::Minor);
+    assert!(BumpLevel::Minor < BumpLevel::Major);
+
+    let levels = [BumpLevel::Patch, BumpLevel::None, BumpLevel::Major, BumpLevel::Minor];
+    assert_eq!(levels.iter().max(), Some(&BumpLevel::Major));
+    assert_eq!(levels.iter().min(), Some(&BumpLevel::None));
+    assert_eq!(BumpLevel::Patch.max(BumpLevel::Minor), BumpLevel::Minor);
+}
+
+#[test]
+fn test_version_bump_shim() {
+    for level in [BumpLevel::None, BumpLevel::Patch, BumpLevel::Minor, BumpLevel::Major] {
+        let bump = VersionBump::from(level);
+        assert_eq!(bump.bump_level(), level);
+        assert_eq!(bump.level(), level.as_str());
+    }
+    assert_eq!(VersionBump::from(BumpLevel::Minor), VersionBump { major: false, minor: true, patch: false });
+
+    // Lower flags set alongside a higher one do not change the level
+    let bump = VersionBump { major: true, minor: true, patch: true };
+    assert_eq!(bump.bump_level(), BumpLevel::Major);
+}
+
+#[test]
+fn test_record_bump_keeps_highest_level() {
+    let mut level = BumpLevel::None;
+    let mut summary = ChangesetSummary::new();
+    record_bump(&mut level, &mut summary, VersionBumpType::Patch, "a");
+    assert_eq!(level, BumpLevel::Patch);
+    record_bump(&mut level, &mut summary, VersionBumpType::Minor, "b");
+    record_bump(&mut level, &mut summary, VersionBumpType::Patch, "c");
+    record_bump(&mut level, &mut summary, VersionBumpType::NoOp, "d");
+    assert_eq!(level, BumpLevel::Minor, "Lower bumps never lower the level");
+    assert_eq!((summary.minor, summary.patch, summary.noop), (1, 2, 1));
+}
+
+#[test]
+fn test_calculate_next_version_by_level() {
+    let current = Version::parse("1.2.3-rc.1+build.5").unwrap();
+    let next = |level| calculate_next_version(&current, &VersionBump::from(level)).to_string();
+    assert_eq!(next(BumpLevel::None), "1.2.3");
+    assert_eq!(next(BumpLevel::Patch), "1.2.4");
+    assert_eq!(next(BumpLevel::Minor), "1.3.0");
+    assert_eq!(next(BumpLevel::Major), "2.0.0");
+}
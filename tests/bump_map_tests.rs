@@ -0,0 +1,90 @@
+use clap::Parser;
+use vnext::cli::Cli;
+use vnext::version::{classify_commit, parse_bump_map, BumpLevel, BumpMap};
+use vnext::core::version::VersionBumpType;
+use vnext::Commit;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext, run_vnext_with_args};
+
+fn classify(message: &str, bump_map: &BumpMap) -> VersionBumpType {
+    classify_commit(&Commit::parse("1".to_string(), message.to_string()), bump_map)
+}
+
+#[test]
+fn test_parse_bump_map() {
+    let levels = parse_bump_map("feat=minor, FIX=patch,perf=patch,docs=none,chore=noop,major=major").unwrap();
+    assert_eq!(levels.len(), 6);
+    assert_eq!(levels["feat"], BumpLevel::Minor);
+    assert_eq!(levels["fix"], BumpLevel::Patch, "Types are lowercased");
+    assert_eq!(levels["docs"], BumpLevel::None);
+    assert_eq!(levels["chore"], BumpLevel::None, "noop is an alias of none");
+    assert_eq!(levels["major"], BumpLevel::Major);
+
+    assert!(parse_bump_map("feat").unwrap_err().contains("type=level"));
+    assert!(parse_bump_map("=minor").is_err());
+    assert!(parse_bump_map("feat=huge").unwrap_err().contains("huge"));
+}
+
+#[test]
+fn test_classify_commit() {
+    let mut bump_map = BumpMap::default();
+    bump_map.extend(parse_bump_map("perf=patch,refactor=patch,docs=none,test=none,ci=none,style=none").unwrap());
+
+    assert_eq!(classify("feat: Add thing", &bump_map), VersionBumpType::Minor);
+    assert_eq!(classify("perf: Faster", &bump_map), VersionBumpType::Patch);
+    assert_eq!(classify("Docs: Typo", &bump_map), VersionBumpType::NoOp);
+    assert_eq!(classify("ci: Cache builds", &bump_map), VersionBumpType::NoOp);
+    assert_eq!(classify("docs!: Drop the old guide", &bump_map), VersionBumpType::Major, "Breaking changes are always major");
+    assert_eq!(classify("build: Unmapped", &bump_map), VersionBumpType::Patch, "Unmapped types bump the patch version");
+
+    // The type lists are sugar for a bump map
+    let from_types = BumpMap::from_types(&["major", "breaking"], &["feat", "minor"], &["chore", "noop"]);
+    assert_eq!(from_types, BumpMap::default());
+    assert_eq!(from_types.types_at(BumpLevel::None), vec!["chore", "noop"]);
+}
+
+#[test]
+fn test_bump_map_precedence() {
+    // Without --bump-map the type lists populate the map
+    let cli = Cli::parse_from(["vnext", "--minor-commit-types", "feat,perf"]);
+    let bump_map = cli.bump_map();
+    assert_eq!(bump_map.level("perf"), Some(BumpLevel::Minor));
+    assert_eq!(bump_map.level("minor"), None, "A given type list replaces its default");
+    assert_eq!(bump_map.level("chore"), Some(BumpLevel::None));
+
+    // --bump-map wins over the type lists for the types it maps
+    let cli = Cli::parse_from([
+        "vnext",
+        "--minor-commit-types", "feat,perf",
+        "--noop-commit-types", "chore,docs",
+        "--bump-map", "perf=patch,docs=minor,style=none",
+    ]);
+    let bump_map = cli.bump_map();
+    assert_eq!(bump_map.level("feat"), Some(BumpLevel::Minor));
+    assert_eq!(bump_map.level("perf"), Some(BumpLevel::Patch));
+    assert_eq!(bump_map.level("docs"), Some(BumpLevel::Minor));
+    assert_eq!(bump_map.level("chore"), Some(BumpLevel::None));
+    assert_eq!(bump_map.level("style"), Some(BumpLevel::None));
+
+    assert!(Cli::try_parse_from(["vnext", "--bump-map", "feat=huge"]).is_err());
+}
+
+#[test]
+fn test_bump_map_version() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    commit_file(repo_path, "docs.md", "docs: Explain the bump map");
+    // Builds the binary
+    run_vnext(repo_path);
+
+    assert_eq!(run_vnext_with_args(repo_path, &[]), "1.0.1", "Unmapped docs commits bump the patch version");
+    assert_eq!(run_vnext_with_args(repo_path, &["--bump-map", "docs=none"]), "1.0.0");
+
+    commit_file(repo_path, "perf.txt", "perf: Cache lookups");
+    assert_eq!(run_vnext_with_args(repo_path, &["--bump-map", "docs=none,perf=minor"]), "1.1.0");
+}
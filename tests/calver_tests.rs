@@ -0,0 +1,119 @@
+use semver::Version;
+use std::path::Path;
+use vnext::core::version::{calculate_next_version_with_scheme, VersionParser};
+use vnext::version::{BumpLevel, CalverFormat, CalverYear, VersionBump, VersionScheme};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext, run_vnext_success};
+
+fn calver() -> VersionScheme {
+    VersionScheme::Calver(CalverFormat::default())
+}
+
+fn next(current: &str, level: BumpLevel, date: &str) -> String {
+    let current = Version::parse(current).unwrap();
+    let scheme = calver();
+    scheme.render(&calculate_next_version_with_scheme(&current, &VersionBump::from(level), &scheme, date))
+}
+
+#[test]
+fn test_scheme_parsing() {
+    assert_eq!(VersionScheme::parse("semver").unwrap(), VersionScheme::Semver);
+    assert_eq!(VersionScheme::parse("calver").unwrap(), calver());
+    assert_eq!(
+        VersionScheme::parse("calver:YY.MM.MICRO").unwrap(),
+        VersionScheme::Calver(CalverFormat { year: CalverYear::Short, padded_month: false })
+    );
+    assert_eq!(CalverFormat::parse("0Y.0M.MICRO").unwrap().to_string(), "0Y.0M.MICRO");
+    assert!(VersionScheme::parse("calver:YYYY.MICRO").is_err());
+    assert!(VersionScheme::parse("calver:YYYY.WW.MICRO").is_err());
+    assert!(VersionScheme::parse("romver").is_err());
+}
+
+#[test]
+fn test_same_month_increments_micro() {
+    assert_eq!(next("2026.1.0", BumpLevel::Patch, "2026-01-20"), "2026.01.1");
+    // Major and minor bumps make no difference
+    assert_eq!(next("2026.1.3", BumpLevel::Major, "2026-01-31"), "2026.01.4");
+}
+
+#[test]
+fn test_month_rollover_resets_micro() {
+    assert_eq!(next("2026.1.3", BumpLevel::Minor, "2026-02-01"), "2026.02.0");
+    assert_eq!(next("2025.12.7", BumpLevel::Patch, "2026-01-05"), "2026.01.0");
+    // The first release of a repository
+    assert_eq!(next("0.0.0", BumpLevel::Patch, "2026-03-10"), "2026.03.0");
+}
+
+#[test]
+fn test_no_bump_keeps_the_version() {
+    assert_eq!(next("2026.1.3", BumpLevel::None, "2026-02-01"), "2026.01.3");
+}
+
+#[test]
+fn test_short_year_formats() {
+    let scheme = VersionScheme::parse("calver:0Y.MM.MICRO").unwrap();
+    let version = calculate_next_version_with_scheme(
+        &Version::new(5, 12, 1), &VersionBump::from(BumpLevel::Patch), &scheme, "2006-01-15",
+    );
+    assert_eq!(scheme.render(&version), "06.1.0");
+}
+
+#[test]
+fn test_calver_tags_parse_with_the_calver_scheme() {
+    let parser = VersionParser::new().with_scheme(calver());
+    assert_eq!(parser.parse("v2026.01.3").unwrap(), Version::new(2026, 1, 3));
+    assert_eq!(parser.parse("2026.10.0-rc.1").unwrap(), Version::parse("2026.10.0-rc.1").unwrap());
+    // Semver does not allow the zero padding
+    assert!(VersionParser::new().parse("v2026.01.3").is_err());
+}
+
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+}
+
+#[test]
+fn test_calver_releases_with_a_frozen_date() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    run_vnext(repo_path);
+
+    let calver = |date: &str, extra: &[&str]| {
+        let mut args = vec!["--scheme", "calver", "--release-date", date];
+        args.extend_from_slice(extra);
+        run_vnext_success(repo_path, &args)
+    };
+    assert_eq!(calver("2026-01-15", &[]), "2026.01.0");
+
+    run_and_show_command("git", &["tag", "v2026.01.0"], repo_path);
+    assert_eq!(calver("2026-01-15", &["--current"]), "2026.01.0");
+    commit_file(repo_path, "a.md", "fix: Fix a");
+    assert_eq!(calver("2026-01-20", &[]), "2026.01.1");
+    assert_eq!(calver("2026-02-02", &[]), "2026.02.0");
+
+    // The newer calendar tag wins over the older one, and semver tags sort below both
+    run_and_show_command("git", &["tag", "v2026.01.1"], repo_path);
+    run_and_show_command("git", &["tag", "v1.9.0", "HEAD~1"], repo_path);
+    commit_file(repo_path, "b.md", "feat!: Drop the old API");
+    assert_eq!(calver("2026-01-31", &[]), "2026.01.2");
+
+    // Breaking changes do not change the version shape but are still surfaced
+    let changelog = calver("2026-01-31", &["--changelog"]);
+    assert!(changelog.starts_with("### What's changed in v2026.01.2"), "{}", changelog);
+    assert!(changelog.contains("Breaking changes"), "{}", changelog);
+
+    let json: serde_json::Value = serde_json::from_str(&calver("2026-02-01", &["--format", "json"])).unwrap();
+    assert_eq!(json["version"], "2026.02.0");
+    assert_eq!(json["current_version"], "2026.01.1");
+
+    // The provenance names the tag the release gets, padding included
+    let provenance_path = repo_path.join("provenance.json");
+    calver("2026-02-01", &["--provenance-out", provenance_path.to_str().unwrap()]);
+    let provenance: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&provenance_path).unwrap()).unwrap();
+    assert_eq!(provenance["tag"], "v2026.02.0");
+    assert_eq!(provenance["previous_tag"], "v2026.01.1");
+    assert_eq!(provenance["next_version"], "2026.02.0");
+}
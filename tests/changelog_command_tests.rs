@@ -0,0 +1,173 @@
+use std::fs;
+use std::process::Command;
+
+use semver::Version;
+use vnext::changelog::{format_changelog_with_options, RepoInfo};
+use vnext::models::options::ChangelogOptions;
+use vnext::version::{ChangesetSummary, Commit};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext};
+
+fn create_tagged_repo(repo_path: &std::path::Path) {
+    init_repo(repo_path);
+
+    fs::write(repo_path.join("README.md"), "# Test Repository").expect("Failed to write README file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+}
+
+fn run_changelog(repo_path: &std::path::Path, args: &[&str]) -> String {
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let binary_path = project_dir.join("target/debug/vnext");
+    let output = Command::new(&binary_path)
+        .arg("changelog")
+        .args(args)
+        .current_dir(repo_path)
+        .output()
+        .expect("Failed to execute vnext changelog");
+    assert!(output.status.success(), "changelog failed: {}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn test_unreleased_lists_commits_since_last_tag() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_tagged_repo(repo_path);
+    // Make sure the binary is built
+    assert_eq!(run_vnext(repo_path), "1.0.0");
+    commit_file(repo_path, "export.md", "feat: Add export");
+    commit_file(repo_path, "fix.md", "fix: Handle empty input");
+
+    let stdout = run_changelog(repo_path, &["--unreleased"]);
+    assert!(stdout.starts_with("### Unreleased\n"), "{}", stdout);
+    assert!(stdout.contains("* feat: Add export"), "{}", stdout);
+    assert!(stdout.contains("* fix: Handle empty input"), "{}", stdout);
+    assert!(!stdout.contains("Initial commit"), "Released commits are not listed: {}", stdout);
+    assert!(!stdout.contains("1.1.0"), "No version is calculated: {}", stdout);
+
+    let stdout = run_changelog(repo_path, &["--unreleased", "--heading", "## [Unreleased]"]);
+    assert!(stdout.starts_with("## [Unreleased]\n"), "{}", stdout);
+
+    let stdout = run_changelog(repo_path, &["--unreleased", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(stdout.trim()).expect("Output should be JSON");
+    assert_eq!(json["next_version"], serde_json::Value::Null);
+    assert_eq!(json["current_version"], "1.0.0");
+    let titles: Vec<&str> = json["commits"].as_array().unwrap().iter().map(|c| c["title"].as_str().unwrap()).collect();
+    assert_eq!(titles, vec!["Handle empty input", "Add export"]);
+}
+
+#[test]
+fn test_unreleased_without_commits_succeeds() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_tagged_repo(repo_path);
+    assert_eq!(run_vnext(repo_path), "1.0.0");
+
+    assert_eq!(run_changelog(repo_path, &["--unreleased"]).trim(), "No unreleased changes");
+
+    let stdout = run_changelog(repo_path, &["--unreleased", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(stdout.trim()).expect("Output should be JSON");
+    assert_eq!(json["commits"], serde_json::json!([]));
+    assert_eq!(json["changelog"], serde_json::Value::Null);
+}
+
+#[test]
+fn test_changelog_renders_next_release() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_tagged_repo(repo_path);
+    assert_eq!(run_vnext(repo_path), "1.0.0");
+    commit_file(repo_path, "export.md", "feat: Add export");
+
+    let stdout = run_changelog(repo_path, &[]);
+    assert!(stdout.starts_with("### What's changed in v1.1.0"), "{}", stdout);
+
+    let stdout = run_changelog(repo_path, &["--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(stdout.trim()).expect("Output should be JSON");
+    assert_eq!(json["next_version"], "1.1.0");
+}
+
+#[test]
+fn test_unreleased_notes_have_no_compare_link() {
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(Commit::parse("commit1".to_string(), "fix: A fix".to_string()));
+
+    let mut repo_info = RepoInfo::new();
+    repo_info.owner = "unbounded-tech".to_string();
+    repo_info.name = "vnext".to_string();
+    repo_info.is_github_repo = true;
+
+    let options = ChangelogOptions {
+        heading: Some("### Unreleased".to_string()),
+        ..ChangelogOptions::default()
+    };
+    let version = Version::new(1, 2, 0);
+    let changelog = format_changelog_with_options(&summary, &version, &version, &repo_info, &options);
+    assert!(!changelog.contains("See full diff"), "{}", changelog);
+}
+
+fn run_changelog_failing(repo_path: &std::path::Path, args: &[&str]) -> String {
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let output = Command::new(project_dir.join("target/debug/vnext"))
+        .arg("changelog")
+        .args(args)
+        .current_dir(repo_path)
+        .output()
+        .expect("Failed to execute vnext changelog");
+    assert!(!output.status.success(), "changelog should fail: {}", String::from_utf8_lossy(&output.stdout));
+    String::from_utf8_lossy(&output.stderr).to_string()
+}
+
+fn create_repo_with_three_releases(repo_path: &std::path::Path) {
+    create_tagged_repo(repo_path);
+    commit_file(repo_path, "export.md", "feat: Add export");
+    commit_file(repo_path, "fix.md", "fix: Handle empty input");
+    run_and_show_command("git", &["tag", "v1.1.0"], repo_path);
+    commit_file(repo_path, "api.md", "feat!: Replace the API");
+    run_and_show_command("git", &["tag", "v2.0.0"], repo_path);
+    commit_file(repo_path, "docs.md", "docs: Pending notes");
+}
+
+#[test]
+fn test_for_tag_regenerates_notes_of_that_release() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo_with_three_releases(repo_path);
+    assert_eq!(run_vnext(repo_path), "2.0.1", "docs commits bump the patch version");
+
+    let stdout = run_changelog(repo_path, &["--for-tag", "v1.1.0"]);
+    assert!(stdout.starts_with("### What's changed in v1.1.0\n"), "{}", stdout);
+    assert!(stdout.contains("* feat: Add export") && stdout.contains("* fix: Handle empty input"), "{}", stdout);
+    for other in ["Initial commit", "Replace the API", "Pending notes"] {
+        assert!(!stdout.contains(other), "Only commits of v1.1.0 are listed, found {}: {}", other, stdout);
+    }
+
+    let stdout = run_changelog(repo_path, &["--for-tag", "v1.1.0", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(stdout.trim()).expect("Output should be JSON");
+    assert_eq!(json["next_version"], "1.1.0");
+    assert_eq!(json["current_version"], "1.0.0");
+    assert_eq!(json["previous_tag"], "v1.0.0");
+    assert_eq!(json["commits"].as_array().unwrap().len(), 2);
+
+    // The first release lists the whole history up to its tag
+    let stdout = run_changelog(repo_path, &["--for-tag", "v1.0.0"]);
+    assert!(stdout.starts_with("### What's changed in v1.0.0\n") && stdout.contains("* feat: Initial commit"), "{}", stdout);
+    assert!(!stdout.contains("Add export"), "{}", stdout);
+}
+
+#[test]
+fn test_for_tag_rejects_unknown_tags() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo_with_three_releases(repo_path);
+    run_and_show_command("git", &["tag", "nightly"], repo_path);
+
+    let stderr = run_changelog_failing(repo_path, &["--for-tag", "v9.9.9"]);
+    assert!(stderr.contains("Tag v9.9.9 not found"), "{}", stderr);
+    let stderr = run_changelog_failing(repo_path, &["--for-tag", "nightly"]);
+    assert!(stderr.contains("Tag nightly is not a release tag"), "{}", stderr);
+}
@@ -0,0 +1,57 @@
+use semver::Version;
+use vnext::changelog::{filter_changelog_commits, format_changelog, RepoInfo};
+use vnext::version::{ChangesetSummary, Commit};
+
+fn default_noop_types() -> Vec<&'static str> {
+    vec!["chore", "noop"]
+}
+
+fn build_summary(messages: &[&str]) -> ChangesetSummary {
+    let mut summary = ChangesetSummary::new();
+    for (i, message) in messages.iter().enumerate() {
+        summary.commits.push(Commit::parse(format!("commit{}", i), message.to_string()));
+    }
+    summary
+}
+
+#[test]
+fn test_exclude_noop_commits_from_changelog() {
+    let mut summary = build_summary(&["feat: Add feature", "chore: Update dependencies", "fix: Fix a bug", "noop: Nothing"]);
+    summary.noop = 2;
+
+    filter_changelog_commits(&mut summary, true, &default_noop_types(), &[]);
+
+    let types: Vec<&str> = summary.commits.iter().map(|c| c.commit_type.as_str()).collect();
+    assert_eq!(types, vec!["feat", "fix"], "No-op commits should be removed from the changelog");
+    assert_eq!(summary.noop, 2, "No-op commits should still be counted");
+}
+
+#[test]
+fn test_exclude_noop_keeps_breaking_changes() {
+    let mut summary = build_summary(&["chore!: Drop node 14", "chore: Update dependencies"]);
+
+    filter_changelog_commits(&mut summary, true, &default_noop_types(), &[]);
+
+    assert_eq!(summary.commits.len(), 1);
+    assert!(summary.commits[0].has_breaking_change, "Breaking no-op commits should be kept");
+}
+
+#[test]
+fn test_include_types_allow_list() {
+    let mut summary = build_summary(&["feat: Add feature", "chore: Update dependencies", "fix: Fix a bug", "docs: Update README"]);
+
+    filter_changelog_commits(&mut summary, false, &default_noop_types(), &["feat", "fix"]);
+
+    let types: Vec<&str> = summary.commits.iter().map(|c| c.commit_type.as_str()).collect();
+    assert_eq!(types, vec!["feat", "fix"], "Only allowed types should remain");
+}
+
+#[test]
+fn test_no_changes_when_everything_is_filtered() {
+    let mut summary = build_summary(&["chore: Update dependencies", "noop: Nothing"]);
+
+    filter_changelog_commits(&mut summary, true, &default_noop_types(), &[]);
+
+    let changelog = format_changelog(&summary, &Version::new(1, 0, 1), false, &Version::new(1, 0, 0), &RepoInfo::new());
+    assert_eq!(changelog, "### What's changed in v1.0.1\n\n* No changes\n");
+}
@@ -96,4 +96,50 @@ fn test_changelog_formatting() {
     // Tag the version
     let tag_name = format!("v{}", version);
     run_and_show_command("git", &["tag", &tag_name], repo_path);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_crlf_and_empty_messages() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    run_and_show_command("git", &["init"], repo_path);
+    run_and_show_command("git", &["config", "user.name", "patrickleet"], repo_path);
+    run_and_show_command("git", &["config", "user.email", "pat@patscott.io"], repo_path);
+    fs::write(repo_path.join("README.md"), "# Test").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "fix: Initial commit"], repo_path);
+
+    // A commit authored on Windows, kept verbatim so the CRLF line endings survive
+    fs::write(repo_path.join("windows.md"), "# Windows").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command(
+        "git",
+        &["commit", "--cleanup=verbatim", "-m", "feat: Add export  \r\n\r\nExports to CSV.   \r\n \r\nBREAKING CHANGE: The old export is removed.\r\n"],
+        repo_path
+    );
+
+    // A commit with an empty message
+    fs::write(repo_path.join("empty.md"), "# Empty").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "--allow-empty-message", "-m", ""], repo_path);
+
+    // The breaking change footer is still detected
+    let version = run_vnext(repo_path);
+    assert_eq!(version, "1.0.0");
+
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let binary_path = project_dir.join("target/debug/vnext");
+    let output = Command::new(&binary_path)
+        .args(["--changelog", "--no-highlight-breaking"])
+        .current_dir(&repo_path)
+        .output()
+        .expect("Failed to execute vnext with --changelog");
+    assert!(output.status.success());
+    let changelog = String::from_utf8_lossy(&output.stdout).to_string();
+    println!("Changelog output:\n{}", changelog);
+
+    assert!(!changelog.contains('\r'), "Changelog should not contain carriage returns");
+    assert!(changelog.contains("* feat: Add export\n"));
+    assert!(changelog.contains("\n  Exports to CSV.\n\n  BREAKING CHANGE: The old export is removed."));
+}
@@ -0,0 +1,88 @@
+use semver::Version;
+use vnext::changelog::{format_changelog, format_changelog_with_options, RepoInfo};
+use vnext::models::options::ChangelogOptions;
+use vnext::utils::date::{format_epoch_date, is_iso_date};
+use vnext::version::{ChangesetSummary, Commit};
+
+fn sample_summary() -> ChangesetSummary {
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(Commit::parse("abc123".to_string(), "feat: Add new feature".to_string()));
+    summary
+}
+
+#[test]
+fn test_default_heading_is_unchanged() {
+    let summary = sample_summary();
+    let next = Version::new(1, 2, 0);
+    let current = Version::new(1, 1, 0);
+    let repo_info = RepoInfo::new();
+
+    let legacy = format_changelog(&summary, &next, false, &current, &repo_info);
+    let with_options = format_changelog_with_options(&summary, &next, &current, &repo_info, &ChangelogOptions::default());
+
+    assert_eq!(legacy, "### What's changed in v1.2.0\n\n* feat: Add new feature\n\n");
+    assert_eq!(legacy, with_options, "Default options should render byte-identical output");
+}
+
+#[test]
+fn test_heading_placeholders() {
+    let summary = sample_summary();
+    let options = ChangelogOptions {
+        heading: Some("## [{version}] - {date} (since {previous_version})".to_string()),
+        release_date: Some("2025-01-10".to_string()),
+        ..ChangelogOptions::default()
+    };
+
+    let changelog = format_changelog_with_options(&summary, &Version::new(1, 2, 0), &Version::new(1, 1, 0), &RepoInfo::new(), &options);
+
+    assert!(changelog.starts_with("## [1.2.0] - 2025-01-10 (since 1.1.0)\n\n"), "Placeholders should be substituted: {}", changelog);
+}
+
+#[test]
+fn test_date_placeholder_defaults_to_today() {
+    let options = ChangelogOptions {
+        heading: Some("{date}".to_string()),
+        ..ChangelogOptions::default()
+    };
+
+    let changelog = format_changelog_with_options(&sample_summary(), &Version::new(1, 0, 0), &Version::new(0, 9, 0), &RepoInfo::new(), &options);
+    let heading = changelog.lines().next().unwrap();
+
+    assert!(is_iso_date(heading), "Date placeholder should render an ISO date, got {}", heading);
+}
+
+#[test]
+fn test_epoch_date_formatting() {
+    assert_eq!(format_epoch_date(0), "1970-01-01");
+    assert_eq!(format_epoch_date(951_782_400), "2000-02-29");
+    assert_eq!(format_epoch_date(1_736_467_200), "2025-01-10");
+    assert!(!is_iso_date("2025-1-10"));
+    assert!(!is_iso_date("2025-13-01"));
+}
+
+#[test]
+fn test_commits_are_listed_oldest_first() {
+    // The revwalk collects commits newest first
+    let mut summary = ChangesetSummary::new();
+    for (id, message) in [("c3", "fix: Third\n\nBody of the third"), ("c2", "feat(api): Second"), ("c1", "chore: First")] {
+        summary.commits.push(Commit::parse(id.to_string(), message.to_string()));
+    }
+
+    let changelog = format_changelog(&summary, &Version::new(1, 1, 0), false, &Version::new(1, 0, 0), &RepoInfo::new());
+
+    let position = |line: &str| changelog.find(line).unwrap_or_else(|| panic!("Missing {}: {}", line, changelog));
+    assert!(changelog.starts_with("### What's changed in v1.1.0\n\n* chore: First\n\n* feat(api): Second\n"), "{}", changelog);
+    assert!(position("* feat(api): Second") < position("* fix: Third"));
+    assert!(position("* fix: Third") < position("Body of the third"));
+    assert_eq!(summary.commits[0].commit_id, "c3", "Formatting leaves the summary untouched");
+}
+
+#[test]
+fn test_free_form_commit_uses_subject() {
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(Commit::parse("def456".to_string(), "updated stuff\n\nMore details".to_string()));
+    assert_eq!(summary.commits[0].display_title(), "updated stuff");
+
+    let changelog = format_changelog_with_options(&summary, &Version::new(1, 0, 1), &Version::new(1, 0, 0), &RepoInfo::new(), &ChangelogOptions::default());
+    assert_eq!(changelog, "### What's changed in v1.0.1\n\n* updated stuff\n\n");
+}
@@ -0,0 +1,98 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use clap::Parser;
+use vnext::cli::Cli;
+use vnext::models::options::ChangelogOrder;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{run_and_show_command, run_vnext, run_vnext_with_args};
+
+/// Commit with both the author and committer date set to `time` (seconds since the epoch)
+fn commit_at(repo_path: &Path, file: &str, message: &str, time: i64) {
+    fs::write(repo_path.join(file), message).expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    git_at(repo_path, &["commit", "-m", message], time);
+}
+
+fn git_at(repo_path: &Path, args: &[&str], time: i64) {
+    let date = format!("@{} +0000", time);
+    let status = Command::new("git")
+        .args(args)
+        .env("GIT_AUTHOR_DATE", &date)
+        .env("GIT_COMMITTER_DATE", &date)
+        .current_dir(repo_path)
+        .status()
+        .expect("Failed to run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+/// Titles of the changelog entries, in order
+fn entries(changelog: &str) -> Vec<String> {
+    changelog
+        .lines()
+        .filter_map(|line| line.strip_prefix("* "))
+        .filter(|line| !line.starts_with("...and"))
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn test_changelog_order_flag() {
+    assert_eq!(Cli::parse_from(["vnext"]).to_options().changelog.order, None);
+    assert_eq!(Cli::parse_from(["vnext", "--changelog-order", "type"]).to_options().changelog.order, Some(ChangelogOrder::Type));
+    assert!(Cli::try_parse_from(["vnext", "--changelog-order", "alphabetical"]).is_err());
+}
+
+#[test]
+fn test_changelog_order_with_merged_history() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    run_and_show_command("git", &["init", "-b", "main"], repo_path);
+    run_and_show_command("git", &["config", "user.name", "patrickleet"], repo_path);
+    run_and_show_command("git", &["config", "user.email", "pat@patscott.io"], repo_path);
+    commit_at(repo_path, "README.md", "feat: Initial commit", 1_000);
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+
+    // A feature branch and main interleave in time before the branch is merged
+    run_and_show_command("git", &["checkout", "-b", "feature"], repo_path);
+    commit_at(repo_path, "a.txt", "feat: Branch feature", 2_000);
+    commit_at(repo_path, "b.txt", "fix: Branch fix", 4_000);
+    run_and_show_command("git", &["checkout", "main"], repo_path);
+    commit_at(repo_path, "c.txt", "docs: Main docs", 3_000);
+    commit_at(repo_path, "d.txt", "fix!: Main breaking fix", 5_000);
+    git_at(repo_path, &["merge", "--no-ff", "-m", "chore: Merge feature", "feature"], 6_000);
+    commit_at(repo_path, "e.txt", "feat: Main feature", 7_000);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let changelog = run_vnext_with_args(repo_path, &["--changelog", "--changelog-order", "chronological", "--no-highlight-breaking"]);
+    assert_eq!(
+        entries(&changelog),
+        vec!["feat: Branch feature", "docs: Main docs", "fix: Branch fix", "fix: Main breaking fix", "chore: Merge feature", "feat: Main feature"],
+        "{}", changelog
+    );
+
+    let changelog = run_vnext_with_args(repo_path, &["--changelog", "--changelog-order", "reverse", "--no-highlight-breaking"]);
+    assert_eq!(
+        entries(&changelog),
+        vec!["feat: Main feature", "chore: Merge feature", "fix: Main breaking fix", "fix: Branch fix", "docs: Main docs", "feat: Branch feature"],
+        "{}", changelog
+    );
+
+    let changelog = run_vnext_with_args(repo_path, &["--changelog", "--changelog-order", "type", "--no-highlight-breaking"]);
+    assert_eq!(
+        entries(&changelog),
+        vec!["fix: Main breaking fix", "feat: Branch feature", "feat: Main feature", "fix: Branch fix", "docs: Main docs", "chore: Merge feature"],
+        "{}", changelog
+    );
+
+    // Every ordering lists the same commits as the default one
+    let mut default_entries = entries(&run_vnext_with_args(repo_path, &["--changelog", "--no-highlight-breaking"]));
+    default_entries.sort();
+    let mut typed_entries = entries(&changelog);
+    typed_entries.sort();
+    assert_eq!(default_entries, typed_entries);
+}
@@ -0,0 +1,127 @@
+use std::fs;
+use std::path::Path;
+
+use semver::Version;
+use vnext::changelog::{format_changelog_with_options, RepoInfo};
+use vnext::models::options::ChangelogOptions;
+use vnext::version::{ChangesetSummary, Commit, CommitAuthor};
+use vnext::ReleaseCounts;
+
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command, run_vnext_output};
+
+fn counts(commits: usize, features: usize, fixes: usize, breaking_changes: usize, contributors: usize) -> String {
+    ReleaseCounts { commits, features, fixes, breaking_changes, contributors }.to_string()
+}
+
+#[test]
+fn test_summary_line_snapshots() {
+    assert_eq!(counts(12, 3, 5, 1, 4), "12 commits, 3 features, 5 fixes, 1 breaking change, by 4 contributors");
+    assert_eq!(counts(1, 1, 0, 0, 1), "1 commit, 1 feature, by 1 contributor");
+    assert_eq!(counts(2, 0, 1, 2, 0), "2 commits, 1 fix, 2 breaking changes");
+    assert_eq!(counts(3, 0, 0, 0, 2), "3 commits, by 2 contributors");
+    assert_eq!(counts(0, 0, 0, 0, 0), "0 commits");
+}
+
+fn authored(id: &str, message: &str, name: &str) -> Commit {
+    let mut commit = Commit::parse(id.to_string(), message.to_string());
+    commit.author = Some(CommitAuthor {
+        name: name.to_string(),
+        email: format!("{}@example.com", name.to_lowercase()),
+        username: None,
+    });
+    commit
+}
+
+#[test]
+fn test_release_counts_of_a_summary() {
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(authored("c1", "feat!: Drop the v1 API", "Alice"));
+    summary.commits.push(authored("c2", "Fix: Handle empty input", "bob"));
+    summary.commits.push(authored("c3", "fix: Trim names", "Bob"));
+    summary.commits.push(Commit::parse("c4".to_string(), "chore: Tidy up".to_string()));
+    summary.truncated = true;
+    summary.omitted_commits = 6;
+
+    let counts = summary.release_counts();
+    assert_eq!(counts, ReleaseCounts { commits: 10, features: 1, fixes: 2, breaking_changes: 1, contributors: 2 });
+    assert_eq!(counts.to_string(), "10 commits, 1 feature, 2 fixes, 1 breaking change, by 2 contributors");
+}
+
+#[test]
+fn test_changelog_ends_with_the_summary_line() {
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(authored("c1", "fix: Trim names", "Alice"));
+    summary.commits.push(authored("c2", "feat: Add export", "Alice"));
+    let options = ChangelogOptions { summary_line: true, no_highlight_breaking: true, ..ChangelogOptions::default() };
+    let render = |summary: &ChangesetSummary| {
+        format_changelog_with_options(summary, &Version::new(1, 1, 0), &Version::new(1, 0, 0), &RepoInfo::new(), &options)
+    };
+
+    assert_eq!(
+        render(&summary),
+        "### What's changed in v1.1.0\n\n\
+         * feat: Add export (by Alice)\n\n\
+         * fix: Trim names (by Alice)\n\n\
+         2 commits, 1 feature, 1 fix, by 1 contributor\n"
+    );
+    assert_eq!(render(&ChangesetSummary::new()), "### What's changed in v1.1.0\n\n* No changes\n\n0 commits\n");
+}
+
+fn commit_file(repo_path: &Path, file: &str, message: &str, author: &str) {
+    fs::write(repo_path.join(file), file).expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    let author = format!("--author={} <{}@example.com>", author, author.to_lowercase());
+    run_and_show_command("git", &["commit", "-m", message, &author], repo_path);
+}
+
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit", "patrickleet");
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    commit_file(repo_path, "a.md", "feat: Add export", "Alice");
+    commit_file(repo_path, "b.md", "fix: Trim names", "Bob");
+    commit_file(repo_path, "c.md", "fix: Handle empty input", "Alice");
+    commit_file(repo_path, "d.md", "docs: Explain the flags", "patrickleet");
+}
+
+#[test]
+fn test_changelog_summary_flag() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+
+    let output = run_vnext_output(repo_path, &["--changelog", "--changelog-summary"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let changelog = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        changelog.trim_end().ends_with("\n\n4 commits, 1 feature, 2 fixes, by 3 contributors"),
+        "{}",
+        changelog
+    );
+
+    let output = run_vnext_output(repo_path, &["--changelog"]);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("4 commits"));
+}
+
+#[test]
+fn test_json_output_always_has_the_summary() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+
+    let output = run_vnext_output(repo_path, &["--format", "json"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Output should be JSON");
+    assert_eq!(
+        json["summary"],
+        serde_json::json!({
+            "commits": 4,
+            "features": 1,
+            "fixes": 2,
+            "breaking_changes": 0,
+            "contributors": 3,
+            "text": "4 commits, 1 feature, 2 fixes, by 3 contributors",
+        })
+    );
+}
@@ -94,7 +94,7 @@ fn test_changelog() {
     
     let changelog = changelog.trim_end().to_string(); // Remove trailing newlines
     let expected_changelog = format!(
-        "### What's changed in v1.0.0\n\n* feat: add new feature\n\n  BREAKING CHANGE: This removes the old API"
+        "### What's changed in v1.0.0\n\n#### ⚠ Breaking changes\n\n* feat: add new feature\n\n  This removes the old API\n\n* feat: add new feature"
     );
 
     assert_eq!(
@@ -0,0 +1,76 @@
+use std::collections::{BTreeMap, HashSet};
+
+use vnext::version::{BumpLevel, BumpMap, ChangesetSummary, Commit, CommitAuthor};
+
+fn authored(id: &str, message: &str, name: &str, email: &str, username: Option<&str>) -> Commit {
+    let mut commit = Commit::parse(id.to_string(), message.to_string());
+    commit.author = Some(CommitAuthor {
+        name: name.to_string(),
+        email: email.to_string(),
+        username: username.map(|u| u.to_string()),
+    });
+    commit
+}
+
+/// Commits newest first, like the walk collects them
+fn summary() -> ChangesetSummary {
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(authored("c5", "Update dependencies", "Zed", "zed@example.com", None));
+    summary.commits.push(authored("c4", "docs: Explain the bump map", "Bob", "bob@example.com", Some("bob")));
+    summary.commits.push(authored("c3", "fix(api)!: Reject empty payloads", "Alice", "alice@example.com", Some("Alice")));
+    summary.commits.push(authored("c2", "Feat: Add export", "Alice", "alice@work.example.com", Some("alice")));
+    summary.commits.push(authored("c1", "feat: Add import", "Bob", "bob@example.com", Some("bob")));
+    summary
+}
+
+fn ids<'a>(commits: impl Iterator<Item = &'a Commit>) -> Vec<&'a str> {
+    commits.map(|commit| commit.commit_id.as_str()).collect()
+}
+
+#[test]
+fn test_breaking_changes_and_by_type() {
+    let summary = summary();
+    assert_eq!(ids(summary.breaking_changes()), vec!["c3"]);
+    assert_eq!(ids(summary.by_type("feat")), vec!["c2", "c1"], "Types match ignoring case");
+    assert_eq!(ids(summary.by_type("FIX")), vec!["c3"]);
+    assert_eq!(ids(summary.by_type("chore")), Vec::<&str>::new());
+}
+
+#[test]
+fn test_type_counts() {
+    let expected: BTreeMap<String, usize> =
+        [("", 1), ("docs", 1), ("feat", 2), ("fix", 1)].into_iter().map(|(t, n)| (t.to_string(), n)).collect();
+    assert_eq!(summary().type_counts(), expected);
+    assert!(ChangesetSummary::new().type_counts().is_empty());
+}
+
+#[test]
+fn test_highest_bump() {
+    let mut summary = summary();
+    assert_eq!(summary.highest_bump(&BumpMap::default()), BumpLevel::Major);
+
+    summary.commits.retain(|commit| !commit.has_breaking_change);
+    assert_eq!(summary.highest_bump(&BumpMap::default()), BumpLevel::Minor);
+
+    let docs_only = BumpMap::from_types(&["major"], &["feat"], &["docs"]);
+    summary.commits.retain(|commit| commit.commit_type == "docs");
+    assert_eq!(summary.highest_bump(&docs_only), BumpLevel::None);
+    assert_eq!(summary.highest_bump(&BumpMap::default()), BumpLevel::Patch, "Unmapped types bump the patch version");
+
+    // Out-of-scope commits are no-ops
+    summary.commits[0].out_of_scope = true;
+    assert_eq!(summary.highest_bump(&BumpMap::default()), BumpLevel::None);
+}
+
+#[test]
+fn test_contributors() {
+    let mut summary = summary();
+    let names: Vec<&str> = summary.contributors().iter().map(|author| author.email.as_str()).collect();
+    // Deduplicated by username (or name), sorted, keeping the newest commit's identity
+    assert_eq!(names, vec!["alice@example.com", "bob@example.com", "zed@example.com"]);
+    assert!(summary.new_contributors().is_empty(), "New contributors need known history");
+
+    summary.known_contributors = Some(HashSet::from(["alice@example.com".to_string(), "bob@example.com".to_string()]));
+    let new: Vec<&str> = summary.new_contributors().iter().map(|author| author.email.as_str()).collect();
+    assert_eq!(new, vec!["alice@work.example.com", "zed@example.com"], "An unknown identity of a contributor counts as new");
+}
@@ -0,0 +1,103 @@
+use std::path::Path;
+use std::process::{Command, Output};
+
+use vnext::commands::check::{check_title, read_event_title};
+use vnext::models::options::CheckOptions;
+use vnext::models::version::BumpMap;
+use vnext::parsers::{ParserFactory, ParserStrategy};
+
+const EVENT_FIXTURE: &str = "tests/fixtures/pull_request_event.json";
+
+fn run_check(args: &[&str], env: &[(&str, &str)]) -> Output {
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    Command::new(project_dir.join("target/debug/vnext"))
+        .arg("check")
+        .args(args)
+        .env_remove("GITHUB_EVENT_PATH")
+        .envs(env.iter().copied())
+        .output()
+        .expect("Failed to execute vnext check")
+}
+
+fn problems(title: &str, check: &CheckOptions) -> Vec<String> {
+    let parser = ParserFactory::create(&ParserStrategy::Conventional).unwrap();
+    check_title(&*parser, title, &BumpMap::default(), check).problems
+}
+
+#[test]
+fn test_check_title_rules() {
+    let check = CheckOptions::default();
+    assert!(problems("feat(api): add export", &check).is_empty());
+    assert!(problems("fix!: drop legacy config", &check).is_empty(), "Common conventional types are allowed");
+    assert!(problems("chore: bump deps", &check).is_empty(), "Types of the bump map are allowed");
+
+    let found = problems("Add export", &check);
+    assert_eq!(found.len(), 1);
+    assert!(found[0].contains("'type(scope): description'"), "{:?}", found);
+
+    let found = problems("feature: add export", &check);
+    assert!(found[0].starts_with("'feature' is not an allowed type; use one of:"), "{:?}", found);
+    assert!(found[0].contains("feat") && found[0].contains("fix"), "{:?}", found);
+
+    let check = CheckOptions {
+        allowed_types: vec!["feat".to_string(), "fix".to_string()],
+        allowed_scopes: vec!["api".to_string(), "cli".to_string()],
+        max_length: Some(20),
+    };
+    assert!(problems("fix(cli): tidy help", &check).is_empty());
+    assert!(problems("fix: unscoped", &check).is_empty(), "Titles without a scope are allowed");
+    let found = problems("docs(web): explain the new export flow", &check);
+    assert_eq!(found, vec![
+        "'docs' is not an allowed type; use one of: feat, fix".to_string(),
+        "'web' is not an allowed scope; use one of: api, cli".to_string(),
+        "it is 38 characters long; the limit is 20".to_string(),
+    ]);
+}
+
+#[test]
+fn test_read_event_title_from_fixture() {
+    assert_eq!(read_event_title(Path::new(EVENT_FIXTURE)).unwrap(), "feat(changelog): add export to CSV");
+
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let push_event = temp_dir.path().join("push.json");
+    std::fs::write(&push_event, r#"{"ref": "refs/heads/main", "after": "abc"}"#).unwrap();
+    let error = read_event_title(&push_event).unwrap_err().to_string();
+    assert!(error.contains("has no pull request"), "{}", error);
+}
+
+#[test]
+fn test_check_command_exit_codes() {
+    let output = run_check(&["--title", "feat(api): add export"], &[]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "'feat(api): add export' is a valid feat commit (minor bump)");
+
+    let output = run_check(&["--title", "Update stuff", "--max-length", "5"], &[]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("'Update stuff' is not a valid commit subject"), "{}", stderr);
+    assert!(stderr.contains("  - it does not match the conventional format"), "{}", stderr);
+    assert!(stderr.contains("  - it is 12 characters long; the limit is 5"), "{}", stderr);
+
+    let output = run_check(&["--title", "wip: stuff", "--format", "json"], &[]);
+    assert!(!output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Output should be JSON");
+    assert_eq!(json["valid"], false);
+    assert_eq!(json["type"], "wip");
+}
+
+#[test]
+fn test_check_command_reads_github_event() {
+    let output = run_check(
+        &["--from-github-event", "--allowed-scopes", "changelog", "--format", "json"],
+        &[("GITHUB_EVENT_PATH", EVENT_FIXTURE)],
+    );
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Output should be JSON");
+    assert_eq!(json["title"], "feat(changelog): add export to CSV");
+    assert_eq!(json["bump"], "minor");
+    assert_eq!(json["scopes"], serde_json::json!(["changelog"]));
+
+    let output = run_check(&["--from-github-event"], &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("GITHUB_EVENT_PATH"));
+}
@@ -0,0 +1,82 @@
+use semver::Version;
+use vnext::changelog::{format_changelog_with_options, RepoInfo};
+use vnext::models::options::ChangelogOptions;
+use vnext::version::{ChangesetSummary, Commit};
+
+fn render(message: &str) -> String {
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(Commit::parse("abc123".to_string(), message.to_string()));
+    format_changelog_with_options(&summary, &Version::new(1, 1, 0), &Version::new(1, 0, 0), &RepoInfo::new(), &ChangelogOptions::default())
+}
+
+#[test]
+fn test_diff_block_is_kept_verbatim() {
+    let message = concat!(
+        "fix: Read the port from the environment\n\n",
+        "# Change\n\n",
+        "```diff\n",
+        "--- a/config.toml\n",
+        "+++ b/config.toml\n",
+        "@@ -1,3 +1,3 @@\n",
+        "-# port = 80\n",
+        "+# port = ${PORT}\n",
+        "\n",
+        " host = \"0.0.0.0\"\n",
+        "```\n\n",
+        "# After",
+    );
+    assert_eq!(
+        render(message),
+        concat!(
+            "### What's changed in v1.1.0\n\n",
+            "* fix: Read the port from the environment\n\n",
+            "  #### Change\n\n",
+            "  ```diff\n",
+            "  --- a/config.toml\n",
+            "  +++ b/config.toml\n",
+            "  @@ -1,3 +1,3 @@\n",
+            "  -# port = 80\n",
+            "  +# port = ${PORT}\n",
+            "\n",
+            "   host = \"0.0.0.0\"\n",
+            "  ```\n\n",
+            "  #### After\n\n",
+        )
+    );
+}
+
+#[test]
+fn test_markdown_table() {
+    let message = "feat: Add output formats\n\n| Format | Flag |\n| ------ | ---- |\n| JSON | `--format json` |\n| Text | (default) |";
+    assert_eq!(
+        render(message),
+        concat!(
+            "### What's changed in v1.1.0\n\n",
+            "* feat: Add output formats\n\n",
+            "  | Format | Flag |\n",
+            "  | ------ | ---- |\n",
+            "  | JSON | `--format json` |\n",
+            "  | Text | (default) |\n\n",
+        )
+    );
+}
+
+#[test]
+fn test_fences_close_on_matching_fence_only() {
+    // A shorter fence, a fence with an info string and inline triple backticks do not close the block
+    let message = "docs: Document fences\n\n````markdown\n```rust\n# not a heading\n```\n````\n\n```inline``` is not a fence\n\n# Heading";
+    assert_eq!(
+        render(message),
+        concat!(
+            "### What's changed in v1.1.0\n\n",
+            "* docs: Document fences\n\n",
+            "  ````markdown\n",
+            "  ```rust\n",
+            "  # not a heading\n",
+            "  ```\n",
+            "  ````\n\n",
+            "  ```inline``` is not a fence\n\n",
+            "  #### Heading\n\n",
+        )
+    );
+}
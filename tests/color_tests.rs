@@ -0,0 +1,93 @@
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+use vnext::models::options::ColorChoice;
+use vnext::utils::color::{colorize, should_color};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command, run_vnext, vnext_command};
+
+#[test]
+fn test_should_color() {
+    // Forced choices ignore the terminal and NO_COLOR
+    for (no_color, is_terminal) in [(false, false), (false, true), (true, false), (true, true)] {
+        assert!(should_color(ColorChoice::Always, no_color, is_terminal));
+        assert!(!should_color(ColorChoice::Never, no_color, is_terminal));
+    }
+    assert!(should_color(ColorChoice::Auto, false, true));
+    assert!(!should_color(ColorChoice::Auto, true, true));
+    assert!(!should_color(ColorChoice::Auto, false, false));
+    assert!(!should_color(ColorChoice::Auto, true, false));
+}
+
+#[test]
+fn test_colorize() {
+    assert_eq!(colorize("1.2.0", &[]), "\x1b[1m1.2.0\x1b[0m");
+    let changelog = "### What's changed in v2.0.0\n\n#### ⚠ Breaking changes\n\n* fix(api): Rename\n\n* fix(api): Rename\n\n* feat: Add export\n\n* chore: Tidy up\n\nPlain text";
+    let colored = colorize(changelog, &["fix(api): Rename".to_string()]);
+    let lines: Vec<&str> = colored.lines().collect();
+    assert_eq!(lines[0], "\x1b[1m### What's changed in v2.0.0\x1b[0m");
+    assert_eq!(lines[2], "\x1b[1m\x1b[31m#### ⚠ Breaking changes\x1b[0m");
+    assert_eq!(lines[4], "\x1b[31m* fix(api): Rename\x1b[0m");
+    assert_eq!(lines[6], "\x1b[31m* fix(api): Rename\x1b[0m");
+    assert_eq!(lines[8], "* \x1b[32mfeat\x1b[0m: Add export");
+    assert_eq!(lines[10], "* \x1b[36mchore\x1b[0m: Tidy up");
+    assert_eq!(lines[12], "Plain text");
+
+    // Stripping the colors gives back the markdown
+    let stripped = regex::Regex::new("\x1b\\[[0-9]+m").unwrap().replace_all(&colored, "");
+    assert_eq!(stripped, changelog);
+}
+
+fn run_vnext_with_no_color(repo_path: &Path, args: &[&str], no_color: Option<&str>) -> Output {
+    let mut command = vnext_command(repo_path, args);
+    command.env_remove("NO_COLOR");
+    if let Some(value) = no_color {
+        command.env("NO_COLOR", value);
+    }
+    let output = command.output().expect("Failed to execute vnext");
+    assert!(output.status.success(), "vnext failed: {}", String::from_utf8_lossy(&output.stderr));
+    output
+}
+
+fn has_escapes(output: &Output) -> bool {
+    output.stdout.contains(&0x1b)
+}
+
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    fs::write(repo_path.join("README.md"), "# Test").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    run_and_show_command("git", &["commit", "--allow-empty", "-m", "feat(api)!: Drop the v1 endpoints"], repo_path);
+}
+
+#[test]
+fn test_color_flag() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    assert_eq!(run_vnext(repo_path), "2.0.0");
+
+    // stdout is piped here, so auto never colors
+    assert!(!has_escapes(&run_vnext_with_no_color(repo_path, &["--changelog"], None)));
+    assert!(!has_escapes(&run_vnext_with_no_color(repo_path, &["--changelog", "--color", "never"], None)));
+    // --color always overrides NO_COLOR
+    let output = run_vnext_with_no_color(repo_path, &["--changelog", "--color", "always"], Some("1"));
+    assert!(has_escapes(&output));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("\x1b[31m* feat(api): Drop the v1 endpoints"));
+
+    // JSON and files stay plain
+    assert!(!has_escapes(&run_vnext_with_no_color(repo_path, &["--changelog", "--color", "always", "--format", "json"], None)));
+    let changelog_file = repo_path.join("CHANGES.md");
+    let version_file = repo_path.join("VERSION");
+    run_vnext_with_no_color(
+        repo_path,
+        &["--changelog", "--color", "always", "--changelog-out", changelog_file.to_str().unwrap(), "--version-file-out", version_file.to_str().unwrap()],
+        None,
+    );
+    assert!(!fs::read_to_string(&changelog_file).unwrap().contains('\x1b'));
+    assert_eq!(fs::read_to_string(&version_file).unwrap(), "2.0.0\n");
+}
@@ -128,4 +128,147 @@ fn test_commit_struct() {
     // Test is_noop_change
     let commit = Commit::parse("abc123".to_string(), "chore: Update dependencies".to_string());
     assert!(commit.is_noop_change(&default_noop_types()));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_breaking_change_footers() {
+    // A footer after the description paragraph is a breaking change
+    let message = "feat: Add new feature\n\nThis is the description.\n\nBREAKING CHANGE: The old API is removed.";
+    let parsed = parse_conventional_commit(message).unwrap();
+    assert!(parsed.breaking_change_body);
+    assert_eq!(parsed.body, Some("This is the description.".to_string()));
+    assert_eq!(parsed.breaking_change_note, Some("The old API is removed.".to_string()));
+
+    // The hyphenated token is equivalent, also among other trailers
+    let message = "fix: Rework loader\n\nLoad lazily.\n\nReviewed-by: Jane Smith\nBREAKING-CHANGE: Config is read on demand.";
+    let parsed = parse_conventional_commit(message).unwrap();
+    assert!(parsed.breaking_change_body);
+    assert_eq!(parsed.body, Some("Load lazily.".to_string()));
+    assert_eq!(parsed.breaking_change_note, Some("Config is read on demand.".to_string()));
+
+    let message = "feat: Add new feature\n\nBREAKING-CHANGE: This breaks the old API.";
+    let parsed = parse_conventional_commit(message).unwrap();
+    assert!(parsed.breaking_change_body);
+    assert_eq!(parsed.body, Some("This breaks the old API.".to_string()));
+
+    // A mention at the start of a line inside a description paragraph is not a footer
+    let message = "feat: Add new feature\n\nThis is the first line.\nBREAKING CHANGE: This is not a footer.\n\nMore text.";
+    let parsed = parse_conventional_commit(message).unwrap();
+    assert!(!parsed.breaking_change_body);
+    assert!(parsed.breaking_change_note.is_none());
+
+    let commit = Commit::parse("abc123".to_string(), "fix: Fix bug\n\nDetails.\n\nBREAKING CHANGE: Removed flag.".to_string());
+    assert!(commit.is_major_change(&default_major_types()));
+    assert_eq!(commit.breaking_change_note, Some("Removed flag.".to_string()));
+}
+
+#[test]
+fn test_footers_are_parsed() {
+    let message = "fix(api): Retry failed requests\n\nRequests are retried three times.\n\nReviewed-by: Jane Smith <jane@example.com>\nRefs: #42\nCloses #10\nAcked-by: John Doe";
+    let parsed = parse_conventional_commit(message).unwrap();
+    assert_eq!(parsed.body, Some("Requests are retried three times.".to_string()));
+    assert_eq!(
+        parsed.footers,
+        vec![
+            ("Reviewed-by".to_string(), "Jane Smith <jane@example.com>".to_string()),
+            ("Refs".to_string(), "#42".to_string()),
+            ("Closes".to_string(), "#10".to_string()),
+            ("Acked-by".to_string(), "John Doe".to_string()),
+        ]
+    );
+    assert!(!parsed.breaking_change_body);
+
+    let commit = Commit::parse("abc123".to_string(), message.to_string());
+    assert_eq!(commit.footers, parsed.footers);
+    assert_eq!(commit.body, parsed.body);
+}
+
+#[test]
+fn test_multiline_footer_values() {
+    let message = "feat: Add export\n\nExports to CSV.\n\nBREAKING CHANGE: The export format changed,\n  so older readers\n  need an update.\nSigned-off-by: Jane Smith <jane@example.com>";
+    let parsed = parse_conventional_commit(message).unwrap();
+    assert_eq!(parsed.body, Some("Exports to CSV.".to_string()));
+    assert_eq!(parsed.footers.len(), 2);
+    assert_eq!(parsed.footers[0].0, "BREAKING CHANGE");
+    assert_eq!(parsed.footers[0].1, "The export format changed, so older readers need an update.");
+    assert_eq!(parsed.breaking_change_note, Some("The export format changed, so older readers need an update.".to_string()));
+    assert!(parsed.breaking_change_body);
+}
+
+#[test]
+fn test_footer_only_body() {
+    let parsed = parse_conventional_commit("chore: Bump deps\n\nSigned-off-by: Jane Smith <jane@example.com>").unwrap();
+    assert!(parsed.body.is_none());
+    assert_eq!(parsed.footers, vec![("Signed-off-by".to_string(), "Jane Smith <jane@example.com>".to_string())]);
+
+    // A body starting with the breaking token keeps it as the body and reports it as a footer
+    let parsed = parse_conventional_commit("feat: Drop v1\n\nBREAKING-CHANGE: The v1 API is gone.").unwrap();
+    assert_eq!(parsed.body, Some("The v1 API is gone.".to_string()));
+    assert_eq!(parsed.footers, vec![("BREAKING-CHANGE".to_string(), "The v1 API is gone.".to_string())]);
+}
+
+#[test]
+fn test_footers_with_hyphenated_tokens() {
+    let message = "docs: Update guide\n\nCo-authored-by: John Doe <john@example.com>\nX-Release-Note: Mention the new flag\nSee-also: #7";
+    let parsed = parse_conventional_commit(message).unwrap();
+    assert!(parsed.body.is_none());
+    let tokens: Vec<&str> = parsed.footers.iter().map(|(token, _)| token.as_str()).collect();
+    assert_eq!(tokens, vec!["Co-authored-by", "X-Release-Note", "See-also"]);
+    assert_eq!(parsed.footers[2].1, "#7");
+
+    // Prose in the last paragraph means there is no footer block
+    let message = "docs: Update guide\n\nSee the guide.\nReviewed-by: Jane";
+    let parsed = parse_conventional_commit(message).unwrap();
+    assert!(parsed.footers.is_empty());
+    assert_eq!(parsed.body, Some("See the guide.\nReviewed-by: Jane".to_string()));
+}
+
+#[test]
+fn test_body_with_colons() {
+    let message = "fix(db): Close idle connections\n\nNote: the pool size is unchanged.\nIdle connections close after 30s.";
+    let parsed = parse_conventional_commit(message).unwrap();
+    assert_eq!(parsed.commit_type, "fix");
+    assert_eq!(parsed.scope, Some("db".to_string()));
+    assert_eq!(parsed.title, "Close idle connections");
+    assert_eq!(parsed.body, Some("Note: the pool size is unchanged.\nIdle connections close after 30s.".to_string()));
+    assert!(!parsed.breaking_change_body);
+
+    // A colon in the title stays in the title
+    let parsed = parse_conventional_commit("feat: Support key: value pairs\n\nDetails.").unwrap();
+    assert_eq!(parsed.title, "Support key: value pairs");
+    assert_eq!(parsed.body, Some("Details.".to_string()));
+}
+
+#[test]
+fn test_crlf_line_endings() {
+    let message = "feat(api): Add export\r\n\r\nExports to CSV.\r\nAnd JSON.\r\n\r\nBREAKING CHANGE: The old export is removed.\r\n";
+    let parsed = parse_conventional_commit(message).unwrap();
+    assert_eq!(parsed.title, "Add export");
+    assert_eq!(parsed.scope, Some("api".to_string()));
+    assert_eq!(parsed.body, Some("Exports to CSV.\nAnd JSON.".to_string()));
+    assert!(parsed.breaking_change_body);
+    assert_eq!(parsed.breaking_change_note, Some("The old export is removed.".to_string()));
+
+    let parsed = parse_conventional_commit("fix: Trim input\r\n").unwrap();
+    assert_eq!(parsed.title, "Trim input");
+    assert!(parsed.body.is_none());
+}
+
+#[test]
+fn test_no_blank_line_after_header() {
+    let parsed = parse_conventional_commit("feat: Add export\nExports to CSV.\nAnd JSON.").unwrap();
+    assert_eq!(parsed.title, "Add export");
+    assert_eq!(parsed.body, Some("Exports to CSV.\nAnd JSON.".to_string()));
+
+    // An empty title does not pull the body into the header
+    let parsed = parse_conventional_commit("feat:\nExports to CSV.").unwrap();
+    assert_eq!(parsed.title, "");
+    assert_eq!(parsed.body, Some("Exports to CSV.".to_string()));
+
+    // A later BREAKING CHANGE line inside the description is not a breaking change
+    let parsed = parse_conventional_commit("feat: Add export\nExports to CSV.\nBREAKING CHANGE: mentioned in passing\nMore text.").unwrap();
+    assert!(!parsed.breaking_change_body);
+
+    // Neither is a header that is not on the first line
+    assert!(parse_conventional_commit("Merge branch 'main'\nfeat: Add export").is_none());
+}
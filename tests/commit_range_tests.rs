@@ -0,0 +1,119 @@
+use std::path::Path;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_command, run_vnext, run_vnext_success};
+
+fn rev_parse(repo_path: &Path, rev: &str) -> String {
+    let output = run_command("git", &["rev-parse", rev], repo_path);
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+}
+
+#[test]
+fn test_json_output_has_the_analyzed_range() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    commit_file(repo_path, "a.md", "feat: Add a");
+    commit_file(repo_path, "b.md", "fix: Fix b");
+    assert_eq!(run_vnext(repo_path), "1.1.0");
+
+    let stdout = run_vnext_success(repo_path, &["--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("Output should be JSON");
+    assert_eq!(json["base_sha"], rev_parse(repo_path, "v1.0.0^{commit}"));
+    assert_eq!(json["head_sha"], rev_parse(repo_path, "HEAD"));
+    assert_eq!(json["base_ref"], "v1.0.0");
+    assert_eq!(json["commit_count"], 2);
+}
+
+#[test]
+fn test_show_range_appends_trailer_lines() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    commit_file(repo_path, "a.md", "fix: Fix a");
+    assert_eq!(run_vnext(repo_path), "1.0.1", "Without --show-range the output is the bare version");
+
+    let stdout = run_vnext_success(repo_path, &["--show-range"]);
+    let expected = format!(
+        "1.0.1\n\nbase_sha: {}\nhead_sha: {}\nbase_ref: v1.0.0\ncommit_count: 1",
+        rev_parse(repo_path, "v1.0.0"),
+        rev_parse(repo_path, "HEAD")
+    );
+    assert_eq!(stdout, expected);
+}
+
+#[test]
+fn test_range_without_release_has_no_base() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    commit_file(repo_path, "a.md", "fix: Fix a");
+
+    let stdout = run_vnext_success(repo_path, &["--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("Output should be JSON");
+    assert_eq!(json["base_sha"], serde_json::Value::Null);
+    assert_eq!(json["base_ref"], serde_json::Value::Null);
+    assert_eq!(json["head_sha"], rev_parse(repo_path, "HEAD"));
+    assert_eq!(json["commit_count"], 2, "The whole history is analyzed");
+}
+
+/// v1.0.0 on main, then v1.0.1 tagged on a release branch off v1.0.0 while main moves on
+fn create_release_branch_repo(repo_path: &Path) {
+    create_repo(repo_path);
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    run_and_show_command("git", &["branch", "-M", "main"], repo_path);
+    run_and_show_command("git", &["checkout", "-b", "release-1.0"], repo_path);
+    commit_file(repo_path, "hotfix.md", "fix: Backport the hotfix");
+    run_and_show_command("git", &["tag", "v1.0.1"], repo_path);
+    run_and_show_command("git", &["checkout", "main"], repo_path);
+    commit_file(repo_path, "a.md", "feat: Add a");
+}
+
+fn changelog_commits(repo_path: &Path) -> Vec<String> {
+    let stdout = run_vnext_success(repo_path, &["changelog", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("Output should be JSON");
+    json["commits"].as_array().unwrap().iter().map(|commit| commit["title"].as_str().unwrap().to_string()).collect()
+}
+
+#[test]
+fn test_tag_on_an_unmerged_release_branch() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_release_branch_repo(repo_path);
+    commit_file(repo_path, "b.md", "fix: Fix b");
+    assert_eq!(run_vnext(repo_path), "1.1.0");
+
+    // The range starts at the fork point, and the release branch commit is not in it
+    let stdout = run_vnext_success(repo_path, &["--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("Output should be JSON");
+    assert_eq!(json["current_version"], "1.0.1");
+    assert_eq!(json["base_sha"], rev_parse(repo_path, "v1.0.0^{commit}"));
+    assert_eq!(json["base_ref"], "v1.0.1");
+    assert_eq!(json["commit_count"], 2);
+    assert_eq!(changelog_commits(repo_path), ["Fix b", "Add a"]);
+}
+
+#[test]
+fn test_tag_on_a_merged_release_branch() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_release_branch_repo(repo_path);
+    run_and_show_command("git", &["merge", "--no-ff", "-m", "chore: Merge release-1.0", "release-1.0"], repo_path);
+    commit_file(repo_path, "b.md", "fix: Fix b");
+    assert_eq!(run_vnext(repo_path), "1.1.0");
+
+    // The merged tag bounds the range; its commit is not counted a second time
+    let stdout = run_vnext_success(repo_path, &["--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("Output should be JSON");
+    assert_eq!(json["base_sha"], rev_parse(repo_path, "v1.0.1^{commit}"));
+    assert_eq!(json["commit_count"], 3);
+    assert_eq!(changelog_commits(repo_path), ["Fix b", "Merge release-1.0", "Add a"]);
+}
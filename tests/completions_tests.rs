@@ -0,0 +1,65 @@
+use std::process::Command;
+
+fn run_vnext_args(args: &[&str]) -> String {
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let output = Command::new(project_dir.join("target/debug/vnext"))
+        .args(args)
+        .output()
+        .expect("Failed to execute vnext");
+    assert!(output.status.success(), "vnext {:?} failed: {}", args, String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn test_bash_completions() {
+    let script = run_vnext_args(&["completions", "bash"]);
+    assert!(script.contains("complete -F _vnext -o nosort -o bashdefault -o default vnext"), "{}", script);
+    for flag in ["--changelog", "--format", "--tag-prefix", "--current", "--unreleased", "--for-tag", "--write-access"] {
+        assert!(script.contains(flag), "The bash script should complete {}", flag);
+    }
+    assert!(script.contains("vnext__subcmd__deploy__subcmd__key__subcmd__rotate)"), "Nested subcommands are completed");
+
+    // The script must at least parse
+    if let Ok(output) = Command::new("bash").arg("-n").arg("-c").arg(&script).output() {
+        assert!(output.status.success(), "bash -n failed: {}", String::from_utf8_lossy(&output.stderr));
+    }
+}
+
+#[test]
+fn test_other_shells_cover_subcommands() {
+    let zsh = run_vnext_args(&["completions", "zsh"]);
+    assert!(zsh.starts_with("#compdef vnext\n"));
+    assert!(zsh.contains("(rotate)") && zsh.contains("--format=[Output format]"), "{}", zsh);
+
+    let fish = run_vnext_args(&["completions", "fish"]);
+    assert!(fish.contains("__fish_vnext_using_subcommand changelog\" -l unreleased"), "{}", fish);
+    assert!(fish.contains("__fish_vnext_using_subcommand deploy-key"), "{}", fish);
+
+    let powershell = run_vnext_args(&["completions", "powershell"]);
+    assert!(powershell.contains("Register-ArgumentCompleter -Native -CommandName 'vnext'"));
+    assert!(powershell.contains("'vnext;check' {"), "{}", powershell);
+}
+
+#[test]
+fn test_man_page() {
+    let page = run_vnext_args(&["man"]);
+    assert!(page.contains(".TH vnext 1 "), "{}", page);
+    assert!(page.contains(".SH NAME\nvnext \\- Calculate the next version based on conventional commits"), "{}", page);
+    assert!(page.contains("\\fB\\-\\-changelog\\fR\n"), "Flags are listed");
+    assert!(page.contains("\\fB\\-\\-format\\fR \\fI<FORMAT>\\fR"), "Flags with values name them");
+    assert!(page.contains("vnext\\-deploy\\-key(1)"), "Subcommands refer to their own pages");
+    assert!(!page.contains("generate\\-deploy\\-key"), "Hidden commands are left out");
+
+    // --dir writes a page per visible subcommand
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    run_vnext_args(&["man", "--dir", temp_dir.path().to_str().unwrap()]);
+    let main_page = std::fs::read_to_string(temp_dir.path().join("vnext.1")).unwrap();
+    assert!(main_page.contains(".SH NAME\nvnext \\- Calculate the next version"), "{}", main_page);
+    let rotate = std::fs::read_to_string(temp_dir.path().join("vnext-deploy-key-rotate.1")).unwrap();
+    assert!(rotate.contains("\\-\\-org\\-secret"), "{}", rotate);
+    assert!(!temp_dir.path().join("vnext-generate-deploy-key.1").exists());
+
+    // Completions and the man page are not in the default help
+    let help = run_vnext_args(&["--help"]);
+    assert!(!help.contains("completions") && !help.contains("  man "), "{}", help);
+}
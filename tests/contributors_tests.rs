@@ -0,0 +1,105 @@
+use std::collections::HashSet;
+use std::fs;
+
+use git2::Repository;
+use semver::Version;
+use vnext::changelog::{format_changelog_with_options, RepoInfo};
+use vnext::git::collect_previous_contributors;
+use vnext::models::options::ChangelogOptions;
+use vnext::version::{ChangesetSummary, Commit, CommitAuthor};
+
+mod test_helpers;
+use test_helpers::run_and_show_command;
+
+fn authored(id: &str, message: &str, name: &str, email: &str, username: Option<&str>) -> Commit {
+    let mut commit = Commit::parse(id.to_string(), message.to_string());
+    commit.author = Some(CommitAuthor {
+        name: name.to_string(),
+        email: email.to_string(),
+        username: username.map(|u| u.to_string()),
+    });
+    commit
+}
+
+fn contributor_options() -> ChangelogOptions {
+    ChangelogOptions {
+        contributors: true,
+        ..ChangelogOptions::default()
+    }
+}
+
+fn render(summary: &ChangesetSummary) -> String {
+    format_changelog_with_options(summary, &Version::new(1, 1, 0), &Version::new(1, 0, 0), &RepoInfo::new(), &contributor_options())
+}
+
+#[test]
+fn test_contributors_are_deduplicated_and_sorted() {
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(authored("c1", "feat: One", "Zed", "zed@example.com", Some("zed")));
+    summary.commits.push(authored("c2", "fix: Two", "Alice", "alice@example.com", Some("Alice")));
+    summary.commits.push(authored("c3", "fix: Three", "Alice", "alice@example.com", Some("alice")));
+    summary.commits.push(authored("c4", "chore: Four", "bob", "bob@example.com", None));
+
+    let changelog = render(&summary);
+
+    assert!(
+        changelog.contains("\n## Contributors\n\n* @Alice\n* bob\n* @zed\n"),
+        "Contributors should be unique and sorted alphabetically: {}",
+        changelog
+    );
+    assert!(!changelog.contains("New contributors"), "New contributors need known history");
+}
+
+#[test]
+fn test_new_contributors_subsection() {
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(authored("c1", "feat: One", "Alice", "alice@example.com", Some("alice")));
+    summary.commits.push(authored("c2", "fix: Two", "Carol", "carol@example.com", Some("carol")));
+    summary.commits.push(authored("c3", "fix: Three", "Bob", "BOB@example.com", None));
+    summary.known_contributors = Some(HashSet::from(["alice@example.com".to_string(), "bob@example.com".to_string()]));
+
+    let changelog = render(&summary);
+
+    assert!(changelog.contains("\n## Contributors\n\n* @alice\n* Bob\n* @carol\n"), "{}", changelog);
+    assert!(
+        changelog.contains("\n### New contributors\n\n* @carol made their first contribution\n"),
+        "Only authors missing from earlier history should be new: {}",
+        changelog
+    );
+}
+
+#[test]
+fn test_contributors_section_is_opt_in() {
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(authored("c1", "feat: One", "Alice", "alice@example.com", Some("alice")));
+
+    let changelog = format_changelog_with_options(
+        &summary, &Version::new(1, 1, 0), &Version::new(1, 0, 0), &RepoInfo::new(), &ChangelogOptions::default(),
+    );
+
+    assert!(!changelog.contains("## Contributors"));
+}
+
+#[test]
+fn test_collect_previous_contributors_from_history() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    run_and_show_command("git", &["init"], repo_path);
+
+    for (i, (name, email)) in [("Alice", "alice@example.com"), ("Bob", "bob@example.com")].iter().enumerate() {
+        run_and_show_command("git", &["config", "user.name", name], repo_path);
+        run_and_show_command("git", &["config", "user.email", email], repo_path);
+        fs::write(repo_path.join(format!("{}.md", i)), "content").expect("Failed to write file");
+        run_and_show_command("git", &["add", "."], repo_path);
+        run_and_show_command("git", &["commit", "-m", &format!("fix: change {}", i)], repo_path);
+    }
+
+    let repo = Repository::open(repo_path).expect("Failed to open repository");
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+
+    let all = collect_previous_contributors(&repo, &head, 100).unwrap();
+    assert!(all.contains("alice@example.com") && all.contains("bob"), "{:?}", all);
+
+    let bounded = collect_previous_contributors(&repo, &head, 1).unwrap();
+    assert!(!bounded.contains("alice@example.com"), "The walk should respect its limit");
+}
@@ -0,0 +1,130 @@
+use std::fs;
+use std::path::Path;
+
+use git2::Repository;
+use vnext::core::git::working_tree_status;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command, run_vnext, run_vnext_output};
+
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    fs::write(repo_path.join("README.md"), "# Test Repository").expect("Failed to write README file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+}
+
+/// The `dirty` field of `vnext --format json`
+fn dirty_flag(repo_path: &Path, args: &[&str]) -> bool {
+    let output = run_vnext_output(repo_path, &[&["--format", "json"][..], args].concat());
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Output should be JSON");
+    json["dirty"].as_bool().expect("dirty should be a boolean")
+}
+
+#[test]
+fn test_clean_tree() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    assert_eq!(run_vnext(repo_path), "0.1.0");
+
+    let repo = Repository::open(repo_path).unwrap();
+    let status = working_tree_status(&repo).unwrap();
+    assert!(!status.is_dirty(false));
+
+    let output = run_vnext_output(repo_path, &["--require-clean"]);
+    assert!(output.status.success());
+    assert!(!dirty_flag(repo_path, &[]));
+}
+
+#[test]
+fn test_modified_tree() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    run_vnext(repo_path);
+    fs::write(repo_path.join("README.md"), "# Changed").expect("Failed to modify README file");
+
+    let repo = Repository::open(repo_path).unwrap();
+    let status = working_tree_status(&repo).unwrap();
+    assert_eq!((status.modified, status.untracked), (1, 0));
+    assert!(status.is_dirty(true), "Modified files are dirty even when untracked files are allowed");
+
+    let output = run_vnext_output(repo_path, &[]);
+    assert!(output.status.success(), "A dirty tree only warns by default");
+
+    let output = run_vnext_output(repo_path, &["--require-clean", "--allow-untracked"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("Working tree is dirty: 1 modified and 0 untracked files"), "{}", stderr);
+    assert!(dirty_flag(repo_path, &["--allow-untracked"]));
+}
+
+#[test]
+fn test_untracked_only_tree() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    run_vnext(repo_path);
+    fs::write(repo_path.join("notes.txt"), "scratch").expect("Failed to write untracked file");
+
+    let repo = Repository::open(repo_path).unwrap();
+    let status = working_tree_status(&repo).unwrap();
+    assert_eq!((status.modified, status.untracked), (0, 1));
+
+    let output = run_vnext_output(repo_path, &["--require-clean"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("--allow-untracked"), "{}", stderr);
+
+    let output = run_vnext_output(repo_path, &["--require-clean", "--allow-untracked"]);
+    assert!(output.status.success(), "Untracked-only changes are allowed with --allow-untracked");
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "0.1.0");
+
+    let output = run_vnext_output(repo_path, &["simulate", "--message", "fix: x", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim()).unwrap();
+    assert_eq!(json["dirty"], true);
+
+    assert!(dirty_flag(repo_path, &[]));
+    assert!(!dirty_flag(repo_path, &["--allow-untracked"]));
+}
+
+#[test]
+fn test_staged_changes_and_aliases() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    run_vnext(repo_path);
+    fs::write(repo_path.join("README.md"), "# Staged").expect("Failed to modify README file");
+    run_and_show_command("git", &["add", "README.md"], repo_path);
+
+    let repo = Repository::open(repo_path).unwrap();
+    let status = working_tree_status(&repo).unwrap();
+    assert_eq!((status.modified, status.untracked), (1, 0), "Staged changes are uncommitted changes");
+
+    let output = run_vnext_output(repo_path, &["--fail-on-dirty", "--ignore-untracked"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Working tree is dirty"));
+
+    run_and_show_command("git", &["commit", "-m", "fix: Update README"], repo_path);
+    fs::write(repo_path.join("notes.txt"), "scratch").expect("Failed to write untracked file");
+    let output = run_vnext_output(repo_path, &["--fail-on-dirty", "--ignore-untracked"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn test_bare_repository_is_clean() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path().join("work");
+    fs::create_dir(&repo_path).expect("Failed to create repository directory");
+    create_repo(&repo_path);
+    let bare_path = temp_dir.path().join("bare.git");
+    run_and_show_command("git", &["clone", "--bare", "work", "bare.git"], temp_dir.path());
+
+    let repo = Repository::open(&bare_path).unwrap();
+    assert!(repo.is_bare());
+    let status = working_tree_status(&repo).unwrap();
+    assert!(!status.is_dirty(false));
+}
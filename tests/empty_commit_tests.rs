@@ -0,0 +1,39 @@
+use std::fs;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command, run_vnext, run_vnext_with_args};
+
+#[test]
+fn test_empty_commit_policies() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    init_repo(repo_path);
+
+    let file_path = repo_path.join("README.md");
+    fs::write(&file_path, "# Test Repository").expect("Failed to write README file");
+    run_and_show_command("git", &["add", file_path.to_str().unwrap()], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+
+    let version = run_vnext(repo_path);
+    assert_eq!(version, "0.1.0", "Initial version should be 0.1.0");
+    run_and_show_command("git", &["tag", "v0.1.0"], repo_path);
+
+    // An empty fix commit, e.g. to trigger a CI build
+    run_and_show_command("git", &["commit", "--allow-empty", "-m", "fix: trigger build"], repo_path);
+
+    // Default (classify) keeps today's behavior: a patch bump
+    let version = run_vnext(repo_path);
+    assert_eq!(version, "0.1.1", "Empty fix commit should bump the patch version by default");
+    let version = run_vnext_with_args(repo_path, &["--empty-commits", "classify"]);
+    assert_eq!(version, "0.1.1", "Empty fix commit should bump the patch version with classify");
+    let changelog = run_vnext_with_args(repo_path, &["--changelog"]);
+    assert_eq!(changelog, "### What's changed in v0.1.1\n\n* fix: trigger build");
+
+    // With the noop policy the empty commit does not bump but is still listed
+    let version = run_vnext_with_args(repo_path, &["--empty-commits", "noop"]);
+    assert_eq!(version, "0.1.0", "Empty fix commit should not bump the version with noop");
+    let changelog = run_vnext_with_args(repo_path, &["--empty-commits", "noop", "--changelog"]);
+    assert_eq!(changelog, "### What's changed in v0.1.0\n\n* fix: trigger build (empty)");
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use vnext::changelog::exclude_labeled_commits;
+use vnext::core::progress::ProgressReporter;
+use vnext::core::release::exclude_labeled_from_bump;
+use vnext::github::{enhance_with_github_info, CommitMetadata, CommitMetadataProvider, PullRequestInfo};
+use vnext::models::options::VNextOptions;
+use vnext::models::review::IgnoreRevs;
+use vnext::{ChangesetSummary, Commit, CommitAuthor, RepoInfo, VNextError, VersionCalculator};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext, run_vnext_output};
+
+/// Provider answering with the pull request labels of each commit
+struct LabelProvider {
+    labels: HashMap<String, Vec<String>>,
+    provides_labels: bool,
+}
+
+impl LabelProvider {
+    fn new(labels: &[(&str, &[&str])], provides_labels: bool) -> Self {
+        let labels = labels
+            .iter()
+            .map(|(id, labels)| (id.to_string(), labels.iter().map(|l| l.to_string()).collect()))
+            .collect();
+        LabelProvider { labels, provides_labels }
+    }
+}
+
+impl CommitMetadataProvider for LabelProvider {
+    fn fetch_authors(
+        &self,
+        _repo: &RepoInfo,
+        _commit_ids: &[String],
+        _progress: &ProgressReporter,
+    ) -> Result<HashMap<String, CommitAuthor>, VNextError> {
+        Ok(HashMap::new())
+    }
+
+    fn fetch_metadata(
+        &self,
+        _repo: &RepoInfo,
+        commit_ids: &[String],
+        _progress: &ProgressReporter,
+    ) -> Result<HashMap<String, CommitMetadata>, VNextError> {
+        let mut metadata = HashMap::new();
+        for (i, commit_id) in commit_ids.iter().enumerate() {
+            let Some(labels) = self.labels.get(commit_id) else { continue };
+            let pull_request = PullRequestInfo { number: i as u64 + 1, title: format!("PR {}", i + 1), labels: labels.clone() };
+            metadata.insert(commit_id.clone(), CommitMetadata { author: None, pull_request: Some(pull_request) });
+        }
+        Ok(metadata)
+    }
+
+    fn provides_labels(&self) -> bool {
+        self.provides_labels
+    }
+}
+
+fn repo_info() -> RepoInfo {
+    RepoInfo { owner: "owner".to_string(), name: "repo".to_string(), is_github_repo: true, ..RepoInfo::new() }
+}
+
+fn summary(messages: &[(&str, &str)]) -> ChangesetSummary {
+    let mut summary = ChangesetSummary::new();
+    for (id, message) in messages {
+        summary.commits.push(Commit::parse(id.to_string(), message.to_string()));
+    }
+    summary
+}
+
+fn titles(summary: &ChangesetSummary) -> Vec<&str> {
+    summary.commits.iter().map(|commit| commit.title.as_str()).collect()
+}
+
+#[test]
+fn test_exclude_labeled_commits() {
+    let provider = LabelProvider::new(&[("a", &["dependencies"]), ("b", &["Internal", "ci"]), ("c", &["enhancement"])], true);
+    let mut summary = summary(&[
+        ("a", "chore: Bump serde"),
+        ("b", "ci: Cache the toolchain"),
+        ("c", "feat: Add export"),
+        ("d", "fix: Direct push without a pull request"),
+    ]);
+    enhance_with_github_info(&provider, &repo_info(), &mut summary, &ProgressReporter::none()).unwrap();
+    assert!(summary.labels_known);
+
+    let labels = vec!["dependencies".to_string(), "internal".to_string()];
+    exclude_labeled_commits(&mut summary, &labels);
+    // Labels match case-insensitively; commits without a pull request have no labels
+    assert_eq!(titles(&summary), vec!["Add export", "Direct push without a pull request"]);
+}
+
+#[test]
+fn test_unknown_labels_exclude_nothing() {
+    // A provider that only knows authors leaves the labels unknown
+    let provider = LabelProvider::new(&[("a", &["dependencies"])], false);
+    let mut summary = summary(&[("a", "chore: Bump serde"), ("b", "feat: Add export")]);
+    enhance_with_github_info(&provider, &repo_info(), &mut summary, &ProgressReporter::none()).unwrap();
+    assert!(!summary.labels_known);
+
+    exclude_labeled_commits(&mut summary, &["dependencies".to_string()]);
+    assert_eq!(titles(&summary), vec!["Bump serde", "Add export"]);
+}
+
+/// A repository released as 1.0.0 with a dependency update feature and a fix pending
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    commit_file(repo_path, "Cargo.lock", "feat(deps): Update serde to 1.1");
+    commit_file(repo_path, "fix.txt", "fix: Handle empty files");
+}
+
+#[test]
+fn test_labels_affect_bump() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+
+    let options = VNextOptions {
+        changelog_exclude_labels: vec!["dependencies".to_string()],
+        labels_affect_bump: true,
+        ..VNextOptions::default()
+    };
+    let mut plan = VersionCalculator::from_options(repo_path, &options).run().unwrap();
+    assert_eq!(plan.next_version.to_string(), "1.1.0");
+
+    // Without fetched labels the bump stays
+    exclude_labeled_from_bump(repo_path, &mut plan, &options, &IgnoreRevs::new()).unwrap();
+    assert_eq!(plan.next_version.to_string(), "1.1.0");
+
+    let dependency_update = plan.summary.commits.iter().find(|c| c.title.starts_with("Update serde")).unwrap();
+    let provider = LabelProvider::new(&[(&dependency_update.commit_id.clone(), &["dependencies"])], true);
+    enhance_with_github_info(&provider, &repo_info(), &mut plan.summary, &ProgressReporter::none()).unwrap();
+    exclude_labeled_from_bump(repo_path, &mut plan, &options, &IgnoreRevs::new()).unwrap();
+    assert_eq!(plan.next_version.to_string(), "1.0.1");
+    assert_eq!(plan.bump.level(), "patch");
+    assert_eq!((plan.summary.minor, plan.summary.patch, plan.summary.noop), (0, 1, 1));
+    assert_eq!(plan.summary.commits.len(), 2, "The commits stay listed for the changelog filter");
+}
+
+#[test]
+fn test_exclude_labels_without_enrichment() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    // A repository without a GitHub remote has no labels: nothing is excluded, with a warning
+    let output = run_vnext_output(repo_path, &["--changelog", "--exclude-labels", "dependencies", "--labels-affect-bump"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let changelog = String::from_utf8_lossy(&output.stdout);
+    assert!(changelog.contains("What's changed in v1.1.0"), "{}", changelog);
+    assert!(changelog.contains("feat(deps): Update serde to 1.1"), "{}", changelog);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no commits are excluded by label"), "{}", stderr);
+
+    // Without --exclude-labels there is no warning
+    let output = run_vnext_output(repo_path, &["--changelog"]);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("excluded by label"));
+}
@@ -0,0 +1,79 @@
+use std::path::Path;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, run_and_show_command, run_vnext, run_vnext_output, stdout};
+
+fn create_repo(repo_path: &Path) {
+    run_and_show_command("git", &["init", "-b", "main"], repo_path);
+    run_and_show_command("git", &["config", "user.name", "patrickleet"], repo_path);
+    run_and_show_command("git", &["config", "user.email", "pat@patscott.io"], repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.3.0"], repo_path);
+}
+
+#[test]
+fn test_existing_tag_on_another_commit() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // v1.3.1 was tagged on a hotfix branch that never reached main
+    run_and_show_command("git", &["checkout", "-b", "hotfix"], repo_path);
+    commit_file(repo_path, "hotfix.txt", "fix: Hotfix");
+    run_and_show_command("git", &["tag", "v1.3.1"], repo_path);
+    run_and_show_command("git", &["checkout", "main"], repo_path);
+    commit_file(repo_path, "notes.txt", "chore: Tidy up");
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let output = run_vnext_output(repo_path, &[]);
+    assert_eq!(output.status.code(), Some(5));
+    assert_eq!(stdout(&output), "");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("v1.3.1") && stderr.contains("--allow-existing-tag"), "{}", stderr);
+
+    let output = run_vnext_output(repo_path, &["--allow-existing-tag"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(stdout(&output), "1.3.1");
+
+    // Once main has a releasable commit, the next version is free again
+    commit_file(repo_path, "export.txt", "feat: Add export");
+    assert_eq!(stdout(&run_vnext_output(repo_path, &[])), "1.4.0");
+}
+
+#[test]
+fn test_forced_version_with_an_existing_tag() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // 1.2.5 was released from the same commit as 1.3.0
+    run_and_show_command("git", &["tag", "v1.2.5"], repo_path);
+    commit_file(repo_path, "export.txt", "feat: Add export");
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let args = ["--force-version", "1.2.5", "--allow-downgrade"];
+    let output = run_vnext_output(repo_path, &args);
+    assert_eq!(output.status.code(), Some(5));
+    let output = run_vnext_output(repo_path, &[&args[..], &["--allow-existing-tag"]].concat());
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(stdout(&output), "1.2.5");
+}
+
+#[test]
+fn test_existing_tag_at_head() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    commit_file(repo_path, "export.txt", "feat: Add export");
+    // Builds the binary
+    run_vnext(repo_path);
+    assert_eq!(stdout(&run_vnext_output(repo_path, &[])), "1.4.0");
+
+    // Re-running after the release was tagged reports the same version
+    run_and_show_command("git", &["tag", "v1.4.0"], repo_path);
+    let output = run_vnext_output(repo_path, &[]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(stdout(&output), "1.4.0");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already cut"));
+}
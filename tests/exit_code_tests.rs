@@ -0,0 +1,92 @@
+use std::fs;
+use std::process::Command;
+
+use vnext::VNextError;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command, run_vnext, run_vnext_output, stdout};
+
+#[test]
+fn test_exit_code_policy() {
+    assert_eq!(VNextError::ConfigError("bad flag".to_string()).exit_code(), 2);
+    assert_eq!(VNextError::NotARepository("no .git".to_string()).exit_code(), 4);
+    assert_eq!(VNextError::RepositoryState("Working tree is dirty: 1 modified file".to_string()).exit_code(), 5);
+    assert_eq!(VNextError::GitError(git2::Error::from_str("broken")).exit_code(), 5);
+    assert_eq!(VNextError::GithubRequired("rate limited".to_string()).exit_code(), 6);
+    // Only enrichment required by --require-github has its own code
+    assert_eq!(VNextError::GithubError("rate limited".to_string()).exit_code(), 1);
+    assert_eq!(VNextError::Other("anything else".to_string()).exit_code(), 1);
+}
+
+#[test]
+fn test_empty_directory_and_uninitialized_repository() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    // Builds the binary
+    run_vnext(repo_path);
+
+    // An empty directory is not a repository, so no version is printed
+    let output = run_vnext_output(repo_path, &[]);
+    assert_eq!(output.status.code(), Some(4));
+    assert_eq!(stdout(&output), "");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Not a Git repository"));
+    assert_eq!(run_vnext_output(repo_path, &["--current"]).status.code(), Some(4));
+
+    // --lenient restores the initial version fallback
+    let output = run_vnext_output(repo_path, &["--lenient"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "0.0.0");
+
+    // A repository without commits is a first release waiting to happen
+    run_and_show_command("git", &["init"], repo_path);
+    let output = run_vnext_output(repo_path, &[]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "0.0.0");
+}
+
+#[test]
+fn test_usage_state_and_github_errors() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    init_repo(repo_path);
+    fs::write(repo_path.join("README.md"), "# Test").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    assert_eq!(run_vnext_output(repo_path, &["--no-such-flag"]).status.code(), Some(2));
+    assert_eq!(run_vnext_output(repo_path, &["--first-release"]).status.code(), Some(2));
+
+    fs::write(repo_path.join("notes.txt"), "scratch").expect("Failed to write untracked file");
+    assert_eq!(run_vnext_output(repo_path, &["--require-clean"]).status.code(), Some(5));
+    fs::remove_file(repo_path.join("notes.txt")).unwrap();
+
+    // Without a GitHub remote the enrichment --require-github asks for cannot happen
+    let output = run_vnext_output(repo_path, &["--changelog", "--require-github"]);
+    assert_eq!(output.status.code(), Some(6));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--require-github"));
+    // The version alone needs no enrichment
+    assert_eq!(run_vnext_output(repo_path, &["--require-github"]).status.code(), Some(0));
+}
+
+#[test]
+fn test_other_github_errors_exit_with_1() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    // Builds the binary
+    run_vnext(temp_dir.path());
+
+    // Without a token or the GitHub CLI, listing deploy keys fails with a GitHub error
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let output = Command::new(project_dir.join("target/debug/vnext"))
+        .args(["deploy-key", "list", "--owner", "unbounded-tech", "--name", "vnext"])
+        .current_dir(temp_dir.path())
+        .env("PATH", "")
+        .env_remove("GITHUB_TOKEN")
+        .env_remove("GH_TOKEN")
+        .output()
+        .expect("Failed to execute vnext");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("GitHub CLI"), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(output.status.code(), Some(1));
+}
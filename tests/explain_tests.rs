@@ -0,0 +1,91 @@
+use std::path::Path;
+use vnext::core::version::{explain_commit, VersionBumpType};
+use vnext::{BumpMap, BumpReason, Commit};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext, run_vnext_success};
+
+fn explain(message: &str) -> (VersionBumpType, &'static str) {
+    let commit = Commit::parse("abc1234".to_string(), message.to_string());
+    let (bump_type, reason) = explain_commit(&commit, &BumpMap::default());
+    (bump_type, reason.as_str())
+}
+
+#[test]
+fn test_explain_commit_reasons() {
+    assert_eq!(explain("feat!: Drop the v1 API"), (VersionBumpType::Major, "breaking-bang"));
+    assert_eq!(explain("fix(api)!: Rename the endpoint"), (VersionBumpType::Major, "breaking-bang"));
+    assert_eq!(
+        explain("fix: Change the default\n\nBREAKING CHANGE: the default is now off"),
+        (VersionBumpType::Major, "breaking-footer")
+    );
+    assert_eq!(explain("feat: Add export"), (VersionBumpType::Minor, "type-list"));
+    assert_eq!(explain("major: Rewrite"), (VersionBumpType::Major, "type-list"));
+    assert_eq!(explain("chore: Update dependencies"), (VersionBumpType::NoOp, "type-list"));
+    assert_eq!(explain("fix: Handle empty input"), (VersionBumpType::Patch, "unknown-fallback"));
+    assert_eq!(explain("Update README"), (VersionBumpType::Patch, "unknown-fallback"));
+}
+
+#[test]
+fn test_reason_names_match_serialization() {
+    for reason in [
+        BumpReason::BreakingBang,
+        BumpReason::BreakingFooter,
+        BumpReason::TypeList,
+        BumpReason::UnknownFallback,
+        BumpReason::Review,
+        BumpReason::SkipRelease,
+        BumpReason::OutOfScope,
+        BumpReason::EmptyCommit,
+    ] {
+        assert_eq!(serde_json::to_value(reason).unwrap(), reason.as_str());
+    }
+}
+
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    commit_file(repo_path, "a.md", "fix(api)!: Rename the endpoint");
+    commit_file(repo_path, "b.md", "feat: Add export");
+    commit_file(repo_path, "c.md", "chore: Tidy up [skip release]");
+}
+
+#[test]
+fn test_explain_prints_a_table_and_the_decision() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    assert_eq!(run_vnext(repo_path), "2.0.0");
+
+    let stdout = run_vnext_success(repo_path, &["--explain"]);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[0].starts_with("SHA"), "Expected a header line: {}", stdout);
+    assert!(lines[0].ends_with("REASON"));
+    // Newest first
+    let columns = |line: &str| line.split_whitespace().skip(1).map(str::to_string).collect::<Vec<_>>();
+    assert_eq!(columns(lines[1]), ["chore", "-", "no", "none", "skip-release"]);
+    assert_eq!(columns(lines[2]), ["feat", "-", "no", "minor", "type-list"]);
+    assert_eq!(columns(lines[3]), ["fix", "api", "yes", "major", "breaking-bang"]);
+    assert_eq!(lines.last().unwrap(), &"Decision: major bump, 1.0.0 -> 2.0.0 (highest level of 3 commits)");
+}
+
+#[test]
+fn test_explain_json() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+
+    let stdout = run_vnext_success(repo_path, &["--explain", "--format", "json", "--force-bump", "minor"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("Output should be JSON");
+    assert_eq!(json["version"], "1.1.0");
+    assert_eq!(json["bump"], "minor");
+    assert_eq!(json["forced"], "bump");
+    let commits = json["commits"].as_array().unwrap();
+    let reasons: Vec<&str> = commits.iter().map(|commit| commit["reason"].as_str().unwrap()).collect();
+    assert_eq!(reasons, ["skip-release", "type-list", "breaking-bang"]);
+    assert_eq!(commits[2]["level"], "major");
+    assert_eq!(commits[2]["scope"], "api");
+    assert_eq!(commits[2]["breaking"], true);
+}
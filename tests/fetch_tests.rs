@@ -0,0 +1,75 @@
+use std::fs;
+use std::path::Path;
+
+use clap::Parser;
+use vnext::cli::Cli;
+use vnext::models::options::FetchMode;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, run_and_show_command, run_vnext, run_vnext_output, stdout};
+
+fn configure(repo_path: &Path) {
+    run_and_show_command("git", &["config", "user.name", "patrickleet"], repo_path);
+    run_and_show_command("git", &["config", "user.email", "pat@patscott.io"], repo_path);
+}
+
+#[test]
+fn test_fetch_flags() {
+    assert_eq!(Cli::parse_from(["vnext"]).to_options().fetch, None);
+    assert_eq!(Cli::parse_from(["vnext", "--fetch"]).to_options().fetch, Some(FetchMode::Optional));
+    assert_eq!(Cli::parse_from(["vnext", "--fetch=required"]).to_options().fetch, Some(FetchMode::Required));
+    assert_eq!(Cli::parse_from(["vnext", "--fetch", "--no-fetch"]).to_options().fetch, None, "The last flag wins");
+    assert_eq!(Cli::parse_from(["vnext", "--no-fetch", "--fetch"]).to_options().fetch, Some(FetchMode::Optional));
+    assert!(Cli::try_parse_from(["vnext", "--fetch=always"]).is_err());
+}
+
+#[test]
+fn test_fetch_tags_from_origin() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let upstream = temp_dir.path().join("upstream");
+    let work = temp_dir.path().join("work");
+    fs::create_dir(&upstream).unwrap();
+    run_and_show_command("git", &["init"], &upstream);
+    configure(&upstream);
+    commit_file(&upstream, "README.md", "feat: Initial commit");
+
+    let url = format!("file://{}", upstream.display());
+    run_and_show_command("git", &["clone", &url, work.to_str().unwrap()], temp_dir.path());
+    configure(&work);
+    // The release is tagged upstream after the clone
+    run_and_show_command("git", &["tag", "v1.0.0"], &upstream);
+    commit_file(&work, "fix.txt", "fix: Handle empty input");
+    // Builds the binary
+    run_vnext(&work);
+
+    assert_eq!(stdout(&run_vnext_output(&work, &[])), "0.1.0", "Without --fetch only local tags count");
+    let output = run_vnext_output(&work, &["--fetch"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(stdout(&output), "1.0.1");
+    assert!(git2::Repository::open(&work).unwrap().find_reference("refs/tags/v1.0.0").is_ok(), "The tag is stored locally");
+}
+
+#[test]
+fn test_fetch_failure() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    run_and_show_command("git", &["init"], repo_path);
+    configure(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    commit_file(repo_path, "fix.txt", "fix: Handle empty input");
+    run_and_show_command("git", &["remote", "add", "origin", "file:///nonexistent/vnext-upstream"], repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    // A failed fetch only warns by default
+    let output = run_vnext_output(repo_path, &["--fetch"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "1.0.1");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to fetch tags"), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let output = run_vnext_output(repo_path, &["--fetch=required"]);
+    assert_eq!(output.status.code(), Some(5));
+    assert_eq!(stdout(&output), "");
+}
@@ -0,0 +1,105 @@
+use std::fs;
+
+use semver::Version;
+use vnext::changelog::{filter_changelog_commits, format_changelog_with_options, RepoInfo};
+use vnext::core::version::{determine_version_bump, VersionBumpType};
+use vnext::models::options::ChangelogOptions;
+use vnext::version::{ChangesetSummary, Commit};
+use vnext::{apply_footer_rules, parse_footer_rule, FooterAction, FooterRule};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command, run_vnext, run_vnext_with_args};
+
+fn rules(specs: &[&str]) -> Vec<FooterRule> {
+    specs.iter().map(|spec| parse_footer_rule(spec).expect("Rule should parse")).collect()
+}
+
+fn parse_with_rules(message: &str, rules: &[FooterRule]) -> Commit {
+    let mut commit = Commit::parse("abc123".to_string(), message.to_string());
+    apply_footer_rules(&mut commit, rules);
+    commit
+}
+
+#[test]
+fn test_parse_footer_rules() {
+    assert_eq!(
+        parse_footer_rule("Reverts=classify-as:Revert").unwrap(),
+        FooterRule { token: "Reverts".to_string(), value: None, action: FooterAction::ClassifyAs("revert".to_string()) }
+    );
+    assert_eq!(
+        parse_footer_rule("Refs:RELEASE-BLOCKER=force-include").unwrap(),
+        FooterRule { token: "Refs".to_string(), value: Some("RELEASE-BLOCKER".to_string()), action: FooterAction::ForceInclude }
+    );
+    assert_eq!(parse_footer_rule("Closes=link-as-issue").unwrap().action, FooterAction::LinkAsIssue);
+
+    assert!(parse_footer_rule("Reverts").is_err());
+    assert!(parse_footer_rule("=force-include").is_err());
+    assert!(parse_footer_rule("Reverts=classify-as").is_err());
+    assert!(parse_footer_rule("Reverts=explode").is_err());
+}
+
+#[test]
+fn test_classify_as_overrides_the_subject_type() {
+    let message = "fix: Undo the cache change\n\nThe cache broke logins.\n\nReverts: 1a2b3c4";
+    let noop_types = ["chore", "noop"];
+
+    let plain = parse_with_rules(message, &[]);
+    assert_eq!(determine_version_bump(&plain, &["major"], &["feat"], &noop_types), VersionBumpType::Patch);
+
+    let classified = parse_with_rules(message, &rules(&["reverts=classify-as:noop"]));
+    assert_eq!(classified.commit_type, "noop");
+    assert_eq!(determine_version_bump(&classified, &["major"], &["feat"], &noop_types), VersionBumpType::NoOp);
+
+    // Footers inside a description paragraph are ignored
+    let not_footer = parse_with_rules("fix: Undo\n\nReverts: 1a2b3c4\nbecause it broke logins.", &rules(&["Reverts=classify-as:noop"]));
+    assert_eq!(not_footer.commit_type, "fix");
+}
+
+#[test]
+fn test_force_include_bypasses_changelog_filters() {
+    let rules = rules(&["Refs:release-blocker=force-include"]);
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(parse_with_rules("chore: Pin the toolchain\n\nRefs: RELEASE-BLOCKER", &rules));
+    summary.commits.push(parse_with_rules("chore: Tidy imports\n\nRefs: JIRA-12", &rules));
+    summary.commits.push(parse_with_rules("docs: Fix typo", &rules));
+
+    filter_changelog_commits(&mut summary, true, &["chore"], &["feat", "fix"]);
+
+    assert_eq!(summary.commits.len(), 1);
+    assert_eq!(summary.commits[0].title, "Pin the toolchain");
+}
+
+#[test]
+fn test_link_as_issue_renders_references() {
+    let rules = rules(&["Closes=link-as-issue", "Refs=link-as-issue"]);
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(parse_with_rules("fix: Handle empty input\n\nCloses: #42\nRefs: ABC-7", &rules));
+
+    let changelog = format_changelog_with_options(
+        &summary, &Version::new(1, 0, 1), &Version::new(1, 0, 0), &RepoInfo::new(), &ChangelogOptions::default(),
+    );
+
+    assert_eq!(changelog, "### What's changed in v1.0.1\n\n* fix: Handle empty input (refs #42, ABC-7)\n\n");
+}
+
+#[test]
+fn test_footer_rule_flag() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    init_repo(repo_path);
+
+    fs::write(repo_path.join("README.md"), "# Test").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+
+    fs::write(repo_path.join("README.md"), "# Test again").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "fix: Undo the readme change\n\nReverts: 1a2b3c4"], repo_path);
+
+    // Builds the binary; without a rule the fix is a patch
+    assert_eq!(run_vnext(repo_path), "1.0.1");
+    assert_eq!(run_vnext_with_args(repo_path, &["--footer-rule", "Reverts=classify-as:noop"]), "1.0.0");
+}
@@ -0,0 +1,109 @@
+use std::path::Path;
+
+use semver::Version;
+use vnext::core::version::apply_version_override;
+use vnext::version::{BumpLevel, VersionBump, VersionOverride};
+use vnext::VNextError;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext, run_vnext_output, stdout};
+
+/// A repository released as 1.2.0 with only a pending chore
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.2.0"], repo_path);
+    commit_file(repo_path, "Dockerfile", "chore: Rebuild on the patched base image");
+}
+
+#[test]
+fn test_apply_version_override() {
+    let current = Version::new(1, 2, 0);
+    let noop = VersionBump::from(BumpLevel::None);
+
+    let (next, bump) = apply_version_override(&current, noop, None, false).unwrap();
+    assert_eq!((next.to_string(), bump.bump_level()), ("1.2.0".to_string(), BumpLevel::None));
+
+    let forced = VersionOverride::Bump(BumpLevel::Patch);
+    let (next, bump) = apply_version_override(&current, noop, Some(&forced), false).unwrap();
+    assert_eq!((next.to_string(), bump.bump_level()), ("1.2.1".to_string(), BumpLevel::Patch));
+
+    // A forced bump also replaces a higher calculated one
+    let forced = VersionOverride::Bump(BumpLevel::Minor);
+    let (next, _) = apply_version_override(&current, VersionBump::from(BumpLevel::Major), Some(&forced), false).unwrap();
+    assert_eq!(next.to_string(), "1.3.0");
+
+    let forced = VersionOverride::Version(Version::new(2, 0, 0));
+    let (next, bump) = apply_version_override(&current, noop, Some(&forced), false).unwrap();
+    assert_eq!((next.to_string(), bump.bump_level()), ("2.0.0".to_string(), BumpLevel::Major));
+
+    for version in [Version::new(1, 2, 0), Version::new(1, 1, 9)] {
+        let forced = VersionOverride::Version(version);
+        match apply_version_override(&current, noop, Some(&forced), false) {
+            Err(VNextError::ConfigError(message)) => assert!(message.contains("--allow-downgrade"), "{}", message),
+            other => panic!("Expected a downgrade error, got {:?}", other.map(|(v, _)| v)),
+        }
+        assert!(apply_version_override(&current, noop, Some(&forced), true).is_ok());
+    }
+}
+
+#[test]
+fn test_force_bump() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    assert_eq!(stdout(&run_vnext_output(repo_path, &[])), "1.2.0");
+    assert_eq!(stdout(&run_vnext_output(repo_path, &["--force-bump", "patch"])), "1.2.1");
+
+    let changelog = stdout(&run_vnext_output(repo_path, &["--force-bump", "major", "--changelog"]));
+    assert!(changelog.contains("What's changed in v2.0.0"), "The heading shows the forced version: {}", changelog);
+    assert!(changelog.contains("chore: Rebuild on the patched base image"), "{}", changelog);
+
+    let output = run_vnext_output(repo_path, &["--force-bump", "patch", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).expect("stdout should be JSON");
+    assert_eq!(json["version"], "1.2.1");
+    assert_eq!(json["bump"], "patch");
+    assert_eq!(json["forced"], "bump");
+
+    let output = run_vnext_output(repo_path, &["--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).expect("stdout should be JSON");
+    assert_eq!(json["bump"], "none");
+    assert!(json["forced"].is_null());
+    assert_eq!(json["commit_types"], serde_json::json!({ "chore": 1 }));
+    assert_eq!(json["breaking_changes"], 0);
+
+    assert!(!run_vnext_output(repo_path, &["--force-bump", "none"]).status.success());
+}
+
+#[test]
+fn test_force_version() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let output = run_vnext_output(repo_path, &["--force-version", "v2.0.0", "--changelog", "--format", "json"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).expect("stdout should be JSON");
+    assert_eq!(json["version"], "2.0.0");
+    assert_eq!(json["current_version"], "1.2.0");
+    assert_eq!(json["bump"], "major");
+    assert_eq!(json["forced"], "version");
+    let changelog = json["changelog"].as_str().unwrap();
+    assert!(changelog.contains("What's changed in v2.0.0"), "{}", changelog);
+    assert!(changelog.contains("chore: Rebuild on the patched base image"), "The changelog covers the commits since the last tag: {}", changelog);
+
+    // Versions not greater than the current one need --allow-downgrade
+    let output = run_vnext_output(repo_path, &["--force-version", "1.2.0"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--allow-downgrade"));
+    assert_eq!(stdout(&run_vnext_output(repo_path, &["--force-version", "1.1.5", "--allow-downgrade"])), "1.1.5");
+
+    // The two overrides are exclusive
+    assert!(!run_vnext_output(repo_path, &["--force-version", "2.0.0", "--force-bump", "minor"]).status.success());
+}
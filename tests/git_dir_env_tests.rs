@@ -0,0 +1,45 @@
+use std::fs;
+
+use semver::Version;
+use vnext::git::{open_repository, open_repository_at};
+use vnext::VersionCalculator;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command};
+
+/// GIT_DIR is process-wide, so this file holds a single test that sets it
+#[test]
+fn test_explicit_paths_win_over_git_dir() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path().join("work");
+    let other_path = temp_dir.path().join("other");
+    for path in [&repo_path, &other_path] {
+        fs::create_dir(path).expect("Failed to create repository directory");
+        init_repo(path);
+        commit_file(path, "README.md", "feat: Initial commit");
+        run_and_show_command("git", &["tag", "v1.0.0"], path);
+    }
+    commit_file(&repo_path, "fix.txt", "fix: Handle empty files");
+    commit_file(&other_path, "feature.txt", "feat: Add export");
+
+    std::env::set_var("GIT_DIR", other_path.join(".git"));
+
+    // Only the path-less entry point follows GIT_DIR, like git itself
+    let repo = open_repository().expect("GIT_DIR should open");
+    assert_eq!(repo.path(), other_path.join(".git").canonicalize().unwrap().join(""));
+    let repo = open_repository_at(&repo_path).expect("The path should open");
+    assert_eq!(repo.path(), repo_path.join(".git").canonicalize().unwrap().join(""));
+
+    let plan = VersionCalculator::for_repo(&repo_path).run().expect("Calculation should succeed");
+    assert_eq!(plan.next_version, Version::new(1, 0, 1));
+
+    // An explicit git directory wins over the path
+    let plan = VersionCalculator::for_repo(&repo_path)
+        .with_git_dir(other_path.join(".git"))
+        .run()
+        .expect("Calculation should succeed");
+    assert_eq!(plan.next_version, Version::new(1, 1, 0));
+
+    std::env::remove_var("GIT_DIR");
+}
@@ -0,0 +1,82 @@
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, run_and_show_command, run_vnext, stdout, vnext_command};
+
+fn run_vnext_with_git_dir(dir: &Path, args: &[&str], git_dir: Option<&Path>) -> Output {
+    let mut command = vnext_command(dir, args);
+    command.env("LOG_LEVEL", "debug");
+    if let Some(git_dir) = git_dir {
+        command.env("GIT_DIR", git_dir);
+    }
+    command.output().expect("Failed to execute vnext")
+}
+
+/// Create a checkout at `<root>/work` whose git directory lives at `<root>/meta.git`,
+/// released as 1.0.0 with a pending fix in `src/`
+fn create_repo(root: &Path) -> (std::path::PathBuf, std::path::PathBuf) {
+    let repo_path = root.join("work");
+    let git_dir = root.join("meta.git");
+    fs::create_dir(&repo_path).expect("Failed to create repository directory");
+    run_and_show_command("git", &["init", "--separate-git-dir", git_dir.to_str().unwrap()], &repo_path);
+    run_and_show_command("git", &["config", "user.name", "patrickleet"], &repo_path);
+    run_and_show_command("git", &["config", "user.email", "pat@patscott.io"], &repo_path);
+    commit_file(&repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], &repo_path);
+    fs::create_dir_all(repo_path.join("src/nested")).expect("Failed to create src directory");
+    commit_file(&repo_path, "src/nested/fix.txt", "fix: Handle empty files");
+    (repo_path, git_dir)
+}
+
+#[test]
+fn test_nested_directory() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let (repo_path, _) = create_repo(temp_dir.path());
+    // Builds the binary
+    run_vnext(&repo_path);
+
+    let output = run_vnext_with_git_dir(&repo_path.join("src/nested"), &[], None);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(stdout(&output), "1.0.1");
+}
+
+#[test]
+fn test_git_dir_override() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let (repo_path, git_dir) = create_repo(temp_dir.path());
+    let outside = temp_dir.path().join("outside");
+    fs::create_dir(&outside).expect("Failed to create directory");
+    // Builds the binary
+    run_vnext(&repo_path);
+
+    // Outside any repository the error names the searched path
+    let output = run_vnext_with_git_dir(&outside, &[], None);
+    assert_eq!(output.status.code(), Some(4));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No Git repository found in"), "{}", stderr);
+    assert!(stderr.contains("outside and its parent directories"), "{}", stderr);
+
+    // GIT_DIR points at the repository from anywhere
+    let output = run_vnext_with_git_dir(&outside, &[], Some(&git_dir));
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(stdout(&output), "1.0.1");
+
+    // --git-dir does the same and wins over GIT_DIR
+    let output = run_vnext_with_git_dir(&outside, &["--git-dir", git_dir.to_str().unwrap()], Some(&outside));
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(stdout(&output), "1.0.1");
+
+    // A GIT_DIR that is not a repository is reported as such
+    let output = run_vnext_with_git_dir(&repo_path, &[], Some(&outside));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("in the git directory") && stderr.contains("outside"), "{}", stderr);
+
+    // A missing --git-dir is a usage error
+    let missing = temp_dir.path().join("missing.git");
+    let output = run_vnext_with_git_dir(&repo_path, &["--git-dir", missing.to_str().unwrap()], None);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("missing.git"));
+}
@@ -0,0 +1,173 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use vnext::core::github::GitHubClient;
+use vnext::core::progress::ProgressReporter;
+use vnext::VNextError;
+
+mod mock_server;
+use mock_server::MockServer;
+
+fn commit_json(sha: &str) -> String {
+    format!(
+        r#"{{"sha": "{sha}", "commit": {{"author": {{"name": "Author {sha}", "email": "{sha}@example.com"}}, "message": "fix: Change"}}, "author": {{"login": "user-{sha}"}}}}"#
+    )
+}
+
+#[test]
+fn test_parallel_lookups_keep_order_and_bound_concurrency() {
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let max_in_flight = Arc::new(AtomicUsize::new(0));
+    let (current, max) = (in_flight.clone(), max_in_flight.clone());
+    let server = MockServer::start(move |request| {
+        let running = current.fetch_add(1, Ordering::SeqCst) + 1;
+        max.fetch_max(running, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(50));
+        current.fetch_sub(1, Ordering::SeqCst);
+        let sha = request.path.rsplit('/').next().unwrap().to_string();
+        // An unpushed commit is unknown to the API
+        if sha == "c5" {
+            (422, r#"{"message": "No commit found for SHA: c5"}"#.to_string())
+        } else {
+            (200, commit_json(&sha))
+        }
+    });
+    let client = GitHubClient::new(&server.url, Some("secret".to_string())).with_concurrency(3);
+
+    let commit_ids: Vec<String> = (0..12).map(|i| format!("c{}", i)).collect();
+    let authors = client.fetch_commit_authors("owner", "repo", &commit_ids, &ProgressReporter::none()).unwrap();
+
+    assert_eq!(authors.len(), 12, "Every commit should get a result");
+    for (i, (commit_id, author)) in authors.iter().enumerate() {
+        assert_eq!(commit_id, &format!("c{}", i), "Results should keep the order of the commit ids");
+        match author {
+            Some(author) => assert_eq!(author.username.as_deref(), Some(format!("user-c{}", i).as_str())),
+            None => assert_eq!(i, 5, "Only the unknown commit lacks an author"),
+        }
+    }
+    assert!(authors[5].1.is_none());
+
+    let max = max_in_flight.load(Ordering::SeqCst);
+    assert!(max <= 3, "At most 3 requests should run at once, saw {}", max);
+    assert!(max > 1, "Requests should run in parallel");
+    let requests = server.requests.lock().unwrap();
+    assert_eq!(requests.len(), 12);
+    assert!(requests.iter().all(|r| r.path.starts_with("/repos/owner/repo/commits/")));
+    assert!(requests.iter().all(|r| r.header("Authorization") == Some("token secret")));
+}
+
+#[test]
+fn test_failed_requests_do_not_abort_the_lookup() {
+    // Nothing listens on the port of a dropped listener
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    let client = GitHubClient::new(&url, None);
+
+    let commit_ids: Vec<String> = (0..6).map(|i| format!("c{}", i)).collect();
+    let authors = client.fetch_commit_authors("owner", "repo", &commit_ids, &ProgressReporter::none()).unwrap();
+    assert_eq!(authors.len(), 6);
+    assert!(authors.iter().all(|(_, author)| author.is_none()));
+
+    // A broken response only costs its own commit the author
+    let server = MockServer::start(|request| {
+        let sha = request.path.rsplit('/').next().unwrap().to_string();
+        if sha == "c2" {
+            (200, "{not json".to_string())
+        } else {
+            (200, commit_json(&sha))
+        }
+    });
+    let client = GitHubClient::new(&server.url, None).with_concurrency(2);
+    let authors = client.fetch_commit_authors("owner", "repo", &commit_ids, &ProgressReporter::none()).unwrap();
+    assert_eq!(server.request_count(), 6, "Every commit should still be looked up");
+    for (i, (_, author)) in authors.iter().enumerate() {
+        assert_eq!(author.is_some(), i != 2, "Only the failed lookup should lack an author");
+    }
+}
+
+#[test]
+fn test_cached_authors_skip_the_api() {
+    let server = MockServer::start(|request| {
+        let sha = request.path.rsplit('/').next().unwrap().to_string();
+        if sha == "c2" {
+            (422, r#"{"message": "No commit found for SHA: c2"}"#.to_string())
+        } else {
+            (200, commit_json(&sha))
+        }
+    });
+    let cache_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let client = GitHubClient::new(&server.url, None).with_cache_dir(cache_dir.path());
+    let commit_ids: Vec<String> = vec!["c0".to_string(), "c1".to_string(), "c3".to_string()];
+
+    let first = client.fetch_commit_authors("owner", "repo", &commit_ids, &ProgressReporter::none()).unwrap();
+    assert_eq!(server.request_count(), 3);
+    assert!(cache_dir.path().join("owner/repo/c1.json").is_file());
+
+    let second = client.fetch_commit_authors("owner", "repo", &commit_ids, &ProgressReporter::none()).unwrap();
+    assert_eq!(server.request_count(), 3, "The second run should be served from the cache");
+    for ((_, a), (_, b)) in first.iter().zip(&second) {
+        let (a, b) = (a.as_ref().unwrap(), b.as_ref().unwrap());
+        assert_eq!((&a.name, &a.email, &a.username), (&b.name, &b.email, &b.username));
+    }
+
+    // Unknown commits are not cached, since they may be pushed later
+    client.fetch_commit_authors("owner", "repo", &["c2".to_string()], &ProgressReporter::none()).unwrap();
+    client.fetch_commit_authors("owner", "repo", &["c2".to_string()], &ProgressReporter::none()).unwrap();
+    assert_eq!(server.request_count(), 5);
+
+    // Corrupted entries are fetched again and overwritten
+    std::fs::write(cache_dir.path().join("owner/repo/c1.json"), "{not json").unwrap();
+    let authors = client.fetch_commit_authors("owner", "repo", &commit_ids, &ProgressReporter::none()).unwrap();
+    assert_eq!(server.request_count(), 6);
+    assert_eq!(authors[1].1.as_ref().unwrap().username.as_deref(), Some("user-c1"));
+    client.fetch_commit_authors("owner", "repo", &commit_ids, &ProgressReporter::none()).unwrap();
+    assert_eq!(server.request_count(), 6);
+
+    // Without a cache directory every run asks the API
+    let uncached = GitHubClient::new(&server.url, None);
+    uncached.fetch_commit_authors("owner", "repo", &commit_ids, &ProgressReporter::none()).unwrap();
+    assert_eq!(server.request_count(), 9);
+}
+
+#[test]
+fn test_cache_flags() {
+    use clap::Parser;
+    use vnext::cli::Cli;
+
+    let options = Cli::parse_from(["vnext", "--github-cache", "/tmp/vnext-cache"]).to_options();
+    assert_eq!(options.github_cache, Some(std::path::PathBuf::from("/tmp/vnext-cache")));
+    assert_eq!(Cli::parse_from(["vnext", "--no-cache"]).to_options().github_cache, None);
+    assert!(Cli::try_parse_from(["vnext", "--no-cache", "--github-cache", "/tmp/x"]).is_err());
+}
+
+#[test]
+fn test_secret_exists() {
+    let server = MockServer::start(|request| match request.path.as_str() {
+        "/repos/owner/repo/actions/secrets/DEPLOY_KEY" => (200, r#"{"name": "DEPLOY_KEY", "created_at": "2024-05-01T00:00:00Z", "updated_at": "2024-05-01T00:00:00Z"}"#.to_string()),
+        "/orgs/owner/actions/secrets/ORG_KEY" => (200, r#"{"name": "ORG_KEY", "created_at": "2024-05-01T00:00:00Z", "updated_at": "2024-05-01T00:00:00Z", "visibility": "selected"}"#.to_string()),
+        "/repos/owner/private/actions/secrets/DEPLOY_KEY" => (403, r#"{"message": "Resource not accessible by integration"}"#.to_string()),
+        _ => (404, r#"{"message": "Not Found"}"#.to_string()),
+    });
+    let client = GitHubClient::new(&server.url, Some("secret".to_string()));
+
+    assert!(client.secret_exists("owner", "repo", "DEPLOY_KEY").unwrap());
+    assert!(!client.secret_exists("owner", "repo", "OTHER_KEY").unwrap());
+    assert!(matches!(client.secret_exists("owner", "private", "DEPLOY_KEY"), Err(VNextError::GithubError(_))));
+    let requests = server.requests.lock().unwrap();
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].header("Authorization"), Some("token secret"));
+    drop(requests);
+
+    // Secrets are never readable anonymously
+    let anonymous = GitHubClient::new(&server.url, None);
+    assert!(matches!(anonymous.secret_exists("owner", "repo", "DEPLOY_KEY"), Err(VNextError::GithubError(_))));
+    assert_eq!(server.request_count(), 3);
+
+    // Organization secrets are looked up under the organization
+    assert!(client.org_secret_exists("owner", "ORG_KEY").unwrap());
+    assert!(!client.org_secret_exists("owner", "DEPLOY_KEY").unwrap());
+    assert_eq!(server.requests.lock().unwrap()[3].path, "/orgs/owner/actions/secrets/ORG_KEY");
+}
@@ -0,0 +1,159 @@
+use clap::Parser;
+use regex::Regex;
+use semver::Version;
+use vnext::changelog::format_changelog_with_options;
+use vnext::cli::Cli;
+use vnext::core::progress::ProgressReporter;
+use vnext::github::{enhance_with_github_info, GitHubClient, GRAPHQL_BATCH_SIZE};
+use vnext::models::options::ChangelogOptions;
+use vnext::{ChangesetSummary, Commit, RepoInfo, VNextError};
+
+mod mock_server;
+use mock_server::{MockServer, RecordedRequest};
+
+fn sha(i: usize) -> String {
+    format!("{:040x}", i + 1)
+}
+
+fn repo_info() -> RepoInfo {
+    RepoInfo { owner: "owner".to_string(), name: "repo".to_string(), is_github_repo: true, ..RepoInfo::new() }
+}
+
+/// Answer a GraphQL query for every `object(oid:)` lookup in it: commit 1 is unknown,
+/// odd commits were merged through a pull request
+fn graphql_response(request: &RecordedRequest) -> (u16, String) {
+    let body: serde_json::Value = serde_json::from_str(&request.body).expect("The request is JSON");
+    let query = body["query"].as_str().unwrap();
+    let lookup = Regex::new(r#"(c\d+): object\(oid: "([0-9a-f]+)"\)"#).unwrap();
+    let mut repository = serde_json::Map::new();
+    for captures in lookup.captures_iter(query) {
+        let i = usize::from_str_radix(&captures[2], 16).unwrap() - 1;
+        let object = if i == 1 {
+            serde_json::Value::Null
+        } else {
+            let nodes = if i % 2 == 1 {
+                serde_json::json!([{
+                    "number": 1000 + i,
+                    "title": format!("Pull request {}", i),
+                    "labels": { "nodes": [{ "name": "enhancement" }, { "name": format!("area-{}", i % 3) }] }
+                }])
+            } else {
+                serde_json::json!([])
+            };
+            serde_json::json!({
+                "author": { "name": format!("Author {}", i), "email": format!("{}@example.com", i), "user": { "login": format!("user-{}", i) } },
+                "associatedPullRequests": { "nodes": nodes }
+            })
+        };
+        repository.insert(captures[1].to_string(), object);
+    }
+    (200, serde_json::json!({ "data": { "repository": repository } }).to_string())
+}
+
+#[test]
+fn test_graphql_batches_lookups() {
+    let server = MockServer::start(graphql_response);
+    let client = GitHubClient::new(&server.url, Some("secret".to_string())).with_graphql(true);
+    let commit_ids: Vec<String> = (0..150).map(sha).collect();
+
+    let metadata = client.fetch_commit_metadata_graphql("owner", "repo", &commit_ids, &ProgressReporter::none()).unwrap();
+    assert_eq!(metadata.len(), 149, "The unknown commit is left out");
+
+    let requests = server.requests.lock().unwrap();
+    assert_eq!(requests.len(), 2, "150 commits take two queries");
+    let mut lookups = Vec::new();
+    for request in requests.iter() {
+        assert_eq!((request.method.as_str(), request.path.as_str()), ("POST", "/graphql"));
+        assert_eq!(request.header("Authorization"), Some("bearer secret"));
+        let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(body["variables"], serde_json::json!({ "owner": "owner", "name": "repo" }));
+        lookups.push(body["query"].as_str().unwrap().matches("object(oid:").count());
+    }
+    lookups.sort_unstable();
+    assert_eq!(lookups, vec![50, GRAPHQL_BATCH_SIZE]);
+}
+
+#[test]
+fn test_graphql_field_mapping() {
+    let server = MockServer::start(graphql_response);
+    let client = GitHubClient::new(&server.url, Some("secret".to_string())).with_graphql(true);
+    let mut summary = ChangesetSummary::new();
+    summary.commits = (0..4).map(|i| Commit::parse(sha(i), "feat: Add export".to_string())).collect();
+
+    enhance_with_github_info(&client, &repo_info(), &mut summary, &ProgressReporter::none()).unwrap();
+    let commits = &summary.commits;
+    let author = commits[3].author.as_ref().unwrap();
+    assert_eq!((author.name.as_str(), author.email.as_str(), author.username.as_deref()), ("Author 3", "3@example.com", Some("user-3")));
+    assert_eq!(commits[3].pr_number, Some(1003));
+    assert_eq!(commits[3].pr_title.as_deref(), Some("Pull request 3"));
+    assert_eq!(commits[3].labels, vec!["enhancement", "area-0"]);
+
+    // A commit pushed directly has an author but no pull request
+    assert_eq!(commits[2].author.as_ref().unwrap().username.as_deref(), Some("user-2"));
+    assert_eq!((commits[2].pr_number, commits[2].labels.len()), (None, 0));
+    // The API does not know commit 1
+    assert!(commits[1].author.is_none());
+    assert_eq!(server.request_count(), 1);
+}
+
+#[test]
+fn test_graphql_needs_a_token() {
+    let server = MockServer::start(|request| {
+        let sha = request.path.rsplit('/').next().unwrap().to_string();
+        let body = format!(
+            r#"{{"sha": "{sha}", "commit": {{"author": {{"name": "Jane", "email": "jane@example.com"}}, "message": "fix: Change"}}, "author": {{"login": "jane"}}}}"#
+        );
+        (200, body)
+    });
+    let client = GitHubClient::new(&server.url, None).with_graphql(true);
+    let mut summary = ChangesetSummary::new();
+    summary.commits = vec![Commit::parse(sha(0), "fix: Change".to_string())];
+
+    enhance_with_github_info(&client, &repo_info(), &mut summary, &ProgressReporter::none()).unwrap();
+    assert_eq!(summary.commits[0].author.as_ref().unwrap().username.as_deref(), Some("jane"));
+    assert_eq!(summary.commits[0].pr_number, None);
+    let requests = server.requests.lock().unwrap();
+    assert!(requests[0].path.starts_with("/repos/owner/repo/commits/"), "Without a token the REST API is used: {}", requests[0].path);
+}
+
+#[test]
+fn test_graphql_errors() {
+    let server = MockServer::start(|_| (200, r#"{"data": null, "errors": [{"message": "Bad credentials"}]}"#.to_string()));
+    let client = GitHubClient::new(&server.url, Some("expired".to_string())).with_graphql(true);
+    match client.fetch_commit_metadata_graphql("owner", "repo", &[sha(0)], &ProgressReporter::none()) {
+        Err(VNextError::GithubError(message)) => assert!(message.contains("Bad credentials"), "{}", message),
+        other => panic!("Expected a GitHub error, got {:?}", other.map(|m| m.len())),
+    }
+
+    let server = MockServer::start(|_| (502, "{}".to_string()));
+    let client = GitHubClient::new(&server.url, Some("secret".to_string())).with_graphql(true);
+    assert!(client.fetch_commit_metadata_graphql("owner", "repo", &[sha(0)], &ProgressReporter::none()).is_err());
+}
+
+#[test]
+fn test_prefer_pr_titles() {
+    let mut merged = Commit::parse(sha(0), "feat: add csv export".to_string());
+    merged.pr_number = Some(42);
+    merged.pr_title = Some("Add CSV export for <reports>".to_string());
+    let direct = Commit::parse(sha(1), "fix: Handle empty reports".to_string());
+    let mut summary = ChangesetSummary::new();
+    summary.commits = vec![direct, merged];
+    let (next, current) = (Version::new(1, 1, 0), Version::new(1, 0, 0));
+
+    let options = ChangelogOptions { prefer_pr_titles: true, ..ChangelogOptions::default() };
+    let changelog = format_changelog_with_options(&summary, &next, &current, &RepoInfo::new(), &options);
+    assert!(changelog.contains("* Add CSV export for \\<reports\\> (#42)\n"), "{}", changelog);
+    assert!(changelog.contains("* fix: Handle empty reports\n"), "{}", changelog);
+
+    let changelog = format_changelog_with_options(&summary, &next, &current, &RepoInfo::new(), &ChangelogOptions::default());
+    assert!(changelog.contains("* feat: add csv export\n"), "PR titles are opt-in: {}", changelog);
+}
+
+#[test]
+fn test_graphql_flags() {
+    let options = Cli::parse_from(["vnext", "--github-graphql", "--prefer-pr-titles"]).to_options();
+    assert!(options.github_graphql);
+    assert!(options.changelog.prefer_pr_titles);
+    let options = Cli::parse_from(["vnext"]).to_options();
+    assert!(!options.github_graphql && !options.changelog.prefer_pr_titles);
+}
@@ -0,0 +1,113 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use vnext::utils::github_output::{append_outputs, format_outputs};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext};
+
+/// Parse a step output file the way the runner does: `name=value` lines and
+/// `name<<DELIMITER` blocks ending at a line equal to the delimiter
+fn parse_outputs(text: &str) -> Vec<(String, String)> {
+    let mut outputs = Vec::new();
+    let mut lines = text.lines();
+    while let Some(line) = lines.next() {
+        if let Some((name, delimiter)) = line.split_once("<<") {
+            let value: Vec<&str> = lines.by_ref().take_while(|line| *line != delimiter).collect();
+            outputs.push((name.to_string(), value.join("\n")));
+        } else {
+            let (name, value) = line.split_once('=').expect("name=value line");
+            outputs.push((name.to_string(), value.to_string()));
+        }
+    }
+    outputs
+}
+
+#[test]
+fn test_single_and_multi_line_outputs() {
+    let text = format_outputs(&[("version", "1.2.0"), ("changelog", "### What's changed\n\n* feat: Add export")]);
+    assert_eq!(text, "version=1.2.0\nchangelog<<EOF\n### What's changed\n\n* feat: Add export\nEOF\n");
+}
+
+#[test]
+fn test_delimiter_collision_picks_a_random_token() {
+    let changelog = "### What's changed\n\n* fix: Stop at the EOF marker\n\nEOF\n* chore: Tidy up";
+    let text = format_outputs(&[("changelog", changelog)]);
+    let delimiter = text.lines().next().unwrap().strip_prefix("changelog<<").unwrap();
+    assert_ne!(delimiter, "EOF");
+    assert!(delimiter.starts_with("EOF_"), "{}", delimiter);
+    assert!(!changelog.contains(delimiter));
+    assert_eq!(parse_outputs(&text), [("changelog".to_string(), changelog.to_string())]);
+    // A fresh token for every value
+    assert_ne!(format_outputs(&[("changelog", changelog)]), text);
+}
+
+#[test]
+fn test_append_outputs_keeps_earlier_outputs() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let path = temp_dir.path().join("github_output");
+    fs::write(&path, "earlier=step\n").unwrap();
+    let changelog = "Notes\nEOF\nmore";
+    append_outputs(&path, &[("version", "1.2.0"), ("bump", "minor"), ("changelog", changelog)]).unwrap();
+
+    let outputs = parse_outputs(&fs::read_to_string(&path).unwrap());
+    let expected: Vec<(String, String)> = [("earlier", "step"), ("version", "1.2.0"), ("bump", "minor"), ("changelog", changelog)]
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    assert_eq!(outputs, expected);
+}
+
+fn run_vnext_with_env(repo_path: &Path, args: &[&str], github_output: Option<&Path>) -> Output {
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let mut command = Command::new(project_dir.join("target/debug/vnext"));
+    command.args(args).current_dir(repo_path).env_remove("GITHUB_OUTPUT");
+    if let Some(path) = github_output {
+        command.env("GITHUB_OUTPUT", path);
+    }
+    let output = command.output().expect("Failed to execute vnext");
+    assert!(output.status.success(), "vnext failed: {}", String::from_utf8_lossy(&output.stderr));
+    output
+}
+
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    commit_file(repo_path, "a.md", "feat: Add export\n\nEOF");
+}
+
+#[test]
+fn test_github_output_flag() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    assert_eq!(run_vnext(repo_path), "1.1.0");
+
+    let output_file = repo_path.join("github_output");
+    let output = run_vnext_with_env(repo_path, &["--github-output"], Some(&output_file));
+    // stdout is unchanged
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "1.1.0");
+
+    let outputs = parse_outputs(&fs::read_to_string(&output_file).unwrap());
+    let names: Vec<&str> = outputs.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["version", "current_version", "bump", "changelog"]);
+    assert_eq!(outputs[0].1, "1.1.0");
+    assert_eq!(outputs[1].1, "1.0.0");
+    assert_eq!(outputs[2].1, "minor");
+    let changelog = String::from_utf8_lossy(&run_vnext_with_env(repo_path, &["--changelog"], None).stdout).trim_end().to_string();
+    assert_eq!(outputs[3].1.trim_end(), changelog);
+}
+
+#[test]
+fn test_github_output_without_the_variable_warns() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    run_vnext(repo_path);
+
+    let output = run_vnext_with_env(repo_path, &["--github-output"], None);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "1.1.0");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("GITHUB_OUTPUT is not"), "{}", String::from_utf8_lossy(&output.stderr));
+}
@@ -0,0 +1,96 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use vnext::core::progress::ProgressReporter;
+use vnext::github::{enhance_with_github_info, CommitMetadataProvider};
+use vnext::{ChangesetSummary, Commit, CommitAuthor, RepoInfo, VNextError};
+
+/// Provider answering from a fixed map and recording the lookups it receives
+struct FakeProvider {
+    authors: HashMap<String, CommitAuthor>,
+    error: Option<fn() -> VNextError>,
+    lookups: RefCell<Vec<(String, Vec<String>)>>,
+}
+
+impl FakeProvider {
+    fn new(authors: &[(&str, CommitAuthor)]) -> Self {
+        FakeProvider {
+            authors: authors.iter().map(|(id, author)| (id.to_string(), author.clone())).collect(),
+            error: None,
+            lookups: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl CommitMetadataProvider for FakeProvider {
+    fn fetch_authors(
+        &self,
+        repo: &RepoInfo,
+        commit_ids: &[String],
+        _progress: &ProgressReporter,
+    ) -> Result<HashMap<String, CommitAuthor>, VNextError> {
+        self.lookups.borrow_mut().push((repo.to_string(), commit_ids.to_vec()));
+        match self.error {
+            Some(error) => Err(error()),
+            None => Ok(self.authors.clone()),
+        }
+    }
+}
+
+fn author(name: &str, email: &str, username: Option<&str>) -> CommitAuthor {
+    CommitAuthor { name: name.to_string(), email: email.to_string(), username: username.map(str::to_string) }
+}
+
+fn repo_info() -> RepoInfo {
+    let mut repo_info = RepoInfo::new();
+    repo_info.owner = "unbounded-tech".to_string();
+    repo_info.name = "vnext".to_string();
+    repo_info.is_github_repo = true;
+    repo_info
+}
+
+fn summary(commit_ids: &[&str]) -> ChangesetSummary {
+    let mut summary = ChangesetSummary::new();
+    for id in commit_ids {
+        summary.commits.push(Commit::parse(id.to_string(), format!("fix: Change {}", id)));
+    }
+    summary
+}
+
+#[test]
+fn test_enhance_merges_provider_authors() {
+    let provider = FakeProvider::new(&[
+        ("a", author("Forge Name", "forge@example.com", Some("forge-user"))),
+        ("b", author("Remote Author", "remote@example.com", Some("remote-user"))),
+    ]);
+    let mut summary = summary(&["a", "b", "c"]);
+    // A locally resolved (mailmapped) identity wins; only the username is added
+    summary.commits[0].author = Some(author("Local Name", "local@example.com", None));
+
+    enhance_with_github_info(&provider, &repo_info(), &mut summary, &ProgressReporter::none()).unwrap();
+
+    assert_eq!(summary.commits[0].author, Some(author("Local Name", "local@example.com", Some("forge-user"))));
+    assert_eq!(summary.commits[1].author, Some(author("Remote Author", "remote@example.com", Some("remote-user"))));
+    assert_eq!(summary.commits[2].author, None, "Commits unknown to the provider keep no author");
+    assert_eq!(
+        provider.lookups.borrow().as_slice(),
+        &[("unbounded-tech/vnext".to_string(), vec!["a".to_string(), "b".to_string(), "c".to_string()])]
+    );
+}
+
+#[test]
+fn test_enhance_reports_provider_errors() {
+    let mut provider = FakeProvider::new(&[]);
+    provider.error = Some(|| VNextError::Other("connection reset".to_string()));
+    let mut summary = summary(&["a"]);
+    match enhance_with_github_info(&provider, &repo_info(), &mut summary, &ProgressReporter::none()) {
+        Err(VNextError::GithubError(message)) => assert!(message.contains("connection reset"), "{}", message),
+        other => panic!("Expected a GitHub error, got {:?}", other),
+    }
+    assert_eq!(summary.commits[0].author, None);
+
+    // Cancellation is passed through unchanged
+    provider.error = Some(|| VNextError::Cancelled);
+    let result = enhance_with_github_info(&provider, &repo_info(), &mut summary, &ProgressReporter::none());
+    assert!(matches!(result, Err(VNextError::Cancelled)));
+}
@@ -0,0 +1,124 @@
+use vnext::changelog::RepoInfo;
+use vnext::core::gitlab::{encode_project_path, project_identifier, GitLabClient};
+use vnext::core::progress::ProgressReporter;
+use vnext::github::enhance_with_github_info;
+use vnext::version::{ChangesetSummary, Commit};
+
+mod mock_server;
+use mock_server::MockServer;
+
+fn gitlab_repo_info(owner: &str, name: &str) -> RepoInfo {
+    let mut repo_info = RepoInfo::new();
+    repo_info.owner = owner.to_string();
+    repo_info.name = name.to_string();
+    repo_info.is_gitlab_repo = true;
+    repo_info
+}
+
+#[test]
+fn test_resolve_project_id_for_nested_group() {
+    let server = MockServer::start(|request| {
+        if request.path == "/api/v4/projects/group%2Fsubgroup%2Frepo" {
+            (200, r#"{"id": 4242, "path_with_namespace": "group/subgroup/repo"}"#.to_string())
+        } else {
+            (404, r#"{"message": "404 Project Not Found"}"#.to_string())
+        }
+    });
+    let client = GitLabClient::new(&format!("{}/api/v4", server.url), Some("secret".to_string()));
+
+    let mut repo_info = gitlab_repo_info("group/subgroup", "repo");
+    client.resolve_project(&mut repo_info);
+
+    assert_eq!(repo_info.project_id, Some(4242));
+    assert_eq!(project_identifier(&repo_info), "4242");
+
+    let requests = server.requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].header("PRIVATE-TOKEN"), Some("secret"), "Token should be sent");
+}
+
+#[test]
+fn test_project_id_is_cached() {
+    let server = MockServer::start(|_| (200, r#"{"id": 7, "path_with_namespace": "owner/repo"}"#.to_string()));
+    let client = GitLabClient::new(&format!("{}/api/v4", server.url), None);
+
+    let mut repo_info = gitlab_repo_info("owner", "repo");
+    client.resolve_project(&mut repo_info);
+    client.resolve_project(&mut repo_info);
+
+    assert_eq!(repo_info.project_id, Some(7));
+    assert_eq!(server.request_count(), 1, "The project ID should only be looked up once");
+}
+
+#[test]
+fn test_fall_back_to_encoded_path_when_lookup_fails() {
+    let server = MockServer::start(|_| (404, r#"{"message": "404 Project Not Found"}"#.to_string()));
+    let client = GitLabClient::new(&format!("{}/api/v4", server.url), None);
+
+    let mut repo_info = gitlab_repo_info("group/subgroup", "repo");
+    client.resolve_project(&mut repo_info);
+
+    assert_eq!(repo_info.project_id, None);
+    assert_eq!(project_identifier(&repo_info), "group%2Fsubgroup%2Frepo");
+}
+
+#[test]
+fn test_encode_project_path() {
+    assert_eq!(encode_project_path("owner/repo"), "owner%2Frepo");
+    assert_eq!(encode_project_path("group/sub.group/my-repo"), "group%2Fsub.group%2Fmy-repo");
+}
+
+#[test]
+fn test_extract_repo_info_nested_groups() {
+    let result = vnext::git::extract_repo_info("git@gitlab.com:group/subgroup/repo.git");
+    assert_eq!(result, Some(("gitlab.com".to_string(), "group/subgroup".to_string(), "repo".to_string())));
+
+    let result = vnext::git::extract_repo_info("https://gitlab.com/group/subgroup/repo.git");
+    assert_eq!(result, Some(("gitlab.com".to_string(), "group/subgroup".to_string(), "repo".to_string())));
+}
+
+#[test]
+fn test_merge_requests_of_commits() {
+    let server = MockServer::start(|request| match request.path.as_str() {
+        "/api/v4/projects/group%2Fsubgroup%2Frepo" => (200, r#"{"id": 4242, "path_with_namespace": "group/subgroup/repo"}"#.to_string()),
+        "/api/v4/projects/4242/repository/commits/abc123/merge_requests" => (200, r#"[
+            {"iid": 3, "title": "Draft export", "state": "closed", "labels": []},
+            {"iid": 5, "title": "Add export", "state": "merged", "labels": ["feature", "skip-changelog"]}
+        ]"#.to_string()),
+        "/api/v4/projects/4242/repository/commits/def456/merge_requests" => (200, "[]".to_string()),
+        _ => (404, r#"{"message": "404 Not Found"}"#.to_string()),
+    });
+    let client = GitLabClient::new(&format!("{}/api/v4", server.url), None);
+    let mut repo_info = gitlab_repo_info("group/subgroup", "repo");
+    client.resolve_project(&mut repo_info);
+
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(Commit::parse("abc123".to_string(), "feat: Add export".to_string()));
+    summary.commits.push(Commit::parse("def456".to_string(), "fix: Pushed directly".to_string()));
+    summary.commits.push(Commit::parse("0000000".to_string(), "fix: Not pushed".to_string()));
+    enhance_with_github_info(&client, &repo_info, &mut summary, &ProgressReporter::none()).unwrap();
+
+    let merged = &summary.commits[0];
+    assert_eq!((merged.pr_number, merged.pr_title.as_deref()), (Some(5), Some("Add export")));
+    assert_eq!(merged.labels, ["feature", "skip-changelog"]);
+    assert!(summary.commits[1..].iter().all(|commit| commit.pr_number.is_none() && commit.author.is_none()));
+    assert!(summary.labels_known);
+}
+
+#[test]
+fn test_merge_requests_use_the_encoded_path_when_the_lookup_fails() {
+    let server = MockServer::start(|request| match request.path.as_str() {
+        "/api/v4/projects/group%2Fsubgroup%2Frepo/repository/commits/abc123/merge_requests" => {
+            (200, r#"[{"iid": 9, "title": "Add export", "state": "merged"}]"#.to_string())
+        }
+        _ => (404, r#"{"message": "404 Not Found"}"#.to_string()),
+    });
+    let client = GitLabClient::new(&format!("{}/api/v4", server.url), Some("secret".to_string()));
+    let mut repo_info = gitlab_repo_info("group/subgroup", "repo");
+    client.resolve_project(&mut repo_info);
+
+    let merge_request = client.commit_merge_request(&repo_info, "abc123").unwrap().expect("Merge request should be found");
+    assert_eq!((merge_request.iid, merge_request.labels.len()), (9, 0));
+    let requests = server.requests.lock().unwrap();
+    assert!(requests.iter().all(|request| request.header("PRIVATE-TOKEN") == Some("secret")));
+}
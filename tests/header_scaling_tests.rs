@@ -58,6 +58,9 @@ fn test_header_scaling() {
     assert!(changelog.contains("  #### H1 Header Should Scale to H4"), "H1 should be scaled to H4");
     assert!(changelog.contains("  ##### H2 Header Should Scale to H5"), "H2 should be scaled to H5");
     assert!(changelog.contains("  ###### H3 Header Should Scale to H6"), "H3 should be scaled to H6");
+    assert!(changelog.contains("  ###### H4 Header Should Also Scale to H6"), "H4 should be capped at H6");
+    assert!(changelog.contains("  ###### H5 Header Should Also Scale to H6"), "H5 should be capped at H6");
+    assert!(!changelog.contains("#######"), "Headers should not be scaled past H6");
     assert!(changelog.contains("  Regular text should remain unchanged"), "Regular text should remain unchanged");
     
     // Test 2: Check changelog with header scaling disabled
@@ -87,4 +90,100 @@ fn test_header_scaling() {
     assert!(changelog_no_scaling.contains("  #### H4 Header Should Also Scale to H6"), "H4 should not be scaled when disabled");
     assert!(changelog_no_scaling.contains("  ##### H5 Header Should Also Scale to H6"), "H5 should not be scaled when disabled");
     assert!(changelog_no_scaling.contains("  Regular text should remain unchanged"), "Regular text should remain unchanged");
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_header_scaling_skips_code_blocks() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    run_and_show_command("git", &["init"], repo_path);
+    run_and_show_command("git", &["config", "user.name", "patrickleet"], repo_path);
+    run_and_show_command("git", &["config", "user.email", "pat@patscott.io"], repo_path);
+
+    let file_path = repo_path.join("install.md");
+    fs::write(&file_path, "# Install").expect("Failed to write file");
+    run_and_show_command("git", &["add", file_path.to_str().unwrap()], repo_path);
+    run_and_show_command(
+        "git",
+        &["commit", "-m", "feat: add install script\n\n# Usage\n\n```sh\n# install the tool\ncurl -sSL example.com/install.sh | sh\n## not a heading either\n```\n\n~~~\n# still code\n```\n# still code after a different fence\n~~~\n\n    # indented code\n\n## After the code"],
+        repo_path
+    );
+
+    let version = run_vnext(repo_path);
+    assert_eq!(version, "0.1.0", "First run version should be 0.1.0");
+
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let binary_path = project_dir.join("target/debug/vnext");
+    let output = Command::new(&binary_path)
+        .args(["--changelog"])
+        .current_dir(&repo_path)
+        .output()
+        .expect("Failed to execute vnext with --changelog");
+    let changelog = String::from_utf8_lossy(&output.stdout).to_string();
+    println!("Changelog output with code blocks:\n{}", changelog);
+
+    // Headings outside the code blocks are scaled
+    assert!(changelog.contains("  #### Usage"), "H1 outside code should be scaled to H4");
+    assert!(changelog.contains("  ##### After the code"), "H2 after the code should be scaled to H5");
+
+    // Lines inside the code blocks are left untouched
+    assert!(changelog.contains("  ```sh\n  # install the tool\n  curl -sSL example.com/install.sh | sh\n  ## not a heading either\n  ```"), "Fenced code should not be scaled");
+    assert!(changelog.contains("  # still code\n  ```\n  # still code after a different fence\n  ~~~"), "Tilde fenced code should not be scaled");
+    assert!(changelog.contains("      # indented code"), "Indented code should not be scaled");
+}
+
+#[test]
+fn test_header_scaling_offset() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    run_and_show_command("git", &["init"], repo_path);
+    run_and_show_command("git", &["config", "user.name", "patrickleet"], repo_path);
+    run_and_show_command("git", &["config", "user.email", "pat@patscott.io"], repo_path);
+
+    let file_path = repo_path.join("offset.md");
+    fs::write(&file_path, "# Offset").expect("Failed to write file");
+    run_and_show_command("git", &["add", file_path.to_str().unwrap()], repo_path);
+    run_and_show_command(
+        "git",
+        &["commit", "-m", "feat: add offset test\n\n# Title\n\n##### Deep\n\n###### Deepest"],
+        repo_path
+    );
+
+    let version = run_vnext(repo_path);
+    assert_eq!(version, "0.1.0", "First run version should be 0.1.0");
+
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let binary_path = project_dir.join("target/debug/vnext");
+    let output = Command::new(&binary_path)
+        .args(["--changelog", "--header-scaling-offset", "1"])
+        .current_dir(&repo_path)
+        .output()
+        .expect("Failed to execute vnext with --header-scaling-offset");
+    let changelog = String::from_utf8_lossy(&output.stdout).to_string();
+    println!("Changelog output with offset 1:\n{}", changelog);
+
+    assert!(changelog.contains("  ## Title"), "H1 should be scaled to H2");
+    assert!(changelog.contains("  ###### Deep\n"), "H5 should be scaled to H6");
+    assert!(changelog.contains("  ###### Deepest"), "H6 should be left unchanged");
+    assert!(!changelog.contains("#######"), "Headers should not be scaled past H6");
+
+    // Offsets that would turn every heading into plain text are rejected
+    let output = Command::new(&binary_path)
+        .args(["--changelog", "--header-scaling-offset", "6"])
+        .current_dir(&repo_path)
+        .output()
+        .expect("Failed to execute vnext with --header-scaling-offset");
+    assert!(!output.status.success(), "An offset of 6 should be rejected");
+
+    // --header-scale is the short form of the flag
+    let output = Command::new(&binary_path)
+        .args(["--changelog", "--header-scale", "2"])
+        .current_dir(&repo_path)
+        .output()
+        .expect("Failed to execute vnext with --header-scale");
+    let changelog = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(changelog.contains("  ### Title"), "H1 should be scaled to H3: {}", changelog);
+    assert!(changelog.contains("  ###### Deep\n"), "H5 should be capped at H6");
+}
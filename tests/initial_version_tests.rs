@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use vnext::VersionCalculator;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext, run_vnext_output, stdout};
+
+/// An untagged repository with a feature and a fix
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "chore: Initial commit");
+    commit_file(repo_path, "a.txt", "feat: Add export");
+    commit_file(repo_path, "b.txt", "fix: Handle empty files");
+}
+
+#[test]
+fn test_initial_version() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    assert_eq!(stdout(&run_vnext_output(repo_path, &[])), "0.1.0");
+    // The commits bump the initial version
+    assert_eq!(stdout(&run_vnext_output(repo_path, &["--initial-version", "1.0.0"])), "1.1.0");
+    assert_eq!(stdout(&run_vnext_output(repo_path, &["--initial-version", "v1.0.0", "--current"])), "1.0.0");
+    // 0.x initial versions are bumped like any other, so breaking changes still reach 1.0.0
+    commit_file(repo_path, "c.txt", "feat!: Drop the v0 API");
+    assert_eq!(stdout(&run_vnext_output(repo_path, &["--initial-version", "0.3.0"])), "1.0.0");
+
+    // Once a release is tagged the initial version no longer matters
+    run_and_show_command("git", &["tag", "v2.0.0"], repo_path);
+    commit_file(repo_path, "d.txt", "fix: Typo");
+    assert_eq!(stdout(&run_vnext_output(repo_path, &["--initial-version", "1.0.0"])), "2.0.1");
+
+    let calculated = VersionCalculator::for_repo(repo_path)
+        .with_initial_version(semver::Version::new(5, 0, 0))
+        .run()
+        .expect("Calculation should succeed");
+    assert_eq!(calculated.current_version.to_string(), "2.0.0");
+}
+
+#[test]
+fn test_first_release() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let args = ["--initial-version", "1.0.0", "--first-release"];
+    assert_eq!(stdout(&run_vnext_output(repo_path, &args)), "1.0.0", "The initial version is released verbatim");
+    let changelog = stdout(&run_vnext_output(repo_path, &[&args[..], &["--changelog"]].concat()));
+    assert!(changelog.contains("What's changed in v1.0.0"), "{}", changelog);
+    assert!(changelog.contains("feat: Add export"), "{}", changelog);
+
+    // Prerelease identifiers are kept as well
+    assert_eq!(stdout(&run_vnext_output(repo_path, &["--initial-version", "1.0.0-rc.1", "--first-release"])), "1.0.0-rc.1");
+
+    // --first-release needs an initial version and no release tag
+    assert!(!run_vnext_output(repo_path, &["--first-release"]).status.success());
+    assert!(!run_vnext_output(repo_path, &["--first-release", "--initial-version", "1.0.0", "--force-bump", "minor"]).status.success());
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    let output = run_vnext_output(repo_path, &args);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already tagged"));
+}
+
+#[test]
+fn test_fallback_uses_initial_version() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    // Builds the binary
+    run_vnext(repo_path);
+
+    assert_eq!(stdout(&run_vnext_output(repo_path, &["--lenient"])), "0.0.0");
+    assert_eq!(stdout(&run_vnext_output(repo_path, &["--lenient", "--initial-version", "1.0.0"])), "1.0.0");
+    let changelog = stdout(&run_vnext_output(repo_path, &["--lenient", "--initial-version", "1.0.0", "--changelog"]));
+    assert!(changelog.starts_with("## What's changed in 1.0.0"), "{}", changelog);
+}
@@ -0,0 +1,113 @@
+use std::path::Path;
+use vnext::core::lint::{lint_commit, rule, LintInput, RULES};
+use vnext::{BumpMap, Commit, Diagnostics};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext, run_vnext_output};
+
+/// Warning of the rule `name` for `message`, checked on its own
+fn check(name: &str, message: &str) -> Option<String> {
+    let commit = Commit::parse("abc1234".to_string(), message.to_string());
+    let bump_map = BumpMap::default();
+    (rule(name).expect("rule exists").check)(&LintInput { commit: &commit, message, bump_map: &bump_map })
+}
+
+#[test]
+fn test_subject_length() {
+    let subject = format!("feat: {}", "a".repeat(66));
+    assert_eq!(subject.len(), 72);
+    assert_eq!(check("subject-length", &subject), None);
+    assert_eq!(
+        check("subject-length", &format!("{}b\n\nBody", subject)).as_deref(),
+        Some("subject is 73 characters, over the limit of 72")
+    );
+    // Characters, not bytes
+    assert_eq!(check("subject-length", &format!("feat: {}", "é".repeat(66))), None);
+}
+
+#[test]
+fn test_subject_body_separator() {
+    assert_eq!(check("subject-body-separator", "feat: Add export"), None);
+    assert_eq!(check("subject-body-separator", "feat: Add export\n\nWith a body"), None);
+    assert_eq!(
+        check("subject-body-separator", "feat: Add export\nWith a body").as_deref(),
+        Some("no blank line between the subject and the body")
+    );
+}
+
+#[test]
+fn test_unknown_type() {
+    assert_eq!(check("unknown-type", "feat: Add export"), None);
+    assert_eq!(check("unknown-type", "Chore: Tidy up"), None);
+    assert_eq!(check("unknown-type", "fix: Handle empty input"), None);
+    assert_eq!(check("unknown-type", "Update README"), None);
+    assert_eq!(
+        check("unknown-type", "docs: Explain the flags").as_deref(),
+        Some("type 'docs' is not in any configured type list, so it bumps the patch version")
+    );
+}
+
+#[test]
+fn test_lint_commit_runs_every_rule() {
+    let message = format!("docs: {}\nNo blank line", "a".repeat(80));
+    let commit = Commit::parse("abc1234".to_string(), message.clone());
+    let mut diagnostics = Diagnostics::new();
+    lint_commit(&commit, &message, &BumpMap::default(), &mut diagnostics);
+    let rules: Vec<&str> = diagnostics.rules_for("abc1234").collect();
+    assert_eq!(rules, RULES.iter().map(|rule| rule.name).collect::<Vec<_>>());
+    assert!(diagnostics.iter().next().unwrap().to_string().starts_with("abc1234: subject is 86 characters"));
+}
+
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    commit_file(repo_path, "a.md", "docs: Explain the flags");
+    commit_file(repo_path, "b.md", "feat: Add export\nwithout a blank line");
+}
+
+#[test]
+fn test_warnings_do_not_change_the_bump() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    assert_eq!(run_vnext(repo_path), "1.1.0");
+
+    let output = run_vnext_output(repo_path, &["--format", "json"]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Output should be JSON");
+    assert_eq!(json["version"], "1.1.0");
+    let rules: Vec<&str> = json["warnings"].as_array().unwrap().iter().map(|w| w["rule"].as_str().unwrap()).collect();
+    assert_eq!(rules, ["subject-body-separator", "unknown-type"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("type 'docs' is not in any configured type list"), "{}", stderr);
+
+    // Configuring the type silences its warning
+    let output = run_vnext_output(repo_path, &["--format", "json", "--noop-commit-types", "chore,docs"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Output should be JSON");
+    assert_eq!(json["warnings"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_deny_warnings_fails_the_run() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    run_vnext(repo_path);
+
+    let version_file = repo_path.join("VERSION");
+    let output = run_vnext_output(repo_path, &["--deny-warnings", "--version-file-out", version_file.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(!version_file.exists());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("2 warnings with --deny-warnings"), "{}", stderr);
+
+    commit_file(repo_path, "c.md", "Revert the flag docs");
+    run_and_show_command("git", &["tag", "v1.1.0"], repo_path);
+    commit_file(repo_path, "d.md", "fix: Handle empty input");
+    let output = run_vnext_output(repo_path, &["--deny-warnings"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "1.1.1");
+}
@@ -0,0 +1,88 @@
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+use clap::Parser;
+use log::LevelFilter;
+use vnext::cli::Cli;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command, run_vnext, vnext_command};
+
+/// Run vnext without LOG_LEVEL so only the flags set the level
+fn run_vnext_without_log_level(repo_path: &Path, args: &[&str]) -> Output {
+    vnext_command(repo_path, args).env_remove("LOG_LEVEL").output().expect("Failed to execute vnext")
+}
+
+/// A released repository with a pending feature and an untracked file, so vnext logs a warning
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    fs::write(repo_path.join("README.md"), "# Test").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    fs::write(repo_path.join("export.txt"), "export").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Add export"], repo_path);
+    fs::write(repo_path.join("notes.txt"), "scratch").expect("Failed to write untracked file");
+}
+
+#[test]
+fn test_log_level_flags() {
+    let level = |args: &[&str]| Cli::parse_from(std::iter::once("vnext").chain(args.iter().copied())).log_level();
+    assert_eq!(level(&["-v"]), LevelFilter::Debug);
+    assert_eq!(level(&["-vv"]), LevelFilter::Trace);
+    assert_eq!(level(&["--log-level", "WARN"]), LevelFilter::Warn);
+    assert_eq!(level(&["--quiet"]), LevelFilter::Off);
+
+    assert!(Cli::try_parse_from(["vnext", "--log-level", "loud"]).is_err());
+    assert!(Cli::try_parse_from(["vnext", "-q", "-v"]).is_err());
+    assert!(Cli::try_parse_from(["vnext", "-v", "--log-level", "info"]).is_err());
+}
+
+#[test]
+fn test_log_levels_are_honored() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let output = run_vnext_without_log_level(repo_path, &[]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("uncommitted changes"), "{}", stderr);
+    assert!(!stderr.contains("Debug"), "{}", stderr);
+
+    let output = run_vnext_without_log_level(repo_path, &["-v"]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Debug"));
+
+    let output = run_vnext_without_log_level(repo_path, &["--log-level", "error"]);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("uncommitted changes"));
+
+    let output = run_vnext_without_log_level(repo_path, &["--quiet"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1.1.0\n");
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "", "--quiet leaves stderr empty");
+}
+
+#[test]
+fn test_json_log_format() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let output = run_vnext_without_log_level(repo_path, &["--log-format", "json", "-v"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1.1.0\n", "Logs stay off stdout");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let lines: Vec<serde_json::Value> = stderr
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap_or_else(|e| panic!("Not a JSON log line ({}): {}", e, line)))
+        .collect();
+    assert!(lines.iter().any(|line| line["level"] == "debug"));
+    let warning = lines.iter().find(|line| line["level"] == "warn").expect("The dirty tree warning should be logged");
+    assert!(warning["message"].as_str().unwrap().contains("uncommitted changes"));
+    assert!(warning["target"].as_str().unwrap().starts_with("vnext"));
+    assert!(warning["timestamp"].as_str().unwrap().ends_with('Z'));
+}
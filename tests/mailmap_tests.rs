@@ -0,0 +1,29 @@
+use std::fs;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{run_and_show_command, run_vnext, run_vnext_output};
+
+#[test]
+fn test_changelog_uses_mailmapped_author_name() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    run_and_show_command("git", &["init"], repo_path);
+    run_and_show_command("git", &["config", "user.name", "Old Name"], repo_path);
+    run_and_show_command("git", &["config", "user.email", "old@example.com"], repo_path);
+
+    fs::write(repo_path.join(".mailmap"), "New Name <new@example.com> <old@example.com>\n")
+        .expect("Failed to write .mailmap");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Add mailmap"], repo_path);
+
+    assert_eq!(run_vnext(repo_path), "0.1.0");
+
+    let output = run_vnext_output(repo_path, &["--changelog", "--contributors"]);
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+
+    assert!(stdout.contains("* feat: Add mailmap (by New Name)"), "The commit line should use the mapped name: {}", stdout);
+    assert!(stdout.contains("## Contributors\n\n* New Name\n"), "Contributors should use the mapped name: {}", stdout);
+    assert!(!stdout.contains("Old Name"), "The raw author name should not appear: {}", stdout);
+}
@@ -0,0 +1,50 @@
+use semver::Version;
+use vnext::changelog::{escape_markdown, format_changelog_with_options, RepoInfo};
+use vnext::models::options::ChangelogOptions;
+use vnext::version::{ChangesetSummary, Commit};
+
+fn render(message: &str, options: &ChangelogOptions) -> String {
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(Commit::parse("abc123".to_string(), message.to_string()));
+    format_changelog_with_options(&summary, &Version::new(1, 0, 1), &Version::new(1, 0, 0), &RepoInfo::new(), options)
+}
+
+#[test]
+fn test_escape_markdown() {
+    assert_eq!(escape_markdown("plain text, (parens) and 1.0-rc"), "plain text, (parens) and 1.0-rc");
+    assert_eq!(escape_markdown("snake_case | a\\b ~~gone~~"), "snake\\_case \\| a\\\\b \\~\\~gone\\~\\~");
+    // Code spans are kept, an unmatched backtick is escaped
+    assert_eq!(escape_markdown("handle `Option<T>` and *more*"), "handle `Option<T>` and \\*more\\*");
+    assert_eq!(escape_markdown("a `b_c` d `e_f"), "a `b_c` d \\`e\\_f");
+}
+
+#[test]
+fn test_changelog_escapes_titles() {
+    let changelog = render("fix(parser_core): don't *panic* when <T> is #[repr(C)]", &ChangelogOptions::default());
+    assert_eq!(
+        changelog,
+        "### What's changed in v1.0.1\n\n* fix(parser\\_core): don't \\*panic\\* when \\<T\\> is \\#\\[repr(C)\\]\n\n"
+    );
+
+    // Free-form subjects are escaped too
+    let changelog = render("# Release <b>now</b>", &ChangelogOptions::default());
+    assert_eq!(changelog, "### What's changed in v1.0.1\n\n* \\# Release \\<b\\>now\\</b\\>\n\n");
+
+    let options = ChangelogOptions { no_escape: true, ..ChangelogOptions::default() };
+    let changelog = render("fix: don't *panic* when <T> is #[repr(C)]", &options);
+    assert_eq!(changelog, "### What's changed in v1.0.1\n\n* fix: don't *panic* when <T> is #[repr(C)]\n\n");
+}
+
+#[test]
+fn test_changelog_escapes_break_lines_in_bodies() {
+    let message = "fix: Handle retries\n\nSummary line\n---\nMore *details* here\n\n* * *\n\n```\n---\n```";
+    let changelog = render(message, &ChangelogOptions::default());
+    assert_eq!(
+        changelog,
+        "### What's changed in v1.0.1\n\n* fix: Handle retries\n\n  Summary line\n  \\---\n  More *details* here\n\n  \\* * *\n\n  ```\n  ---\n  ```\n\n",
+        "Rules and setext underlines are escaped outside code; bodies are otherwise kept"
+    );
+
+    let options = ChangelogOptions { no_escape: true, ..ChangelogOptions::default() };
+    assert!(render(message, &options).contains("\n  ---\n  More"));
+}
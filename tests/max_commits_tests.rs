@@ -0,0 +1,77 @@
+use std::path::Path;
+
+use git2::{Repository, Signature};
+use vnext::VersionCalculator;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_vnext, run_vnext_with_args};
+
+/// Create a repository released as 1.0.0 followed by a feature and 300 fixes, newest last
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    let repo = Repository::open(repo_path).expect("Failed to open repository");
+    let signature = Signature::now("patrickleet", "pat@patscott.io").unwrap();
+    let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+
+    // Commits share the empty tree; creating them through git2 keeps the test fast
+    let mut parent = repo.find_commit(repo.commit(Some("HEAD"), &signature, &signature, "chore: Initial commit", &tree, &[]).unwrap()).unwrap();
+    repo.tag_lightweight("v1.0.0", parent.as_object(), false).unwrap();
+    let mut messages = vec!["feat: Add export".to_string()];
+    messages.extend((0..300).map(|i| format!("fix: Fix {}", i)));
+    for message in messages {
+        let id = repo.commit(Some("HEAD"), &signature, &signature, &message, &tree, &[&parent]).unwrap();
+        parent = repo.find_commit(id).unwrap();
+    }
+}
+
+#[test]
+fn test_max_commits_truncates_the_walk() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+
+    let plan = VersionCalculator::for_repo(repo_path).run().unwrap();
+    assert!(!plan.summary.truncated);
+    assert_eq!(plan.summary.commits.len(), 301);
+    assert_eq!(plan.summary.total_commits, 301);
+    assert_eq!(plan.next_version.to_string(), "1.1.0");
+
+    // The oldest commit, the feature, is past the limit and does not count
+    let plan = VersionCalculator::for_repo(repo_path).with_max_commits(50).run().unwrap();
+    assert!(plan.summary.truncated);
+    assert_eq!(plan.summary.commits.len(), 50);
+    assert_eq!(plan.summary.commits[0].title, "Fix 299", "The newest commits are kept");
+    assert_eq!(plan.summary.omitted_commits, 251);
+    assert_eq!(plan.summary.total_commits, 301);
+    assert_eq!(plan.summary.patch, 50);
+    assert_eq!(plan.next_version.to_string(), "1.0.1");
+}
+
+#[test]
+fn test_max_commits_output() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let changelog = run_vnext_with_args(repo_path, &["--changelog", "--max-commits", "50"]);
+    assert_eq!(changelog.matches("* fix: ").count(), 50);
+    assert!(changelog.contains("* ...and 251 more commits"), "{}", changelog);
+    assert!(!changelog.contains("Add export"));
+
+    let output = run_vnext_with_args(repo_path, &["--max-commits", "50", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&output).expect("stdout should be JSON");
+    assert_eq!(json["version"], "1.0.1");
+    assert_eq!(json["truncated"], true);
+    assert_eq!(json["omitted_commits"], 251);
+
+    let output = run_vnext_with_args(repo_path, &["--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&output).expect("stdout should be JSON");
+    assert_eq!(json["version"], "1.1.0");
+    assert_eq!(json["truncated"], false);
+    assert!(json["omitted_commits"].is_null());
+
+    assert_eq!(run_vnext_with_args(repo_path, &["--max-commits", "0"]), "", "A limit of 0 is rejected");
+}
@@ -0,0 +1,68 @@
+use std::collections::HashSet;
+
+use vnext::parsers::parse_conventional_commit;
+use vnext::{BumpLevel, ChangesetSummary, Commit, CommitAuthor, RepoInfo, VersionBump};
+
+fn round_trip<T>(value: &T) -> T
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let json = serde_json::to_string(value).expect("Failed to serialize");
+    serde_json::from_str(&json).expect("Failed to deserialize")
+}
+
+fn author() -> CommitAuthor {
+    CommitAuthor { name: "Patrick Lee Scott".to_string(), email: "pat@patscott.io".to_string(), username: Some("patrickleet".to_string()) }
+}
+
+#[test]
+fn test_commit_round_trip() {
+    let mut commit = Commit::parse(
+        "a1b2c3d4e5f6".to_string(),
+        "feat(api)!: Drop v1 endpoints\n\nBody text\n\nBREAKING CHANGE: v1 is gone\nRefs: #12".to_string(),
+    );
+    commit.author = Some(author());
+    commit.issues = vec!["#12".to_string()];
+    assert_eq!(round_trip(&commit), commit);
+    assert_eq!(commit.to_string(), "a1b2c3d feat(api)!: Drop v1 endpoints");
+    assert_eq!(author().to_string(), "Patrick Lee Scott <pat@patscott.io> (@patrickleet)");
+}
+
+#[test]
+fn test_changeset_summary_round_trip() {
+    let mut summary = ChangesetSummary::new();
+    summary.minor = 1;
+    summary.patch = 1;
+    summary.commits.push(Commit::parse("1".to_string(), "feat: Add export".to_string()));
+    summary.commits.push(Commit::parse("2".to_string(), "fix: Handle empty files".to_string()));
+    summary.known_contributors = Some(HashSet::from(["pat@patscott.io".to_string()]));
+    assert_eq!(round_trip(&summary), summary);
+    assert_eq!(summary.to_string(), "2 commits (major: 0, minor: 1, patch: 1, noop: 0)");
+}
+
+#[test]
+fn test_repo_info_round_trip() {
+    let mut repo_info = RepoInfo::new();
+    repo_info.owner = "unbounded-tech".to_string();
+    repo_info.name = "vnext".to_string();
+    repo_info.is_github_repo = true;
+    assert_eq!(round_trip(&repo_info), repo_info);
+    assert_eq!(repo_info.to_string(), "unbounded-tech/vnext");
+}
+
+#[test]
+fn test_version_bump_round_trip() {
+    for level in [BumpLevel::None, BumpLevel::Patch, BumpLevel::Minor, BumpLevel::Major] {
+        let bump = VersionBump::from(level);
+        assert_eq!(round_trip(&bump), bump);
+        assert_eq!(bump.to_string(), level.as_str());
+        assert_eq!(serde_json::to_value(level).unwrap(), level.as_str());
+    }
+}
+
+#[test]
+fn test_parsed_commit_round_trip() {
+    let parsed = parse_conventional_commit("fix(api, web)!: Reject empty payloads\n\nCloses: #7").expect("Failed to parse");
+    assert_eq!(round_trip(&parsed), parsed);
+    assert_eq!(parsed.to_string(), "fix(api,web)!: Reject empty payloads", "Scopes are normalized");
+}
@@ -0,0 +1,83 @@
+use std::fs;
+use std::path::Path;
+
+use semver::Version;
+use vnext::changelog::{format_changelog_with_options, RepoInfo};
+use vnext::models::options::ChangelogOptions;
+use vnext::version::{parse_version_with_prefix, ChangesetSummary, Commit};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command, run_vnext, run_vnext_with_args};
+
+fn commit_in(repo_path: &Path, dir: &str, file: &str, message: &str) {
+    let dir_path = repo_path.join(dir);
+    fs::create_dir_all(&dir_path).expect("Failed to create package directory");
+    fs::write(dir_path.join(file), message).expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", message], repo_path);
+}
+
+#[test]
+fn test_interleaved_package_tags() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    init_repo(repo_path);
+
+    commit_in(repo_path, "services/api", "main.rs", "feat: Add api");
+    run_and_show_command("git", &["tag", "api-v1.0.0"], repo_path);
+    commit_in(repo_path, "services/web", "index.html", "feat: Add web");
+    run_and_show_command("git", &["tag", "web-v0.9.0"], repo_path);
+    commit_in(repo_path, "services/api", "fix.rs", "fix: Fix api bug");
+    commit_in(repo_path, "services/web", "page.html", "feat: Add web page");
+    run_and_show_command("git", &["tag", "v9.0.0"], repo_path);
+    commit_in(repo_path, "services/api", "more.rs", "chore: Tidy api");
+
+    // Builds the binary; plain tags are unaffected by package tags
+    assert_eq!(run_vnext(repo_path), "9.0.0");
+
+    let api = ["--tag-prefix", "api-v", "--path", "services/api"];
+    let web = ["--tag-prefix", "web-v", "--path", "services/web"];
+
+    assert_eq!(run_vnext_with_args(repo_path, &[&api[..], &["--current"][..]].concat()), "1.0.0");
+    assert_eq!(run_vnext_with_args(repo_path, &api), "1.0.1");
+    assert_eq!(run_vnext_with_args(repo_path, &[&web[..], &["--current"][..]].concat()), "0.9.0");
+    assert_eq!(run_vnext_with_args(repo_path, &web), "0.10.0");
+
+    let api_changelog = run_vnext_with_args(repo_path, &[&api[..], &["--changelog"][..]].concat());
+    assert!(api_changelog.contains("* fix: Fix api bug"), "{}", api_changelog);
+    assert!(!api_changelog.contains("web"), "Commits outside the package should be skipped: {}", api_changelog);
+}
+
+#[test]
+fn test_parse_version_with_prefix() {
+    assert_eq!(parse_version_with_prefix("api-v1.2.3", "api-v"), Some(Version::new(1, 2, 3)));
+    assert_eq!(parse_version_with_prefix("api-1.2.3", "api-"), Some(Version::new(1, 2, 3)));
+    assert_eq!(parse_version_with_prefix("releases/v1.2.3", "releases/"), Some(Version::new(1, 2, 3)));
+    assert_eq!(parse_version_with_prefix("web-v1.2.3", "api-v"), None);
+    assert_eq!(parse_version_with_prefix("v1.2.3", ""), Some(Version::new(1, 2, 3)));
+}
+
+#[test]
+fn test_compare_link_uses_package_tags() {
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(Commit::parse("commit1".to_string(), "fix: A fix".to_string()));
+
+    let mut repo_info = RepoInfo::new();
+    repo_info.owner = "unbounded-tech".to_string();
+    repo_info.name = "vnext".to_string();
+    repo_info.is_github_repo = true;
+
+    let options = ChangelogOptions {
+        tag_prefix: Some("api-v".to_string()),
+        ..ChangelogOptions::default()
+    };
+    let changelog = format_changelog_with_options(&summary, &Version::new(1, 0, 1), &Version::new(1, 0, 0), &repo_info, &options);
+
+    assert!(
+        changelog.contains("[api-v1.0.0...api-v1.0.1](https://github.com/unbounded-tech/vnext/compare/api-v1.0.0...api-v1.0.1)"),
+        "{}",
+        changelog
+    );
+}
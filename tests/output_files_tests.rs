@@ -0,0 +1,96 @@
+use std::fs;
+use std::process::Command;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command, run_vnext};
+
+#[test]
+fn test_version_and_changelog_files_from_one_run() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    init_repo(repo_path);
+
+    let file_path = repo_path.join("README.md");
+    fs::write(&file_path, "# Test Repository").expect("Failed to write README file");
+    run_and_show_command("git", &["add", file_path.to_str().unwrap()], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+
+    let version = run_vnext(repo_path);
+    assert_eq!(version, "0.1.0", "Initial version should be 0.1.0");
+
+    let out_dir = tempfile::tempdir().expect("Failed to create output directory");
+    let version_file = out_dir.path().join("VERSION");
+    let changelog_file = out_dir.path().join("CHANGELOG.md");
+
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let binary_path = project_dir.join("target/debug/vnext");
+    let output = Command::new(&binary_path)
+        .args([
+            "--version-file-out",
+            version_file.to_str().unwrap(),
+            "--changelog-out",
+            changelog_file.to_str().unwrap(),
+        ])
+        .current_dir(&repo_path)
+        .output()
+        .expect("Failed to execute vnext with output files");
+
+    // stdout keeps its configured behavior (the bare version)
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    assert_eq!(stdout, "0.1.0");
+
+    let version_contents = fs::read_to_string(&version_file).expect("Version file should exist");
+    let changelog_contents = fs::read_to_string(&changelog_file).expect("Changelog file should exist");
+
+    assert_eq!(version_contents, "0.1.0\n");
+    assert_eq!(changelog_contents, "### What's changed in v0.1.0\n\n* feat: Initial commit\n\n\n");
+    assert!(
+        changelog_contents.contains(&format!("v{}", version_contents.trim())),
+        "Both files should describe the same version"
+    );
+}
+
+#[test]
+fn test_line_ending_of_output_files() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    init_repo(repo_path);
+    fs::write(repo_path.join("README.md"), "# Test Repository").expect("Failed to write README file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+    assert_eq!(run_vnext(repo_path), "0.1.0");
+
+    let out_dir = tempfile::tempdir().expect("Failed to create output directory");
+    let version_file = out_dir.path().join("VERSION");
+    let changelog_file = out_dir.path().join("CHANGELOG.md");
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let write_files = |line_ending: &str| {
+        let output = Command::new(project_dir.join("target/debug/vnext"))
+            .args(["--line-ending", line_ending, "--version-file-out"])
+            .arg(&version_file)
+            .arg("--changelog-out")
+            .arg(&changelog_file)
+            .current_dir(repo_path)
+            .output()
+            .expect("Failed to execute vnext with output files");
+        assert!(output.status.success(), "vnext failed: {}", String::from_utf8_lossy(&output.stderr));
+        // stdout is not a file and keeps \n
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "0.1.0\n");
+        (fs::read(&version_file).unwrap(), fs::read_to_string(&changelog_file).unwrap())
+    };
+
+    let (version, changelog) = write_files("crlf");
+    assert_eq!(version, b"0.1.0\r\n");
+    assert_eq!(changelog, "### What's changed in v0.1.0\r\n\r\n* feat: Initial commit\r\n\r\n\r\n");
+
+    let (version, changelog) = write_files("native");
+    let newline = if cfg!(windows) { "\r\n" } else { "\n" };
+    assert_eq!(version, format!("0.1.0{}", newline).into_bytes());
+    assert_eq!(changelog, "### What's changed in v0.1.0\n\n* feat: Initial commit\n\n\n".replace('\n', newline));
+
+    let (version, _) = write_files("lf");
+    assert_eq!(version, b"0.1.0\n");
+}
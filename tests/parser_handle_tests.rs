@@ -0,0 +1,84 @@
+use std::fs;
+
+use vnext::models::error::VNextError;
+use vnext::{ParserHandle, ParserStrategy, VersionCalculator};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command};
+
+fn invalid_strategy() -> ParserStrategy {
+    ParserStrategy::CustomRegex {
+        commit_type_pattern: r"^(\w+".to_string(),
+        title_pattern: r".*".to_string(),
+        body_pattern: r".*".to_string(),
+        breaking_pattern: r".*".to_string(),
+        scope_pattern: r".*".to_string(),
+    }
+}
+
+#[test]
+fn test_parser_is_constructed_once() {
+    let handle = ParserHandle::new(ParserStrategy::Conventional);
+    assert_eq!(handle.constructions(), 0);
+
+    let shared = handle.clone();
+    for _ in 0..3 {
+        let parser = handle.parser().expect("Conventional parser should build");
+        assert_eq!(parser.name(), "conventional");
+        shared.parser().expect("Conventional parser should build");
+    }
+
+    assert_eq!(handle.constructions(), 1);
+    assert_eq!(shared.constructions(), 1);
+}
+
+#[test]
+fn test_invalid_patterns_error_is_cached() {
+    let handle = ParserHandle::new(invalid_strategy());
+
+    let first = match handle.parser() {
+        Err(VNextError::ConfigError(message)) => message,
+        Err(other) => panic!("Expected a configuration error, got {}", other),
+        Ok(_) => panic!("Expected invalid patterns to fail"),
+    };
+    let second = match handle.parser() {
+        Err(VNextError::ConfigError(message)) => message,
+        Err(other) => panic!("Expected a configuration error, got {}", other),
+        Ok(_) => panic!("Expected invalid patterns to fail"),
+    };
+
+    assert_eq!(first, second);
+    assert!(first.contains("Invalid custom parser pattern"));
+    assert_eq!(handle.constructions(), 1);
+}
+
+#[test]
+fn test_calculator_runs_share_the_parser() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    init_repo(repo_path);
+    fs::write(repo_path.join("README.md"), "# Test").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+
+    let handle = ParserHandle::new(ParserStrategy::Conventional);
+    for _ in 0..2 {
+        let plan = VersionCalculator::for_repo(repo_path)
+            .with_parser_handle(handle.clone())
+            .run()
+            .expect("Calculation should succeed");
+        assert_eq!(plan.next_version.to_string(), "0.1.0");
+    }
+    assert_eq!(handle.constructions(), 1);
+
+    let invalid = ParserHandle::new(invalid_strategy());
+    for _ in 0..2 {
+        let result = VersionCalculator::for_repo(repo_path)
+            .with_parser_handle(invalid.clone())
+            .run();
+        assert!(matches!(result, Err(VNextError::ConfigError(_))));
+    }
+    assert_eq!(invalid.constructions(), 1);
+}
@@ -1,4 +1,6 @@
-use vnext::models::commit::CommitParser;
+use vnext::models::commit::{Commit, CommitParser};
+use vnext::core::version::{classify_commit, determine_version_bump, VersionBumpType};
+use vnext::version::BumpMap;
 
 // Default commit types for testing
 fn default_major_types() -> Vec<&'static str> {
@@ -16,13 +18,20 @@ use vnext::parsers::{
     ConventionalCommitParser,
     CustomRegexParser,
     ParserFactory,
+    ParserHandle,
+    ParserKind,
     ParserStrategy,
+    PatternErrorKind,
+    PatternName,
+    parse_type_aliases,
     COMMIT_TYPE_REGEX_STR,
     TITLE_REGEX_STR,
     BODY_REGEX_STR,
     BREAKING_REGEX_STR,
     SCOPE_REGEX_STR,
 };
+use vnext::models::error::VNextError;
+use clap::Parser;
 
 #[test]
 fn test_conventional_commit_parser() {
@@ -159,7 +168,7 @@ fn test_custom_regex_parser() {
 #[test]
 fn test_parser_factory() {
     // Test creating a conventional parser
-    let conventional_parser = ParserFactory::create(&ParserStrategy::Conventional);
+    let conventional_parser = ParserFactory::create(&ParserStrategy::Conventional).unwrap();
     assert_eq!(conventional_parser.name(), "conventional");
     
     // Test creating a custom regex parser
@@ -169,18 +178,31 @@ fn test_parser_factory() {
         body_pattern: BODY_REGEX_STR.to_string(),
         breaking_pattern: BREAKING_REGEX_STR.to_string(),
         scope_pattern: SCOPE_REGEX_STR.to_string(),
-    });
+    }).unwrap();
     assert_eq!(custom_parser.name(), "custom-regex");
     
-    // Test with invalid regex patterns (should fall back to defaults)
-    let invalid_parser = ParserFactory::create(&ParserStrategy::CustomRegex {
+    // Test with invalid regex patterns (should fail instead of falling back to defaults)
+    let invalid_strategy = ParserStrategy::CustomRegex {
         commit_type_pattern: "[invalid regex".to_string(),
         title_pattern: TITLE_REGEX_STR.to_string(),
         body_pattern: BODY_REGEX_STR.to_string(),
         breaking_pattern: BREAKING_REGEX_STR.to_string(),
         scope_pattern: SCOPE_REGEX_STR.to_string(),
-    });
-    assert_eq!(invalid_parser.name(), "custom-regex");
+    };
+    match ParserFactory::create(&invalid_strategy) {
+        Err(VNextError::ConfigError(message)) => {
+            assert!(message.contains("--type-pattern"), "{}", message);
+            assert!(message.contains("--allow-regex-fallback"), "{}", message);
+        }
+        Err(e) => panic!("Expected a config error, got {}", e),
+        Ok(_) => panic!("Invalid regex patterns should not build a parser"),
+    }
+
+    // With the fallback the invalid patterns are replaced by the defaults
+    let fallback_parser = ParserFactory::create(&ParserFactory::with_regex_fallback(invalid_strategy)).unwrap();
+    assert_eq!(fallback_parser.name(), "custom-regex");
+    let fallback_commit = fallback_parser.parse_commit("test19".to_string(), "feat: Add new feature".to_string());
+    assert_eq!(fallback_commit.commit_type, "feat");
     
     // Test parsing with different parsers
     let commit_message = "feat: Add new feature";
@@ -197,14 +219,14 @@ fn test_integration_with_version_calculation() {
     // This test would ideally use a mock repository to test the integration with the version calculation logic
     // For simplicity, we'll just test that the parsers can be used with the version calculation logic
     
-    let conventional_parser = ParserFactory::create(&ParserStrategy::Conventional);
+    let conventional_parser = ParserFactory::create(&ParserStrategy::Conventional).unwrap();
     let custom_parser = ParserFactory::create(&ParserStrategy::CustomRegex {
         commit_type_pattern: COMMIT_TYPE_REGEX_STR.to_string(),
         title_pattern: TITLE_REGEX_STR.to_string(),
         body_pattern: BODY_REGEX_STR.to_string(),
         breaking_pattern: BREAKING_REGEX_STR.to_string(),
         scope_pattern: SCOPE_REGEX_STR.to_string(),
-    });
+    }).unwrap();
     
     // Test with a major change
     let major_message = "feat!: Breaking change";
@@ -242,4 +264,255 @@ fn test_integration_with_version_calculation() {
     
     assert!(conv_noop.is_noop_change(&default_noop_types()));
     assert!(custom_noop.is_noop_change(&default_noop_types()));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_bang_is_breaking_for_any_type() {
+    let conventional_parser = ParserFactory::create(&ParserStrategy::Conventional).unwrap();
+    let custom_parser = CustomRegexParser::default();
+    let bump_map = BumpMap::default();
+
+    let parsers: [&dyn CommitParser; 2] = [conventional_parser.as_ref(), &custom_parser];
+    for parser in parsers {
+        for message in ["chore!: drop node 14", "chore(deps)!: drop node 14", "noop!: drop node 14", "breaking: Remove the v1 API"] {
+            let commit = parser.parse_commit("1".to_string(), message.to_string());
+            assert_eq!(classify_commit(&commit, &bump_map), VersionBumpType::Major, "{} with the {} parser", message, parser.name());
+        }
+        let commit = parser.parse_commit("1".to_string(), "chore(deps)!: drop node 14".to_string());
+        assert_eq!((commit.commit_type.as_str(), commit.scope.as_deref(), commit.title.as_str()), ("chore", Some("deps"), "drop node 14"));
+
+        // A `!:` later in the title does not make a commit breaking
+        let commit = parser.parse_commit("1".to_string(), "fix: Stop on wow!: in templates".to_string());
+        assert!(!commit.has_breaking_change, "{} parser", parser.name());
+        assert_eq!(commit.title, "Stop on wow!: in templates", "{} parser", parser.name());
+    }
+}
+
+#[test]
+fn test_mixed_case_commit_types() {
+    let conventional_parser = ParserFactory::create(&ParserStrategy::Conventional).unwrap();
+    let custom_parser = CustomRegexParser::default();
+
+    for parser in [&*conventional_parser, &custom_parser as &dyn CommitParser] {
+        let feat = parser.parse_commit("case1".to_string(), "FEAT: Add thing".to_string());
+        assert_eq!(feat.commit_type, "feat");
+        assert!(feat.is_minor_change(&default_minor_types()));
+
+        let fix = parser.parse_commit("case2".to_string(), "Fix: Repair thing".to_string());
+        assert_eq!(fix.commit_type, "fix");
+        assert_eq!(
+            determine_version_bump(&fix, &default_major_types(), &default_minor_types(), &default_noop_types()),
+            VersionBumpType::Patch
+        );
+
+        let chore = parser.parse_commit("case3".to_string(), "Chore(Deps): Bump thing".to_string());
+        assert!(chore.is_noop_change(&default_noop_types()));
+    }
+
+    // Configured types are compared case-insensitively as well
+    let commit = Commit::parse("case4".to_string(), "feat: Add thing".to_string());
+    assert!(commit.is_minor_change(&["FEAT"]));
+}
+
+#[test]
+fn test_type_aliases() {
+    let aliases = parse_type_aliases("feature=feat, BugFix=fix").expect("Aliases should parse");
+    assert_eq!(aliases.get("feature").map(String::as_str), Some("feat"));
+    assert_eq!(aliases.get("bugfix").map(String::as_str), Some("fix"));
+    assert!(parse_type_aliases("feature").is_err());
+    assert!(parse_type_aliases("=feat").is_err());
+
+    let handle = ParserHandle::with_type_aliases(ParserStrategy::Conventional, aliases);
+    let parser = handle.parser().expect("Parser should build");
+
+    let feature = parser.parse_commit("alias1".to_string(), "Feature(ui): Add button".to_string());
+    assert_eq!(feature.commit_type, "feat");
+    assert_eq!(feature.scope.as_deref(), Some("ui"));
+    assert_eq!(
+        determine_version_bump(&feature, &default_major_types(), &default_minor_types(), &default_noop_types()),
+        VersionBumpType::Minor
+    );
+
+    let bugfix = parser.parse_commit("alias2".to_string(), "bugfix: Repair button".to_string());
+    assert_eq!(bugfix.commit_type, "fix");
+
+    // Breaking changes still win over the aliased type
+    let breaking = parser.parse_commit("alias3".to_string(), "bugfix!: Remove button".to_string());
+    assert_eq!(
+        determine_version_bump(&breaking, &default_major_types(), &default_minor_types(), &default_noop_types()),
+        VersionBumpType::Major
+    );
+}
+
+#[test]
+fn test_crlf_and_whitespace_messages() {
+    let conventional_parser = ParserFactory::create(&ParserStrategy::Conventional).unwrap();
+    let custom_parser = CustomRegexParser::default();
+
+    for parser in [&*conventional_parser, &custom_parser as &dyn CommitParser] {
+        let footer = parser.parse_commit(
+            "crlf1".to_string(),
+            "feat: Add export\r\n\r\nExports to CSV.\r\n\r\nBREAKING CHANGE: The old export is removed.\r\n".to_string(),
+        );
+        assert_eq!(footer.commit_type, "feat");
+        assert_eq!(footer.title, "Add export");
+        assert!(footer.has_breaking_change, "{} parser should detect the CRLF breaking footer", parser.name());
+        assert_eq!(footer.body.as_deref(), Some("Exports to CSV."));
+        assert_eq!(footer.breaking_change_note.as_deref(), Some("The old export is removed."));
+
+        // Blank lines holding only whitespace still separate paragraphs
+        let padded = parser.parse_commit(
+            "crlf2".to_string(),
+            "\n  fix: Trim input  \r\n \t\r\nBREAKING CHANGE: Input is no longer padded.\r\n\r\n".to_string(),
+        );
+        assert_eq!(padded.commit_type, "fix");
+        assert_eq!(padded.title, "Trim input");
+        assert!(padded.has_breaking_change, "{} parser should detect the padded breaking footer", parser.name());
+        assert!(!padded.body.unwrap_or_default().contains('\r'));
+
+        // Empty messages are unknown-type commits, counted as patches
+        for message in ["", "  \r\n\r\n"] {
+            let empty = parser.parse_commit("empty".to_string(), message.to_string());
+            assert_eq!(empty.commit_type, "");
+            assert_eq!(empty.title, "");
+            assert!(!empty.has_breaking_change);
+            assert_eq!(
+                determine_version_bump(&empty, &default_major_types(), &default_minor_types(), &default_noop_types()),
+                VersionBumpType::Patch
+            );
+        }
+    }
+}
+
+#[test]
+fn test_multiple_and_nested_scopes() {
+    let conventional_parser = ParserFactory::create(&ParserStrategy::Conventional).unwrap();
+    let custom_parser = CustomRegexParser::default();
+
+    for parser in [&*conventional_parser, &custom_parser as &dyn CommitParser] {
+        let single = parser.parse_commit("scope1".to_string(), "feat(api): Add export".to_string());
+        assert_eq!(single.scope.as_deref(), Some("api"));
+        assert_eq!(single.scopes, vec!["api"]);
+
+        let multiple = parser.parse_commit("scope2".to_string(), "feat(api, web): Shared change".to_string());
+        assert_eq!(multiple.scope.as_deref(), Some("api,web"), "{} parser", parser.name());
+        assert_eq!(multiple.scopes, vec!["api", "web"]);
+        assert_eq!(multiple.title, "Shared change");
+
+        let nested = parser.parse_commit("scope3".to_string(), "fix(api/auth,web): Refresh tokens".to_string());
+        assert_eq!(nested.scope.as_deref(), Some("api/auth,web"));
+        assert_eq!(nested.scopes, vec!["api/auth", "web"]);
+
+        let unscoped = parser.parse_commit("scope4".to_string(), "fix: Refresh tokens".to_string());
+        assert_eq!(unscoped.scope, None);
+        assert!(unscoped.scopes.is_empty());
+    }
+
+    // Empty entries are dropped
+    assert_eq!(vnext::models::commit::parse_scopes(" api / auth ,, web "), vec!["api/auth", "web"]);
+}
+
+#[test]
+fn test_cli_parser_selection() {
+    let cli = vnext::cli::Cli::parse_from(["vnext", "--parser", "custom"]);
+    assert_eq!(cli.parser, ParserKind::Custom);
+    assert!(matches!(cli.parser_strategy(), ParserStrategy::CustomRegex { .. }));
+
+    // Unknown parser names fail argument parsing instead of falling back to conventional
+    let err = vnext::cli::Cli::try_parse_from(["vnext", "--parser", "convencional"]).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("conventional") && message.contains("custom"), "{}", message);
+
+    // Invalid custom patterns only fall back to the defaults when asked to
+    let strict = vnext::cli::Cli::parse_from(["vnext", "--parser", "custom", "--type-pattern", "[invalid"]);
+    assert!(strict.to_options().parser.parser().is_err());
+    let lenient = vnext::cli::Cli::parse_from(["vnext", "--parser", "custom", "--type-pattern", "[invalid", "--allow-regex-fallback"]);
+    assert!(lenient.to_options().parser.parser().is_ok());
+}
+
+#[test]
+fn test_custom_pattern_errors_name_the_pattern() {
+    let build = |pattern: PatternName, source: &str| {
+        let mut patterns = [COMMIT_TYPE_REGEX_STR, TITLE_REGEX_STR, BODY_REGEX_STR, BREAKING_REGEX_STR, SCOPE_REGEX_STR];
+        let index = [PatternName::Type, PatternName::Title, PatternName::Body, PatternName::Breaking, PatternName::Scope]
+            .iter()
+            .position(|p| *p == pattern)
+            .unwrap();
+        patterns[index] = source;
+        match CustomRegexParser::new(patterns[0], patterns[1], patterns[2], patterns[3], patterns[4]) {
+            Ok(_) => panic!("{:?} pattern {} should be rejected", pattern, source),
+            Err(e) => e,
+        }
+    };
+
+    // Syntax errors name the flag and keep the regex error pointing at the position
+    let err = build(PatternName::Scope, r"^[\w-]+\((.*\):");
+    assert_eq!(err.pattern, PatternName::Scope);
+    assert!(matches!(err.kind, PatternErrorKind::Syntax(_)));
+    let message = err.to_string();
+    assert!(message.contains("--scope-pattern"), "{}", message);
+    assert!(message.contains('^'), "Syntax errors should point at the offending position: {}", message);
+
+    // Patterns without the capture group the parser reads are rejected up front
+    let err = build(PatternName::Title, r"^[\w-]+:\s.*");
+    assert_eq!(err.pattern, PatternName::Title);
+    assert!(matches!(err.kind, PatternErrorKind::MissingCaptureGroup { required: 1, found: 0 }));
+    assert!(err.to_string().contains("--title-pattern"), "{}", err);
+
+    // The breaking pattern is only matched, so it needs no capture group
+    assert!(CustomRegexParser::new(COMMIT_TYPE_REGEX_STR, TITLE_REGEX_STR, BODY_REGEX_STR, r"^[\w-]+!:", SCOPE_REGEX_STR).is_ok());
+
+    // The fallback only replaces the invalid pattern
+    let strategy = ParserFactory::with_regex_fallback(ParserStrategy::CustomRegex {
+        commit_type_pattern: r"^custom-([\w-]+):".to_string(),
+        title_pattern: r"^custom-[\w-]+:\s*(.*)".to_string(),
+        body_pattern: BODY_REGEX_STR.to_string(),
+        breaking_pattern: BREAKING_REGEX_STR.to_string(),
+        scope_pattern: "(unclosed".to_string(),
+    });
+    match strategy {
+        ParserStrategy::CustomRegex { commit_type_pattern, scope_pattern, .. } => {
+            assert_eq!(commit_type_pattern, r"^custom-([\w-]+):");
+            assert_eq!(scope_pattern, SCOPE_REGEX_STR);
+        }
+        other => panic!("Unexpected strategy {:?}", other),
+    }
+}
+
+/// Messages with the breaking flag both parsers must agree on
+const BREAKING_CORPUS: &[(&str, bool)] = &[
+    ("feat!: Drop the v1 API", true),
+    ("fix!: Change the default", true),
+    ("feat(api)!: Drop the v1 endpoints", true),
+    ("refactor(api, web)!: Rename the client", true),
+    ("fix(api/auth)!: Require tokens", true),
+    ("my-type!: Hyphenated type", true),
+    ("fix: Change the default\n\nBREAKING CHANGE: the default is now off", true),
+    ("feat(api): Add export\n\nBody text\n\nBREAKING-CHANGE: the old export is gone", true),
+    ("feat: Add export", false),
+    ("feat(api): Add export", false),
+    ("fix: Handle input\n\nfeat!: only a quote in the body", false),
+    ("Update README!", false),
+];
+
+#[test]
+fn test_breaking_detection_parity_between_parsers() {
+    let conventional = ConventionalCommitParser::new();
+    let custom = CustomRegexParser::default();
+    for (message, breaking) in BREAKING_CORPUS {
+        let conv = conventional.parse_commit("parity".to_string(), message.to_string());
+        let cust = custom.parse_commit("parity".to_string(), message.to_string());
+        assert_eq!(conv.has_breaking_change, *breaking, "conventional parser on {:?}", message);
+        assert_eq!(cust.has_breaking_change, *breaking, "custom parser on {:?}", message);
+        assert_eq!(conv.commit_type, cust.commit_type, "type of {:?}", message);
+        assert_eq!(conv.scope, cust.scope, "scope of {:?}", message);
+    }
+}
+
+#[test]
+fn test_user_breaking_pattern_is_not_extended() {
+    // A user-supplied pattern replaces the default, bang form included
+    let parser = CustomRegexParser::new(COMMIT_TYPE_REGEX_STR, TITLE_REGEX_STR, BODY_REGEX_STR, r"\[breaking\]", SCOPE_REGEX_STR).unwrap();
+    assert!(!parser.parse_commit("user".to_string(), "feat(api)!: Drop the v1 endpoints".to_string()).has_breaking_change);
+    assert!(parser.parse_commit("user".to_string(), "feat: Drop the v1 endpoints [breaking]".to_string()).has_breaking_change);
+}
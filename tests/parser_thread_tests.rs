@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::thread;
+
+use vnext::models::commit::CommitParser;
+use vnext::parsers::{
+    ConventionalCommitParser, CustomRegexParser, ParserHandle, ParserStrategy, BODY_REGEX_STR, BREAKING_REGEX_STR,
+    COMMIT_TYPE_REGEX_STR, SCOPE_REGEX_STR, TITLE_REGEX_STR,
+};
+
+fn assert_send_sync<T: Send + Sync + ?Sized>() {}
+
+/// A message of a type, scope and footer picked by `i`
+fn message(i: usize) -> String {
+    let commit_type = ["feat", "fix", "chore", "docs"][i % 4];
+    let bang = if i.is_multiple_of(10) { "!" } else { "" };
+    format!("{}(pkg-{}){}: Change number {}\n\nDetails of change {}.\n\nRefs: #{}", commit_type, i % 7, bang, i, i, i)
+}
+
+#[test]
+fn test_parsers_are_send_and_sync() {
+    assert_send_sync::<dyn CommitParser>();
+    assert_send_sync::<Box<dyn CommitParser>>();
+    assert_send_sync::<ConventionalCommitParser>();
+    assert_send_sync::<CustomRegexParser>();
+    assert_send_sync::<ParserHandle>();
+}
+
+#[test]
+fn test_parser_shared_across_threads() {
+    const THREADS: usize = 8;
+    const MESSAGES: usize = 2_000;
+
+    let custom_strategy = ParserStrategy::CustomRegex {
+        commit_type_pattern: COMMIT_TYPE_REGEX_STR.to_string(),
+        title_pattern: TITLE_REGEX_STR.to_string(),
+        body_pattern: BODY_REGEX_STR.to_string(),
+        breaking_pattern: BREAKING_REGEX_STR.to_string(),
+        scope_pattern: SCOPE_REGEX_STR.to_string(),
+    };
+    for strategy in [ParserStrategy::Conventional, custom_strategy] {
+        let parser: Arc<dyn CommitParser> = ParserHandle::new(strategy).parser().unwrap();
+        let expected: Vec<_> = (0..MESSAGES).map(|i| parser.parse_commit(i.to_string(), message(i))).collect();
+
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let parser = Arc::clone(&parser);
+                thread::spawn(move || (0..MESSAGES).map(|i| parser.parse_commit(i.to_string(), message(i))).collect::<Vec<_>>())
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), expected, "Every thread parses the same commits");
+        }
+
+        let breaking = expected.iter().filter(|commit| commit.has_breaking_change).count();
+        assert_eq!(breaking, MESSAGES / 10);
+        assert_eq!(expected[3].commit_type, "docs");
+        assert_eq!(expected[3].scope.as_deref(), Some("pkg-3"));
+        assert_eq!(expected[3].footers, vec![("Refs".to_string(), "#3".to_string())]);
+    }
+
+    // A custom parser behind a plain Arc works the same way
+    let custom: Arc<dyn CommitParser> = Arc::new(CustomRegexParser::default());
+    let titles: Vec<String> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let custom = &custom;
+                scope.spawn(move || custom.parse_commit(t.to_string(), message(t)).title)
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+    assert_eq!(titles[5], "Change number 5");
+}
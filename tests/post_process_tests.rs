@@ -0,0 +1,105 @@
+use std::fs;
+use std::process::Command;
+use std::time::Duration;
+
+use vnext::core::post_process::{apply_post_process, run_post_process_command};
+use vnext::models::options::VNextOptions;
+use vnext::VNextError;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command, run_vnext};
+
+/// Write a small sed-like script that rewrites ticket IDs into links
+#[cfg(unix)]
+fn write_link_script(dir: &std::path::Path) -> std::path::PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let script = dir.join("link-tickets.sh");
+    fs::write(&script, "#!/bin/sh\nsed 's/ABC-\\([0-9]*\\)/[ABC-\\1](https:\\/\\/tracker.example.com\\/ABC-\\1)/g'\n")
+        .expect("Failed to write script");
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).expect("Failed to make script executable");
+    script
+}
+
+#[cfg(unix)]
+#[test]
+fn test_post_process_command_rewrites_stdout_and_file() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    init_repo(repo_path);
+
+    let file_path = repo_path.join("README.md");
+    fs::write(&file_path, "# Test Repository").expect("Failed to write README file");
+    run_and_show_command("git", &["add", file_path.to_str().unwrap()], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: ABC-12 Initial commit"], repo_path);
+
+    let version = run_vnext(repo_path);
+    assert_eq!(version, "0.1.0");
+
+    let out_dir = tempfile::tempdir().expect("Failed to create output directory");
+    let script = write_link_script(out_dir.path());
+    let changelog_file = out_dir.path().join("CHANGELOG.md");
+
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let binary_path = project_dir.join("target/debug/vnext");
+    let output = Command::new(&binary_path)
+        .args([
+            "--changelog",
+            "--changelog-out",
+            changelog_file.to_str().unwrap(),
+            "--changelog-post-process",
+            script.to_str().unwrap(),
+        ])
+        .current_dir(&repo_path)
+        .output()
+        .expect("Failed to execute vnext with a post-process command");
+
+    let expected = "* feat: [ABC-12](https://tracker.example.com/ABC-12) Initial commit";
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let file_contents = fs::read_to_string(&changelog_file).expect("Changelog file should exist");
+
+    assert!(output.status.success());
+    assert!(stdout.contains(expected), "stdout should be post-processed: {}", stdout);
+    assert!(file_contents.contains(expected), "The changelog file should be post-processed: {}", file_contents);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_post_process_command_failure_is_an_error() {
+    let result = run_post_process_command("echo broken >&2; exit 3", "changelog", Duration::from_secs(5));
+
+    match result {
+        Err(VNextError::PostProcessError(message)) => assert!(message.contains("broken"), "{}", message),
+        other => panic!("Expected a post-process error, got {:?}", other),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_post_process_command_timeout() {
+    let result = run_post_process_command("sleep 5", "changelog", Duration::from_millis(200));
+
+    match result {
+        Err(VNextError::PostProcessError(message)) => assert!(message.contains("timed out"), "{}", message),
+        other => panic!("Expected a timeout, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_library_hook_runs_after_command() {
+    let options = VNextOptions {
+        changelog_post_process: if cfg!(windows) { None } else { Some("tr a-z A-Z".to_string()) },
+        post_process: Some(Box::new(|text| format!("{}\n\nSee https://wiki.example.com/releases", text))),
+        ..VNextOptions::default()
+    };
+
+    let processed = apply_post_process(&options, "### What's changed in v1.0.0".to_string()).unwrap();
+
+    if cfg!(windows) {
+        assert_eq!(processed, "### What's changed in v1.0.0\n\nSee https://wiki.example.com/releases");
+    } else {
+        assert_eq!(processed, "### WHAT'S CHANGED IN V1.0.0\n\nSee https://wiki.example.com/releases");
+    }
+}
@@ -0,0 +1,79 @@
+use std::path::Path;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext, run_vnext_success};
+
+/// Stable and rc tags interleaved: v1.2.0, v1.3.0-rc.1, v1.3.0, v1.4.0-rc.1, then a fix
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.2.0"], repo_path);
+    commit_file(repo_path, "a.md", "feat: Add a");
+    run_and_show_command("git", &["tag", "v1.3.0-rc.1"], repo_path);
+    commit_file(repo_path, "b.md", "fix: Fix b");
+    run_and_show_command("git", &["tag", "v1.3.0"], repo_path);
+    commit_file(repo_path, "c.md", "feat: Add c");
+    run_and_show_command("git", &["tag", "v1.4.0-rc.1"], repo_path);
+    commit_file(repo_path, "d.md", "fix: Fix d");
+}
+
+#[test]
+fn test_stable_flow_starts_from_the_latest_stable_tag() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+
+    // feat: Add c and fix: Fix d since v1.3.0
+    assert_eq!(run_vnext(repo_path), "1.4.0");
+    assert_eq!(run_vnext_success(repo_path, &["--current"]), "1.3.0");
+    assert_eq!(run_vnext_success(repo_path, &["--current", "--prerelease-base", "exclude"]), "1.3.0");
+}
+
+#[test]
+fn test_prerelease_flow_sees_rc_tags() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    run_vnext(repo_path);
+
+    assert_eq!(run_vnext_success(repo_path, &["--current", "--prerelease-base", "include"]), "1.4.0-rc.1");
+    // Only fix: Fix d is after v1.4.0-rc.1
+    let stdout = run_vnext_success(repo_path, &["--prerelease-base", "include", "--explain", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("Output should be JSON");
+    assert_eq!(json["current_version"], "1.4.0-rc.1");
+    assert_eq!(json["commits"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_only_rc_tags_above_the_stable_one() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.2.0"], repo_path);
+    commit_file(repo_path, "a.md", "feat: Add a");
+    run_and_show_command("git", &["tag", "v1.3.0-rc.1"], repo_path);
+    run_vnext(repo_path);
+
+    assert_eq!(run_vnext_success(repo_path, &["--current"]), "1.2.0");
+    assert_eq!(run_vnext_success(repo_path, &["--current", "--prerelease-base", "include"]), "1.3.0-rc.1");
+}
+
+#[test]
+fn test_stable_release_notes_cover_its_prereleases() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    run_vnext(repo_path);
+
+    let stdout = run_vnext_success(repo_path, &["changelog", "--for-tag", "v1.3.0", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("Output should be JSON");
+    assert_eq!(json["previous_tag"], "v1.2.0");
+    assert_eq!(json["commits"].as_array().unwrap().len(), 2);
+
+    let stdout = run_vnext_success(repo_path, &["--prerelease-base", "include", "changelog", "--for-tag", "v1.3.0", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("Output should be JSON");
+    assert_eq!(json["previous_tag"], "v1.3.0-rc.1");
+    assert_eq!(json["commits"].as_array().unwrap().len(), 1);
+}
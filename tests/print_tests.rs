@@ -0,0 +1,89 @@
+use std::fs;
+use std::path::Path;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext, run_vnext_output};
+
+fn stdout_of(repo_path: &Path, args: &[&str]) -> String {
+    let output = run_vnext_output(repo_path, args);
+    assert!(output.status.success(), "vnext failed: {}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).trim_end().to_string()
+}
+
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    commit_file(repo_path, "a.md", "feat: Add export");
+}
+
+/// Split combined output the way a workflow step would: the first line is the version
+/// and everything after the first `---` line is the changelog
+fn split_output(stdout: &str) -> (&str, &str) {
+    let (version, rest) = stdout.split_once('\n').expect("Output should have several lines");
+    let changelog = rest.strip_prefix("---\n").expect("The version should be followed by a --- line");
+    (version, changelog)
+}
+
+#[test]
+fn test_print_version_and_changelog() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    assert_eq!(run_vnext(repo_path), "1.1.0");
+
+    let changelog = stdout_of(repo_path, &["--changelog"]);
+    let combined = stdout_of(repo_path, &["--print", "version,changelog"]);
+    let (version, combined_changelog) = split_output(&combined);
+    assert_eq!(version, "1.1.0");
+    assert_eq!(combined_changelog, changelog);
+    assert!(combined_changelog.starts_with("### What's changed in v1.1.0"), "{}", combined);
+
+    // Sections come in the requested order
+    let reversed = stdout_of(repo_path, &["--print", "changelog,version"]);
+    assert_eq!(reversed, format!("{}\n---\n1.1.0", changelog));
+    assert_eq!(stdout_of(repo_path, &["--print", "version"]), "1.1.0");
+    assert_eq!(stdout_of(repo_path, &["--print", "changelog"]), changelog);
+}
+
+#[test]
+fn test_print_with_json_and_files() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    run_vnext(repo_path);
+
+    let json: serde_json::Value = serde_json::from_str(&stdout_of(repo_path, &["--print", "version,changelog", "--format", "json"])).unwrap();
+    assert_eq!(json["version"], "1.1.0");
+    assert!(json["changelog"].as_str().unwrap().contains("Add export"));
+
+    // The changelog file holds the changelog alone
+    let changelog_file = repo_path.join("CHANGES.md");
+    let combined = stdout_of(repo_path, &["--print", "version,changelog", "--changelog-out", changelog_file.to_str().unwrap()]);
+    assert_eq!(split_output(&combined).1, fs::read_to_string(&changelog_file).unwrap().trim_end());
+}
+
+#[test]
+fn test_print_before_the_first_commit() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    init_repo(repo_path);
+
+    let combined = stdout_of(repo_path, &["--print", "version,changelog"]);
+    let (version, changelog) = split_output(&combined);
+    assert_eq!(version, "0.0.0");
+    assert!(changelog.contains("* No changes"), "{}", combined);
+}
+
+#[test]
+fn test_print_conflicts_with_changelog() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+
+    let output = run_vnext_output(repo_path, &["--print", "version", "--changelog"]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = run_vnext_output(repo_path, &["--print", "version,notes"]);
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,160 @@
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+use git2::Repository;
+use vnext::core::progress::ProgressReporter;
+use vnext::core::version::{calculate_version_bump, find_version_base};
+use vnext::models::options::{CancellationToken, Phase, ProgressCallback, WalkOptions};
+use vnext::models::version::BumpMap;
+use vnext::parsers::{ParserFactory, ParserStrategy};
+use vnext::{VNextError, VersionCalculator};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command};
+
+fn create_repo_with_commits(repo_path: &std::path::Path, count: usize) {
+    init_repo(repo_path);
+
+    for i in 0..count {
+        let file_path = repo_path.join(format!("{}.md", i));
+        fs::write(&file_path, format!("# {}", i)).expect("Failed to write file");
+        run_and_show_command("git", &["add", file_path.to_str().unwrap()], repo_path);
+        run_and_show_command("git", &["commit", "-m", &format!("fix: change {}", i)], repo_path);
+        if i == 0 {
+            run_and_show_command("git", &["tag", "v0.1.0"], repo_path);
+        }
+    }
+}
+
+#[test]
+fn test_progress_callbacks_are_monotonic() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo_with_commits(repo_path, 6);
+
+    let repo = Repository::open(repo_path).expect("Failed to open repository");
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let parser = ParserFactory::create(&ParserStrategy::Conventional).unwrap();
+
+    let events = Arc::new(Mutex::new(Vec::<(Phase, u64, Option<u64>)>::new()));
+    let recorder = events.clone();
+    let callback: ProgressCallback = Box::new(move |phase, current, total| {
+        recorder.lock().unwrap().push((phase, current, total));
+    });
+    let progress = ProgressReporter::new(Some(&callback), None);
+
+    let base = find_version_base(&repo, &head, &WalkOptions::default(), &progress).unwrap();
+    let (_, summary) = calculate_version_bump(
+        &repo, base.tag_commit(), &head, &*parser, &BumpMap::default(), &WalkOptions::default(), &progress,
+    ).unwrap();
+    assert_eq!(summary.commits.len(), 5);
+
+    let events = events.lock().unwrap();
+    let tag_scan: Vec<_> = events.iter().filter(|(phase, _, _)| *phase == Phase::TagScan).collect();
+    let revwalk: Vec<_> = events.iter().filter(|(phase, _, _)| *phase == Phase::Revwalk).collect();
+
+    assert_eq!(tag_scan.len(), 1, "One tag should be scanned");
+    assert_eq!(tag_scan[0].2, Some(1), "Tag scan total should be known");
+    assert_eq!(revwalk.len(), 5, "Every walked commit should be reported");
+    for window in revwalk.windows(2) {
+        assert!(window[0].1 < window[1].1, "Revwalk counts should increase monotonically");
+    }
+    assert_eq!(revwalk.last().unwrap().1, 5);
+}
+
+#[test]
+fn test_cancellation_stops_the_walk_early() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo_with_commits(repo_path, 10);
+
+    let repo = Repository::open(repo_path).expect("Failed to open repository");
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let parser = ParserFactory::create(&ParserStrategy::Conventional).unwrap();
+
+    let token = CancellationToken::new();
+    let walked = Arc::new(Mutex::new(0u64));
+    let cancel_token = token.clone();
+    let walked_recorder = walked.clone();
+    let callback: ProgressCallback = Box::new(move |phase, current, _| {
+        if phase == Phase::Revwalk {
+            *walked_recorder.lock().unwrap() = current;
+            if current == 3 {
+                cancel_token.cancel();
+            }
+        }
+    });
+    let progress = ProgressReporter::new(Some(&callback), Some(&token));
+
+    let base = find_version_base(&repo, &head, &WalkOptions::default(), &progress).unwrap();
+    let result = calculate_version_bump(
+        &repo, base.tag_commit(), &head, &*parser, &BumpMap::default(), &WalkOptions::default(), &progress,
+    );
+
+    assert!(matches!(result, Err(VNextError::Cancelled)), "Walk should abort with VNextError::Cancelled");
+    assert_eq!(*walked.lock().unwrap(), 3, "Walk should stop right after cancellation");
+}
+
+#[test]
+fn test_tags_are_scanned_once_per_run() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo_with_commits(repo_path, 4);
+    run_and_show_command("git", &["tag", "v0.2.0", "HEAD~2"], repo_path);
+    run_and_show_command("git", &["tag", "not-a-version"], repo_path);
+
+    let events = Arc::new(Mutex::new(Vec::<(u64, Option<u64>)>::new()));
+    let recorder = events.clone();
+    let callback: ProgressCallback = Box::new(move |phase, current, total| {
+        if phase == Phase::TagScan {
+            recorder.lock().unwrap().push((current, total));
+        }
+    });
+    let plan = VersionCalculator::for_repo(repo_path)
+        .with_progress(ProgressReporter::new(Some(&callback), None))
+        .run()
+        .unwrap();
+    assert_eq!(plan.previous_tag.as_deref(), Some("v0.2.0"));
+    assert_eq!(plan.next_version.to_string(), "0.2.1");
+
+    // Every scan reports each of the three tags once
+    let events = events.lock().unwrap();
+    assert_eq!(*events, vec![(1, Some(3)), (2, Some(3)), (3, Some(3))], "The tags should be scanned exactly once");
+}
+
+/// Sink sharing what the status line writes with the test
+#[derive(Clone, Default)]
+struct SharedSink(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for SharedSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_status_line_reports_phase_durations() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo_with_commits(repo_path, 3);
+
+    let sink = SharedSink::default();
+    let callback = vnext::utils::output::status_line(Box::new(sink.clone()));
+    VersionCalculator::for_repo(repo_path)
+        .with_progress(ProgressReporter::new(Some(&callback), None))
+        .run()
+        .unwrap();
+    drop(callback);
+
+    // Each phase ends on its own line with its final count and duration
+    let output = String::from_utf8(sink.0.lock().unwrap().clone()).unwrap();
+    let finished: Vec<&str> = output.lines().map(|line| line.rsplit('\r').next().unwrap()).collect();
+    let pattern = regex::Regex::new(r"^(Scanning tags: 1/1|Walking commits: 2) \(\d+ ms\)$").unwrap();
+    assert_eq!(finished.len(), 2, "{:?}", output);
+    assert!(finished.iter().all(|line| pattern.is_match(line)), "{:?}", output);
+}
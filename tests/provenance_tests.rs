@@ -0,0 +1,112 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext};
+
+fn rev_parse(repo_path: &Path, rev: &str) -> String {
+    let output = Command::new("git")
+        .args(["rev-parse", &format!("{}^{{commit}}", rev)])
+        .current_dir(repo_path)
+        .output()
+        .expect("Failed to run git rev-parse");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+fn run_with_provenance(repo_path: &Path, extra_args: &[&str]) -> serde_json::Value {
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let binary_path = project_dir.join("target/debug/vnext");
+    let provenance_path = repo_path.join("provenance.json");
+    let output = Command::new(&binary_path)
+        .arg("--provenance-out")
+        .arg(&provenance_path)
+        .args(extra_args)
+        .current_dir(repo_path)
+        .output()
+        .expect("Failed to execute vnext");
+    assert!(output.status.success(), "vnext failed: {}", String::from_utf8_lossy(&output.stderr));
+
+    let contents = fs::read_to_string(&provenance_path).expect("Provenance file should be written");
+    fs::remove_file(&provenance_path).expect("Failed to remove provenance file");
+    serde_json::from_str(&contents).expect("Provenance should be JSON")
+}
+
+fn is_timestamp(value: &serde_json::Value) -> bool {
+    let text = value.as_str().unwrap_or("");
+    text.len() == 20 && text.as_bytes()[10] == b'T' && text.ends_with('Z')
+}
+
+#[test]
+fn test_provenance_matches_repository_state() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    init_repo(repo_path);
+    run_and_show_command("git", &["remote", "add", "origin", "git@github.com:unbounded-tech/vnext.git"], repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    commit_file(repo_path, "a.txt", "feat: Add a");
+    commit_file(repo_path, "b.txt", "chore: Tidy b");
+
+    // Builds the binary
+    assert_eq!(run_vnext(repo_path), "1.1.0");
+
+    // Changelog filters do not change the commit count
+    let provenance = run_with_provenance(repo_path, &["--changelog", "--changelog-exclude-noop"]);
+
+    assert_eq!(provenance["schema_version"], 1);
+    assert_eq!(provenance["repository"], "unbounded-tech/vnext");
+    assert_eq!(provenance["tag"], "v1.1.0");
+    assert_eq!(provenance["previous_tag"], "v1.0.0");
+    assert_eq!(provenance["current_version"], "1.0.0");
+    assert_eq!(provenance["next_version"], "1.1.0");
+    assert_eq!(provenance["head_sha"], rev_parse(repo_path, "HEAD"));
+    assert_eq!(provenance["baseline_sha"], rev_parse(repo_path, "v1.0.0"));
+    assert_eq!(provenance["commit_count"], 2);
+    assert_eq!(provenance["tool"]["name"], "vnext");
+    assert_eq!(provenance["tool"]["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(provenance["configuration_fingerprint"].as_str().unwrap().len(), 16);
+    assert!(is_timestamp(&provenance["timestamps"]["generated_at"]), "{}", provenance);
+    assert!(is_timestamp(&provenance["timestamps"]["head_committed_at"]), "{}", provenance);
+}
+
+#[test]
+fn test_provenance_without_previous_release() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    commit_file(repo_path, "a.txt", "fix: Fix a");
+
+    // Builds the binary
+    run_vnext(repo_path);
+    let provenance = run_with_provenance(repo_path, &["--tag-prefix", "api-v"]);
+
+    assert!(provenance["repository"].is_null());
+    assert!(provenance["previous_tag"].is_null());
+    assert_eq!(provenance["tag"], "api-v0.1.0");
+    assert_eq!(provenance["commit_count"], 2);
+    assert_eq!(provenance["head_sha"], rev_parse(repo_path, "HEAD"));
+    assert_eq!(provenance["baseline_sha"], rev_parse(repo_path, "HEAD~1"));
+}
+
+#[test]
+fn test_configuration_fingerprint() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+
+    // Builds the binary
+    run_vnext(repo_path);
+    let first = run_with_provenance(repo_path, &[]);
+    let second = run_with_provenance(repo_path, &["--changelog"]);
+    let different = run_with_provenance(repo_path, &["--minor-commit-types", "feat,minor,perf"]);
+
+    assert_eq!(first["configuration_fingerprint"], second["configuration_fingerprint"], "Output flags do not affect the fingerprint");
+    assert_ne!(first["configuration_fingerprint"], different["configuration_fingerprint"]);
+}
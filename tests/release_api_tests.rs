@@ -0,0 +1,70 @@
+use std::path::Path;
+
+use vnext::{calculate_release, ParserStrategy, ReleaseOptions};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext, run_vnext_with_args};
+
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "api-v1.2.0"], repo_path);
+    commit_file(repo_path, "export.txt", "feat(api): Add export\n\nExports the report as CSV.");
+    commit_file(repo_path, "deps.txt", "chore: Bump dependencies");
+    commit_file(repo_path, "empty.txt", "fix: Handle empty reports");
+}
+
+#[test]
+fn test_calculate_release_defaults() {
+    let options = ReleaseOptions::default();
+    assert!(matches!(options.parser, ParserStrategy::Conventional));
+    assert_eq!(options.major_types, vec!["breaking", "major"]);
+    assert_eq!(options.minor_types, vec!["feat", "minor"]);
+    assert_eq!(options.noop_types, vec!["chore", "noop"]);
+    assert!(!options.changelog);
+
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+
+    // Without the tag prefix there is no release yet
+    let plan = calculate_release(repo_path, &options).unwrap();
+    assert_eq!(plan.current_version.to_string(), "0.0.0");
+    assert!(plan.changelog.is_none(), "The changelog is only rendered when asked for");
+
+    let options = ReleaseOptions {
+        tag_prefix: Some("api-v".to_string()),
+        minor_types: vec!["feat".to_string(), "fix".to_string()],
+        ..ReleaseOptions::default()
+    };
+    let plan = calculate_release(repo_path, &options).unwrap();
+    assert_eq!(plan.current_version.to_string(), "1.2.0");
+    assert_eq!(plan.next_version.to_string(), "1.3.0");
+    assert_eq!(plan.previous_tag.as_deref(), Some("api-v1.2.0"));
+    assert_eq!(plan.summary.commits.len(), 3);
+}
+
+#[test]
+fn test_calculate_release_matches_cli() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let options = ReleaseOptions {
+        tag_prefix: Some("api-v".to_string()),
+        changelog: true,
+        changelog_exclude_noop: true,
+        ..ReleaseOptions::default()
+    };
+    let plan = calculate_release(repo_path, &options).unwrap();
+    let changelog = plan.changelog.expect("The changelog was asked for");
+    assert!(!changelog.contains("Bump dependencies"), "{}", changelog);
+    assert_eq!(
+        changelog.trim(),
+        run_vnext_with_args(repo_path, &["--tag-prefix", "api-v", "--changelog", "--changelog-exclude-noop"]),
+        "The library renders the same changelog as the CLI"
+    );
+}
@@ -0,0 +1,112 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use vnext::models::changeset::ChangesetSummary;
+use vnext::{Commit, CommitAuthor};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command, run_vnext, run_vnext_with_args};
+
+const DAY: i64 = 86_400;
+/// 2024-05-01T00:00:00Z
+const START: i64 = 1_714_521_600;
+
+/// Commit as `name` with both the author and committer date set to `time` (seconds since the epoch)
+fn commit_at(repo_path: &Path, file: &str, message: &str, name: &str, time: i64) {
+    commit_authored_at(repo_path, file, message, name, time, time);
+}
+
+/// Commit as `name`, authored at `authored` and committed at `committed`, like a rebased commit
+fn commit_authored_at(repo_path: &Path, file: &str, message: &str, name: &str, authored: i64, committed: i64) {
+    fs::write(repo_path.join(file), message).expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    let status = Command::new("git")
+        .args(["commit", "-m", message])
+        .env("GIT_AUTHOR_NAME", name)
+        .env("GIT_AUTHOR_DATE", format!("@{} +0000", authored))
+        .env("GIT_COMMITTER_DATE", format!("@{} +0000", committed))
+        .current_dir(repo_path)
+        .status()
+        .expect("Failed to run git");
+    assert!(status.success(), "git commit failed");
+}
+
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    commit_at(repo_path, "README.md", "feat: Initial commit", "patrickleet", START);
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    commit_at(repo_path, "a.txt", "feat: Add export", "Ada", START + 3 * DAY);
+    // Written before the export, but rebased onto it
+    commit_authored_at(repo_path, "b.txt", "fix: Handle empty files", "Grace", START + 2 * DAY, START + 9 * DAY + 3600);
+    commit_at(repo_path, "c.txt", "docs: Describe the export", "Ada", START + 10 * DAY + 7200);
+}
+
+fn commit(id: &str, author: Option<&str>, timestamp: Option<i64>) -> Commit {
+    let mut commit = Commit::parse(id.to_string(), "fix: Something".to_string());
+    commit.author = author.map(|name| CommitAuthor { name: name.to_string(), email: String::new(), username: None });
+    commit.timestamp = timestamp;
+    commit
+}
+
+#[test]
+fn test_summary_stats() {
+    let mut summary = ChangesetSummary::new();
+    assert_eq!(summary.commit_time_range(), None);
+    summary.commits = vec![
+        commit("3", Some("Ada"), Some(300)),
+        commit("2", None, Some(100)),
+        commit("1", Some("Grace"), None),
+        commit("0", Some("Ada"), Some(200)),
+    ];
+    assert_eq!(summary.commit_time_range(), Some((100, 300)));
+    assert_eq!(summary.author_time_range(), None, "Author times are only known for commits read from a repository");
+    summary.commits[1].author_timestamp = Some(50);
+    summary.commits[2].author_timestamp = Some(250);
+    assert_eq!(summary.author_time_range(), Some((50, 250)));
+    let counts = summary.commits_per_author();
+    assert_eq!(counts.len(), 2, "Commits without an author are not counted");
+    assert_eq!(counts["Ada"], 2);
+    assert_eq!(counts["Grace"], 1);
+}
+
+#[test]
+fn test_release_stats_in_json() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let json: serde_json::Value = serde_json::from_str(&run_vnext_with_args(repo_path, &["--format", "json"])).expect("stdout should be JSON");
+    let stats = &json["stats"];
+    assert_eq!(stats["first_commit_at"], "2024-05-04T00:00:00Z");
+    assert_eq!(stats["last_commit_at"], "2024-05-11T02:00:00Z");
+    assert_eq!(stats["first_authored_at"], "2024-05-03T00:00:00Z");
+    assert_eq!(stats["last_authored_at"], "2024-05-11T02:00:00Z");
+    assert_eq!(stats["days_since_previous_release"], 10);
+    assert_eq!(stats["commits_per_author"], serde_json::json!({ "Ada": 2, "Grace": 1 }));
+
+    // Without a previous release there is no duration
+    run_and_show_command("git", &["tag", "-d", "v1.0.0"], repo_path);
+    let json: serde_json::Value = serde_json::from_str(&run_vnext_with_args(repo_path, &["--format", "json"])).expect("stdout should be JSON");
+    assert!(json["stats"]["days_since_previous_release"].is_null());
+    assert_eq!(json["stats"]["first_commit_at"], "2024-05-01T00:00:00Z");
+}
+
+#[test]
+fn test_show_dates() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let changelog = run_vnext_with_args(repo_path, &["--changelog", "--show-dates"]);
+    assert!(changelog.contains("* feat: Add export (2024-05-04)\n"), "{}", changelog);
+    assert!(changelog.contains("* fix: Handle empty files (2024-05-10)\n"), "{}", changelog);
+
+    let changelog = run_vnext_with_args(repo_path, &["--changelog"]);
+    assert!(changelog.contains("* feat: Add export\n"), "Dates are opt-in: {}", changelog);
+}
@@ -0,0 +1,78 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use vnext::git::{get_repo_info, get_repo_info_from_remote};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command};
+
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    fs::write(repo_path.join("README.md"), "# Test").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+}
+
+/// A fork: `origin` is the fork and `upstream` the canonical repository
+fn add_fork_remotes(repo_path: &Path) {
+    run_and_show_command("git", &["remote", "add", "origin", "https://github.com/patrickleet/vnext.git"], repo_path);
+    run_and_show_command("git", &["remote", "add", "upstream", "git@github.com:unbounded-tech/vnext.git"], repo_path);
+}
+
+#[test]
+fn test_remote_selects_the_repository() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    add_fork_remotes(repo_path);
+    let repo = git2::Repository::open(repo_path).unwrap();
+
+    let origin = get_repo_info(&repo);
+    assert_eq!((origin.owner.as_str(), origin.name.as_str()), ("patrickleet", "vnext"));
+    let upstream = get_repo_info_from_remote(&repo, "upstream");
+    assert_eq!((upstream.owner.as_str(), upstream.name.as_str()), ("unbounded-tech", "vnext"));
+    assert!(upstream.is_github_repo);
+}
+
+#[test]
+fn test_missing_remote_falls_back_to_the_first_one() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    let repo = git2::Repository::open(repo_path).unwrap();
+    assert!(get_repo_info(&repo).owner.is_empty());
+
+    // No origin at all
+    run_and_show_command("git", &["remote", "add", "upstream", "https://gitlab.com/unbounded-tech/vnext.git"], repo_path);
+    let info = get_repo_info(&repo);
+    assert_eq!((info.owner.as_str(), info.name.as_str()), ("unbounded-tech", "vnext"));
+    assert!(info.is_gitlab_repo);
+}
+
+fn provenance_repository(repo_path: &Path, args: &[&str]) -> serde_json::Value {
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let provenance = repo_path.join("provenance.json");
+    let output = Command::new(project_dir.join("target/debug/vnext"))
+        .args(args)
+        .args(["--provenance-out", provenance.to_str().unwrap()])
+        .current_dir(repo_path)
+        .output()
+        .expect("Failed to execute vnext");
+    assert!(output.status.success(), "vnext failed: {}", String::from_utf8_lossy(&output.stderr));
+    let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&provenance).unwrap()).unwrap();
+    fs::remove_file(&provenance).unwrap();
+    json["repository"].clone()
+}
+
+#[test]
+fn test_remote_flag() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    add_fork_remotes(repo_path);
+
+    assert_eq!(provenance_repository(repo_path, &[]), "patrickleet/vnext");
+    assert_eq!(provenance_repository(repo_path, &["--remote", "upstream"]), "unbounded-tech/vnext");
+    assert_eq!(provenance_repository(repo_path, &["--remote", "missing"]), "patrickleet/vnext");
+}
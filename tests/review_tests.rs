@@ -0,0 +1,152 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use vnext::core::ignore_revs::{format_ignore_revs, load_ignore_revs, parse_ignore_revs, save_ignore_revs};
+use vnext::{IgnoreRevs, RevDecision, VersionCalculator};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command, run_vnext, vnext_command};
+
+fn commit(repo_path: &Path, file: &str, message: &str) -> String {
+    fs::write(repo_path.join(file), message).expect("Failed to write file");
+    run_and_show_command("git", &["add", file], repo_path);
+    run_and_show_command("git", &["commit", "-m", message], repo_path);
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(repo_path)
+        .output()
+        .expect("Failed to run git rev-parse");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Create a repository released as 1.0.0 with a pending feat and fix, returning their SHAs
+fn create_repo(repo_path: &Path) -> (String, String) {
+    init_repo(repo_path);
+    commit(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    let feat = commit(repo_path, "export.txt", "feat: Add export");
+    let fix = commit(repo_path, "typo.txt", "fix: Fix typo");
+    (feat, fix)
+}
+
+fn run_vnext_without_stdin(repo_path: &Path, args: &[&str]) -> Output {
+    vnext_command(repo_path, args).stdin(Stdio::null()).output().expect("Failed to execute vnext")
+}
+
+#[test]
+fn test_ignore_revs_file_format() {
+    let revs = parse_ignore_revs("# reviewed\n\nabc1234 hide\n\nDEF5678 noop\n0123abcd\n").expect("File should parse");
+    assert_eq!(revs.decision("abc1234fffffffffffffffffffffffffffffffff"), Some(RevDecision::Hide));
+    assert_eq!(revs.decision("def5678000000000000000000000000000000000"), Some(RevDecision::Noop));
+    assert_eq!(revs.decision("0123abcd00000000000000000000000000000000"), Some(RevDecision::Hide));
+    assert_eq!(revs.decision("1111111000000000000000000000000000000000"), None);
+
+    assert_eq!(parse_ignore_revs(&format_ignore_revs(&revs)).unwrap(), revs);
+    assert!(parse_ignore_revs("not-a-sha hide").unwrap_err().contains("line 1"));
+    assert!(parse_ignore_revs("abc1234 drop").unwrap_err().contains("unknown decision 'drop'"));
+
+    // A full SHA replaces abbreviated entries for the same commit, and None clears it
+    let mut revs = revs;
+    revs.set("ABC1234FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF", Some(RevDecision::Noop));
+    assert_eq!(revs.decision("abc1234fffffffffffffffffffffffffffffffff"), Some(RevDecision::Noop));
+    assert_eq!(revs.iter().filter(|(rev, _)| rev.starts_with("abc1234")).count(), 1);
+    revs.set("abc1234fffffffffffffffffffffffffffffffff", None);
+    assert_eq!(revs.decision("abc1234fffffffffffffffffffffffffffffffff"), None);
+}
+
+#[test]
+fn test_ignore_revs_persistence() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let path = temp_dir.path().join(".vnext-ignore-revs");
+
+    // A missing file holds no decisions
+    assert!(load_ignore_revs(&path).unwrap().is_empty());
+
+    let mut revs = IgnoreRevs::new();
+    revs.set("abc1234", Some(RevDecision::Hide));
+    revs.set("def5678", Some(RevDecision::Noop));
+    save_ignore_revs(&path, &revs).expect("Decisions should be saved");
+    assert_eq!(load_ignore_revs(&path).unwrap(), revs);
+
+    fs::write(&path, "abc1234 maybe\n").unwrap();
+    let error = load_ignore_revs(&path).unwrap_err().to_string();
+    assert!(error.contains("Configuration error") && error.contains(".vnext-ignore-revs"), "{}", error);
+}
+
+#[test]
+fn test_review_decisions_change_the_version() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    let (feat, fix) = create_repo(repo_path);
+
+    let plan = VersionCalculator::for_repo(repo_path).run().expect("Calculation should succeed");
+    assert_eq!(plan.next_version.to_string(), "1.1.0");
+
+    // Hidden commits are neither counted nor listed
+    let mut revs = IgnoreRevs::new();
+    revs.set(&feat, Some(RevDecision::Hide));
+    let plan = VersionCalculator::for_repo(repo_path).with_ignore_revs(revs.clone()).run().unwrap();
+    assert_eq!(plan.next_version.to_string(), "1.0.1");
+    assert_eq!(plan.summary.commits.len(), 1);
+    assert_eq!(plan.summary.commits[0].commit_id, fix);
+
+    // No-op commits are listed but do not bump the version
+    revs.set(&fix, Some(RevDecision::Noop));
+    let plan = VersionCalculator::for_repo(repo_path).with_ignore_revs(revs).run().unwrap();
+    assert_eq!(plan.next_version.to_string(), "1.0.0");
+    assert_eq!(plan.summary.commits.len(), 1);
+    assert_eq!(plan.summary.noop, 1);
+}
+
+#[test]
+fn test_cli_reads_ignore_revs_file() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    let (feat, _) = create_repo(repo_path);
+    assert_eq!(run_vnext(repo_path), "1.1.0");
+
+    // The default file at the repository root, with an abbreviated SHA
+    fs::write(repo_path.join(".vnext-ignore-revs"), format!("{} hide\n", &feat[..7])).unwrap();
+    assert_eq!(run_vnext(repo_path), "1.0.1");
+
+    // Relative paths are resolved against the working directory of the repository
+    let subdir = repo_path.join("docs");
+    fs::create_dir(&subdir).unwrap();
+    assert_eq!(run_vnext(&subdir), "1.0.1", "The default file is found from a subdirectory");
+    fs::write(repo_path.join("decisions.txt"), format!("{} hide\n", &feat[..7])).unwrap();
+    let output = run_vnext_without_stdin(&subdir, &["--ignore-revs-file", "decisions.txt"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "1.0.1");
+    fs::remove_file(repo_path.join("decisions.txt")).unwrap();
+
+    // An explicit file replaces the default one
+    let custom = temp_dir.path().join("decisions.txt");
+    fs::write(&custom, "").unwrap();
+    let output = run_vnext_without_stdin(repo_path, &["--ignore-revs-file", custom.to_str().unwrap()]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "1.1.0");
+
+    // An invalid file is a configuration error
+    fs::write(&custom, "zzz\n").unwrap();
+    let output = run_vnext_without_stdin(repo_path, &["--ignore-revs-file", custom.to_str().unwrap()]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid ignore-revs file"));
+}
+
+#[test]
+fn test_review_requires_a_terminal() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let output = run_vnext_without_stdin(repo_path, &["review"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "review should fail without a terminal");
+    assert!(stderr.contains("not a terminal"), "{}", stderr);
+    assert!(stderr.contains(".vnext-ignore-revs"), "The error should point to the ignore-revs file: {}", stderr);
+
+    // No tag was created
+    let tags = Command::new("git").args(["tag"]).current_dir(repo_path).output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&tags.stdout).trim(), "v1.0.0");
+}
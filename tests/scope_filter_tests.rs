@@ -0,0 +1,117 @@
+use std::path::Path;
+
+use vnext::version::commit_in_scope;
+use vnext::models::options::WalkOptions;
+use vnext::Commit;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext, run_vnext_with_args};
+
+/// Create a repository released as 1.0.0 with scoped and unscoped pending commits
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    commit_file(repo_path, "api.txt", "fix(api): Handle timeouts");
+    commit_file(repo_path, "web.txt", "feat(web)!: Redesign the dashboard");
+    commit_file(repo_path, "admin.txt", "feat(web-admin): Add user list");
+    commit_file(repo_path, "deps.txt", "fix: Bump dependencies");
+}
+
+#[test]
+fn test_commit_in_scope() {
+    let walk = WalkOptions {
+        scopes: vec!["api".to_string(), "web-*".to_string()],
+        ..WalkOptions::default()
+    };
+    let in_scope = |message: &str| commit_in_scope(&Commit::parse("1".to_string(), message.to_string()), &walk);
+
+    assert!(in_scope("fix(api): Handle timeouts"));
+    assert!(in_scope("fix(API): Handle timeouts"), "Scopes are compared case-insensitively");
+    assert!(in_scope("feat(web-admin): Add user list"));
+    assert!(in_scope("feat(web,api): Share the client"), "Any of several scopes may match");
+    assert!(!in_scope("feat(web): Redesign the dashboard"));
+    assert!(!in_scope("fix(apis): Rename"));
+    assert!(in_scope("fix: Bump dependencies"), "Unscoped commits are included by default");
+
+    let exclusive = WalkOptions { exclude_unscoped: true, ..walk.clone() };
+    assert!(!commit_in_scope(&Commit::parse("2".to_string(), "fix: Bump dependencies".to_string()), &exclusive));
+
+    // Nested scopes match their own path and their parents
+    let nested = WalkOptions { scopes: vec!["auth".to_string(), "web/admin".to_string()], ..WalkOptions::default() };
+    let in_nested = |message: &str| commit_in_scope(&Commit::parse("4".to_string(), message.to_string()), &nested);
+    assert!(in_nested("fix(auth/tokens): Refresh tokens"));
+    assert!(in_nested("fix(web/admin): Fix list"));
+    assert!(!in_nested("fix(web): Fix layout"), "A parent does not match a nested filter");
+    assert!(!in_nested("fix(api/auth): Refresh tokens"), "Only leading segments are parents");
+    assert!(in_nested("fix(api, auth): Refresh tokens"));
+
+    // Without scopes every commit counts
+    let unfiltered = WalkOptions::default();
+    assert!(commit_in_scope(&Commit::parse("3".to_string(), "feat(web): x".to_string()), &unfiltered));
+}
+
+#[test]
+fn test_scope_filter_version() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+
+    // Without a filter the breaking web change wins
+    assert_eq!(run_vnext(repo_path), "2.0.0");
+
+    // Only the api fix and the unscoped fix count
+    assert_eq!(run_vnext_with_args(repo_path, &["--scope", "api"]), "1.0.1");
+
+    // Globs and repeated scopes
+    assert_eq!(run_vnext_with_args(repo_path, &["--scope", "api", "--scope", "web-*"]), "1.1.0");
+
+    // Unscoped commits can be excluded, and the last of the two flags wins
+    assert_eq!(run_vnext_with_args(repo_path, &["--scope", "admin", "--exclude-unscoped"]), "1.0.0");
+    assert_eq!(run_vnext_with_args(repo_path, &["--scope", "admin"]), "1.0.1");
+    assert_eq!(run_vnext_with_args(repo_path, &["--scope", "admin", "--exclude-unscoped", "--include-unscoped"]), "1.0.1");
+}
+
+#[test]
+fn test_scope_filter_changelog() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    // Filtered commits are still listed by default
+    let changelog = run_vnext_with_args(repo_path, &["--scope", "api", "--changelog"]);
+    println!("Changelog:\n{}", changelog);
+    assert!(changelog.contains("### What's changed in v1.0.1"));
+    assert!(changelog.contains("* fix(api): Handle timeouts"));
+    assert!(changelog.contains("* feat(web): Redesign the dashboard"));
+    assert!(changelog.contains("* fix: Bump dependencies"));
+
+    // --changelog-scope-filter drops them
+    let changelog = run_vnext_with_args(repo_path, &["--scope", "api", "--exclude-unscoped", "--changelog", "--changelog-scope-filter"]);
+    println!("Filtered changelog:\n{}", changelog);
+    assert!(changelog.contains("### What's changed in v1.0.1"));
+    assert!(changelog.contains("* fix(api): Handle timeouts"));
+    assert!(!changelog.contains("Redesign the dashboard"));
+    assert!(!changelog.contains("Add user list"));
+    assert!(!changelog.contains("Bump dependencies"));
+}
+
+#[test]
+fn test_changelog_lists_all_scopes() {
+    let mut summary = vnext::ChangesetSummary::new();
+    summary.commits.push(Commit::parse("1".to_string(), "feat(api, web): Shared change".to_string()));
+    summary.commits.push(Commit::parse("2".to_string(), "fix(api/auth): Refresh tokens".to_string()));
+
+    let changelog = vnext::changelog::format_changelog_with_options(
+        &summary,
+        &semver::Version::new(1, 1, 0),
+        &semver::Version::new(1, 0, 0),
+        &vnext::changelog::RepoInfo::new(),
+        &vnext::models::options::ChangelogOptions::default(),
+    );
+    assert!(changelog.contains("* feat(api,web): Shared change\n"), "{}", changelog);
+    assert!(changelog.contains("* fix(api/auth): Refresh tokens\n"), "{}", changelog);
+}
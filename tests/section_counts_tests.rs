@@ -0,0 +1,110 @@
+use semver::Version;
+use vnext::changelog::{format_changelog_with_options, section_counts, RepoInfo};
+use vnext::models::options::ChangelogOptions;
+use vnext::version::{ChangesetSummary, Commit, CommitAuthor};
+
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext_output};
+
+fn authored(id: &str, message: &str, name: &str) -> Commit {
+    let mut commit = Commit::parse(id.to_string(), message.to_string());
+    commit.author = Some(CommitAuthor { name: name.to_string(), email: format!("{}@example.com", name), username: None });
+    commit
+}
+
+/// Commits of a release, newest first like the walk collects them
+fn summary() -> ChangesetSummary {
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(authored("c5", "chore: Bump dependencies", "carol"));
+    summary.commits.push(authored("c4", "fix: Trim names", "bob"));
+    summary.commits.push(authored("c3", "feat!: Drop the v1 API", "alice"));
+    summary.commits.push(authored("c2", "fix: Handle empty input", "alice"));
+    summary.commits.push(authored("c1", "feat: Add export", "bob"));
+    summary
+}
+
+fn render(options: &ChangelogOptions) -> String {
+    format_changelog_with_options(&summary(), &Version::new(2, 0, 0), &Version::new(1, 0, 0), &RepoInfo::new(), options)
+}
+
+#[test]
+fn test_grouped_changelog_with_section_counts() {
+    let options = ChangelogOptions { group_sections: true, section_counts: true, ..ChangelogOptions::default() };
+    assert_eq!(
+        render(&options),
+        "### What's changed in v2.0.0\n\n\
+         #### ⚠ Breaking changes (1)\n\n\
+         * feat: Drop the v1 API (by alice)\n\n\
+         #### ✨ Features (1)\n\n\
+         * feat: Add export (by bob)\n\n\
+         #### 🐛 Fixes (2)\n\n\
+         * fix: Handle empty input (by alice)\n\n\
+         * fix: Trim names (by bob)\n\n\
+         #### 🧰 Other changes (1)\n\n\
+         * chore: Bump dependencies (by carol)\n\n\
+         5 commits from 3 contributors\n"
+    );
+}
+
+#[test]
+fn test_grouped_changelog_without_section_counts() {
+    let options = ChangelogOptions { group_sections: true, ..ChangelogOptions::default() };
+    assert_eq!(
+        render(&options),
+        "### What's changed in v2.0.0\n\n\
+         #### ⚠ Breaking changes\n\n\
+         * feat: Drop the v1 API (by alice)\n\n\
+         #### ✨ Features\n\n\
+         * feat: Add export (by bob)\n\n\
+         #### 🐛 Fixes\n\n\
+         * fix: Handle empty input (by alice)\n\n\
+         * fix: Trim names (by bob)\n\n\
+         #### 🧰 Other changes\n\n\
+         * chore: Bump dependencies (by carol)\n\n"
+    );
+}
+
+#[test]
+fn test_section_counts_and_totals() {
+    let mut summary = summary();
+    let counts = section_counts(&summary);
+    assert_eq!(counts.values().sum::<usize>(), summary.commits.len());
+    assert_eq!((counts["breaking"], counts["features"], counts["fixes"], counts["other"]), (1, 1, 2, 1));
+
+    // Empty sections are left out, and commits past the commit limit count toward the total
+    summary.commits.retain(|commit| commit.commit_type == "fix");
+    summary.truncated = true;
+    summary.omitted_commits = 4;
+    let options = ChangelogOptions { group_sections: true, section_counts: true, ..ChangelogOptions::default() };
+    let changelog = format_changelog_with_options(&summary, &Version::new(1, 0, 1), &Version::new(1, 0, 0), &RepoInfo::new(), &options);
+    assert!(!changelog.contains("Features") && !changelog.contains("Breaking"), "{}", changelog);
+    assert!(changelog.ends_with("* ...and 4 more commits\n\n6 commits from 2 contributors\n"), "{}", changelog);
+}
+
+#[test]
+fn test_section_counts_follow_the_changelog_filters() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    commit_file(repo_path, "a.md", "feat: Add export");
+    commit_file(repo_path, "b.md", "fix: Trim names");
+    commit_file(repo_path, "c.md", "chore: Tidy up");
+
+    let args = ["--changelog", "--group-sections", "--section-counts", "--changelog-exclude-noop"];
+    let output = run_vnext_output(repo_path, &args);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let changelog = String::from_utf8_lossy(&output.stdout);
+    assert!(changelog.contains("#### ✨ Features (1)\n") && changelog.contains("#### 🐛 Fixes (1)\n"), "{}", changelog);
+    assert!(!changelog.contains("Other changes"), "{}", changelog);
+    assert!(changelog.trim_end().ends_with("2 commits from 1 contributor"), "{}", changelog);
+
+    let output = run_vnext_output(repo_path, &["--format", "json", "--changelog-exclude-noop"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Output should be JSON");
+    assert_eq!(json["stats"]["sections"], serde_json::json!({"breaking": 0, "features": 1, "fixes": 1, "other": 0}));
+
+    // --section-counts needs --group-sections
+    let output = run_vnext_output(repo_path, &["--changelog", "--section-counts"]);
+    assert!(!output.status.success());
+}
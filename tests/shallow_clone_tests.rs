@@ -0,0 +1,49 @@
+use std::fs;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext, run_vnext_output};
+
+#[test]
+fn test_shallow_clone_is_an_error() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let origin = temp_dir.path().join("origin");
+    fs::create_dir(&origin).expect("Failed to create origin directory");
+    init_repo(&origin);
+    commit_file(&origin, "a.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], &origin);
+    commit_file(&origin, "b.md", "fix: A fix");
+    commit_file(&origin, "c.md", "fix: Another fix");
+
+    let clone = temp_dir.path().join("clone");
+    let origin_url = format!("file://{}", origin.display());
+    run_and_show_command("git", &["clone", "--depth", "1", &origin_url, clone.to_str().unwrap()], temp_dir.path());
+    assert!(clone.join(".git/shallow").exists(), "The clone should be shallow");
+
+    // Builds the binary
+    run_vnext(&origin);
+
+    let output = run_vnext_output(&clone, &[]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "A shallow clone should fail by default");
+    assert!(stderr.contains("shallow clone"), "{}", stderr);
+    assert!(stderr.contains("--allow-shallow"), "The error should name the flag: {}", stderr);
+
+    let output = run_vnext_output(&clone, &["--allow-shallow"]);
+    assert!(output.status.success(), "--allow-shallow should calculate on a best-effort basis");
+}
+
+#[test]
+fn test_detached_head_without_trunk_branch() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    init_repo(repo_path);
+    run_and_show_command("git", &["checkout", "-b", "develop"], repo_path);
+    commit_file(repo_path, "a.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    commit_file(repo_path, "b.md", "feat: New feature");
+    run_and_show_command("git", &["checkout", "--detach", "HEAD"], repo_path);
+
+    let version = run_vnext(repo_path);
+    assert_eq!(version, "1.1.0", "A detached HEAD without main or master should still be versioned");
+}
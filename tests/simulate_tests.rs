@@ -0,0 +1,90 @@
+use std::fs;
+use std::process::Command;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command, run_vnext};
+
+fn create_repo_with_pending_fix(repo_path: &std::path::Path) {
+    init_repo(repo_path);
+
+    fs::write(repo_path.join("README.md"), "# Test Repository").expect("Failed to write README file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+
+    fs::write(repo_path.join("fix.md"), "fix").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "fix: Real pending fix"], repo_path);
+}
+
+fn run_simulate(repo_path: &std::path::Path, args: &[&str]) -> String {
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let binary_path = project_dir.join("target/debug/vnext");
+    let output = Command::new(&binary_path)
+        .arg("simulate")
+        .args(args)
+        .current_dir(repo_path)
+        .output()
+        .expect("Failed to execute vnext simulate");
+    assert!(output.status.success(), "simulate failed: {}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn test_simulated_breaking_message_elevates_bump() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo_with_pending_fix(repo_path);
+
+    let version = run_vnext(repo_path);
+    assert_eq!(version, "1.0.1", "The real pending commit is a patch");
+
+    let stdout = run_simulate(repo_path, &["--message", "feat: a", "--message", "fix!: b", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(stdout.trim()).expect("Output should be JSON");
+
+    assert_eq!(json["simulated"], true);
+    assert_eq!(json["current_version"], "1.0.0");
+    assert_eq!(json["version"], "2.0.0", "The simulated breaking change should force a major bump");
+    assert_eq!(json["bump"], "major");
+
+    let changelog = json["changelog"].as_str().unwrap();
+    assert!(changelog.starts_with("### What's changed in v2.0.0 (simulated)"), "{}", changelog);
+    assert!(changelog.contains("* fix: b") && changelog.contains("* feat: a") && changelog.contains("* fix: Real pending fix"));
+
+    // The repository itself is untouched
+    assert_eq!(run_vnext(repo_path), "1.0.1");
+}
+
+#[test]
+fn test_simulate_text_output() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo_with_pending_fix(repo_path);
+    run_vnext(repo_path);
+
+    let stdout = run_simulate(repo_path, &["--message", "feat: New feature"]);
+
+    assert!(stdout.starts_with("1.1.0 (minor bump from 1.0.0)"), "{}", stdout);
+    assert!(stdout.contains("### What's changed in v1.1.0 (simulated)"), "{}", stdout);
+}
+
+#[test]
+fn test_simulate_json_includes_footers() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo_with_pending_fix(repo_path);
+    run_vnext(repo_path);
+
+    let stdout = run_simulate(repo_path, &["--message", "feat(api): Add export\n\nRefs: #42\nReviewed-by: Jane", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(stdout.trim()).expect("Output should be JSON");
+
+    let commit = &json["commits"][0];
+    assert_eq!(commit["type"], "feat");
+    assert_eq!(commit["scope"], "api");
+    assert_eq!(commit["title"], "Add export");
+    assert_eq!(commit["footers"][0]["token"], "Refs");
+    assert_eq!(commit["footers"][0]["value"], "#42");
+    assert_eq!(commit["footers"][1]["token"], "Reviewed-by");
+    assert_eq!(json["commits"][1]["footers"].as_array().unwrap().len(), 0);
+}
@@ -0,0 +1,57 @@
+use clap::Parser;
+use vnext::cli::Cli;
+use vnext::core::version::has_skip_marker;
+use vnext::models::options::WalkOptions;
+use vnext::Commit;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext, run_vnext_with_args};
+
+fn marked(message: &str, walk: &WalkOptions) -> bool {
+    has_skip_marker(&Commit::parse("1".to_string(), message.to_string()), &walk.skip_release_markers())
+}
+
+#[test]
+fn test_default_skip_markers() {
+    let walk = WalkOptions::default();
+    assert!(marked("fix: Handle empty files [skip release]", &walk));
+    assert!(marked("fix: Handle empty files [No Release]", &walk), "Markers ignore case");
+    assert!(marked("feat: Add export\n\nAlready shipped in v1.2.1.\n\n[skip release]", &walk), "Markers in the body count");
+    assert!(!marked("fix: Skip release notes for drafts", &walk));
+    assert!(!marked("ci: Add [skip ci] to bot commits", &walk));
+}
+
+#[test]
+fn test_skip_markers_flag() {
+    let walk = Cli::parse_from(["vnext", "--skip-markers", r"\[skip ci\]", "--skip-markers", "^hotfix"]).to_options().walk;
+    assert!(marked("ci: Add [skip ci] to bot commits", &walk));
+    assert!(marked("hotfix: Backport the parser fix", &walk));
+    assert!(!marked("fix: Handle empty files [skip release]", &walk), "Given markers replace the defaults");
+
+    assert!(Cli::try_parse_from(["vnext", "--skip-markers", "[unclosed"]).is_err());
+    assert!(Cli::try_parse_from(["vnext", "--skip-markers", ""]).is_err());
+}
+
+#[test]
+fn test_skipped_commit_does_not_bump() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    init_repo(repo_path);
+    commit_file(repo_path, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.2.0"], repo_path);
+    commit_file(repo_path, "export.txt", "feat: Add export [skip release]");
+    // Builds the binary
+    run_vnext(repo_path);
+
+    assert_eq!(run_vnext_with_args(repo_path, &[]), "1.2.0", "The marked feature does not bump the minor version");
+
+    let changelog = run_vnext_with_args(repo_path, &["--changelog"]);
+    assert!(changelog.contains("* feat: Add export \\[skip release\\] (skip release)"), "{}", changelog);
+    let changelog = run_vnext_with_args(repo_path, &["--changelog", "--hide-skipped"]);
+    assert!(!changelog.contains("Add export"), "{}", changelog);
+
+    commit_file(repo_path, "fix.txt", "fix: Handle empty files");
+    assert_eq!(run_vnext_with_args(repo_path, &[]), "1.2.1");
+    assert_eq!(run_vnext_with_args(repo_path, &["--skip-markers", "empty files"]), "1.3.0", "Custom markers replace the defaults");
+}
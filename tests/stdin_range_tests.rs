@@ -0,0 +1,115 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use vnext::core::push::{parse_ref_updates, RefUpdate};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{run_and_show_command, run_command, run_vnext};
+
+const ZERO: &str = "0000000000000000000000000000000000000000";
+
+fn rev_parse(repo_path: &Path, rev: &str) -> String {
+    let output = run_command("git", &["rev-parse", rev], repo_path);
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+fn commit_file(repo_path: &Path, file: &str, message: &str) -> String {
+    fs::write(repo_path.join(file), file).expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", message], repo_path);
+    rev_parse(repo_path, "HEAD")
+}
+
+/// Run `vnext --stdin-range` in `dir` with `input` on stdin, returning the parsed JSON lines
+fn run_stdin_range(dir: &Path, input: &str, args: &[&str]) -> Vec<serde_json::Value> {
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let mut child = Command::new(project_dir.join("target/debug/vnext"))
+        .arg("--stdin-range")
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute vnext --stdin-range");
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "vnext failed: {}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("Each line should be a JSON object"))
+        .collect()
+}
+
+#[test]
+fn test_parse_ref_updates() {
+    let sha = "a".repeat(40);
+    let updates = parse_ref_updates(&format!("{ZERO} {sha} refs/heads/feature\n\n{sha} {ZERO} refs/tags/old\n")).unwrap();
+    assert_eq!(updates.len(), 2);
+    let RefUpdate { old, new, refname } = &updates[0];
+    assert_eq!((old, new.map(|oid| oid.to_string()), refname.as_str()), (&None, Some(sha.clone()), "refs/heads/feature"));
+    assert_eq!(updates[1].new, None, "A zero new ID deletes the ref");
+
+    let error = parse_ref_updates(&format!("{sha} refs/heads/main\n")).unwrap_err().to_string();
+    assert!(error.contains("stdin line 1") && error.contains("<old> <new> <ref>"), "{}", error);
+    let error = parse_ref_updates(&format!("{sha} abc123 refs/heads/main\n")).unwrap_err().to_string();
+    assert!(error.contains("'abc123' is not a full commit ID"), "{}", error);
+}
+
+#[test]
+fn test_stdin_range_in_a_bare_repository() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let work = temp_dir.path().join("work");
+    fs::create_dir(&work).unwrap();
+    run_and_show_command("git", &["init"], &work);
+    run_and_show_command("git", &["config", "user.name", "patrickleet"], &work);
+    run_and_show_command("git", &["config", "user.email", "pat@patscott.io"], &work);
+    let released = commit_file(&work, "README.md", "feat: Initial commit");
+    run_and_show_command("git", &["tag", "v1.0.0"], &work);
+    commit_file(&work, "a.md", "feat: Add a");
+    let trunk_tip = commit_file(&work, "b.md", "fix: Fix b");
+    let trunk = String::from_utf8_lossy(&run_command("git", &["branch", "--show-current"], &work).stdout).trim().to_string();
+    run_and_show_command("git", &["checkout", "-b", "feature"], &work);
+    let feature_tip = commit_file(&work, "c.md", "feat!: Replace the API");
+    assert_eq!(run_vnext(&work), "2.0.0");
+
+    // The server side has the pushed objects, but the new branch is not created yet
+    let bare = temp_dir.path().join("server.git");
+    run_and_show_command("git", &["clone", "--bare", work.to_str().unwrap(), bare.to_str().unwrap()], temp_dir.path());
+    run_and_show_command("git", &["branch", "-D", "feature"], &bare);
+
+    let input = format!(
+        "{released} {trunk_tip} refs/heads/{trunk}\n{ZERO} {feature_tip} refs/heads/feature\n{trunk_tip} {ZERO} refs/heads/old\n"
+    );
+    let results = run_stdin_range(&bare, &input, &[]);
+    assert_eq!(results.len(), 3, "One object per ref: {:?}", results);
+
+    let main = &results[0];
+    assert_eq!(main["ref"], format!("refs/heads/{}", trunk));
+    assert_eq!(main["base_sha"], released);
+    assert_eq!(main["new_sha"], trunk_tip);
+    assert_eq!(main["bump"], "minor");
+    assert_eq!(main["commit_count"], 2);
+    assert_eq!(main["current_version"], "1.0.0");
+    assert_eq!(main["version"], "1.1.0");
+
+    // A new branch is compared with the trunk it branched from
+    let feature = &results[1];
+    assert_eq!(feature["old_sha"], serde_json::Value::Null);
+    assert_eq!(feature["base_sha"], trunk_tip);
+    assert_eq!(feature["bump"], "major");
+    assert_eq!(feature["commit_count"], 1);
+    assert_eq!(feature["breaking_changes"], 1);
+    assert_eq!(feature["commits"][0]["title"], "Replace the API");
+    assert_eq!(feature["version"], "2.0.0");
+
+    assert_eq!(results[2]["deleted"], true);
+
+    let results = run_stdin_range(&bare, &format!("{released} {trunk_tip} refs/heads/{trunk}\n"), &["--changelog"]);
+    let changelog = results[0]["changelog"].as_str().unwrap();
+    assert!(changelog.starts_with("### What's changed in v1.1.0"), "{}", changelog);
+    assert!(changelog.contains("* feat: Add a") && changelog.contains("* fix: Fix b"), "{}", changelog);
+}
@@ -0,0 +1,120 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Output, Stdio};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command, run_vnext, vnext_command};
+
+/// Run vnext with debug logging and no stdin, so any log or prompt on stdout shows up
+fn run_vnext_debug(repo_path: &Path, args: &[&str]) -> Output {
+    vnext_command(repo_path, args).env("LOG_LEVEL", "debug").stdin(Stdio::null()).output().expect("Failed to execute vnext")
+}
+
+/// Parse stdout as exactly one JSON object
+fn parse_stdout(output: &Output) -> serde_json::Value {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1, "stdout should hold a single JSON line: {}", stdout);
+    serde_json::from_str(&stdout).unwrap_or_else(|e| panic!("stdout is not JSON ({}): {}", e, stdout))
+}
+
+/// A released repository with a pending feature and an untracked file, so vnext logs a warning
+fn create_repo(repo_path: &Path) {
+    init_repo(repo_path);
+    fs::write(repo_path.join("README.md"), "# Test").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    fs::write(repo_path.join("export.txt"), "export").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Add export"], repo_path);
+    fs::write(repo_path.join("notes.txt"), "scratch").expect("Failed to write untracked file");
+}
+
+#[test]
+fn test_text_output_is_only_the_result() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let output = run_vnext_debug(repo_path, &[]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1.1.0\n");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("uncommitted changes"), "Warnings should go to stderr: {}", stderr);
+    assert!(stderr.contains("Debug"), "Debug logs should go to stderr: {}", stderr);
+
+    let output = run_vnext_debug(repo_path, &["--current"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1.0.0\n");
+}
+
+#[test]
+fn test_progress_keeps_stdout_clean() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let output = run_vnext_debug(repo_path, &["--progress", "--changelog"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("### What's changed in v1.1.0\n"), "{}", stdout);
+    assert!(!stdout.contains('\r') && !stdout.contains("Walking commits"), "{}", stdout);
+
+    // stderr is not a terminal here, so only the timings are logged
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("Walking commits"), "{}", stderr);
+    assert!(stderr.contains("Scanned 1 tags in"), "{}", stderr);
+    assert!(stderr.contains("Walked 1 commits in"), "{}", stderr);
+
+    let output = run_vnext_debug(repo_path, &["--progress", "--format", "json"]);
+    assert_eq!(parse_stdout(&output)["version"], "1.1.0");
+}
+
+#[test]
+fn test_every_command_with_json_format() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    // Builds the binary
+    run_vnext(repo_path);
+
+    let json = parse_stdout(&run_vnext_debug(repo_path, &["--format", "json"]));
+    assert_eq!(json["version"], "1.1.0");
+    assert_eq!(json["current_version"], "1.0.0");
+    assert!(json["changelog"].is_null());
+
+    let json = parse_stdout(&run_vnext_debug(repo_path, &["--format", "json", "--changelog"]));
+    assert_eq!(json["version"], "1.1.0");
+    assert!(json["changelog"].as_str().unwrap().contains("* feat: Add export"));
+
+    let json = parse_stdout(&run_vnext_debug(repo_path, &["--format", "json", "--current"]));
+    assert_eq!(json["version"], "1.0.0");
+
+    let json = parse_stdout(&run_vnext_debug(repo_path, &["simulate", "--message", "fix: x", "--format", "json"]));
+    assert_eq!(json["version"], "1.1.0");
+
+    // Interactive commands fail without a terminal and write nothing to stdout
+    for args in [&["--format", "json", "review"][..], &["--format", "json", "deploy-key", "generate"][..]] {
+        let output = run_vnext_debug(repo_path, args);
+        assert!(!output.status.success(), "{:?} should fail without a terminal", args);
+        assert!(output.stdout.is_empty(), "{:?} wrote to stdout: {}", args, String::from_utf8_lossy(&output.stdout));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("not a terminal"));
+    }
+}
+
+#[test]
+fn test_fallback_json_output() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    // Builds the binary
+    run_vnext(temp_dir.path());
+
+    // Outside a repository the result is 0.0.0 with --lenient
+    let json = parse_stdout(&run_vnext_debug(temp_dir.path(), &["--format", "json", "--changelog", "--lenient"]));
+    assert_eq!(json["version"], "0.0.0");
+    assert_eq!(json["current_version"], "0.0.0");
+    assert!(json["changelog"].as_str().unwrap().contains("* No changes"));
+}
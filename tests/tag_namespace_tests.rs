@@ -0,0 +1,88 @@
+use std::fs;
+
+use semver::Version;
+use vnext::changelog::{format_changelog_with_options, RepoInfo};
+use vnext::models::options::ChangelogOptions;
+use vnext::version::{ChangesetSummary, Commit};
+use vnext::VersionCalculator;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command, run_vnext, run_vnext_with_args};
+
+#[test]
+fn test_namespaced_and_plain_tags_coexist() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    init_repo(repo_path);
+
+    fs::write(repo_path.join("README.md"), "# Test Repository").expect("Failed to write README file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+    run_and_show_command("git", &["tag", "v5.0.0"], repo_path);
+    run_and_show_command("git", &["tag", "releases/v1.2.0"], repo_path);
+    run_and_show_command("git", &["tag", "app/v2.0.0"], repo_path);
+
+    fs::write(repo_path.join("fix.md"), "fix").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "fix: A fix"], repo_path);
+
+    assert_eq!(run_vnext(repo_path), "5.0.1", "Without a namespace only plain tags are used");
+    assert_eq!(run_vnext_with_args(repo_path, &["--tag-namespace", "releases/"]), "1.2.1");
+    assert_eq!(run_vnext_with_args(repo_path, &["--tag-namespace", "app"]), "2.0.1", "The trailing slash is optional");
+    assert_eq!(run_vnext_with_args(repo_path, &["--tag-namespace", "app/", "--current"]), "2.0.0");
+}
+
+#[test]
+fn test_compare_link_uses_namespaced_tags() {
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(Commit::parse("commit1".to_string(), "fix: A fix".to_string()));
+
+    let mut repo_info = RepoInfo::new();
+    repo_info.owner = "unbounded-tech".to_string();
+    repo_info.name = "vnext".to_string();
+    repo_info.is_github_repo = true;
+
+    let options = ChangelogOptions {
+        tag_namespace: Some("releases/".to_string()),
+        ..ChangelogOptions::default()
+    };
+    let changelog = format_changelog_with_options(&summary, &Version::new(1, 2, 1), &Version::new(1, 2, 0), &repo_info, &options);
+
+    assert!(
+        changelog.contains("See full diff: [releases/v1.2.0...releases/v1.2.1](https://github.com/unbounded-tech/vnext/compare/releases/v1.2.0...releases/v1.2.1)"),
+        "{}",
+        changelog
+    );
+}
+
+#[test]
+fn test_calculator_tag_namespace_reaches_the_changelog() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+
+    init_repo(repo_path);
+    run_and_show_command("git", &["remote", "add", "origin", "https://github.com/unbounded-tech/vnext.git"], repo_path);
+    fs::write(repo_path.join("README.md"), "# Test Repository").expect("Failed to write README file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+    run_and_show_command("git", &["tag", "releases/v1.2.0"], repo_path);
+    fs::write(repo_path.join("fix.md"), "fix").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "fix: A fix"], repo_path);
+
+    let calculator = VersionCalculator::for_repo(repo_path).with_tag_namespace("releases");
+    assert_eq!(calculator.changelog_options().tag_namespace.as_deref(), Some("releases/"));
+
+    let plan = calculator.run().expect("Calculation should succeed");
+    assert_eq!(plan.next_version, Version::new(1, 2, 1));
+    let changelog = format_changelog_with_options(
+        &plan.summary, &plan.next_version, &plan.current_version, &plan.repo_info, calculator.changelog_options(),
+    );
+    assert!(
+        changelog.contains("[releases/v1.2.0...releases/v1.2.1](https://github.com/unbounded-tech/vnext/compare/releases/v1.2.0...releases/v1.2.1)"),
+        "{}",
+        changelog
+    );
+}
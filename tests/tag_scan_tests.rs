@@ -0,0 +1,129 @@
+use std::path::Path;
+
+use git2::{Oid, Repository};
+use vnext::core::progress::ProgressReporter;
+use vnext::git::{find_latest_prefixed_tag, find_latest_tag};
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{commit_file, init_repo, run_and_show_command, run_vnext, run_vnext_output};
+
+/// Create a repository with three commits and a few hundred release tags spread over them
+fn create_repo(repo_path: &Path) -> Vec<Oid> {
+    init_repo(repo_path);
+    let repo = Repository::open(repo_path).expect("Failed to open repository");
+    let mut commits = Vec::new();
+    for i in 0..3 {
+        commit_file(repo_path, &format!("{}.txt", i), &format!("feat: Change {}", i));
+        commits.push(repo.head().unwrap().target().unwrap());
+    }
+
+    for minor in 0..20 {
+        for patch in 0..15 {
+            let target = repo.find_object(commits[(minor * 15 + patch) % 3], None).unwrap();
+            repo.tag_lightweight(&format!("v1.{}.{}", minor, patch), &target, false).unwrap();
+            repo.tag_lightweight(&format!("api-v2.{}.{}", minor, patch), &target, false).unwrap();
+        }
+    }
+    commits
+}
+
+#[test]
+fn test_latest_of_many_tags() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    let commits = create_repo(repo_path);
+    let repo = Repository::open(repo_path).unwrap();
+
+    // v1.19.14 is the 300th tag, created on (19 * 15 + 14) % 3
+    let (tag, commit) = find_latest_tag(&repo).expect("A release tag should be found");
+    assert_eq!(tag, "v1.19.14");
+    assert_eq!(commit.id(), commits[(19 * 15 + 14) % 3]);
+
+    let (tag, _) = find_latest_prefixed_tag(&repo, Some("api-v"), &ProgressReporter::none()).unwrap().unwrap();
+    assert_eq!(tag, "api-v2.19.14");
+
+    // Higher versions that do not point at a commit are skipped
+    let tree = repo.find_commit(commits[0]).unwrap().tree().unwrap();
+    repo.tag_lightweight("v9.0.0", tree.as_object(), false).unwrap();
+    // Annotated tags are peeled to their commit
+    let signature = repo.signature().unwrap();
+    let target = repo.find_object(commits[1], None).unwrap();
+    repo.tag("v1.20.0", &target, &signature, "Release 1.20.0", false).unwrap();
+
+    let (tag, commit) = find_latest_tag(&repo).expect("A release tag should be found");
+    assert_eq!(tag, "v1.20.0");
+    assert_eq!(commit.id(), commits[1]);
+}
+
+#[test]
+fn test_tag_on_a_tree_is_not_a_release() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    init_repo(repo_path);
+    commit_file(repo_path, "a.txt", "feat: Add a");
+    run_and_show_command("git", &["tag", "v3.0.0", "HEAD^{tree}"], repo_path);
+
+    let repo = Repository::open(repo_path).unwrap();
+    assert!(find_latest_tag(&repo).is_none(), "A tag of a tree is skipped");
+    assert_eq!(run_vnext(repo_path), "0.1.0");
+}
+
+/// Tag `v1.2.3` on the trunk and `1.2.3` on a side branch HEAD cannot reach
+fn create_duplicate_version_repo(repo_path: &Path) -> (Oid, Oid) {
+    run_and_show_command("git", &["init", "-b", "main"], repo_path);
+    run_and_show_command("git", &["config", "user.name", "patrickleet"], repo_path);
+    run_and_show_command("git", &["config", "user.email", "pat@patscott.io"], repo_path);
+    commit_file(repo_path, "a.txt", "feat: Add a");
+    run_and_show_command("git", &["checkout", "-b", "side"], repo_path);
+    commit_file(repo_path, "side.txt", "fix: Side fix");
+    // Sorts before v1.2.3, so the first tag of the version is not the reachable one
+    run_and_show_command("git", &["tag", "1.2.3"], repo_path);
+    run_and_show_command("git", &["checkout", "main"], repo_path);
+    commit_file(repo_path, "b.txt", "fix: Fix b");
+    run_and_show_command("git", &["tag", "v1.2.3"], repo_path);
+    commit_file(repo_path, "c.txt", "feat: Add c");
+    let repo = Repository::open(repo_path).unwrap();
+    let side = repo.revparse_single("1.2.3").unwrap().id();
+    let trunk = repo.revparse_single("v1.2.3").unwrap().id();
+    (side, trunk)
+}
+
+#[test]
+fn test_duplicate_version_tags_prefer_the_one_reachable_from_head() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    let (_, trunk) = create_duplicate_version_repo(repo_path);
+    let repo = Repository::open(repo_path).unwrap();
+
+    let (tag, commit) = find_latest_tag(&repo).expect("A release tag should be found");
+    assert_eq!(tag, "v1.2.3");
+    assert_eq!(commit.id(), trunk);
+    // Only the commit after v1.2.3 counts
+    assert_eq!(run_vnext(repo_path), "1.3.0");
+
+    // From the side branch the other tag is the reachable one
+    run_and_show_command("git", &["checkout", "side"], repo_path);
+    let repo = Repository::open(repo_path).unwrap();
+    let (tag, _) = find_latest_tag(&repo).expect("A release tag should be found");
+    assert_eq!(tag, "1.2.3");
+}
+
+#[test]
+fn test_strict_tags_rejects_duplicate_versions() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_duplicate_version_repo(repo_path);
+    run_vnext(repo_path);
+
+    let output = run_vnext_output(repo_path, &["--strict-tags"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("1.2.3, v1.2.3"), "Expected both tags in the error: {}", stderr);
+
+    // The same tags on one commit are not ambiguous
+    run_and_show_command("git", &["tag", "-f", "1.2.3", "v1.2.3"], repo_path);
+    let output = run_vnext_output(repo_path, &["--strict-tags"]);
+    assert!(output.status.success(), "vnext failed: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "1.3.0");
+}
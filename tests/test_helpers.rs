@@ -1,3 +1,6 @@
+#![allow(dead_code)]
+
+use std::fs;
 use std::path::Path;
 use std::process::{Command, Output};
 
@@ -50,4 +53,48 @@ pub fn run_vnext(dir: &Path) -> String {
     println!("Version: {}", version);
 
     version
-}
\ No newline at end of file
+}
+
+/// Command running the built vnext binary with `args` in `dir`, outside of any GIT_DIR
+pub fn vnext_command(dir: &Path, args: &[&str]) -> Command {
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let mut command = Command::new(project_dir.join("target/debug/vnext"));
+    command.args(args).current_dir(dir).env_remove("GIT_DIR");
+    command
+}
+
+/// Run vnext with `args` in `dir` and return its output
+pub fn run_vnext_output(dir: &Path, args: &[&str]) -> Output {
+    vnext_command(dir, args).output().expect("Failed to execute vnext")
+}
+
+/// Run vnext with `args` in `dir` and return its trimmed stdout
+pub fn run_vnext_with_args(dir: &Path, args: &[&str]) -> String {
+    stdout(&run_vnext_output(dir, args))
+}
+
+/// Run vnext with `args` in `dir`, failing the test when it fails, and return its trimmed stdout
+pub fn run_vnext_success(dir: &Path, args: &[&str]) -> String {
+    let output = run_vnext_output(dir, args);
+    assert!(output.status.success(), "vnext failed: {}", String::from_utf8_lossy(&output.stderr));
+    stdout(&output)
+}
+
+/// Trimmed stdout of a command
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Create a repository in `dir` with the test identity configured
+pub fn init_repo(dir: &Path) {
+    run_and_show_command("git", &["init"], dir);
+    run_and_show_command("git", &["config", "user.name", "patrickleet"], dir);
+    run_and_show_command("git", &["config", "user.email", "pat@patscott.io"], dir);
+}
+
+/// Write `message` to `file` and commit it with that message
+pub fn commit_file(dir: &Path, file: &str, message: &str) {
+    fs::write(dir.join(file), message).expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], dir);
+    run_and_show_command("git", &["commit", "-m", message], dir);
+}
@@ -0,0 +1,160 @@
+use semver::Version;
+use vnext::changelog::{format_changelog_with_options, RepoInfo};
+use vnext::models::options::ChangelogOptions;
+use vnext::models::commit::CommitParser;
+use vnext::parsers::CustomRegexParser;
+use vnext::parsers::trailers::{coauthor_names, split_breaking_footer, split_trailers};
+use vnext::version::{ChangesetSummary, CommitAuthor, Commit};
+
+#[test]
+fn test_single_trailer_is_stripped() {
+    let (body, trailers) = split_trailers("Fix the retry loop.\n\nSigned-off-by: Jane Smith <jane@example.com>");
+    assert_eq!(body, "Fix the retry loop.");
+    assert_eq!(trailers, vec![("Signed-off-by".to_string(), "Jane Smith <jane@example.com>".to_string())]);
+}
+
+#[test]
+fn test_multiple_trailers_are_stripped() {
+    let message = "Fix the retry loop.\n\nMore details here.\n\nSigned-off-by: Jane Smith <jane@example.com>\nCo-authored-by: John Doe <john@example.com>\nReviewed-by: Alex <alex@example.com>\n";
+    let (body, trailers) = split_trailers(message);
+    assert_eq!(body, "Fix the retry loop.\n\nMore details here.");
+    assert_eq!(trailers.len(), 3);
+    assert_eq!(trailers[1].0, "Co-authored-by");
+    assert_eq!(coauthor_names(&trailers), vec!["John Doe".to_string()]);
+}
+
+#[test]
+fn test_body_with_colon_mid_sentence_is_kept() {
+    let message = "This fixes the issue: requests are now retried.";
+    let (body, trailers) = split_trailers(message);
+    assert_eq!(body, message);
+    assert!(trailers.is_empty());
+
+    let message = "Some context.\n\nThe cause was simple: a missing check.\nSigned-off-by: Jane Smith <jane@example.com>";
+    let (body, trailers) = split_trailers(message);
+    assert_eq!(body, message, "A paragraph mixing prose and trailers is not a trailer block");
+    assert!(trailers.is_empty());
+}
+
+#[test]
+fn test_breaking_change_footer_is_kept_in_body() {
+    let (body, trailers) = split_trailers("Rework the API.\n\nBREAKING CHANGE: old endpoints removed\nSigned-off-by: Jane Smith <jane@example.com>");
+    assert_eq!(body, "Rework the API.\n\nBREAKING CHANGE: old endpoints removed");
+    assert_eq!(trailers.len(), 2);
+}
+
+#[test]
+fn test_trailer_continuation_lines() {
+    let (_, trailers) = split_trailers("Body.\n\nReviewed-by: A very long\n  reviewer name <r@example.com>");
+    assert_eq!(trailers, vec![("Reviewed-by".to_string(), "A very long reviewer name <r@example.com>".to_string())]);
+}
+
+fn summary_with_trailers() -> ChangesetSummary {
+    let mut summary = ChangesetSummary::new();
+    let mut commit = Commit::parse(
+        "abc123".to_string(),
+        "feat: Add feature\n\nAdds the feature.\n\nCo-authored-by: John Doe <john@example.com>\nSigned-off-by: Jane Smith <jane@example.com>".to_string(),
+    );
+    commit.author = Some(CommitAuthor {
+        name: "Jane Smith".to_string(),
+        email: "jane@example.com".to_string(),
+        username: Some("janesmith".to_string()),
+    });
+    summary.commits.push(commit);
+    summary
+}
+
+#[test]
+fn test_changelog_strips_trailers_by_default() {
+    let changelog = format_changelog_with_options(
+        &summary_with_trailers(), &Version::new(1, 1, 0), &Version::new(1, 0, 0), &RepoInfo::new(), &ChangelogOptions::default(),
+    );
+    assert_eq!(changelog, "### What's changed in v1.1.0\n\n* feat: Add feature (by @janesmith)\n\n  Adds the feature.\n\n");
+}
+
+#[test]
+fn test_changelog_keeps_trailers_when_requested() {
+    let options = ChangelogOptions { keep_trailers: true, ..ChangelogOptions::default() };
+    let changelog = format_changelog_with_options(
+        &summary_with_trailers(), &Version::new(1, 1, 0), &Version::new(1, 0, 0), &RepoInfo::new(), &options,
+    );
+    assert!(changelog.contains("  Signed-off-by: Jane Smith <jane@example.com>"));
+}
+
+#[test]
+fn test_changelog_credits_coauthors() {
+    let options = ChangelogOptions { credit_coauthors: true, ..ChangelogOptions::default() };
+    let changelog = format_changelog_with_options(
+        &summary_with_trailers(), &Version::new(1, 1, 0), &Version::new(1, 0, 0), &RepoInfo::new(), &options,
+    );
+    assert!(changelog.contains("* feat: Add feature (by @janesmith, John Doe)"), "{}", changelog);
+}
+
+#[test]
+fn test_split_breaking_footer() {
+    let (body, note) = split_breaking_footer("Rework the API.\n\nBREAKING CHANGE: old endpoints removed\nSigned-off-by: Jane Smith <jane@example.com>");
+    assert_eq!(body, "Rework the API.\n\nSigned-off-by: Jane Smith <jane@example.com>");
+    assert_eq!(note, Some("old endpoints removed".to_string()));
+
+    let (body, note) = split_breaking_footer("Rework the API.\n\nThis line has BREAKING CHANGE: in the middle.");
+    assert_eq!(body, "Rework the API.\n\nThis line has BREAKING CHANGE: in the middle.");
+    assert!(note.is_none());
+}
+
+#[test]
+fn test_changelog_renders_breaking_footer_below_body() {
+    let parser = CustomRegexParser::default();
+    let commit = parser.parse_commit(
+        "abc123".to_string(),
+        "feat: Rework the API\n\nEndpoints are grouped by resource.\n\nBREAKING-CHANGE: The v1 endpoints are removed.\nSigned-off-by: Jane Smith <jane@example.com>".to_string(),
+    );
+    assert!(commit.has_breaking_change);
+
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(commit);
+    // Without the breaking changes section the note stays with its entry
+    let options = ChangelogOptions { no_highlight_breaking: true, ..ChangelogOptions::default() };
+    let changelog = format_changelog_with_options(&summary, &Version::new(2, 0, 0), &Version::new(1, 0, 0), &RepoInfo::new(), &options);
+    assert_eq!(
+        changelog,
+        "### What's changed in v2.0.0\n\n* feat: Rework the API\n\n  Endpoints are grouped by resource.\n\n  BREAKING CHANGE: The v1 endpoints are removed.\n\n"
+    );
+}
+
+#[test]
+fn test_custom_parser_footers() {
+    let parser = CustomRegexParser::default();
+    let commit = parser.parse_commit(
+        "abc123".to_string(),
+        "fix: Handle timeouts\n\nTimeouts are retried.\n\nRefs: ABC-7\nFixes #12\nReviewed-by: Jane\n  Smith".to_string(),
+    );
+    assert_eq!(commit.body, Some("Timeouts are retried.".to_string()));
+    assert_eq!(
+        commit.footers,
+        vec![
+            ("Refs".to_string(), "ABC-7".to_string()),
+            ("Fixes".to_string(), "#12".to_string()),
+            ("Reviewed-by".to_string(), "Jane Smith".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_changelog_issue_references() {
+    let mut summary = ChangesetSummary::new();
+    summary.commits.push(Commit::parse(
+        "abc123".to_string(),
+        "fix: Handle timeouts\n\nTimeouts are retried.\n\nRefs: ABC-7\nCloses #12\nReviewed-by: Jane".to_string(),
+    ));
+
+    let changelog = format_changelog_with_options(
+        &summary, &Version::new(1, 0, 1), &Version::new(1, 0, 0), &RepoInfo::new(), &ChangelogOptions::default(),
+    );
+    assert_eq!(changelog, "### What's changed in v1.0.1\n\n* fix: Handle timeouts\n\n  Timeouts are retried.\n\n");
+
+    let options = ChangelogOptions { issue_references: true, ..ChangelogOptions::default() };
+    let changelog = format_changelog_with_options(
+        &summary, &Version::new(1, 0, 1), &Version::new(1, 0, 0), &RepoInfo::new(), &options,
+    );
+    assert_eq!(changelog, "### What's changed in v1.0.1\n\n* fix: Handle timeouts (refs ABC-7, #12)\n\n  Timeouts are retried.\n\n");
+}
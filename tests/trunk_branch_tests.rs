@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::Path;
+
+use git2::Repository;
+use vnext::git::find_trunk_branch;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{run_and_show_command, run_vnext};
+
+fn init_repo_on_branch(repo_path: &Path, branch: &str) {
+    run_and_show_command("git", &["init"], repo_path);
+    run_and_show_command("git", &["checkout", "-b", branch], repo_path);
+    run_and_show_command("git", &["config", "user.name", "patrickleet"], repo_path);
+    run_and_show_command("git", &["config", "user.email", "pat@patscott.io"], repo_path);
+    fs::write(repo_path.join("README.md"), "# Test Repository").expect("Failed to write README file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+}
+
+#[test]
+fn test_repo_with_only_trunk_branch() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    init_repo_on_branch(repo_path, "trunk");
+
+    let repo = Repository::open(repo_path).expect("Failed to open repository");
+    assert_eq!(find_trunk_branch(&repo).as_deref(), Some("trunk"));
+    assert_eq!(run_vnext(repo_path), "0.1.0");
+}
+
+#[test]
+fn test_remote_only_origin_main() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let origin = temp_dir.path().join("origin");
+    fs::create_dir(&origin).expect("Failed to create origin directory");
+    init_repo_on_branch(&origin, "main");
+
+    let clone = temp_dir.path().join("clone");
+    run_and_show_command("git", &["clone", origin.to_str().unwrap(), clone.to_str().unwrap()], temp_dir.path());
+    // Mimic a CI checkout: detached HEAD and no local branches
+    run_and_show_command("git", &["checkout", "--detach"], &clone);
+    run_and_show_command("git", &["branch", "-D", "main"], &clone);
+
+    let repo = Repository::open(&clone).expect("Failed to open repository");
+    assert!(repo.find_branch("main", git2::BranchType::Local).is_err(), "There should be no local main branch");
+    assert_eq!(find_trunk_branch(&repo).as_deref(), Some("main"));
+    assert_eq!(run_vnext(&clone), "0.1.0");
+}
+
+#[test]
+fn test_origin_head_takes_precedence() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let origin = temp_dir.path().join("origin");
+    fs::create_dir(&origin).expect("Failed to create origin directory");
+    init_repo_on_branch(&origin, "develop");
+    run_and_show_command("git", &["branch", "main"], &origin);
+
+    let clone = temp_dir.path().join("clone");
+    run_and_show_command("git", &["clone", origin.to_str().unwrap(), clone.to_str().unwrap()], temp_dir.path());
+
+    let repo = Repository::open(&clone).expect("Failed to open repository");
+    assert_eq!(find_trunk_branch(&repo).as_deref(), Some("develop"), "origin/HEAD should point at develop");
+}
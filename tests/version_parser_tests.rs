@@ -0,0 +1,113 @@
+use semver::Version;
+use vnext::models::options::WalkOptions;
+use vnext::version::{parse_version, VersionParseError, VersionParser};
+
+fn assert_wrong_prefix(parser: &VersionParser, input: &str) {
+    match parser.parse(input) {
+        Err(VersionParseError::WrongPrefix { input: actual, expected }) => {
+            assert_eq!(actual, input);
+            assert_eq!(expected, parser.expected_prefix());
+        }
+        other => panic!("Expected a wrong prefix error for '{}', got {:?}", input, other),
+    }
+}
+
+fn assert_invalid_semver(parser: &VersionParser, input: &str) {
+    match parser.parse(input) {
+        Err(VersionParseError::InvalidSemver { input: actual, .. }) => assert_eq!(actual, input),
+        other => panic!("Expected an invalid semver error for '{}', got {:?}", input, other),
+    }
+}
+
+#[test]
+fn test_default_parser() {
+    let parser = VersionParser::new();
+    assert_eq!(parser.parse("1.2.3").unwrap(), Version::new(1, 2, 3));
+    assert_eq!(parser.parse("v1.2.3").unwrap(), Version::new(1, 2, 3));
+    assert_eq!(parser.parse("v1.2.3-rc.1").unwrap(), Version::parse("1.2.3-rc.1").unwrap());
+
+    assert_invalid_semver(&parser, "vv1.2.3");
+    assert_invalid_semver(&parser, "1.2");
+    assert_invalid_semver(&parser, " 1.2.3");
+    assert_invalid_semver(&parser, "release");
+    assert_invalid_semver(&parser, "");
+}
+
+#[test]
+fn test_prefix() {
+    let parser = VersionParser::new().with_prefix("api-v");
+    assert_eq!(parser.expected_prefix(), "api-v");
+    assert_eq!(parser.parse("api-v1.2.3").unwrap(), Version::new(1, 2, 3));
+
+    assert_wrong_prefix(&parser, "web-v1.2.3");
+    assert_wrong_prefix(&parser, "v1.2.3");
+    assert_invalid_semver(&parser, "api-vnext");
+}
+
+#[test]
+fn test_namespace() {
+    let parser = VersionParser::new().with_namespace("releases");
+    assert_eq!(parser.expected_prefix(), "releases/");
+    assert_eq!(parser.parse("releases/v1.2.3").unwrap(), Version::new(1, 2, 3));
+    assert_eq!(parser.parse("releases/1.2.3").unwrap(), Version::new(1, 2, 3));
+
+    assert_wrong_prefix(&parser, "v1.2.3");
+    assert_wrong_prefix(&parser, "nightly/v1.2.3");
+    assert_invalid_semver(&parser, "releases/latest");
+}
+
+#[test]
+fn test_namespace_and_prefix() {
+    let parser = VersionParser::new().with_namespace("releases/").with_prefix("api-v");
+    assert_eq!(parser.expected_prefix(), "releases/api-v");
+    assert_eq!(parser.parse("releases/api-v2.0.0").unwrap(), Version::new(2, 0, 0));
+
+    assert_wrong_prefix(&parser, "api-v2.0.0");
+    assert_wrong_prefix(&parser, "releases/web-v2.0.0");
+    assert_invalid_semver(&parser, "releases/api-v2.0.x");
+
+    let walk = WalkOptions {
+        tag_namespace: Some("releases/".to_string()),
+        tag_prefix: Some("api-v".to_string()),
+        ..WalkOptions::default()
+    };
+    let from_walk = VersionParser::from_walk_options(&walk);
+    assert_eq!(from_walk.expected_prefix(), walk.release_tag_prefix().unwrap());
+    assert_eq!(from_walk.parse("releases/api-v2.0.0").unwrap(), Version::new(2, 0, 0));
+}
+
+#[test]
+fn test_lenient() {
+    let parser = VersionParser::new().lenient(true);
+    assert_eq!(parser.parse(" v1.2.3\n").unwrap(), Version::new(1, 2, 3));
+    assert_eq!(parser.parse("1.2").unwrap(), Version::new(1, 2, 0));
+    assert_eq!(parser.parse("v1").unwrap(), Version::new(1, 0, 0));
+    assert_eq!(parser.parse("1.2-rc.1").unwrap(), Version::parse("1.2.0-rc.1").unwrap());
+    assert_eq!(parser.parse("1+build.5").unwrap(), Version::parse("1.0.0+build.5").unwrap());
+
+    assert_invalid_semver(&parser, "1.2.3.4");
+    assert_invalid_semver(&parser, "v");
+
+    let prefixed = VersionParser::new().with_namespace("releases").with_prefix("api-").lenient(true);
+    assert_eq!(prefixed.parse(" releases/api-v3.1 ").unwrap(), Version::new(3, 1, 0));
+    assert_wrong_prefix(&prefixed, "api-v3.1");
+}
+
+#[test]
+fn test_parse_version_wrapper() {
+    assert_eq!(parse_version("v1.2.3").unwrap(), Version::new(1, 2, 3));
+    assert!(matches!(parse_version("not-a-version"), Err(VersionParseError::InvalidSemver { .. })));
+    assert_eq!(
+        parse_version("1.2").unwrap_err().to_string(),
+        "'1.2' is not a semantic version: unexpected end of input while parsing minor version number"
+    );
+}
+
+#[test]
+fn test_error_messages() {
+    let parser = VersionParser::new().with_prefix("api-v");
+    let wrong_prefix = parser.parse("web-v1.0.0").unwrap_err().to_string();
+    assert_eq!(wrong_prefix, "'web-v1.0.0' does not start with the prefix 'api-v'");
+    let invalid = parser.parse("api-v1.x").unwrap_err().to_string();
+    assert!(invalid.starts_with("'api-v1.x' is not a semantic version"), "{}", invalid);
+}
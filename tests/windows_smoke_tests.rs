@@ -0,0 +1,55 @@
+//! Smoke tests of the Windows build: version calculation and file output in a temporary
+//! repository, with Windows paths and line endings
+#![cfg(windows)]
+
+use std::fs;
+use std::process::Command;
+
+// Import the test_helpers module
+mod test_helpers;
+use test_helpers::{init_repo, run_and_show_command, run_vnext};
+
+fn create_repo(repo_path: &std::path::Path) {
+    init_repo(repo_path);
+    run_and_show_command("git", &["config", "core.autocrlf", "true"], repo_path);
+    fs::write(repo_path.join("README.md"), "# Test Repository\r\n").expect("Failed to write README file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "feat: Initial commit"], repo_path);
+    run_and_show_command("git", &["tag", "v1.0.0"], repo_path);
+    fs::create_dir(repo_path.join("src")).expect("Failed to create directory");
+    fs::write(repo_path.join("src").join("fix.txt"), "fix\r\n").expect("Failed to write file");
+    run_and_show_command("git", &["add", "."], repo_path);
+    run_and_show_command("git", &["commit", "-m", "fix: Handle backslash paths"], repo_path);
+}
+
+#[test]
+fn test_version_calculation() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    create_repo(temp_dir.path());
+
+    assert_eq!(run_vnext(temp_dir.path()), "1.0.1");
+}
+
+#[test]
+fn test_changelog_file_in_a_nested_directory() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let repo_path = temp_dir.path();
+    create_repo(repo_path);
+    let out_dir = repo_path.join("target").join("release notes");
+    fs::create_dir_all(&out_dir).expect("Failed to create output directory");
+    let changelog_file = out_dir.join("CHANGELOG.md");
+
+    let project_dir = std::env::current_dir().expect("Failed to get current directory");
+    let output = Command::new(project_dir.join("target").join("debug").join("vnext.exe"))
+        .args(["--line-ending", "native", "--path", "src", "--changelog-out"])
+        .arg(&changelog_file)
+        .current_dir(repo_path)
+        .output()
+        .expect("Failed to execute vnext");
+    assert!(output.status.success(), "vnext failed: {}", String::from_utf8_lossy(&output.stderr));
+
+    let changelog = fs::read_to_string(&changelog_file).expect("Changelog file should exist");
+    assert!(changelog.starts_with("### What's changed in v1.0.1\r\n\r\n"), "{:?}", changelog);
+    assert!(changelog.contains("* fix: Handle backslash paths\r\n"), "{:?}", changelog);
+    assert!(!changelog.replace("\r\n", "").contains('\n'), "Every line ends with CRLF: {:?}", changelog);
+}